@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+use super::currency_rate;
+
+/// Currency amounts are expressed in. Amounts are implicitly in the base
+/// currency, the Vietnamese dong, unless a document or price says otherwise.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "currency")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  #[sea_orm(column_type = "Text", unique)]
+  pub code: String,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  #[sea_orm(column_type = "Text")]
+  pub symbol: String,
+  pub decimal_places: i32,
+  /// Precision amounts are rounded to, e.g. `0.01` for cents.
+  pub rounding: Decimal,
+  /// Whether this is the company currency, which rates are expressed in.
+  pub is_base: bool,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(has_many = "super::currency_rate::Entity")]
+  CurrencyRate,
+}
+
+impl Related<super::currency_rate::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::CurrencyRate.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub code: String,
+  pub name: String,
+  pub symbol: String,
+  pub decimal_places: i32,
+  pub rounding: Decimal,
+  pub is_base: bool,
+}
+
+impl From<Model> for PartialModel {
+  fn from(currency: Model) -> Self {
+    Self {
+      id: currency.id,
+      code: currency.code,
+      name: currency.name,
+      symbol: currency.symbol,
+      decimal_places: currency.decimal_places,
+      rounding: currency.rounding,
+      is_base: currency.is_base,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyDTO {
+  pub id: Uuid,
+  pub code: String,
+  pub name: String,
+  pub symbol: String,
+  pub decimal_places: i32,
+  pub rounding: Decimal,
+  pub is_base: bool,
+  /// Latest rate in effect; `None` for the base currency or when no rate has
+  /// been entered yet.
+  pub current_rate: Option<currency_rate::PartialModel>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedAmountDTO {
+  pub amount: Decimal,
+  pub currency_id: Uuid,
+  /// Units of the target currency one unit of the source currency was worth.
+  pub rate: Decimal,
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// Value of one unit of a currency in the base currency, e.g. 25 400 dong per
+/// US dollar, from `date_start` until the next rate of the same currency.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "currency_rate")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub currency_id: Uuid,
+  pub rate: Decimal,
+  pub date_start: ChronoDateTimeWithTimeZone,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::currency::Entity",
+    from = "Column::CurrencyId",
+    to = "super::currency::Column::Id",
+    on_delete = "Cascade"
+  )]
+  Currency,
+}
+
+impl Related<super::currency::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Currency.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub currency_id: Uuid,
+  pub rate: Decimal,
+  pub date_start: ChronoDateTimeWithTimeZone,
+}
+
+impl From<Model> for PartialModel {
+  fn from(rate: Model) -> Self {
+    Self {
+      id: rate.id,
+      currency_id: rate.currency_id,
+      rate: rate.rate,
+      date_start: rate.date_start,
+    }
+  }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyRateImportDTO {
+  /// Rows written, including those that replaced an existing rate.
+  pub imported: usize,
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "location")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  pub location_type: LocationType,
+  #[sea_orm(nullable)]
+  pub parent_location_id: Option<Uuid>,
+  #[sea_orm(nullable)]
+  pub warehouse_id: Option<Uuid>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "Entity",
+    from = "Column::ParentLocationId",
+    to = "Column::Id"
+  )]
+  SelfReferencingLocation,
+  #[sea_orm(
+    belongs_to = "super::warehouse::Entity",
+    from = "Column::WarehouseId",
+    to = "super::warehouse::Column::Id"
+  )]
+  Warehouse,
+}
+
+impl Related<super::warehouse::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Warehouse.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub name: String,
+  pub location_type: LocationType,
+  pub parent_location_id: Option<Uuid>,
+  pub warehouse_id: Option<Uuid>,
+}
+
+/// Where a location sits in the flow of goods. Only `Internal` locations hold
+/// stock the company owns; the others are the counterparts of moves.
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "location_type")]
+pub enum LocationType {
+  #[sea_orm(string_value = "internal")]
+  #[serde(rename = "internal")]
+  Internal,
+  #[sea_orm(string_value = "supplier")]
+  #[serde(rename = "supplier")]
+  Supplier,
+  #[sea_orm(string_value = "customer")]
+  #[serde(rename = "customer")]
+  Customer,
+  #[sea_orm(string_value = "scrap")]
+  #[serde(rename = "scrap")]
+  Scrap,
+  #[sea_orm(string_value = "adjustment")]
+  #[serde(rename = "adjustment")]
+  Adjustment,
+  /// Where raw materials are consumed and finished goods come from.
+  #[sea_orm(string_value = "production")]
+  #[serde(rename = "production")]
+  Production,
+}
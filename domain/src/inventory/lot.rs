@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// Production lot or serial number of one product.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "lot")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub product_id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "crate::product::product::Entity",
+    from = "Column::ProductId",
+    to = "crate::product::product::Column::Id"
+  )]
+  Product,
+}
+
+impl Related<crate::product::product::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Product.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub product_id: Uuid,
+  pub name: String,
+}
+
+/// Done move of a lot out to a customer location. The customer and sales
+/// order are only known for moves linked to a sales order line.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct LotDeliveryDTO {
+  pub stock_move_id: Uuid,
+  pub reference: String,
+  pub location_id: Uuid,
+  pub location_name: String,
+  pub partner_id: Option<Uuid>,
+  pub partner_name: Option<String>,
+  pub sales_order_id: Option<Uuid>,
+  pub sales_order_reference: Option<String>,
+  pub quantity: Decimal,
+  pub done_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+/// Lot linked to the traced one through production, with the quantity
+/// consumed in the stock unit of the component.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct LotLinkDTO {
+  pub lot_id: Uuid,
+  pub lot_name: String,
+  pub product_id: Uuid,
+  pub quantity: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LotTraceDTO {
+  pub lot: PartialModel,
+  /// Done moves of the lot into customer locations, with the customer that
+  /// received each when the move fulfils a sales order line.
+  pub deliveries: Vec<LotDeliveryDTO>,
+  /// Component lots consumed to produce the lot.
+  pub components: Vec<LotLinkDTO>,
+  /// Finished lots the lot was consumed into.
+  pub produced_lots: Vec<LotLinkDTO>,
+}
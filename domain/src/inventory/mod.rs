@@ -0,0 +1,5 @@
+pub mod location;
+pub mod lot;
+pub mod stock_move;
+pub mod stock_valuation_layer;
+pub mod warehouse;
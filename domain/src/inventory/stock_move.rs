@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// Movement of a quantity of one product from a source to a destination
+/// location. Every done move is both an outflow of its source and an inflow
+/// of its destination, so stock is never created or lost.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "stock_move")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub reference: String,
+  pub product_id: Uuid,
+  pub source_location_id: Uuid,
+  pub destination_location_id: Uuid,
+  /// Quantity in `uom_id`, as entered.
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+  /// `quantity` converted into the stock unit of the product's template.
+  pub product_quantity: Decimal,
+  /// Cost of one `uom_id` unit, for receipts valued at their purchase price.
+  #[sea_orm(nullable)]
+  pub unit_cost: Option<Decimal>,
+  /// Lot carried, required for products whose template is tracked.
+  #[sea_orm(nullable)]
+  pub lot_id: Option<Uuid>,
+  /// Finished lot this move's components went into, for moves consuming
+  /// materials in a production location.
+  #[sea_orm(nullable)]
+  pub produced_lot_id: Option<Uuid>,
+  /// Sales order line a delivery to a customer location fulfils.
+  #[sea_orm(nullable)]
+  pub sales_order_line_id: Option<Uuid>,
+  pub state: StockMoveState,
+  #[sea_orm(nullable)]
+  pub done_at: Option<ChronoDateTimeWithTimeZone>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "crate::product::product::Entity",
+    from = "Column::ProductId",
+    to = "crate::product::product::Column::Id"
+  )]
+  Product,
+  #[sea_orm(
+    belongs_to = "super::location::Entity",
+    from = "Column::SourceLocationId",
+    to = "super::location::Column::Id"
+  )]
+  SourceLocation,
+  #[sea_orm(
+    belongs_to = "super::location::Entity",
+    from = "Column::DestinationLocationId",
+    to = "super::location::Column::Id"
+  )]
+  DestinationLocation,
+  #[sea_orm(
+    belongs_to = "super::lot::Entity",
+    from = "Column::LotId",
+    to = "super::lot::Column::Id"
+  )]
+  Lot,
+  #[sea_orm(
+    belongs_to = "super::lot::Entity",
+    from = "Column::ProducedLotId",
+    to = "super::lot::Column::Id"
+  )]
+  ProducedLot,
+  #[sea_orm(
+    belongs_to = "crate::sales::sales_order_line::Entity",
+    from = "Column::SalesOrderLineId",
+    to = "crate::sales::sales_order_line::Column::Id",
+    on_delete = "SetNull"
+  )]
+  SalesOrderLine,
+}
+
+impl Related<crate::product::product::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Product.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "stock_move_state")]
+pub enum StockMoveState {
+  #[sea_orm(string_value = "draft")]
+  #[serde(rename = "draft")]
+  Draft,
+  #[sea_orm(string_value = "done")]
+  #[serde(rename = "done")]
+  Done,
+  #[sea_orm(string_value = "cancelled")]
+  #[serde(rename = "cancelled")]
+  Cancelled,
+}
+
+/// Quantity of a product held at an internal location, in the stock unit of
+/// its template, split by lot for tracked products.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct OnHandDTO {
+  pub product_id: Uuid,
+  pub location_id: Uuid,
+  pub lot_id: Option<Uuid>,
+  pub quantity: Decimal,
+}
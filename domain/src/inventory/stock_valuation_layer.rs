@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// Value a done stock move added to or took from a warehouse. Receipts carry a
+/// positive quantity and deliveries a negative one; the sum of a product's
+/// layers up to a date is its inventory value at that date.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "stock_valuation_layer")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub product_id: Uuid,
+  pub stock_move_id: Uuid,
+  /// Warehouse of the internal location the move entered or left, `None` for
+  /// internal locations outside any warehouse.
+  #[sea_orm(nullable)]
+  pub warehouse_id: Option<Uuid>,
+  /// Signed quantity in the stock unit of the product's template.
+  pub quantity: Decimal,
+  pub unit_cost: Decimal,
+  pub value: Decimal,
+  /// Part of an incoming layer not yet consumed by later deliveries, which
+  /// FIFO costing draws from oldest first. Always zero on outgoing layers.
+  pub remaining_quantity: Decimal,
+  pub created_at: ChronoDateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "crate::product::product::Entity",
+    from = "Column::ProductId",
+    to = "crate::product::product::Column::Id"
+  )]
+  Product,
+  #[sea_orm(
+    belongs_to = "super::stock_move::Entity",
+    from = "Column::StockMoveId",
+    to = "super::stock_move::Column::Id"
+  )]
+  StockMove,
+  #[sea_orm(
+    belongs_to = "super::warehouse::Entity",
+    from = "Column::WarehouseId",
+    to = "super::warehouse::Column::Id"
+  )]
+  Warehouse,
+}
+
+impl Related<super::stock_move::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::StockMove.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+}
+
+/// Inventory value at a date, per product, per warehouse or per both depending
+/// on the grouping requested.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct StockValuationDTO {
+  pub product_id: Option<Uuid>,
+  pub warehouse_id: Option<Uuid>,
+  pub quantity: Decimal,
+  pub value: Decimal,
+}
@@ -1,2 +1,9 @@
+pub mod currency;
+pub mod inventory;
 pub mod measurement;
+pub mod partner;
+pub mod pricelist;
 pub mod product;
+pub mod purchase;
+pub mod sales;
+pub mod tax;
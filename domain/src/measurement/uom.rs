@@ -12,9 +12,15 @@ pub struct Model {
   pub id: Uuid,
   #[sea_orm(column_type = "Text")]
   pub name: String,
+  pub category: UomCategory,
+  pub ratio: Decimal,
+  pub rounding: Decimal,
+  pub is_reference: bool,
   pub created_at: ChronoDateTimeWithTimeZone,
   #[sea_orm(nullable)]
   pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -47,4 +53,46 @@ impl ActiveModelBehavior for ActiveModel {
 pub struct PartialModel {
   pub id: Uuid,
   pub name: String,
+  pub category: UomCategory,
+  pub ratio: Decimal,
+  pub rounding: Decimal,
+  pub is_reference: bool,
+}
+
+impl From<Model> for PartialModel {
+  fn from(uom: Model) -> Self {
+    Self {
+      id: uom.id,
+      name: uom.name,
+      category: uom.category,
+      ratio: uom.ratio,
+      rounding: uom.rounding,
+      is_reference: uom.is_reference,
+    }
+  }
+}
+
+/// Physical quantity a unit measures. Quantities only convert between units of
+/// the same category.
+#[derive(
+  Debug, Default, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "uom_category")]
+pub enum UomCategory {
+  #[default]
+  #[sea_orm(string_value = "unit")]
+  #[serde(rename = "unit")]
+  Unit,
+  #[sea_orm(string_value = "weight")]
+  #[serde(rename = "weight")]
+  Weight,
+  #[sea_orm(string_value = "length")]
+  #[serde(rename = "length")]
+  Length,
+  #[sea_orm(string_value = "area")]
+  #[serde(rename = "area")]
+  Area,
+  #[sea_orm(string_value = "volume")]
+  #[serde(rename = "volume")]
+  Volume,
 }
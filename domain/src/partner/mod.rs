@@ -0,0 +1,4 @@
+#[allow(clippy::module_inception)]
+pub mod partner;
+pub mod partner_address;
+pub mod partner_contact;
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+use super::{partner_address, partner_contact};
+
+/// Company or person we sell to, buy from, or both.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "partner")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  pub partner_type: PartnerType,
+  /// Vietnamese tax code (MST).
+  #[sea_orm(column_type = "Text", nullable)]
+  pub tax_code: Option<String>,
+  pub is_customer: bool,
+  pub is_supplier: bool,
+  #[sea_orm(column_type = "Text", nullable)]
+  pub email: Option<String>,
+  #[sea_orm(column_type = "Text", nullable)]
+  pub phone: Option<String>,
+  /// Days after invoicing payment is due by default, 0 meaning immediately.
+  pub payment_term_days: i32,
+  /// Pricelist quoted to the partner as a customer, if any.
+  #[sea_orm(nullable)]
+  pub pricelist_id: Option<Uuid>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(has_many = "super::partner_address::Entity")]
+  PartnerAddress,
+  #[sea_orm(has_many = "super::partner_contact::Entity")]
+  PartnerContact,
+}
+
+impl Related<super::partner_address::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PartnerAddress.def()
+  }
+}
+
+impl Related<super::partner_contact::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PartnerContact.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub name: String,
+  pub partner_type: PartnerType,
+  pub tax_code: Option<String>,
+  pub is_customer: bool,
+  pub is_supplier: bool,
+  pub email: Option<String>,
+  pub phone: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartnerDTO {
+  pub id: Uuid,
+  pub name: String,
+  pub partner_type: PartnerType,
+  pub tax_code: Option<String>,
+  pub is_customer: bool,
+  pub is_supplier: bool,
+  pub email: Option<String>,
+  pub phone: Option<String>,
+  pub payment_term_days: i32,
+  pub pricelist_id: Option<Uuid>,
+  pub addresses: Vec<partner_address::PartialModel>,
+  pub contacts: Vec<partner_contact::PartialModel>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "partner_type")]
+pub enum PartnerType {
+  #[sea_orm(string_value = "company")]
+  #[serde(rename = "company")]
+  Company,
+  #[sea_orm(string_value = "individual")]
+  #[serde(rename = "individual")]
+  Individual,
+}
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "partner_address")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub partner_id: Uuid,
+  pub address_type: AddressType,
+  #[sea_orm(column_type = "Text")]
+  pub street: String,
+  #[sea_orm(column_type = "Text")]
+  pub ward: String,
+  #[sea_orm(column_type = "Text")]
+  pub district: String,
+  #[sea_orm(column_type = "Text")]
+  pub city: String,
+  /// ISO 3166-1 alpha-2 code.
+  #[sea_orm(column_type = "Text")]
+  pub country_code: String,
+  /// Address used for its type when a document does not pick one. At most one
+  /// per partner and type.
+  pub is_default: bool,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::partner::Entity",
+    from = "Column::PartnerId",
+    to = "super::partner::Column::Id",
+    on_delete = "Cascade"
+  )]
+  Partner,
+}
+
+impl Related<super::partner::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Partner.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub address_type: AddressType,
+  pub street: String,
+  pub ward: String,
+  pub district: String,
+  pub city: String,
+  pub country_code: String,
+  pub is_default: bool,
+}
+
+#[derive(
+  Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Serialize,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "address_type")]
+pub enum AddressType {
+  #[sea_orm(string_value = "billing")]
+  #[serde(rename = "billing")]
+  Billing,
+  #[sea_orm(string_value = "delivery")]
+  #[serde(rename = "delivery")]
+  Delivery,
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// Person to reach at a partner.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "partner_contact")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub partner_id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  #[sea_orm(column_type = "Text")]
+  pub job_title: String,
+  #[sea_orm(column_type = "Text", nullable)]
+  pub email: Option<String>,
+  #[sea_orm(column_type = "Text", nullable)]
+  pub phone: Option<String>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::partner::Entity",
+    from = "Column::PartnerId",
+    to = "super::partner::Column::Id",
+    on_delete = "Cascade"
+  )]
+  Partner,
+}
+
+impl Related<super::partner::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Partner.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub name: String,
+  pub job_title: String,
+  pub email: Option<String>,
+  pub phone: Option<String>,
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+use super::pricelist_rule;
+
+/// Named set of pricing rules, e.g. a customer's negotiated prices.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "pricelist")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  /// Currency of the fixed prices of the rules and of the computed prices.
+  pub currency_id: Uuid,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(has_many = "super::pricelist_rule::Entity")]
+  PricelistRule,
+}
+
+impl Related<super::pricelist_rule::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PricelistRule.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub name: String,
+  pub currency_id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PricelistDTO {
+  pub id: Uuid,
+  pub name: String,
+  pub currency_id: Uuid,
+  pub rules: Vec<pricelist_rule::PartialModel>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+/// Price of a product resolved against a pricelist.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputedPriceDTO {
+  pub product_id: Uuid,
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+  /// Price of one `uom_id` unit.
+  pub unit_price: Decimal,
+  pub currency_id: Uuid,
+  /// Rule the price came from; `None` when no rule applies and the product's
+  /// own price is used.
+  pub rule: Option<pricelist_rule::PartialModel>,
+}
@@ -0,0 +1,149 @@
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// One pricing rule of a pricelist. Quantities and prices are expressed in the
+/// stock unit of the product's template.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "pricelist_rule")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub pricelist_id: Uuid,
+  /// Breaks ties between equally specific rules, lowest first.
+  pub sequence: i32,
+  pub scope: PricelistRuleScope,
+  /// Set for `Variant` rules.
+  #[sea_orm(nullable)]
+  pub product_id: Option<Uuid>,
+  /// Set for `Template` rules.
+  #[sea_orm(nullable)]
+  pub product_template_id: Option<Uuid>,
+  /// Set for `Category` rules, which also cover its subcategories.
+  #[sea_orm(nullable)]
+  pub category_id: Option<Uuid>,
+  pub min_quantity: Decimal,
+  #[sea_orm(nullable)]
+  pub date_start: Option<ChronoDateTimeWithTimeZone>,
+  /// Exclusive end of validity.
+  #[sea_orm(nullable)]
+  pub date_end: Option<ChronoDateTimeWithTimeZone>,
+  pub compute_type: PricelistComputeType,
+  #[sea_orm(nullable)]
+  pub fixed_price: Option<Decimal>,
+  /// Percentage taken off the product's price.
+  #[sea_orm(nullable)]
+  pub discount: Option<Decimal>,
+  /// Percentage added on top of the product's cost.
+  #[sea_orm(nullable)]
+  pub margin: Option<Decimal>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::pricelist::Entity",
+    from = "Column::PricelistId",
+    to = "super::pricelist::Column::Id",
+    on_delete = "Cascade"
+  )]
+  Pricelist,
+}
+
+impl Related<super::pricelist::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Pricelist.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub sequence: i32,
+  pub scope: PricelistRuleScope,
+  pub product_id: Option<Uuid>,
+  pub product_template_id: Option<Uuid>,
+  pub category_id: Option<Uuid>,
+  pub min_quantity: Decimal,
+  pub date_start: Option<ChronoDateTimeWithTimeZone>,
+  pub date_end: Option<ChronoDateTimeWithTimeZone>,
+  pub compute_type: PricelistComputeType,
+  pub fixed_price: Option<Decimal>,
+  pub discount: Option<Decimal>,
+  pub margin: Option<Decimal>,
+}
+
+impl From<Model> for PartialModel {
+  fn from(rule: Model) -> Self {
+    Self {
+      id: rule.id,
+      sequence: rule.sequence,
+      scope: rule.scope,
+      product_id: rule.product_id,
+      product_template_id: rule.product_template_id,
+      category_id: rule.category_id,
+      min_quantity: rule.min_quantity,
+      date_start: rule.date_start,
+      date_end: rule.date_end,
+      compute_type: rule.compute_type,
+      fixed_price: rule.fixed_price,
+      discount: rule.discount,
+      margin: rule.margin,
+    }
+  }
+}
+
+/// What a rule applies to, from the most to the least specific.
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(
+  rs_type = "String",
+  db_type = "Enum",
+  enum_name = "pricelist_rule_scope"
+)]
+pub enum PricelistRuleScope {
+  #[sea_orm(string_value = "variant")]
+  #[serde(rename = "variant")]
+  Variant,
+  #[sea_orm(string_value = "template")]
+  #[serde(rename = "template")]
+  Template,
+  #[sea_orm(string_value = "category")]
+  #[serde(rename = "category")]
+  Category,
+  #[sea_orm(string_value = "global")]
+  #[serde(rename = "global")]
+  Global,
+}
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(
+  rs_type = "String",
+  db_type = "Enum",
+  enum_name = "pricelist_compute_type"
+)]
+pub enum PricelistComputeType {
+  /// `fixed_price` per stock unit.
+  #[sea_orm(string_value = "fixed")]
+  #[serde(rename = "fixed")]
+  Fixed,
+  /// The product's price less `discount` percent.
+  #[sea_orm(string_value = "discount")]
+  #[serde(rename = "discount")]
+  Discount,
+  /// The product's cost plus `margin` percent.
+  #[sea_orm(string_value = "cost_plus_margin")]
+  #[serde(rename = "cost_plus_margin")]
+  CostPlusMargin,
+}
@@ -18,6 +18,8 @@ pub struct Model {
   pub value: String,
   #[sea_orm(column_type = "Uuid")]
   pub attribute_id: Uuid,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -14,9 +14,19 @@ pub struct Model {
   pub name: String,
   #[sea_orm(nullable)]
   pub parent_category_id: Option<Uuid>,
+  pub costing_method: CostingMethod,
+  /// Default tax of sales lines for the category's products, overriding the
+  /// template's. Subcategories inherit it unless they set their own.
+  #[sea_orm(nullable)]
+  pub sales_tax_id: Option<Uuid>,
+  /// Purchase counterpart of `sales_tax_id`.
+  #[sea_orm(nullable)]
+  pub purchase_tax_id: Option<Uuid>,
   pub created_at: ChronoDateTimeWithTimeZone,
   #[sea_orm(nullable)]
   pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -62,10 +72,55 @@ impl ActiveModelBehavior for ActiveModel {
     Ok(this)
   }
 }
-#[derive(Debug, DerivePartialModel, Serialize, FromQueryResult)]
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
 #[sea_orm(entity = "Entity")]
 #[serde(rename_all = "camelCase")]
 pub struct PartialModel {
   pub id: Uuid,
   pub name: String,
 }
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryDTO {
+  pub id: Uuid,
+  pub name: String,
+  pub parent_category_id: Option<Uuid>,
+  pub costing_method: CostingMethod,
+  pub sales_tax_id: Option<Uuid>,
+  pub purchase_tax_id: Option<Uuid>,
+  pub path: String,
+  pub breadcrumbs: Vec<PartialModel>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTreeNode {
+  pub id: Uuid,
+  pub name: String,
+  pub parent_category_id: Option<Uuid>,
+  pub path: String,
+  pub children: Vec<CategoryTreeNode>,
+}
+
+/// How incoming stock of the category's products is valued and how outgoing
+/// stock is costed.
+#[derive(
+  Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize, Default,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "costing_method")]
+pub enum CostingMethod {
+  /// Stock is valued at `product.cost`, whatever it was bought for.
+  #[default]
+  #[sea_orm(string_value = "standard")]
+  #[serde(rename = "standard")]
+  Standard,
+  /// `product.cost` follows the weighted average of the stock on hand.
+  #[sea_orm(string_value = "average")]
+  #[serde(rename = "average")]
+  Average,
+  /// Outgoing stock consumes the oldest receipts first, at their own cost.
+  #[sea_orm(string_value = "fifo")]
+  #[serde(rename = "fifo")]
+  Fifo,
+}
@@ -1,6 +1,8 @@
 pub mod attribute;
 pub mod attribute_option;
 pub mod category;
+#[allow(clippy::module_inception)]
 pub mod product;
 pub mod product_combination;
+pub mod product_mould;
 pub mod product_template;
@@ -4,7 +4,7 @@ use infra::uuid::Uuid;
 use sea_orm::{entity::prelude::*, FromQueryResult, Set};
 use serde::{Deserialize, Serialize};
 
-use super::{attribute, attribute_option};
+use super::{attribute, attribute_option, product_template};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, Copy)]
 #[sea_orm(table_name = "product")]
@@ -14,11 +14,16 @@ pub struct Model {
   pub id: Uuid,
   pub product_template_id: Uuid,
   pub price: Decimal,
+  /// Unit `price` is quoted in, e.g. a price per box on a template stocked in
+  /// pieces.
+  pub price_uom_id: Uuid,
   pub cost: Decimal,
   pub is_product_variant: bool,
   pub created_at: ChronoDateTimeWithTimeZone,
   #[sea_orm(nullable)]
   pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -74,23 +79,32 @@ pub struct QueryProductResult {
   pub id: Uuid,
   pub name: String,
   pub is_product_variant: bool,
-  pub product_template_id: Option<Uuid>,
-  pub attribute_id: Option<Uuid>,
-  pub attribute_name: Option<String>,
-  pub attribute_option_id: Option<Uuid>,
-  pub attribute_option_value: Option<String>,
+  pub product_template_id: Uuid,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProductDTO {
   pub id: Uuid,
+  pub product_template_id: Uuid,
   pub name: String,
   pub is_product_variant: bool,
   pub combinations: Vec<AttributeWithOptionDTO>,
 }
 
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductVariantDTO {
+  pub id: Uuid,
+  pub price: Decimal,
+  pub price_uom_id: Uuid,
+  pub cost: Decimal,
+  pub is_product_variant: bool,
+  pub combinations: Vec<AttributeWithOptionDTO>,
+  pub moulds: Vec<product_template::PartialModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct AttributeWithOptionDTO {
   pub attribute: attribute::PartialModel,
   pub option: attribute_option::PartialModel,
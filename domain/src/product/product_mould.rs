@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use infra::uuid::Uuid;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Links a printed packaging variant to the mould template used to print it.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "product_mould")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub product_id: Uuid,
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub mould_template_id: Uuid,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::product::Entity",
+    from = "Column::ProductId",
+    to = "super::product::Column::Id"
+  )]
+  Product,
+  #[sea_orm(
+    belongs_to = "super::product_template::Entity",
+    from = "Column::MouldTemplateId",
+    to = "super::product_template::Column::Id"
+  )]
+  MouldTemplate,
+}
+
+impl Related<super::product::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Product.def()
+  }
+}
+
+impl Related<super::product_template::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::MouldTemplate.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {}
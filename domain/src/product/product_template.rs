@@ -4,6 +4,12 @@ use infra::uuid::Uuid;
 use sea_orm::{entity::prelude::*, FromQueryResult, Set};
 use serde::{Deserialize, Serialize};
 
+use super::{
+  category,
+  product::{ProductDTO, ProductVariantDTO},
+};
+use crate::measurement::uom;
+
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "product_template")]
 #[serde(rename_all = "camelCase")]
@@ -15,14 +21,28 @@ pub struct Model {
   #[sea_orm(column_type = "Text")]
   pub description: String,
   pub uom_id: Uuid,
+  pub purchase_uom_id: Uuid,
+  pub sales_uom_id: Uuid,
   #[sea_orm(nullable)]
   pub category_id: Option<Uuid>,
+  /// Default tax of sales lines, unless the category sets one.
+  #[sea_orm(nullable)]
+  pub sales_tax_id: Option<Uuid>,
+  /// Default tax of purchase lines, unless the category sets one.
+  #[sea_orm(nullable)]
+  pub purchase_tax_id: Option<Uuid>,
+  /// Currency variant prices are quoted in. Costs are always in the base
+  /// currency, which stock is valued in.
+  pub currency_id: Uuid,
   pub product_type: ProductType,
   pub product_subtype: ProductSubtype,
   pub is_track_inventory: bool,
+  pub tracking: Tracking,
   pub created_at: ChronoDateTimeWithTimeZone,
   #[sea_orm(nullable)]
   pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -58,7 +78,7 @@ impl ActiveModelBehavior for ActiveModel {
     Ok(this)
   }
 }
-#[derive(Debug, DerivePartialModel, Serialize, FromQueryResult)]
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
 #[sea_orm(entity = "Entity")]
 #[serde(rename_all = "camelCase")]
 pub struct PartialModel {
@@ -66,14 +86,48 @@ pub struct PartialModel {
   pub name: String,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductTemplateDTO {
+  pub id: Uuid,
+  pub name: String,
+  pub description: String,
+  pub product_type: ProductType,
+  pub product_subtype: ProductSubtype,
+  pub is_track_inventory: bool,
+  pub tracking: Tracking,
+  pub uom: uom::PartialModel,
+  pub purchase_uom: uom::PartialModel,
+  pub sales_uom: uom::PartialModel,
+  pub category: Option<category::PartialModel>,
+  pub sales_tax_id: Option<Uuid>,
+  pub purchase_tax_id: Option<Uuid>,
+  pub currency_id: Uuid,
+  pub variants: Vec<ProductVariantDTO>,
+  pub packagings: Vec<PartialModel>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductTemplateListDTO {
+  pub id: Uuid,
+  pub name: String,
+  pub product_type: ProductType,
+  pub product_subtype: ProductSubtype,
+  pub variants: Vec<ProductDTO>,
+}
+
 #[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, PartialEq, Eq, Serialize)]
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "product_type")]
 pub enum ProductType {
   #[sea_orm(string_value = "goods")]
-  #[serde(rename(deserialize = "goods"))]
+  #[serde(rename = "goods")]
   Goods,
   #[sea_orm(string_value = "service")]
-  #[serde(rename(deserialize = "service"))]
+  #[serde(rename = "service")]
   Service,
 }
 
@@ -81,12 +135,32 @@ pub enum ProductType {
 #[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "product_subtype")]
 pub enum ProductSubtype {
   #[sea_orm(string_value = "normal")]
-  #[serde(rename(deserialize = "normal"))]
+  #[serde(rename = "normal")]
   Normal,
   #[sea_orm(string_value = "packaging_with_print")]
-  #[serde(rename(deserialize = "packaging_with_print"))]
+  #[serde(rename = "packaging_with_print")]
   PackagingWithPrint,
   #[sea_orm(string_value = "mould")]
-  #[serde(rename(deserialize = "mould"))]
+  #[serde(rename = "mould")]
   Mould,
 }
+
+/// How the units of an inventory-tracked template are identified in stock.
+#[derive(
+  Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize, Default,
+)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tracking")]
+pub enum Tracking {
+  #[default]
+  #[sea_orm(string_value = "none")]
+  #[serde(rename = "none")]
+  None,
+  /// Every move names the production lot it carries.
+  #[sea_orm(string_value = "lot")]
+  #[serde(rename = "lot")]
+  Lot,
+  /// Every unit has its own lot, which is never in stock twice.
+  #[sea_orm(string_value = "serial")]
+  #[serde(rename = "serial")]
+  Serial,
+}
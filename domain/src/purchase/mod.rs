@@ -0,0 +1,3 @@
+pub mod purchase_order;
+pub mod purchase_order_line;
+pub mod supplier_info;
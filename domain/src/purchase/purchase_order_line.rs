@@ -0,0 +1,87 @@
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "purchase_order_line")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub purchase_order_id: Uuid,
+  /// Position of the line on the order.
+  pub sequence: i32,
+  /// Product variant bought.
+  pub product_id: Uuid,
+  /// Supplier price the unit price was taken from, if any.
+  #[sea_orm(nullable)]
+  pub supplier_info_id: Option<Uuid>,
+  #[sea_orm(column_type = "Text")]
+  pub description: String,
+  /// Quantity in `uom_id`, as entered.
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+  /// `quantity` converted into the stock unit of the product's template.
+  pub product_quantity: Decimal,
+  /// Price of one `uom_id` unit.
+  pub unit_price: Decimal,
+  /// Untaxed amount of the line.
+  pub subtotal: Decimal,
+  #[sea_orm(nullable)]
+  pub tax_id: Option<Uuid>,
+  pub tax_amount: Decimal,
+  /// Incoming move created when the order was received.
+  #[sea_orm(nullable)]
+  pub stock_move_id: Option<Uuid>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::purchase_order::Entity",
+    from = "Column::PurchaseOrderId",
+    to = "super::purchase_order::Column::Id",
+    on_delete = "Cascade"
+  )]
+  PurchaseOrder,
+  #[sea_orm(
+    belongs_to = "crate::product::product::Entity",
+    from = "Column::ProductId",
+    to = "crate::product::product::Column::Id"
+  )]
+  Product,
+}
+
+impl Related<super::purchase_order::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::PurchaseOrder.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub sequence: i32,
+  pub product_id: Uuid,
+  pub supplier_info_id: Option<Uuid>,
+  pub description: String,
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+  pub product_quantity: Decimal,
+  pub unit_price: Decimal,
+  pub subtotal: Decimal,
+  pub tax_id: Option<Uuid>,
+  pub tax_amount: Decimal,
+  pub stock_move_id: Option<Uuid>,
+}
@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// What a supplier charges for a product variant, or for every variant of a
+/// template, from a minimum quantity on. A supplier may quote several tiers
+/// for the same product.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "supplier_info")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub partner_id: Uuid,
+  /// Set for a variant's price, exclusive with `product_template_id`.
+  #[sea_orm(nullable)]
+  pub product_id: Option<Uuid>,
+  #[sea_orm(nullable)]
+  pub product_template_id: Option<Uuid>,
+  /// The supplier's own code for the product.
+  #[sea_orm(column_type = "Text", nullable)]
+  pub supplier_sku: Option<String>,
+  /// Price of one `uom_id` unit.
+  pub price: Decimal,
+  pub currency_id: Uuid,
+  pub uom_id: Uuid,
+  /// Smallest quantity, in `uom_id`, the price applies to.
+  pub min_quantity: Decimal,
+  /// Days between ordering and receiving the goods.
+  pub lead_time_days: i32,
+  /// First moment the price applies, inclusive.
+  #[sea_orm(nullable)]
+  pub date_start: Option<ChronoDateTimeWithTimeZone>,
+  /// Moment the price stops applying, exclusive.
+  #[sea_orm(nullable)]
+  pub date_end: Option<ChronoDateTimeWithTimeZone>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "crate::partner::partner::Entity",
+    from = "Column::PartnerId",
+    to = "crate::partner::partner::Column::Id",
+    on_delete = "Cascade"
+  )]
+  Partner,
+  #[sea_orm(
+    belongs_to = "crate::product::product::Entity",
+    from = "Column::ProductId",
+    to = "crate::product::product::Column::Id",
+    on_delete = "Cascade"
+  )]
+  Product,
+  #[sea_orm(
+    belongs_to = "crate::product::product_template::Entity",
+    from = "Column::ProductTemplateId",
+    to = "crate::product::product_template::Column::Id",
+    on_delete = "Cascade"
+  )]
+  ProductTemplate,
+}
+
+impl Related<crate::partner::partner::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Partner.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub partner_id: Uuid,
+  pub product_id: Option<Uuid>,
+  pub product_template_id: Option<Uuid>,
+  pub supplier_sku: Option<String>,
+  pub price: Decimal,
+  pub currency_id: Uuid,
+  pub uom_id: Uuid,
+  pub min_quantity: Decimal,
+  pub lead_time_days: i32,
+  pub date_start: Option<ChronoDateTimeWithTimeZone>,
+  pub date_end: Option<ChronoDateTimeWithTimeZone>,
+}
@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+use super::sales_order_line;
+use crate::partner::{partner, partner_address};
+
+/// Quotation sent to a customer, which becomes a sales order once confirmed.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sales_order")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  /// `SO00001` style number assigned by the database on insert.
+  #[sea_orm(column_type = "Text", unique)]
+  pub reference: String,
+  pub partner_id: Uuid,
+  #[sea_orm(nullable)]
+  pub invoice_address_id: Option<Uuid>,
+  #[sea_orm(nullable)]
+  pub delivery_address_id: Option<Uuid>,
+  pub state: SalesOrderState,
+  pub order_date: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub confirmed_at: Option<ChronoDateTimeWithTimeZone>,
+  pub payment_term_days: i32,
+  #[sea_orm(column_type = "Text")]
+  pub note: String,
+  /// Currency of the prices and amounts of the order and its lines.
+  pub currency_id: Uuid,
+  /// Sum of the line subtotals.
+  pub amount_untaxed: Decimal,
+  /// Sum of the line taxes.
+  pub amount_tax: Decimal,
+  /// `amount_untaxed` plus `amount_tax`.
+  pub amount_total: Decimal,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "crate::partner::partner::Entity",
+    from = "Column::PartnerId",
+    to = "crate::partner::partner::Column::Id"
+  )]
+  Partner,
+  #[sea_orm(has_many = "super::sales_order_line::Entity")]
+  SalesOrderLine,
+}
+
+impl Related<crate::partner::partner::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::Partner.def()
+  }
+}
+
+impl Related<super::sales_order_line::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::SalesOrderLine.def()
+  }
+}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+/// Row of the sales order list.
+#[derive(Debug, Clone, Serialize, FromQueryResult)]
+#[serde(rename_all = "camelCase")]
+pub struct SalesOrderListItem {
+  pub id: Uuid,
+  pub reference: String,
+  pub partner_id: Uuid,
+  pub partner_name: String,
+  pub state: SalesOrderState,
+  pub order_date: ChronoDateTimeWithTimeZone,
+  pub currency_id: Uuid,
+  pub amount_total: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SalesOrderDTO {
+  pub id: Uuid,
+  pub reference: String,
+  pub partner: partner::PartialModel,
+  pub invoice_address: Option<partner_address::PartialModel>,
+  pub delivery_address: Option<partner_address::PartialModel>,
+  pub state: SalesOrderState,
+  pub order_date: ChronoDateTimeWithTimeZone,
+  pub confirmed_at: Option<ChronoDateTimeWithTimeZone>,
+  pub payment_term_days: i32,
+  pub note: String,
+  pub currency_id: Uuid,
+  pub amount_untaxed: Decimal,
+  pub amount_tax: Decimal,
+  pub amount_total: Decimal,
+  pub lines: Vec<sales_order_line::PartialModel>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+/// A quotation is confirmed into an order, which ends up done or cancelled.
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "sales_order_state")]
+pub enum SalesOrderState {
+  #[sea_orm(string_value = "quotation")]
+  #[serde(rename = "quotation")]
+  Quotation,
+  #[sea_orm(string_value = "confirmed")]
+  #[serde(rename = "confirmed")]
+  Confirmed,
+  #[sea_orm(string_value = "done")]
+  #[serde(rename = "done")]
+  Done,
+  #[sea_orm(string_value = "cancelled")]
+  #[serde(rename = "cancelled")]
+  Cancelled,
+}
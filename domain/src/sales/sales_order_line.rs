@@ -0,0 +1,82 @@
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "sales_order_line")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  pub sales_order_id: Uuid,
+  /// Position of the line on the order.
+  pub sequence: i32,
+  /// Product variant sold.
+  pub product_id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub description: String,
+  /// Quantity in `uom_id`, as entered.
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+  /// `quantity` converted into the stock unit of the product's template.
+  pub product_quantity: Decimal,
+  /// Price of one `uom_id` unit before discount.
+  pub unit_price: Decimal,
+  /// Percentage taken off `unit_price`.
+  pub discount: Decimal,
+  /// Untaxed amount of the line.
+  pub subtotal: Decimal,
+  #[sea_orm(nullable)]
+  pub tax_id: Option<Uuid>,
+  pub tax_amount: Decimal,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+  #[sea_orm(
+    belongs_to = "super::sales_order::Entity",
+    from = "Column::SalesOrderId",
+    to = "super::sales_order::Column::Id",
+    on_delete = "Cascade"
+  )]
+  SalesOrder,
+  #[sea_orm(
+    belongs_to = "crate::product::product::Entity",
+    from = "Column::ProductId",
+    to = "crate::product::product::Column::Id"
+  )]
+  Product,
+}
+
+impl Related<super::sales_order::Entity> for Entity {
+  fn to() -> RelationDef {
+    Relation::SalesOrder.def()
+  }
+}
+
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub sequence: i32,
+  pub product_id: Uuid,
+  pub description: String,
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+  pub product_quantity: Decimal,
+  pub unit_price: Decimal,
+  pub discount: Decimal,
+  pub subtotal: Decimal,
+  pub tax_id: Option<Uuid>,
+  pub tax_amount: Decimal,
+}
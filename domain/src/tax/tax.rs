@@ -0,0 +1,173 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use infra::uuid::Uuid;
+use sea_orm::{entity::prelude::*, FromQueryResult, Set};
+use serde::{Deserialize, Serialize};
+
+/// Tax charged on sales or purchase lines.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "tax")]
+#[serde(rename_all = "camelCase")]
+pub struct Model {
+  #[sea_orm(primary_key, auto_increment = false)]
+  pub id: Uuid,
+  #[sea_orm(column_type = "Text")]
+  pub name: String,
+  pub tax_use: TaxUse,
+  pub amount_type: TaxAmountType,
+  /// Percentage of the untaxed amount, or amount per stock unit.
+  pub amount: Decimal,
+  /// Whether line prices already include the tax.
+  pub price_include: bool,
+  /// VAT rate group the tax is declared under, if it is a VAT.
+  #[sea_orm(nullable)]
+  pub vat_group: Option<VatGroup>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  #[sea_orm(nullable)]
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  #[sea_orm(nullable)]
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+#[async_trait]
+impl ActiveModelBehavior for ActiveModel {
+  fn new() -> Self {
+    Self {
+      id: Set(Uuid::new()),
+      ..ActiveModelTrait::default()
+    }
+  }
+
+  async fn before_save<C>(self, db: &C, insert: bool) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let _ = db;
+    let mut this = self;
+    if !insert {
+      this.updated_at = Set(Some(Utc::now().into()));
+    }
+    Ok(this)
+  }
+}
+
+#[derive(Debug, DerivePartialModel, Clone, Serialize, FromQueryResult)]
+#[sea_orm(entity = "Entity")]
+#[serde(rename_all = "camelCase")]
+pub struct PartialModel {
+  pub id: Uuid,
+  pub name: String,
+  pub tax_use: TaxUse,
+  pub amount_type: TaxAmountType,
+  pub amount: Decimal,
+  pub price_include: bool,
+  pub vat_group: Option<VatGroup>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxDTO {
+  pub id: Uuid,
+  pub name: String,
+  pub tax_use: TaxUse,
+  pub amount_type: TaxAmountType,
+  pub amount: Decimal,
+  pub price_include: bool,
+  pub vat_group: Option<VatGroup>,
+  pub created_at: ChronoDateTimeWithTimeZone,
+  pub updated_at: Option<ChronoDateTimeWithTimeZone>,
+  pub archived_at: Option<ChronoDateTimeWithTimeZone>,
+}
+
+/// Amounts of one line after taxes.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineTaxDTO {
+  pub tax_id: Option<Uuid>,
+  /// Untaxed amount.
+  pub subtotal: Decimal,
+  pub tax_amount: Decimal,
+  pub total: Decimal,
+}
+
+/// Amount charged under one tax across all lines.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxBreakdownDTO {
+  pub tax_id: Uuid,
+  pub name: String,
+  pub vat_group: Option<VatGroup>,
+  /// Untaxed amount the tax was charged on.
+  pub base: Decimal,
+  pub amount: Decimal,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxComputationDTO {
+  pub currency_id: Uuid,
+  pub lines: Vec<LineTaxDTO>,
+  pub taxes: Vec<TaxBreakdownDTO>,
+  pub amount_untaxed: Decimal,
+  pub amount_tax: Decimal,
+  pub amount_total: Decimal,
+}
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tax_use")]
+pub enum TaxUse {
+  #[sea_orm(string_value = "sales")]
+  #[serde(rename = "sales")]
+  Sales,
+  #[sea_orm(string_value = "purchase")]
+  #[serde(rename = "purchase")]
+  Purchase,
+}
+
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "tax_amount_type")]
+pub enum TaxAmountType {
+  #[sea_orm(string_value = "percent")]
+  #[serde(rename = "percent")]
+  Percent,
+  #[sea_orm(string_value = "fixed")]
+  #[serde(rename = "fixed")]
+  Fixed,
+}
+
+/// Vietnamese VAT rate groups. `Exempt` goods are outside the scope of VAT,
+/// unlike zero-rated `Vat0` exports.
+#[derive(Debug, EnumIter, DeriveActiveEnum, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
+#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "vat_group")]
+pub enum VatGroup {
+  #[sea_orm(string_value = "vat_0")]
+  #[serde(rename = "vat_0")]
+  Vat0,
+  #[sea_orm(string_value = "vat_5")]
+  #[serde(rename = "vat_5")]
+  Vat5,
+  #[sea_orm(string_value = "vat_8")]
+  #[serde(rename = "vat_8")]
+  Vat8,
+  #[sea_orm(string_value = "vat_10")]
+  #[serde(rename = "vat_10")]
+  Vat10,
+  #[sea_orm(string_value = "exempt")]
+  #[serde(rename = "exempt")]
+  Exempt,
+}
+
+impl VatGroup {
+  /// Percentage charged under the group.
+  pub fn rate(self) -> Decimal {
+    match self {
+      VatGroup::Vat0 | VatGroup::Exempt => Decimal::ZERO,
+      VatGroup::Vat5 => Decimal::from(5),
+      VatGroup::Vat8 => Decimal::from(8),
+      VatGroup::Vat10 => Decimal::from(10),
+    }
+  }
+}
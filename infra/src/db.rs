@@ -0,0 +1,231 @@
+use std::{convert::Infallible, future::Future, pin::Pin, sync::Arc};
+
+use axum::{
+  async_trait,
+  extract::{FromRequestParts, Request, State},
+  http::{request::Parts, HeaderValue},
+  middleware::Next,
+  response::Response,
+};
+use sea_orm::{
+  AccessMode, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, DbErr,
+  ExecResult, IsolationLevel, QueryResult, Statement, TransactionError, TransactionTrait,
+};
+
+use crate::state::AppState;
+
+/// Header carrying the primary's WAL position after a write. Clients echo it
+/// back on later reads so they never see a replica that is behind their own
+/// changes.
+pub const CONSISTENCY_TOKEN_HEADER: &str = "x-consistency-token";
+
+/// Connection to the read replica. It only implements `ConnectionTrait`, so a
+/// command usecase taking `impl WriteConnection` cannot be handed one. The
+/// connection is shared through an `Arc` because mock connections used in
+/// tests are not `Clone`.
+#[derive(Clone, Debug)]
+pub struct ReadDb(Arc<DatabaseConnection>);
+
+/// Connection to the primary.
+#[derive(Clone, Debug)]
+pub struct WriteDb(Arc<DatabaseConnection>);
+
+/// Connections query usecases may run on: the replica, the primary or an open
+/// transaction.
+pub trait ReadConnection: ConnectionTrait {}
+
+/// Connections command usecases may run on: the primary or an open
+/// transaction.
+pub trait WriteConnection: ConnectionTrait + TransactionTrait {}
+
+impl ReadConnection for ReadDb {}
+impl ReadConnection for WriteDb {}
+impl ReadConnection for DatabaseTransaction {}
+impl WriteConnection for WriteDb {}
+impl WriteConnection for DatabaseTransaction {}
+
+impl ReadDb {
+  pub fn new(db: DatabaseConnection) -> Self {
+    Self(Arc::new(db))
+  }
+
+  /// Whether the replica has replayed the WAL up to `token`. A server that is
+  /// not in recovery compares against its own WAL position instead.
+  pub async fn has_replayed(&self, token: &str) -> Result<bool, DbErr> {
+    let statement = Statement::from_sql_and_values(
+      DbBackend::Postgres,
+      "SELECT COALESCE(pg_last_wal_replay_lsn(), pg_current_wal_lsn()) >= $1::pg_lsn AS replayed",
+      [token.into()],
+    );
+    let row = self.0.query_one(statement).await?;
+
+    match row {
+      Some(row) => row.try_get("", "replayed"),
+      None => Ok(false),
+    }
+  }
+}
+
+impl WriteDb {
+  pub fn new(db: DatabaseConnection) -> Self {
+    Self(Arc::new(db))
+  }
+
+  /// Reads served by the primary, used while the replica lags behind a
+  /// client's last write.
+  pub fn as_read(&self) -> ReadDb {
+    ReadDb(self.0.clone())
+  }
+
+  /// Current WAL position of the primary, handed to clients after a write.
+  pub async fn consistency_token(&self) -> Result<String, DbErr> {
+    let statement = Statement::from_string(
+      DbBackend::Postgres,
+      "SELECT pg_current_wal_lsn()::text AS token",
+    );
+    let row = self
+      .0
+      .query_one(statement)
+      .await?
+      .ok_or_else(|| DbErr::RecordNotFound("pg_current_wal_lsn".to_string()))?;
+
+    row.try_get("", "token")
+  }
+}
+
+macro_rules! delegate_connection {
+  ($db:ty) => {
+    #[async_trait]
+    impl ConnectionTrait for $db {
+      fn get_database_backend(&self) -> DbBackend {
+        self.0.get_database_backend()
+      }
+
+      async fn execute(&self, stmt: Statement) -> Result<ExecResult, DbErr> {
+        self.0.execute(stmt).await
+      }
+
+      async fn execute_unprepared(&self, sql: &str) -> Result<ExecResult, DbErr> {
+        self.0.execute_unprepared(sql).await
+      }
+
+      async fn query_one(&self, stmt: Statement) -> Result<Option<QueryResult>, DbErr> {
+        self.0.query_one(stmt).await
+      }
+
+      async fn query_all(&self, stmt: Statement) -> Result<Vec<QueryResult>, DbErr> {
+        self.0.query_all(stmt).await
+      }
+
+      fn support_returning(&self) -> bool {
+        self.0.support_returning()
+      }
+
+      fn is_mock_connection(&self) -> bool {
+        self.0.is_mock_connection()
+      }
+    }
+  };
+}
+
+delegate_connection!(ReadDb);
+delegate_connection!(WriteDb);
+
+#[async_trait]
+impl TransactionTrait for WriteDb {
+  async fn begin(&self) -> Result<DatabaseTransaction, DbErr> {
+    self.0.begin().await
+  }
+
+  async fn begin_with_config(
+    &self,
+    isolation_level: Option<IsolationLevel>,
+    access_mode: Option<AccessMode>,
+  ) -> Result<DatabaseTransaction, DbErr> {
+    self.0.begin_with_config(isolation_level, access_mode).await
+  }
+
+  async fn transaction<F, T, E>(&self, callback: F) -> Result<T, TransactionError<E>>
+  where
+    F: for<'c> FnOnce(
+        &'c DatabaseTransaction,
+      ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+      + Send,
+    T: Send,
+    E: std::error::Error + Send,
+  {
+    self.0.transaction(callback).await
+  }
+
+  async fn transaction_with_config<F, T, E>(
+    &self,
+    callback: F,
+    isolation_level: Option<IsolationLevel>,
+    access_mode: Option<AccessMode>,
+  ) -> Result<T, TransactionError<E>>
+  where
+    F: for<'c> FnOnce(
+        &'c DatabaseTransaction,
+      ) -> Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'c>>
+      + Send,
+    T: Send,
+    E: std::error::Error + Send,
+  {
+    self
+      .0
+      .transaction_with_config(callback, isolation_level, access_mode)
+      .await
+  }
+}
+
+/// Read connection for a request. Requests carrying a consistency token the
+/// replica has not replayed yet read from the primary instead, so a client
+/// always sees its own writes.
+pub struct Reader(pub ReadDb);
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for Reader {
+  type Rejection = Infallible;
+
+  async fn from_request_parts(
+    parts: &mut Parts,
+    state: &Arc<AppState>,
+  ) -> Result<Self, Self::Rejection> {
+    let Some(token) = parts
+      .headers
+      .get(CONSISTENCY_TOKEN_HEADER)
+      .and_then(|token| token.to_str().ok())
+    else {
+      return Ok(Reader(state.read_db.clone()));
+    };
+
+    match state.read_db.has_replayed(token).await {
+      Ok(true) => Ok(Reader(state.read_db.clone())),
+      Ok(false) | Err(_) => Ok(Reader(state.write_db.as_read())),
+    }
+  }
+}
+
+/// Middleware attaching the primary's WAL position to successful responses of
+/// mutating requests.
+pub async fn attach_consistency_token(
+  State(state): State<Arc<AppState>>,
+  request: Request,
+  next: Next,
+) -> Response {
+  let is_write = !request.method().is_safe();
+  let mut response = next.run(request).await;
+  if !is_write || !response.status().is_success() {
+    return response;
+  }
+
+  // Without a token the client simply keeps reading from the replica.
+  let token = state.write_db.consistency_token().await.ok();
+  if let Some(token) = token.and_then(|token| HeaderValue::from_str(&token).ok()) {
+    response
+      .headers_mut()
+      .insert(CONSISTENCY_TOKEN_HEADER, token);
+  }
+
+  response
+}
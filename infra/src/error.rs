@@ -0,0 +1,355 @@
+use std::{collections::BTreeMap, fmt};
+
+use axum::{
+  http::StatusCode,
+  response::{IntoResponse, Response},
+  Json,
+};
+use sea_orm::{DbErr, RuntimeErr, SqlxError, SqlxPostgresError};
+use serde::Serialize;
+
+use crate::response::ErrorResponse;
+
+const UNIQUE_VIOLATION: &str = "23505";
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+const CHECK_VIOLATION: &str = "23514";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+  BadRequest,
+  NotFound,
+  Validation,
+  Conflict,
+  ForeignKeyViolation,
+  Internal,
+}
+
+impl ErrorKind {
+  pub fn status(&self) -> StatusCode {
+    match self {
+      ErrorKind::BadRequest => StatusCode::BAD_REQUEST,
+      ErrorKind::NotFound => StatusCode::NOT_FOUND,
+      ErrorKind::Validation | ErrorKind::ForeignKeyViolation => StatusCode::UNPROCESSABLE_ENTITY,
+      ErrorKind::Conflict => StatusCode::CONFLICT,
+      ErrorKind::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+  }
+}
+
+/// Error shared by every usecase. Usecase-specific error enums convert into it
+/// inside their `IntoResponse`, so status codes and the `ErrorResponse` shape
+/// are decided in one place.
+#[derive(Debug, Clone)]
+pub struct AppError {
+  pub kind: ErrorKind,
+  pub code: String,
+  pub source: Option<String>,
+  pub fields: BTreeMap<String, Vec<String>>,
+  pub details: Option<serde_json::Value>,
+}
+
+impl AppError {
+  pub fn new(kind: ErrorKind, code: impl Into<String>) -> Self {
+    Self {
+      kind,
+      code: code.into(),
+      source: None,
+      fields: BTreeMap::new(),
+      details: None,
+    }
+  }
+
+  pub fn bad_request(code: impl Into<String>) -> Self {
+    Self::new(ErrorKind::BadRequest, code)
+  }
+
+  pub fn not_found(code: impl Into<String>) -> Self {
+    Self::new(ErrorKind::NotFound, code)
+  }
+
+  pub fn validation(code: impl Into<String>) -> Self {
+    Self::new(ErrorKind::Validation, code)
+  }
+
+  pub fn conflict(code: impl Into<String>) -> Self {
+    Self::new(ErrorKind::Conflict, code)
+  }
+
+  pub fn foreign_key_violation(code: impl Into<String>) -> Self {
+    Self::new(ErrorKind::ForeignKeyViolation, code)
+  }
+
+  pub fn internal() -> Self {
+    Self::new(ErrorKind::Internal, "internal_server_error")
+  }
+
+  pub fn status(&self) -> StatusCode {
+    self.kind.status()
+  }
+
+  pub fn with_source(mut self, source: impl Into<String>) -> Self {
+    self.source = Some(source.into());
+    self
+  }
+
+  pub fn with_details(mut self, details: serde_json::Value) -> Self {
+    self.details = Some(details);
+    self
+  }
+
+  pub fn with_field(mut self, field: impl Into<String>, message: impl Into<String>) -> Self {
+    self
+      .fields
+      .entry(field.into())
+      .or_default()
+      .push(message.into());
+    self
+  }
+}
+
+impl fmt::Display for AppError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.code)
+  }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<DbErr> for AppError {
+  fn from(err: DbErr) -> Self {
+    match err {
+      DbErr::RecordNotFound(_) | DbErr::RecordNotUpdated => AppError::not_found("record_not_found"),
+      err => constraint_violation(&err).unwrap_or_else(|| {
+        tracing::error!(error = %err, "unmapped database error");
+        AppError::internal()
+      }),
+    }
+  }
+}
+
+impl IntoResponse for AppError {
+  fn into_response(self) -> Response {
+    let status = self.status();
+    let fields = (!self.fields.is_empty()).then_some(self.fields);
+
+    (
+      status,
+      Json(ErrorResponse {
+        ok: false,
+        code: self.code,
+        source: self.source,
+        fields,
+        details: self.details,
+      }),
+    )
+      .into_response()
+  }
+}
+
+/// Maps Postgres unique, foreign-key and check violations to their error kind,
+/// naming the offending columns in `fields`.
+fn constraint_violation(err: &DbErr) -> Option<AppError> {
+  let (DbErr::Exec(RuntimeErr::SqlxError(SqlxError::Database(database_error)))
+  | DbErr::Query(RuntimeErr::SqlxError(SqlxError::Database(database_error)))) = err
+  else {
+    return None;
+  };
+  let pg_error = database_error.try_downcast_ref::<SqlxPostgresError>()?;
+  let detail = pg_error.detail().unwrap_or_default();
+  let columns = match key_columns(detail) {
+    columns if !columns.is_empty() => columns,
+    _ => pg_error
+      .constraint()
+      .and_then(|constraint| constraint.rsplit('-').next())
+      .map(|column| vec![column.to_string()])
+      .unwrap_or_default(),
+  };
+
+  let (error, message) = match pg_error.code() {
+    UNIQUE_VIOLATION => (AppError::conflict("unique_violation"), "already_exists"),
+    FOREIGN_KEY_VIOLATION if detail.contains("is still referenced") => {
+      (AppError::conflict("record_in_use"), "in_use")
+    }
+    FOREIGN_KEY_VIOLATION => (
+      AppError::foreign_key_violation("foreign_key_violation"),
+      "not_found",
+    ),
+    CHECK_VIOLATION => (AppError::validation("check_violation"), "invalid"),
+    _ => return None,
+  };
+
+  let error = columns.iter().fold(error, |error, column| {
+    error.with_field(camel_case(column), message)
+  });
+
+  Some(match pg_error.constraint() {
+    Some(constraint) => error.with_details(serde_json::json!({ "constraint": constraint })),
+    None => error,
+  })
+}
+
+/// Column names from a Postgres detail such as `Key (uom_id)=(...) is not present`.
+/// Expression index keys like `lower(f_unaccent(name::text))` resolve to the
+/// column they wrap.
+fn key_columns(detail: &str) -> Vec<String> {
+  let Some((keys, _)) = detail
+    .strip_prefix("Key (")
+    .and_then(|rest| rest.split_once(")="))
+  else {
+    return vec![];
+  };
+
+  let mut expressions = vec![];
+  let mut depth = 0;
+  let mut start = 0;
+  for (index, c) in keys.char_indices() {
+    match c {
+      '(' => depth += 1,
+      ')' => depth -= 1,
+      ',' if depth == 0 => {
+        expressions.push(&keys[start..index]);
+        start = index + 1;
+      }
+      _ => {}
+    }
+  }
+  expressions.push(&keys[start..]);
+
+  expressions
+    .into_iter()
+    .filter_map(expression_column)
+    .collect()
+}
+
+/// First identifier in `expression` that is neither a function name, a type
+/// cast nor part of a string literal.
+fn expression_column(expression: &str) -> Option<String> {
+  let chars = expression.chars().collect::<Vec<_>>();
+  let mut index = 0;
+  let mut in_literal = false;
+
+  while index < chars.len() {
+    let c = chars[index];
+    if c == '\'' {
+      in_literal = !in_literal;
+      index += 1;
+      continue;
+    }
+    if in_literal || !(c.is_ascii_alphabetic() || c == '_') {
+      index += 1;
+      continue;
+    }
+
+    let start = index;
+    while index < chars.len() && (chars[index].is_ascii_alphanumeric() || chars[index] == '_') {
+      index += 1;
+    }
+    let is_function = chars.get(index) == Some(&'(');
+    let is_cast = start >= 2 && chars[start - 2..start] == [':', ':'];
+    if !is_function && !is_cast {
+      return Some(chars[start..index].iter().collect());
+    }
+  }
+
+  None
+}
+
+fn camel_case(column: &str) -> String {
+  let mut result = String::with_capacity(column.len());
+  let mut upper = false;
+
+  for c in column.chars() {
+    if c == '_' {
+      upper = true;
+    } else if upper {
+      result.extend(c.to_uppercase());
+      upper = false;
+    } else {
+      result.push(c);
+    }
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn key_columns_reads_plain_unique_keys() {
+    assert_eq!(
+      key_columns("Key (code)=(VND) already exists."),
+      vec!["code"]
+    );
+    assert_eq!(
+      key_columns("Key (partner_id, product_id)=(0193a2b4-7c1e-7d2f-9a10-3b5c6d7e8f90, 0193a2b4-7c1e-7d2f-9a10-3b5c6d7e8f91) already exists."),
+      vec!["partner_id", "product_id"]
+    );
+  }
+
+  #[test]
+  fn key_columns_unwraps_unaccent_expression_indexes() {
+    assert_eq!(
+      key_columns("Key (lower(f_unaccent(name)))=(hộp carton) already exists."),
+      vec!["name"]
+    );
+    assert_eq!(
+      key_columns("Key (lower(f_unaccent(name::text)))=(hộp carton) already exists."),
+      vec!["name"]
+    );
+    assert_eq!(
+      key_columns(
+        "Key (attribute_id, lower(f_unaccent(value)))=(0193a2b4-7c1e-7d2f-9a10-3b5c6d7e8f90, đỏ) already exists."
+      ),
+      vec!["attribute_id", "value"]
+    );
+  }
+
+  #[test]
+  fn key_columns_reads_foreign_key_details() {
+    assert_eq!(
+      key_columns(
+        r#"Key (uom_id)=(0193a2b4-7c1e-7d2f-9a10-3b5c6d7e8f90) is not present in table "uom"."#
+      ),
+      vec!["uom_id"]
+    );
+    assert_eq!(
+      key_columns(
+        r#"Key (id)=(0193a2b4-7c1e-7d2f-9a10-3b5c6d7e8f90) is still referenced from table "product"."#
+      ),
+      vec!["id"]
+    );
+  }
+
+  #[test]
+  fn key_columns_is_empty_for_check_violations() {
+    assert!(key_columns(
+      "Failing row contains (0193a2b4-7c1e-7d2f-9a10-3b5c6d7e8f90, Box, -1.000000, 2025-01-15 00:00:00+00, null)."
+    )
+    .is_empty());
+    assert!(key_columns("").is_empty());
+  }
+
+  #[test]
+  fn expression_column_skips_functions_casts_and_literals() {
+    assert_eq!(expression_column("name"), Some("name".to_string()));
+    assert_eq!(
+      expression_column("lower(f_unaccent(name::text))"),
+      Some("name".to_string())
+    );
+    assert_eq!(
+      expression_column("COALESCE('none'::text, parent_category_id::text)"),
+      Some("parent_category_id".to_string())
+    );
+    assert_eq!(expression_column("lower('abc')"), None);
+  }
+
+  #[test]
+  fn camel_case_converts_snake_case_columns() {
+    assert_eq!(camel_case("uom_id"), "uomId");
+    assert_eq!(camel_case("product_template_id"), "productTemplateId");
+    assert_eq!(camel_case("name"), "name");
+  }
+}
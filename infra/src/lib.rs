@@ -1,4 +1,7 @@
+pub mod db;
+pub mod error;
 pub mod response;
 pub mod state;
 pub mod util;
 pub mod uuid;
+pub mod validation;
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use axum::{response::IntoResponse, response::Response, Json};
 use serde::Serialize;
 
@@ -8,6 +10,10 @@ pub struct ErrorResponse {
   pub ok: bool,
   pub code: String,
   pub source: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fields: Option<BTreeMap<String, Vec<String>>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub details: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -36,6 +42,51 @@ where
   }
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPaginationMeta {
+  pub per_page: u64,
+  pub next_cursor: Option<String>,
+  pub prev_cursor: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub total: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPaginatedResponse<T> {
+  pub ok: bool,
+  pub data: Vec<T>,
+  pub meta: CursorPaginationMeta,
+}
+
+impl<T> IntoResponse for CursorPaginatedResponse<T>
+where
+  T: Serialize,
+{
+  fn into_response(self) -> Response {
+    Json(self).into_response()
+  }
+}
+
+/// Response of a `*.list` endpoint, which pages either by offset or by cursor.
+pub enum ListResponse<T> {
+  Paginated(PaginatedResponse<T>),
+  CursorPaginated(CursorPaginatedResponse<T>),
+}
+
+impl<T> IntoResponse for ListResponse<T>
+where
+  T: Serialize,
+{
+  fn into_response(self) -> Response {
+    match self {
+      ListResponse::Paginated(response) => response.into_response(),
+      ListResponse::CursorPaginated(response) => response.into_response(),
+    }
+  }
+}
+
 #[derive(Serialize)]
 pub struct CreateResponse {
   pub ok: bool,
@@ -1,13 +1,13 @@
-use sea_orm::DatabaseConnection;
+use crate::db::{ReadDb, WriteDb};
 
 #[derive(Clone)]
 pub struct AppState {
-  pub write_db: DatabaseConnection,
-  pub read_db: DatabaseConnection,
+  pub write_db: WriteDb,
+  pub read_db: ReadDb,
 }
 
 impl AppState {
-  pub fn new(write_db: DatabaseConnection, read_db: DatabaseConnection) -> Self {
+  pub fn new(write_db: WriteDb, read_db: ReadDb) -> Self {
     Self { write_db, read_db }
   }
 }
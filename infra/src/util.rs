@@ -1,11 +1,47 @@
 use crate::response::ErrorResponse;
 use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Deserializer};
 
 pub fn error(code: String, source: Option<String>) -> impl IntoResponse {
   Json(ErrorResponse {
     ok: false,
     code,
     source,
+    fields: None,
+    details: None,
   })
   .into_response()
 }
+
+/// Deserializes a nullable field that may also be omitted: `Some(None)` when
+/// it is `null`, `None` (through `#[serde(default)]`) when it is left out, so
+/// updates can tell "clear" from "keep".
+pub fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de>,
+{
+  Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Deserialize)]
+  struct Payload {
+    #[serde(default, deserialize_with = "double_option")]
+    value: Option<Option<u32>>,
+  }
+
+  fn value(json: &str) -> Option<Option<u32>> {
+    serde_json::from_str::<Payload>(json).unwrap().value
+  }
+
+  #[test]
+  fn double_option_tells_omitted_from_null() {
+    assert_eq!(value("{}"), None);
+    assert_eq!(value(r#"{"value":null}"#), Some(None));
+    assert_eq!(value(r#"{"value":3}"#), Some(Some(3)));
+  }
+}
@@ -1,9 +1,10 @@
 use sea_orm::TryFromU64;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use short_uuid::ShortUuid;
+use std::{fmt, str::FromStr};
 use uuid::Uuid as OriginalUuid;
 
-#[derive(Debug, Clone, Eq, PartialEq, Copy, Hash)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Copy, Hash)]
 pub struct Uuid(OriginalUuid);
 
 impl<'de> Deserialize<'de> for Uuid {
@@ -33,9 +34,31 @@ impl Uuid {
   }
 }
 
-impl Into<OriginalUuid> for Uuid {
-  fn into(self) -> OriginalUuid {
-    self.0.into()
+impl Default for Uuid {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl FromStr for Uuid {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    ShortUuid::parse_str(s)
+      .map(|short_uuid| Uuid(short_uuid.to_uuid()))
+      .map_err(|e| e.to_string())
+  }
+}
+
+impl fmt::Display for Uuid {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", ShortUuid::from_uuid(&self.0))
+  }
+}
+
+impl From<Uuid> for OriginalUuid {
+  fn from(v: Uuid) -> Self {
+    v.0
   }
 }
 
@@ -51,9 +74,9 @@ impl AsRef<Uuid> for Uuid {
   }
 }
 
-impl Into<sea_orm::Value> for Uuid {
-  fn into(self) -> sea_orm::Value {
-    self.0.into()
+impl From<Uuid> for sea_orm::Value {
+  fn from(v: Uuid) -> Self {
+    v.0.into()
   }
 }
 
@@ -75,7 +98,7 @@ impl TryFromU64 for Uuid {
 
 impl sea_orm::sea_query::ValueType for Uuid {
   fn try_from(v: sea_orm::Value) -> Result<Self, sea_orm::sea_query::ValueTypeErr> {
-    <OriginalUuid as sea_orm::sea_query::ValueType>::try_from(v).map(|v| Uuid(v))
+    <OriginalUuid as sea_orm::sea_query::ValueType>::try_from(v).map(Uuid)
   }
 
   fn type_name() -> String {
@@ -0,0 +1,372 @@
+use std::collections::{BTreeMap, HashSet};
+
+use axum::{
+  async_trait,
+  extract::{rejection::JsonRejection, FromRequest, Request},
+  response::{IntoResponse, Response},
+  Json,
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::error::AppError;
+
+/// Request payloads that can check themselves before a usecase runs.
+pub trait Validate {
+  fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// Field → messages map collected while validating a payload. Field names use
+/// the JSON spelling so the frontend can bind them to form inputs, with
+/// `variants[0].price` style paths for nested items.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationErrors {
+  fields: BTreeMap<String, Vec<String>>,
+}
+
+impl ValidationErrors {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Runs every rule against `field` and records the messages of the ones
+  /// that fail.
+  pub fn field<I>(mut self, field: impl Into<String>, rules: I) -> Self
+  where
+    I: IntoIterator<Item = Rule>,
+  {
+    let messages = rules
+      .into_iter()
+      .filter_map(Result::err)
+      .map(str::to_string)
+      .collect::<Vec<_>>();
+    if !messages.is_empty() {
+      self
+        .fields
+        .entry(field.into())
+        .or_default()
+        .extend(messages);
+    }
+    self
+  }
+
+  /// Validates every item of a list with `f`, prefixing its fields with
+  /// `field[index]`.
+  pub fn each<T, F>(self, field: &str, items: &[T], f: F) -> Self
+  where
+    F: Fn(&T) -> ValidationErrors,
+  {
+    items
+      .iter()
+      .enumerate()
+      .fold(self, |errors, (index, item)| {
+        errors.nested(&format!("{}[{}]", field, index), f(item))
+      })
+  }
+
+  pub fn nested(mut self, prefix: &str, other: ValidationErrors) -> Self {
+    for (field, messages) in other.fields {
+      self
+        .fields
+        .entry(format!("{}.{}", prefix, field))
+        .or_default()
+        .extend(messages);
+    }
+    self
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.fields.is_empty()
+  }
+
+  pub fn fields(&self) -> &BTreeMap<String, Vec<String>> {
+    &self.fields
+  }
+
+  pub fn into_result(self) -> Result<(), ValidationErrors> {
+    if self.is_empty() {
+      Ok(())
+    } else {
+      Err(self)
+    }
+  }
+}
+
+impl From<ValidationErrors> for AppError {
+  fn from(errors: ValidationErrors) -> Self {
+    errors.fields.into_iter().fold(
+      AppError::validation("validation_failed"),
+      |error, (field, messages)| {
+        messages.into_iter().fold(error, |error, message| {
+          error.with_field(field.clone(), message)
+        })
+      },
+    )
+  }
+}
+
+impl IntoResponse for ValidationErrors {
+  fn into_response(self) -> Response {
+    AppError::from(self).into_response()
+  }
+}
+
+/// Outcome of a single rule; the error is the message reported for the field.
+pub type Rule = Result<(), &'static str>;
+
+pub mod rules {
+  use sea_orm::prelude::Decimal;
+
+  use super::{HashSet, Rule};
+
+  pub fn required(value: &str) -> Rule {
+    if value.trim().is_empty() {
+      Err("required")
+    } else {
+      Ok(())
+    }
+  }
+
+  pub fn non_negative(value: Decimal) -> Rule {
+    if value.is_sign_negative() && !value.is_zero() {
+      Err("must_not_be_negative")
+    } else {
+      Ok(())
+    }
+  }
+
+  pub fn positive(value: Decimal) -> Rule {
+    if value > Decimal::ZERO {
+      Ok(())
+    } else {
+      Err("must_be_positive")
+    }
+  }
+
+  pub fn not_empty<T>(items: &[T]) -> Rule {
+    if items.is_empty() {
+      Err("required")
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Fails when `condition` holds, reporting `message`.
+  pub fn reject_if(condition: bool, message: &'static str) -> Rule {
+    if condition {
+      Err(message)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Indexes of the values that repeat an earlier one, compared trimmed and
+  /// case-insensitively.
+  pub fn duplicate_indexes<'a, I>(values: I) -> Vec<usize>
+  where
+    I: IntoIterator<Item = &'a str>,
+  {
+    let mut seen = HashSet::new();
+    values
+      .into_iter()
+      .enumerate()
+      .filter(|(_, value)| !seen.insert(value.trim().to_lowercase()))
+      .map(|(index, _)| index)
+      .collect()
+  }
+}
+
+/// Bodies that are not JSON are bad requests. JSON of the wrong shape is a
+/// validation error on the field serde stopped at: `required` for a missing
+/// field, `invalid` otherwise, with serde's message in the details.
+impl From<JsonRejection> for AppError {
+  fn from(rejection: JsonRejection) -> Self {
+    let JsonRejection::JsonDataError(err) = rejection else {
+      return AppError::bad_request("invalid_json")
+        .with_details(json!({ "message": rejection.body_text() }));
+    };
+
+    let error = AppError::validation("invalid_payload");
+    let Some(err) = std::error::Error::source(&err)
+      .and_then(std::error::Error::source)
+      .and_then(|source| source.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>())
+    else {
+      return error.with_details(json!({ "message": err.body_text() }));
+    };
+
+    let path = err.path().to_string();
+    let message = err.inner().to_string();
+    let missing_field = message
+      .strip_prefix("missing field `")
+      .and_then(|rest| rest.split_once('`'))
+      .map(|(field, _)| field);
+    let error = match (path.as_str(), missing_field) {
+      (".", Some(field)) => error.with_field(field, "required"),
+      (path, Some(field)) => error.with_field(format!("{}.{}", path, field), "required"),
+      (".", None) => error,
+      (path, None) => error.with_field(path, "invalid"),
+    };
+
+    error.with_details(json!({ "message": message }))
+  }
+}
+
+/// `Json` extractor that also runs `Validate` and rejects invalid payloads
+/// with a 422 `ErrorResponse` listing the failing fields.
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+  T: DeserializeOwned + Validate,
+  S: Send + Sync,
+{
+  type Rejection = Response;
+
+  async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+    let Json(value) = Json::<T>::from_request(req, state)
+      .await
+      .map_err(|rejection| AppError::from(rejection).into_response())?;
+    value.validate().map_err(IntoResponse::into_response)?;
+
+    Ok(ValidatedJson(value))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use axum::{body::Body, http::header::CONTENT_TYPE};
+  use sea_orm::prelude::Decimal;
+  use serde::Deserialize;
+
+  use super::*;
+  use crate::error::ErrorKind;
+
+  #[derive(Debug, Deserialize)]
+  #[allow(dead_code)]
+  struct Payload {
+    name: String,
+    lines: Vec<Line>,
+  }
+
+  #[derive(Debug, Deserialize)]
+  #[allow(dead_code)]
+  struct Line {
+    quantity: Decimal,
+  }
+
+  async fn rejection(content_type: &str, body: &str) -> AppError {
+    let req = Request::builder()
+      .header(CONTENT_TYPE, content_type)
+      .body(Body::from(body.to_string()))
+      .unwrap();
+    let rejection = Json::<Payload>::from_request(req, &()).await.err().unwrap();
+
+    AppError::from(rejection)
+  }
+
+  fn fields(error: &AppError) -> Vec<(&str, &str)> {
+    error
+      .fields
+      .iter()
+      .flat_map(|(field, messages)| {
+        messages
+          .iter()
+          .map(move |message| (field.as_str(), message.as_str()))
+      })
+      .collect()
+  }
+
+  #[tokio::test]
+  async fn missing_field_is_required_at_its_path() {
+    let top = rejection("application/json", r#"{"lines": []}"#).await;
+    assert_eq!(top.code, "invalid_payload");
+    assert_eq!(fields(&top), [("name", "required")]);
+
+    let nested = rejection("application/json", r#"{"name": "Box", "lines": [{}]}"#).await;
+    assert_eq!(fields(&nested), [("lines[0].quantity", "required")]);
+  }
+
+  #[tokio::test]
+  async fn wrong_type_is_invalid_at_its_path() {
+    let error = rejection(
+      "application/json",
+      r#"{"name": "Box", "lines": [{"quantity": 1}, {"quantity": true}]}"#,
+    )
+    .await;
+
+    assert_eq!(error.kind, ErrorKind::Validation);
+    assert_eq!(fields(&error), [("lines[1].quantity", "invalid")]);
+    assert!(error.details.is_some());
+  }
+
+  #[tokio::test]
+  async fn malformed_json_is_a_bad_request() {
+    let syntax = rejection("application/json", r#"{"name": "#).await;
+    assert_eq!(syntax.kind, ErrorKind::BadRequest);
+    assert_eq!(syntax.code, "invalid_json");
+
+    let content_type = rejection("text/plain", r#"{"name": "Box", "lines": []}"#).await;
+    assert_eq!(content_type.kind, ErrorKind::BadRequest);
+  }
+
+  #[test]
+  fn required_rejects_blank_strings() {
+    assert_eq!(rules::required("Box"), Ok(()));
+    assert_eq!(rules::required("  "), Err("required"));
+    assert_eq!(rules::required(""), Err("required"));
+  }
+
+  #[test]
+  fn non_negative_accepts_zero() {
+    assert_eq!(rules::non_negative(Decimal::ZERO), Ok(()));
+    assert_eq!(rules::non_negative(-Decimal::ZERO), Ok(()));
+    assert_eq!(rules::non_negative(Decimal::ONE), Ok(()));
+    assert_eq!(
+      rules::non_negative(-Decimal::ONE),
+      Err("must_not_be_negative")
+    );
+  }
+
+  #[test]
+  fn positive_rejects_zero() {
+    assert_eq!(rules::positive(Decimal::ONE), Ok(()));
+    assert_eq!(rules::positive(Decimal::ZERO), Err("must_be_positive"));
+    assert_eq!(rules::positive(-Decimal::ONE), Err("must_be_positive"));
+  }
+
+  #[test]
+  fn not_empty_requires_an_item() {
+    assert_eq!(rules::not_empty(&[1]), Ok(()));
+    assert_eq!(rules::not_empty::<i32>(&[]), Err("required"));
+  }
+
+  #[test]
+  fn reject_if_reports_its_message() {
+    assert_eq!(rules::reject_if(false, "taken"), Ok(()));
+    assert_eq!(rules::reject_if(true, "taken"), Err("taken"));
+  }
+
+  #[test]
+  fn duplicate_indexes_compares_trimmed_and_case_insensitively() {
+    assert_eq!(
+      rules::duplicate_indexes(["Red", "Blue", " red ", "BLUE", "Green"]),
+      vec![2, 3]
+    );
+    assert!(rules::duplicate_indexes(["Red", "Blue"]).is_empty());
+  }
+
+  #[test]
+  fn errors_nest_under_indexed_paths() {
+    let errors = ValidationErrors::new()
+      .field("name", [rules::required("")])
+      .each("lines", &[Decimal::ONE, Decimal::ZERO], |quantity| {
+        ValidationErrors::new().field("quantity", [rules::positive(*quantity)])
+      });
+
+    assert_eq!(
+      errors.fields().keys().collect::<Vec<_>>(),
+      ["lines[1].quantity", "name"]
+    );
+  }
+}
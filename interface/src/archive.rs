@@ -0,0 +1,36 @@
+use axum::{extract::State, Json};
+use infra::{response::OkResponse, state::AppState};
+use service::archive::{
+  Archivable, ArchiveError, ArchivePayload, ArchiveUsecase, UnarchiveUsecase,
+};
+use std::sync::Arc;
+
+/// Archives the `E` named in the payload; routed once per entity, e.g.
+/// `post(archive::<tax::Entity>)`.
+pub async fn archive<E>(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<ArchivePayload>,
+) -> Result<OkResponse, ArchiveError<E>>
+where
+  E: Archivable,
+{
+  let usecase = ArchiveUsecase::<E>::new(payload.id);
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+pub async fn unarchive<E>(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<ArchivePayload>,
+) -> Result<OkResponse, ArchiveError<E>>
+where
+  E: Archivable,
+{
+  let usecase = UnarchiveUsecase::<E>::new(payload.id);
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
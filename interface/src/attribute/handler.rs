@@ -1,7 +1,6 @@
 use axum::{
   extract::{Path, Query, State},
   http::StatusCode,
-  Json,
 };
 use axum_macros::debug_handler;
 use domain::product::{
@@ -9,10 +8,16 @@ use domain::product::{
   attribute_option,
 };
 use infra::{
-  response::{CreateResponse, FindOneResponse, OkResponse, PaginatedResponse, QueryResponse},
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse, QueryResponse,
+  },
   state::AppState,
   uuid::Uuid,
+  validation::ValidatedJson,
 };
+use service::list_query::uses_cursor;
 use service::product::{
   update_attribute_usecase::{UpdateAttributeError, UpdateAttributeUsecase},
   CreateAttributeError, CreateAttributePayload, CreateAttributeUsecase, FindAttributeError,
@@ -25,7 +30,7 @@ use std::sync::Arc;
 #[debug_handler]
 pub async fn create_attribute(
   State(state): State<Arc<AppState>>,
-  Json(payload): Json<CreateAttributePayload>,
+  ValidatedJson(payload): ValidatedJson<CreateAttributePayload>,
 ) -> Result<(StatusCode, CreateResponse), CreateAttributeError> {
   let usecase = CreateAttributeUsecase {
     name: payload.name,
@@ -43,32 +48,50 @@ pub async fn create_attribute(
   ))
 }
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn list_paginated_attributes(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Query(query): Query<ListPaginatedAttributesParams>,
-) -> Result<Json<PaginatedResponse<attribute::PartialModel>>, ListPaginatedAttributesError> {
+) -> Result<ListResponse<attribute::PartialModel>, ListPaginatedAttributesError> {
   let usecase = ListPaginatedAttributesUsecase {
     page: query.page,
     per_page: query.per_page,
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
   };
 
-  let (attributes, pagination_meta) = usecase.invoke(state.read_db.clone()).await?;
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (attributes, meta) = usecase.invoke_cursor(db).await?;
 
-  Ok(Json(PaginatedResponse {
-    data: attributes,
-    meta: pagination_meta,
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse {
+      ok: true,
+      data: attributes,
+      meta,
+    }));
+  }
+
+  let (attributes, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse {
     ok: true,
+    data: attributes,
+    meta,
   }))
 }
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn find_attribute(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Path(id): Path<Uuid>,
 ) -> Result<FindOneResponse<AttributeDTO>, FindAttributeError> {
   let usecase = FindAttributeUsecase { id };
-  let attribute = usecase.invoke(state.read_db.clone()).await?;
+  let attribute = usecase.invoke(db).await?;
   Ok(FindOneResponse::<AttributeDTO> {
     ok: true,
     data: attribute,
@@ -78,24 +101,25 @@ pub async fn find_attribute(
 #[debug_handler]
 pub async fn update_attribute(
   State(state): State<Arc<AppState>>,
-  Json(payload): Json<UpdateAttributePayload>,
+  ValidatedJson(payload): ValidatedJson<UpdateAttributePayload>,
 ) -> Result<OkResponse, UpdateAttributeError> {
   let usecase = UpdateAttributeUsecase {
     id: payload.id,
     name: payload.name,
     attribute_options: payload.attribute_options,
+    force_archive: payload.force_archive,
   };
-  usecase.invoke(state.read_db.clone()).await?;
+  usecase.invoke(state.write_db.clone()).await?;
   Ok(OkResponse { ok: true })
 }
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn find_options_by_attribute_id(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Path(attribute_id): Path<Uuid>,
 ) -> Result<QueryResponse<Vec<attribute_option::PartialModel>>, FindOptionsByAttributeIdError> {
   let usecase = FindOptionsByAttributeIdUsecase { attribute_id };
-  let options = usecase.invoke(state.read_db.clone()).await?;
+  let options = usecase.invoke(db).await?;
   Ok(QueryResponse::<Vec<attribute_option::PartialModel>> {
     ok: true,
     data: options,
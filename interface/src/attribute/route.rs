@@ -4,8 +4,11 @@ use axum::{
   routing::{get, post},
   Router,
 };
+use domain::product::attribute::Entity as Attribute;
 use infra::state::AppState;
 
+use crate::archive::{archive, unarchive};
+
 use super::handler::{
   create_attribute, find_attribute, find_options_by_attribute_id, list_paginated_attributes,
   update_attribute,
@@ -13,6 +16,7 @@ use super::handler::{
 pub struct AttributeRouter {}
 
 impl AttributeRouter {
+  #[allow(clippy::new_ret_no_self)]
   pub fn new() -> Router<Arc<AppState>> {
     Router::new()
       .route("/attributes.create", post(create_attribute))
@@ -23,5 +27,7 @@ impl AttributeRouter {
         "/attributes.find_options/:attribute_id",
         get(find_options_by_attribute_id),
       )
+      .route("/attributes.archive", post(archive::<Attribute>))
+      .route("/attributes.unarchive", post(unarchive::<Attribute>))
   }
 }
@@ -1,27 +1,138 @@
-use axum::extract::{Query, State};
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+};
 use axum_macros::debug_handler;
-use domain::product::category::PartialModel as Category;
-use infra::{response::PaginatedResponse, state::AppState};
+use domain::product::category::{CategoryDTO, CategoryTreeNode, PartialModel as Category};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse, QueryResponse,
+  },
+  state::AppState,
+  validation::ValidatedJson,
+};
+use service::list_query::uses_cursor;
 use service::product::{
-  ListPaginatedCategoriesError, ListPaginatedCategoriesParams, ListPaginatedCategoriesUsecase,
+  CreateCategoryError, CreateCategoryPayload, CreateCategoryUsecase, FindCategoryError,
+  FindCategoryParams, FindCategoryUsecase, ListCategoryTreeError, ListCategoryTreeParams,
+  ListCategoryTreeUsecase, ListPaginatedCategoriesError, ListPaginatedCategoriesParams,
+  ListPaginatedCategoriesUsecase, UpdateCategoryError, UpdateCategoryPayload,
+  UpdateCategoryUsecase,
 };
 use std::sync::Arc;
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn list_paginated_categories(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Query(query): Query<ListPaginatedCategoriesParams>,
-) -> Result<PaginatedResponse<Category>, ListPaginatedCategoriesError> {
+) -> Result<ListResponse<Category>, ListPaginatedCategoriesError> {
   let usecase = ListPaginatedCategoriesUsecase {
     page: Some(query.page.unwrap_or(1)),
     per_page: Some(query.per_page.unwrap_or(30)),
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
   };
 
-  let (categories, meta) = usecase.invoke(state.read_db.clone()).await?;
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (categories, meta) = usecase.invoke_cursor(db).await?;
 
-  Ok(PaginatedResponse::<Category> {
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse::<
+      Category,
+    > {
+      ok: true,
+      data: categories,
+      meta,
+    }));
+  }
+
+  let (categories, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse::<Category> {
     ok: true,
     data: categories,
     meta,
+  }))
+}
+
+#[debug_handler]
+pub async fn create_category(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateCategoryPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateCategoryError> {
+  let usecase = CreateCategoryUsecase {
+    name: payload.name,
+    parent_category_id: payload.parent_category_id,
+    sales_tax_id: payload.sales_tax_id,
+    purchase_tax_id: payload.purchase_tax_id,
+    costing_method: payload.costing_method,
+  };
+
+  let category = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: category.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_category(
+  Reader(db): Reader,
+  Path(path): Path<FindCategoryParams>,
+) -> Result<FindOneResponse<CategoryDTO>, FindCategoryError> {
+  let usecase = FindCategoryUsecase { id: path.id };
+
+  let category = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<CategoryDTO> {
+    ok: true,
+    data: category,
+  })
+}
+
+#[debug_handler]
+pub async fn update_category(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdateCategoryPayload>,
+) -> Result<OkResponse, UpdateCategoryError> {
+  let usecase = UpdateCategoryUsecase {
+    id: payload.id,
+    name: payload.name,
+    parent_category_id: payload.parent_category_id,
+    sales_tax_id: payload.sales_tax_id,
+    purchase_tax_id: payload.purchase_tax_id,
+    costing_method: payload.costing_method,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_category_tree(
+  Reader(db): Reader,
+  Query(query): Query<ListCategoryTreeParams>,
+) -> Result<QueryResponse<Vec<CategoryTreeNode>>, ListCategoryTreeError> {
+  let usecase = ListCategoryTreeUsecase {
+    include_archived: query.include_archived,
+  };
+
+  let tree = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<CategoryTreeNode>> {
+    ok: true,
+    data: tree,
   })
 }
@@ -1,13 +1,29 @@
 use std::sync::Arc;
 
-use axum::{routing::get, Router};
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use domain::product::category::Entity as Category;
 use infra::state::AppState;
 
-use super::handler::list_paginated_categories;
+use crate::archive::{archive, unarchive};
+
+use super::handler::{
+  create_category, find_category, list_category_tree, list_paginated_categories, update_category,
+};
 pub struct CategoryRouter {}
 
 impl CategoryRouter {
+  #[allow(clippy::new_ret_no_self)]
   pub fn new() -> Router<Arc<AppState>> {
-    Router::new().route("/categories.list", get(list_paginated_categories))
+    Router::new()
+      .route("/categories.list", get(list_paginated_categories))
+      .route("/categories.create", post(create_category))
+      .route("/categories.find/:id", get(find_category))
+      .route("/categories.update", post(update_category))
+      .route("/categories.tree", get(list_category_tree))
+      .route("/categories.archive", post(archive::<Category>))
+      .route("/categories.unarchive", post(unarchive::<Category>))
   }
 }
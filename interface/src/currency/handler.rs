@@ -0,0 +1,211 @@
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+  Json,
+};
+use axum_macros::debug_handler;
+use domain::currency::{
+  currency::{self, ConvertedAmountDTO, CurrencyDTO},
+  currency_rate::{self, CurrencyRateImportDTO},
+};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse,
+  },
+  state::AppState,
+  uuid::Uuid,
+  validation::ValidatedJson,
+};
+use service::currency::{
+  ConvertCurrencyError, ConvertCurrencyParams, ConvertCurrencyUsecase, CreateCurrencyError,
+  CreateCurrencyPayload, CreateCurrencyUsecase, DeleteCurrencyRateError, DeleteCurrencyRatePayload,
+  DeleteCurrencyRateUsecase, FindCurrencyError, FindCurrencyUsecase, ImportCurrencyRatesError,
+  ImportCurrencyRatesUsecase, ListCurrencyRatesError, ListCurrencyRatesParams,
+  ListCurrencyRatesUsecase, ListPaginatedCurrenciesError, ListPaginatedCurrenciesParams,
+  ListPaginatedCurrenciesUsecase, SetCurrencyRateError, SetCurrencyRatePayload,
+  SetCurrencyRateUsecase, UpdateCurrencyError, UpdateCurrencyPayload, UpdateCurrencyUsecase,
+};
+use service::list_query::uses_cursor;
+use std::sync::Arc;
+
+#[debug_handler]
+pub async fn create_currency(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateCurrencyPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateCurrencyError> {
+  let usecase = CreateCurrencyUsecase {
+    code: payload.code,
+    name: payload.name,
+    symbol: payload.symbol,
+    decimal_places: payload.decimal_places,
+    rounding: payload.rounding,
+  };
+
+  let created_currency = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: created_currency.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_paginated_currencies(
+  Reader(db): Reader,
+  Query(query): Query<ListPaginatedCurrenciesParams>,
+) -> Result<ListResponse<currency::PartialModel>, ListPaginatedCurrenciesError> {
+  let usecase = ListPaginatedCurrenciesUsecase {
+    page: query.page,
+    per_page: query.per_page,
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
+  };
+
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (currencies, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse {
+      ok: true,
+      data: currencies,
+      meta,
+    }));
+  }
+
+  let (currencies, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse {
+    ok: true,
+    data: currencies,
+    meta,
+  }))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_currency(
+  Reader(db): Reader,
+  Path(id): Path<Uuid>,
+) -> Result<FindOneResponse<CurrencyDTO>, FindCurrencyError> {
+  let usecase = FindCurrencyUsecase { id };
+  let currency = usecase.invoke(db).await?;
+  Ok(FindOneResponse::<CurrencyDTO> {
+    ok: true,
+    data: currency,
+  })
+}
+
+#[debug_handler]
+pub async fn update_currency(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdateCurrencyPayload>,
+) -> Result<OkResponse, UpdateCurrencyError> {
+  let usecase = UpdateCurrencyUsecase {
+    id: payload.id,
+    code: payload.code,
+    name: payload.name,
+    symbol: payload.symbol,
+    decimal_places: payload.decimal_places,
+    rounding: payload.rounding,
+  };
+  usecase.invoke(state.write_db.clone()).await?;
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn set_currency_rate(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<SetCurrencyRatePayload>,
+) -> Result<FindOneResponse<currency_rate::PartialModel>, SetCurrencyRateError> {
+  let usecase = SetCurrencyRateUsecase {
+    currency_id: payload.currency_id,
+    rate: payload.rate,
+    date_start: payload.date_start,
+  };
+
+  let rate = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(FindOneResponse::<currency_rate::PartialModel> {
+    ok: true,
+    data: rate.into(),
+  })
+}
+
+#[debug_handler]
+pub async fn delete_currency_rate(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<DeleteCurrencyRatePayload>,
+) -> Result<OkResponse, DeleteCurrencyRateError> {
+  let usecase = DeleteCurrencyRateUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_currency_rates(
+  Reader(db): Reader,
+  Query(query): Query<ListCurrencyRatesParams>,
+) -> Result<ListResponse<currency_rate::PartialModel>, ListCurrencyRatesError> {
+  let usecase = ListCurrencyRatesUsecase {
+    currency_id: query.currency_id,
+    date_from: query.date_from,
+    date_to: query.date_to,
+    page: query.page,
+    per_page: query.per_page,
+  };
+
+  let (rates, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse {
+    ok: true,
+    data: rates,
+    meta,
+  }))
+}
+
+/// Takes the CSV as the raw request body.
+#[debug_handler]
+pub async fn import_currency_rates(
+  State(state): State<Arc<AppState>>,
+  csv: String,
+) -> Result<FindOneResponse<CurrencyRateImportDTO>, ImportCurrencyRatesError> {
+  let usecase = ImportCurrencyRatesUsecase { csv };
+
+  let import = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(FindOneResponse::<CurrencyRateImportDTO> {
+    ok: true,
+    data: import,
+  })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn convert_currency(
+  Reader(db): Reader,
+  Query(query): Query<ConvertCurrencyParams>,
+) -> Result<FindOneResponse<ConvertedAmountDTO>, ConvertCurrencyError> {
+  let usecase = ConvertCurrencyUsecase {
+    amount: query.amount,
+    from_currency_id: query.from_currency_id,
+    to_currency_id: query.to_currency_id,
+    date: query.date,
+  };
+
+  let converted = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<ConvertedAmountDTO> {
+    ok: true,
+    data: converted,
+  })
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use domain::currency::currency::Entity as Currency;
+use infra::state::AppState;
+
+use crate::archive::{archive, unarchive};
+
+use super::handler::{
+  convert_currency, create_currency, delete_currency_rate, find_currency, import_currency_rates,
+  list_currency_rates, list_paginated_currencies, set_currency_rate, update_currency,
+};
+pub struct CurrencyRouter {}
+
+impl CurrencyRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/currencies.create", post(create_currency))
+      .route("/currencies.list", get(list_paginated_currencies))
+      .route("/currencies.find/:id", get(find_currency))
+      .route("/currencies.update", post(update_currency))
+      .route("/currencies.archive", post(archive::<Currency>))
+      .route("/currencies.unarchive", post(unarchive::<Currency>))
+      .route("/currencies.set_rate", post(set_currency_rate))
+      .route("/currencies.delete_rate", post(delete_currency_rate))
+      .route("/currencies.list_rates", get(list_currency_rates))
+      .route("/currencies.import_rates", post(import_currency_rates))
+      .route("/currencies.convert", get(convert_currency))
+  }
+}
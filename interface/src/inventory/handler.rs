@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use axum::{
+  extract::{Query, State},
+  http::StatusCode,
+  Json,
+};
+use axum_macros::debug_handler;
+use domain::inventory::{
+  location::PartialModel as Location,
+  lot::{LotTraceDTO, PartialModel as Lot},
+  stock_move::OnHandDTO,
+  stock_valuation_layer::StockValuationDTO,
+};
+use infra::{
+  db::Reader,
+  response::{CreateResponse, OkResponse, QueryResponse},
+  state::AppState,
+  validation::ValidatedJson,
+};
+use service::inventory::{
+  CreateLocationError, CreateLocationPayload, CreateLocationUsecase, CreateLotError,
+  CreateLotPayload, CreateLotUsecase, CreateStockMoveError, CreateStockMovePayload,
+  CreateStockMoveUsecase, CreateWarehouseError, CreateWarehousePayload, CreateWarehouseUsecase,
+  FindOnHandError, FindOnHandParams, FindOnHandUsecase, ListLocationsError, ListLocationsParams,
+  ListLocationsUsecase, ListLotsError, ListLotsParams, ListLotsUsecase, StockValuationReportError,
+  StockValuationReportParams, StockValuationReportUsecase, TraceLotError, TraceLotParams,
+  TraceLotUsecase, ValidateStockMoveError, ValidateStockMovePayload, ValidateStockMoveUsecase,
+};
+
+#[debug_handler]
+pub async fn create_warehouse(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateWarehousePayload>,
+) -> Result<(StatusCode, CreateResponse), CreateWarehouseError> {
+  let usecase = CreateWarehouseUsecase {
+    name: payload.name,
+    code: payload.code,
+  };
+
+  let warehouse = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: warehouse.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler]
+pub async fn create_location(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateLocationPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateLocationError> {
+  let usecase = CreateLocationUsecase {
+    name: payload.name,
+    location_type: payload.location_type,
+    parent_location_id: payload.parent_location_id,
+    warehouse_id: payload.warehouse_id,
+  };
+
+  let location = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: location.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_locations(
+  Reader(db): Reader,
+  Query(query): Query<ListLocationsParams>,
+) -> Result<QueryResponse<Vec<Location>>, ListLocationsError> {
+  let usecase = ListLocationsUsecase {
+    warehouse_id: query.warehouse_id,
+    location_type: query.location_type,
+    include_archived: query.include_archived,
+  };
+
+  let locations = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<Location>> {
+    ok: true,
+    data: locations,
+  })
+}
+
+#[debug_handler]
+pub async fn create_stock_move(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateStockMovePayload>,
+) -> Result<(StatusCode, CreateResponse), CreateStockMoveError> {
+  let usecase = CreateStockMoveUsecase {
+    reference: payload.reference,
+    product_id: payload.product_id,
+    source_location_id: payload.source_location_id,
+    destination_location_id: payload.destination_location_id,
+    quantity: payload.quantity,
+    uom_id: payload.uom_id,
+    unit_cost: payload.unit_cost,
+    lot_id: payload.lot_id,
+    produced_lot_id: payload.produced_lot_id,
+    sales_order_line_id: payload.sales_order_line_id,
+  };
+
+  let stock_move = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: stock_move.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler]
+pub async fn validate_stock_move(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<ValidateStockMovePayload>,
+) -> Result<OkResponse, ValidateStockMoveError> {
+  let usecase = ValidateStockMoveUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_on_hand(
+  Reader(db): Reader,
+  Query(query): Query<FindOnHandParams>,
+) -> Result<QueryResponse<Vec<OnHandDTO>>, FindOnHandError> {
+  let usecase = FindOnHandUsecase {
+    product_id: query.product_id,
+    location_id: query.location_id,
+    lot_id: query.lot_id,
+    include_children: query.include_children,
+  };
+
+  let quantities = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<OnHandDTO>> {
+    ok: true,
+    data: quantities,
+  })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn stock_valuation_report(
+  Reader(db): Reader,
+  Query(query): Query<StockValuationReportParams>,
+) -> Result<QueryResponse<Vec<StockValuationDTO>>, StockValuationReportError> {
+  let usecase = StockValuationReportUsecase {
+    at: query.at,
+    product_id: query.product_id,
+    warehouse_id: query.warehouse_id,
+    group_by: query.group_by,
+  };
+
+  let valuation = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<StockValuationDTO>> {
+    ok: true,
+    data: valuation,
+  })
+}
+
+#[debug_handler]
+pub async fn create_lot(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateLotPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateLotError> {
+  let usecase = CreateLotUsecase {
+    product_id: payload.product_id,
+    name: payload.name,
+  };
+
+  let lot = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: lot.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_lots(
+  Reader(db): Reader,
+  Query(query): Query<ListLotsParams>,
+) -> Result<QueryResponse<Vec<Lot>>, ListLotsError> {
+  let usecase = ListLotsUsecase {
+    product_id: query.product_id,
+  };
+
+  let lots = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<Lot>> {
+    ok: true,
+    data: lots,
+  })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn trace_lot(
+  Reader(db): Reader,
+  Query(query): Query<TraceLotParams>,
+) -> Result<QueryResponse<LotTraceDTO>, TraceLotError> {
+  let usecase = TraceLotUsecase { id: query.id };
+
+  let trace = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<LotTraceDTO> {
+    ok: true,
+    data: trace,
+  })
+}
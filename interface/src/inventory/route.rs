@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use infra::state::AppState;
+
+use super::handler::{
+  create_location, create_lot, create_stock_move, create_warehouse, find_on_hand, list_locations,
+  list_lots, stock_valuation_report, trace_lot, validate_stock_move,
+};
+pub struct InventoryRouter {}
+
+impl InventoryRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/warehouses.create", post(create_warehouse))
+      .route("/locations.create", post(create_location))
+      .route("/locations.list", get(list_locations))
+      .route("/lots.create", post(create_lot))
+      .route("/lots.list", get(list_lots))
+      .route("/lots.trace", get(trace_lot))
+      .route("/stock_moves.create", post(create_stock_move))
+      .route("/stock_moves.validate", post(validate_stock_move))
+      .route("/stock_moves.on_hand", get(find_on_hand))
+      .route("/stock_valuation.report", get(stock_valuation_report))
+  }
+}
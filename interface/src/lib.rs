@@ -1,4 +1,12 @@
+pub mod archive;
 pub mod attribute;
 pub mod category;
+pub mod currency;
+pub mod inventory;
+pub mod partner;
+pub mod pricelist;
 pub mod product;
+pub mod purchase;
+pub mod sales;
+pub mod tax;
 pub mod uom;
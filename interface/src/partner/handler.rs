@@ -0,0 +1,134 @@
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+};
+use axum_macros::debug_handler;
+use domain::partner::partner::{PartialModel as Partner, PartnerDTO};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse,
+  },
+  state::AppState,
+  validation::ValidatedJson,
+};
+use service::list_query::uses_cursor;
+use service::partner::{
+  CreatePartnerError, CreatePartnerPayload, CreatePartnerUsecase, FindPartnerError,
+  FindPartnerParams, FindPartnerUsecase, ListPaginatedPartnersError, ListPaginatedPartnersParams,
+  ListPaginatedPartnersUsecase, UpdatePartnerError, UpdatePartnerPayload, UpdatePartnerUsecase,
+};
+use std::sync::Arc;
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_paginated_partners(
+  Reader(db): Reader,
+  Query(query): Query<ListPaginatedPartnersParams>,
+) -> Result<ListResponse<Partner>, ListPaginatedPartnersError> {
+  let usecase = ListPaginatedPartnersUsecase {
+    page: Some(query.page.unwrap_or(1)),
+    per_page: Some(query.per_page.unwrap_or(30)),
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    q: query.q,
+    partner_type: query.partner_type,
+    is_customer: query.is_customer,
+    is_supplier: query.is_supplier,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
+  };
+
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (partners, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse::<
+      Partner,
+    > {
+      ok: true,
+      data: partners,
+      meta,
+    }));
+  }
+
+  let (partners, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse::<Partner> {
+    ok: true,
+    data: partners,
+    meta,
+  }))
+}
+
+#[debug_handler]
+pub async fn create_partner(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreatePartnerPayload>,
+) -> Result<(StatusCode, CreateResponse), CreatePartnerError> {
+  let usecase = CreatePartnerUsecase {
+    name: payload.name,
+    partner_type: payload.partner_type,
+    tax_code: payload.tax_code,
+    is_customer: payload.is_customer,
+    is_supplier: payload.is_supplier,
+    email: payload.email,
+    phone: payload.phone,
+    payment_term_days: payload.payment_term_days,
+    pricelist_id: payload.pricelist_id,
+    addresses: payload.addresses,
+    contacts: payload.contacts,
+  };
+
+  let partner = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: partner.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_partner(
+  Reader(db): Reader,
+  Path(path): Path<FindPartnerParams>,
+) -> Result<FindOneResponse<PartnerDTO>, FindPartnerError> {
+  let usecase = FindPartnerUsecase { id: path.id };
+
+  let partner = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<PartnerDTO> {
+    ok: true,
+    data: partner,
+  })
+}
+
+#[debug_handler]
+pub async fn update_partner(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdatePartnerPayload>,
+) -> Result<OkResponse, UpdatePartnerError> {
+  let usecase = UpdatePartnerUsecase {
+    id: payload.id,
+    name: payload.name,
+    partner_type: payload.partner_type,
+    tax_code: payload.tax_code,
+    is_customer: payload.is_customer,
+    is_supplier: payload.is_supplier,
+    email: payload.email,
+    phone: payload.phone,
+    payment_term_days: payload.payment_term_days,
+    pricelist_id: payload.pricelist_id,
+    addresses: payload.addresses,
+    contacts: payload.contacts,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use domain::partner::partner::Entity as Partner;
+use infra::state::AppState;
+
+use crate::archive::{archive, unarchive};
+
+use super::handler::{create_partner, find_partner, list_paginated_partners, update_partner};
+pub struct PartnerRouter {}
+
+impl PartnerRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/partners.list", get(list_paginated_partners))
+      .route("/partners.create", post(create_partner))
+      .route("/partners.find/:id", get(find_partner))
+      .route("/partners.update", post(update_partner))
+      .route("/partners.archive", post(archive::<Partner>))
+      .route("/partners.unarchive", post(unarchive::<Partner>))
+  }
+}
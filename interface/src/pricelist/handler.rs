@@ -0,0 +1,134 @@
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+};
+use axum_macros::debug_handler;
+use domain::pricelist::pricelist::{self, ComputedPriceDTO, PricelistDTO};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse,
+  },
+  state::AppState,
+  uuid::Uuid,
+  validation::ValidatedJson,
+};
+use service::list_query::uses_cursor;
+use service::pricelist::{
+  ComputePriceError, ComputePriceParams, ComputePriceUsecase, CreatePricelistError,
+  CreatePricelistPayload, CreatePricelistUsecase, FindPricelistError, FindPricelistUsecase,
+  ListPaginatedPricelistsError, ListPaginatedPricelistsParams, ListPaginatedPricelistsUsecase,
+  UpdatePricelistError, UpdatePricelistPayload, UpdatePricelistUsecase,
+};
+use std::sync::Arc;
+
+#[debug_handler]
+pub async fn create_pricelist(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreatePricelistPayload>,
+) -> Result<(StatusCode, CreateResponse), CreatePricelistError> {
+  let usecase = CreatePricelistUsecase {
+    name: payload.name,
+    currency_id: payload.currency_id,
+    rules: payload.rules,
+  };
+
+  let created_pricelist = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: created_pricelist.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_paginated_pricelists(
+  Reader(db): Reader,
+  Query(query): Query<ListPaginatedPricelistsParams>,
+) -> Result<ListResponse<pricelist::PartialModel>, ListPaginatedPricelistsError> {
+  let usecase = ListPaginatedPricelistsUsecase {
+    page: query.page,
+    per_page: query.per_page,
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
+  };
+
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (pricelists, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse {
+      ok: true,
+      data: pricelists,
+      meta,
+    }));
+  }
+
+  let (pricelists, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse {
+    ok: true,
+    data: pricelists,
+    meta,
+  }))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_pricelist(
+  Reader(db): Reader,
+  Path(id): Path<Uuid>,
+) -> Result<FindOneResponse<PricelistDTO>, FindPricelistError> {
+  let usecase = FindPricelistUsecase { id };
+  let pricelist = usecase.invoke(db).await?;
+  Ok(FindOneResponse::<PricelistDTO> {
+    ok: true,
+    data: pricelist,
+  })
+}
+
+#[debug_handler]
+pub async fn update_pricelist(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdatePricelistPayload>,
+) -> Result<OkResponse, UpdatePricelistError> {
+  let usecase = UpdatePricelistUsecase {
+    id: payload.id,
+    name: payload.name,
+    currency_id: payload.currency_id,
+    rules: payload.rules,
+  };
+  usecase.invoke(state.write_db.clone()).await?;
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn compute_price(
+  Reader(db): Reader,
+  Query(query): Query<ComputePriceParams>,
+) -> Result<FindOneResponse<ComputedPriceDTO>, ComputePriceError> {
+  let usecase = ComputePriceUsecase {
+    pricelist_id: query.pricelist_id,
+    partner_id: query.partner_id,
+    product_id: query.product_id,
+    quantity: query.quantity,
+    uom_id: query.uom_id,
+    date: query.date,
+    currency_id: query.currency_id,
+  };
+
+  let price = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<ComputedPriceDTO> {
+    ok: true,
+    data: price,
+  })
+}
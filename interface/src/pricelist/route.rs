@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use domain::pricelist::pricelist::Entity as Pricelist;
+use infra::state::AppState;
+
+use crate::archive::{archive, unarchive};
+
+use super::handler::{
+  compute_price, create_pricelist, find_pricelist, list_paginated_pricelists, update_pricelist,
+};
+pub struct PricelistRouter {}
+
+impl PricelistRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/pricelists.create", post(create_pricelist))
+      .route("/pricelists.list", get(list_paginated_pricelists))
+      .route("/pricelists.find/:id", get(find_pricelist))
+      .route("/pricelists.update", post(update_pricelist))
+      .route("/pricelists.archive", post(archive::<Pricelist>))
+      .route("/pricelists.unarchive", post(unarchive::<Pricelist>))
+      .route("/pricelists.compute_price", get(compute_price))
+  }
+}
@@ -1,59 +1,106 @@
 use std::sync::Arc;
 
 use axum::{
-  extract::{Query, State},
+  extract::{Path, Query, State},
   http::StatusCode,
-  Json,
 };
 use axum_macros::debug_handler;
-use domain::product::product::ProductDTO;
+use domain::product::product_template::ProductTemplateDTO;
 use infra::{
-  response::{CreateResponse, PaginatedResponse},
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse, QueryResponse,
+  },
   state::AppState,
+  validation::ValidatedJson,
 };
+use service::list_query::uses_cursor;
 use service::product::{
   list_paginated_products_usecase::{
     ListPaginatedProductsError, ListPaginatedProductsParams, ListPaginatedProductsUsecase,
+    ProductListItem,
   },
-  CreateProductError, CreateProductPayload, CreateProductUsecase,
+  CreateProductError, CreateProductPayload, CreateProductUsecase, FindProductError,
+  FindProductParams, FindProductUsecase, GenerateVariantsError, GenerateVariantsPayload,
+  GenerateVariantsUsecase, UpdateProductError, UpdateProductPayload, UpdateProductUsecase, Variant,
 };
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn list_paginated_products(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Query(query): Query<ListPaginatedProductsParams>,
-) -> Result<PaginatedResponse<ProductDTO>, ListPaginatedProductsError> {
+) -> Result<ListResponse<ProductListItem>, ListPaginatedProductsError> {
   let usecase = ListPaginatedProductsUsecase {
     page: Some(query.page.unwrap_or(1)),
     per_page: Some(query.per_page.unwrap_or(30)),
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    category_id: query.category_id,
+    include_subcategories: query.include_subcategories,
+    uom_id: query.uom_id,
+    product_type: query.product_type,
+    product_subtype: query.product_subtype,
+    is_track_inventory: query.is_track_inventory,
+    attribute_option_ids: query.attribute_option_ids,
+    sort_by: query.sort_by,
+    order: query.order,
+    mode: query.mode,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
   };
 
-  let (products, meta) = usecase.invoke(state.read_db.clone()).await?;
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (products, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse::<
+      ProductListItem,
+    > {
+      ok: true,
+      data: products,
+      meta,
+    }));
+  }
 
-  Ok(PaginatedResponse::<ProductDTO> {
+  let (products, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse::<
+    ProductListItem,
+  > {
     ok: true,
     data: products,
     meta,
-  })
+  }))
 }
 
 #[debug_handler]
 pub async fn create_product(
   State(state): State<Arc<AppState>>,
-  Json(payload): Json<CreateProductPayload>,
+  ValidatedJson(payload): ValidatedJson<CreateProductPayload>,
 ) -> Result<(StatusCode, CreateResponse), CreateProductError> {
   let usecase = CreateProductUsecase {
     name: payload.name,
     product_type: payload.product_type,
     product_subtype: payload.product_subtype,
     is_track_inventory: payload.is_track_inventory,
+    tracking: payload.tracking,
     price: payload.price,
     cost: payload.cost,
     uom_id: payload.uom_id,
+    purchase_uom_id: payload.purchase_uom_id,
+    sales_uom_id: payload.sales_uom_id,
+    price_uom_id: payload.price_uom_id,
     category_id: payload.category_id,
+    sales_tax_id: payload.sales_tax_id,
+    purchase_tax_id: payload.purchase_tax_id,
+    currency_id: payload.currency_id,
     create_corresponding_moulds: payload.create_corresponding_moulds,
+    mould_mode: payload.mould_mode,
     is_multiple_variants: payload.is_multiple_variants,
     variants: payload.variants,
+    attribute_selections: payload.attribute_selections,
   };
 
   let products = usecase.invoke(state.write_db.clone()).await?;
@@ -76,3 +123,65 @@ pub async fn create_product(
     ))
   }
 }
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_product(
+  Reader(db): Reader,
+  Path(path): Path<FindProductParams>,
+) -> Result<FindOneResponse<ProductTemplateDTO>, FindProductError> {
+  let usecase = FindProductUsecase { id: path.id };
+
+  let product = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<ProductTemplateDTO> {
+    ok: true,
+    data: product,
+  })
+}
+
+#[debug_handler]
+pub async fn update_product(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdateProductPayload>,
+) -> Result<OkResponse, UpdateProductError> {
+  let usecase = UpdateProductUsecase {
+    id: payload.id,
+    name: payload.name,
+    description: payload.description,
+    product_type: payload.product_type,
+    product_subtype: payload.product_subtype,
+    is_track_inventory: payload.is_track_inventory,
+    tracking: payload.tracking,
+    uom_id: payload.uom_id,
+    purchase_uom_id: payload.purchase_uom_id,
+    sales_uom_id: payload.sales_uom_id,
+    category_id: payload.category_id,
+    sales_tax_id: payload.sales_tax_id,
+    purchase_tax_id: payload.purchase_tax_id,
+    currency_id: payload.currency_id,
+    variants: payload.variants,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn generate_variants(
+  Reader(db): Reader,
+  ValidatedJson(payload): ValidatedJson<GenerateVariantsPayload>,
+) -> Result<QueryResponse<Vec<Variant>>, GenerateVariantsError> {
+  let usecase = GenerateVariantsUsecase {
+    price: payload.price,
+    cost: payload.cost,
+    attributes: payload.attributes,
+  };
+
+  let variants = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<Variant>> {
+    ok: true,
+    data: variants,
+  })
+}
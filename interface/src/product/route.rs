@@ -4,15 +4,26 @@ use axum::{
   routing::{get, post},
   Router,
 };
+use domain::product::product_template::Entity as ProductTemplate;
 use infra::state::AppState;
 
-use super::handler::{create_product, list_paginated_products};
+use crate::archive::{archive, unarchive};
+
+use super::handler::{
+  create_product, find_product, generate_variants, list_paginated_products, update_product,
+};
 pub struct ProductRouter {}
 
 impl ProductRouter {
+  #[allow(clippy::new_ret_no_self)]
   pub fn new() -> Router<Arc<AppState>> {
     Router::new()
       .route("/products.list", get(list_paginated_products))
       .route("/products.create", post(create_product))
+      .route("/products.find/:id", get(find_product))
+      .route("/products.update", post(update_product))
+      .route("/products.archive", post(archive::<ProductTemplate>))
+      .route("/products.unarchive", post(unarchive::<ProductTemplate>))
+      .route("/products.generate_variants", post(generate_variants))
   }
 }
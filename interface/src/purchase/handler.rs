@@ -0,0 +1,258 @@
+use axum::{
+  extract::{Json, Path, Query, State},
+  http::StatusCode,
+};
+use axum_macros::debug_handler;
+use domain::purchase::{
+  purchase_order::{PurchaseOrderDTO, PurchaseOrderListItem},
+  supplier_info::PartialModel as SupplierInfo,
+};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse, QueryResponse,
+  },
+  state::AppState,
+  validation::ValidatedJson,
+};
+use service::list_query::uses_cursor;
+use service::purchase::{
+  CancelPurchaseOrderPayload, CancelPurchaseOrderUsecase, ConfirmPurchaseOrderPayload,
+  ConfirmPurchaseOrderUsecase, CreatePurchaseOrderError, CreatePurchaseOrderPayload,
+  CreatePurchaseOrderUsecase, CreateSupplierInfoError, CreateSupplierInfoPayload,
+  CreateSupplierInfoUsecase, DeleteSupplierInfoError, DeleteSupplierInfoPayload,
+  DeleteSupplierInfoUsecase, FindPurchaseOrderError, FindPurchaseOrderParams,
+  FindPurchaseOrderUsecase, ListPaginatedPurchaseOrdersError, ListPaginatedPurchaseOrdersParams,
+  ListPaginatedPurchaseOrdersUsecase, ListSupplierInfosError, ListSupplierInfosParams,
+  ListSupplierInfosUsecase, PurchaseOrderStateError, ReceivePurchaseOrderError,
+  ReceivePurchaseOrderPayload, ReceivePurchaseOrderUsecase, UpdatePurchaseOrderError,
+  UpdatePurchaseOrderPayload, UpdatePurchaseOrderUsecase, UpdateSupplierInfoError,
+  UpdateSupplierInfoPayload, UpdateSupplierInfoUsecase,
+};
+use std::sync::Arc;
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_supplier_infos(
+  Reader(db): Reader,
+  Query(query): Query<ListSupplierInfosParams>,
+) -> Result<QueryResponse<Vec<SupplierInfo>>, ListSupplierInfosError> {
+  let usecase = ListSupplierInfosUsecase {
+    product_id: query.product_id,
+    product_template_id: query.product_template_id,
+    partner_id: query.partner_id,
+  };
+
+  let supplier_infos = usecase.invoke(db).await?;
+
+  Ok(QueryResponse::<Vec<SupplierInfo>> {
+    ok: true,
+    data: supplier_infos,
+  })
+}
+
+#[debug_handler]
+pub async fn create_supplier_info(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateSupplierInfoPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateSupplierInfoError> {
+  let usecase = CreateSupplierInfoUsecase {
+    partner_id: payload.partner_id,
+    product_id: payload.product_id,
+    product_template_id: payload.product_template_id,
+    supplier_sku: payload.supplier_sku,
+    price: payload.price,
+    currency_id: payload.currency_id,
+    uom_id: payload.uom_id,
+    min_quantity: payload.min_quantity,
+    lead_time_days: payload.lead_time_days,
+    date_start: payload.date_start,
+    date_end: payload.date_end,
+  };
+
+  let supplier_info = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: supplier_info.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler]
+pub async fn update_supplier_info(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdateSupplierInfoPayload>,
+) -> Result<OkResponse, UpdateSupplierInfoError> {
+  let usecase = UpdateSupplierInfoUsecase {
+    id: payload.id,
+    supplier_sku: payload.supplier_sku,
+    price: payload.price,
+    currency_id: payload.currency_id,
+    uom_id: payload.uom_id,
+    min_quantity: payload.min_quantity,
+    lead_time_days: payload.lead_time_days,
+    date_start: payload.date_start,
+    date_end: payload.date_end,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn delete_supplier_info(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<DeleteSupplierInfoPayload>,
+) -> Result<OkResponse, DeleteSupplierInfoError> {
+  let usecase = DeleteSupplierInfoUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_paginated_purchase_orders(
+  Reader(db): Reader,
+  Query(query): Query<ListPaginatedPurchaseOrdersParams>,
+) -> Result<ListResponse<PurchaseOrderListItem>, ListPaginatedPurchaseOrdersError> {
+  let usecase = ListPaginatedPurchaseOrdersUsecase {
+    page: Some(query.page.unwrap_or(1)),
+    per_page: Some(query.per_page.unwrap_or(30)),
+    q: query.q,
+    state: query.state,
+    partner_id: query.partner_id,
+    date_from: query.date_from,
+    date_to: query.date_to,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
+  };
+
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (purchase_orders, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse::<
+      PurchaseOrderListItem,
+    > {
+      ok: true,
+      data: purchase_orders,
+      meta,
+    }));
+  }
+
+  let (purchase_orders, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse::<
+    PurchaseOrderListItem,
+  > {
+    ok: true,
+    data: purchase_orders,
+    meta,
+  }))
+}
+
+#[debug_handler]
+pub async fn create_purchase_order(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreatePurchaseOrderPayload>,
+) -> Result<(StatusCode, CreateResponse), CreatePurchaseOrderError> {
+  let usecase = CreatePurchaseOrderUsecase {
+    partner_id: payload.partner_id,
+    order_date: payload.order_date,
+    currency_id: payload.currency_id,
+    note: payload.note,
+    lines: payload.lines,
+  };
+
+  let purchase_order = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: purchase_order.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_purchase_order(
+  Reader(db): Reader,
+  Path(path): Path<FindPurchaseOrderParams>,
+) -> Result<FindOneResponse<PurchaseOrderDTO>, FindPurchaseOrderError> {
+  let usecase = FindPurchaseOrderUsecase { id: path.id };
+
+  let purchase_order = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<PurchaseOrderDTO> {
+    ok: true,
+    data: purchase_order,
+  })
+}
+
+#[debug_handler]
+pub async fn update_purchase_order(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdatePurchaseOrderPayload>,
+) -> Result<OkResponse, UpdatePurchaseOrderError> {
+  let usecase = UpdatePurchaseOrderUsecase {
+    id: payload.id,
+    partner_id: payload.partner_id,
+    order_date: payload.order_date,
+    currency_id: payload.currency_id,
+    note: payload.note,
+    lines: payload.lines,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn confirm_purchase_order(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<ConfirmPurchaseOrderPayload>,
+) -> Result<OkResponse, PurchaseOrderStateError> {
+  let usecase = ConfirmPurchaseOrderUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn cancel_purchase_order(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<CancelPurchaseOrderPayload>,
+) -> Result<OkResponse, PurchaseOrderStateError> {
+  let usecase = CancelPurchaseOrderUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn receive_purchase_order(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<ReceivePurchaseOrderPayload>,
+) -> Result<OkResponse, ReceivePurchaseOrderError> {
+  let usecase = ReceivePurchaseOrderUsecase {
+    id: payload.id,
+    destination_location_id: payload.destination_location_id,
+    source_location_id: payload.source_location_id,
+    lots: payload.lots,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
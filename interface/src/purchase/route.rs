@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use infra::state::AppState;
+
+use super::handler::{
+  cancel_purchase_order, confirm_purchase_order, create_purchase_order, create_supplier_info,
+  delete_supplier_info, find_purchase_order, list_paginated_purchase_orders, list_supplier_infos,
+  receive_purchase_order, update_purchase_order, update_supplier_info,
+};
+pub struct PurchaseRouter {}
+
+impl PurchaseRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/supplier_infos.list", get(list_supplier_infos))
+      .route("/supplier_infos.create", post(create_supplier_info))
+      .route("/supplier_infos.update", post(update_supplier_info))
+      .route("/supplier_infos.delete", post(delete_supplier_info))
+      .route("/purchase_orders.list", get(list_paginated_purchase_orders))
+      .route("/purchase_orders.create", post(create_purchase_order))
+      .route("/purchase_orders.find/:id", get(find_purchase_order))
+      .route("/purchase_orders.update", post(update_purchase_order))
+      .route("/purchase_orders.confirm", post(confirm_purchase_order))
+      .route("/purchase_orders.cancel", post(cancel_purchase_order))
+      .route("/purchase_orders.receive", post(receive_purchase_order))
+  }
+}
@@ -0,0 +1,168 @@
+use axum::{
+  extract::{Json, Path, Query, State},
+  http::StatusCode,
+};
+use axum_macros::debug_handler;
+use domain::sales::sales_order::{SalesOrderDTO, SalesOrderListItem};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse,
+  },
+  state::AppState,
+  validation::ValidatedJson,
+};
+use service::list_query::uses_cursor;
+use service::sales::{
+  CancelSalesOrderPayload, CancelSalesOrderUsecase, ConfirmSalesOrderPayload,
+  ConfirmSalesOrderUsecase, CreateSalesOrderError, CreateSalesOrderPayload,
+  CreateSalesOrderUsecase, FindSalesOrderError, FindSalesOrderParams, FindSalesOrderUsecase,
+  ListPaginatedSalesOrdersError, ListPaginatedSalesOrdersParams, ListPaginatedSalesOrdersUsecase,
+  MarkSalesOrderDonePayload, MarkSalesOrderDoneUsecase, SalesOrderStateError,
+  UpdateSalesOrderError, UpdateSalesOrderPayload, UpdateSalesOrderUsecase,
+};
+use std::sync::Arc;
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_paginated_sales_orders(
+  Reader(db): Reader,
+  Query(query): Query<ListPaginatedSalesOrdersParams>,
+) -> Result<ListResponse<SalesOrderListItem>, ListPaginatedSalesOrdersError> {
+  let usecase = ListPaginatedSalesOrdersUsecase {
+    page: Some(query.page.unwrap_or(1)),
+    per_page: Some(query.per_page.unwrap_or(30)),
+    q: query.q,
+    state: query.state,
+    partner_id: query.partner_id,
+    date_from: query.date_from,
+    date_to: query.date_to,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
+  };
+
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (sales_orders, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse::<
+      SalesOrderListItem,
+    > {
+      ok: true,
+      data: sales_orders,
+      meta,
+    }));
+  }
+
+  let (sales_orders, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse::<
+    SalesOrderListItem,
+  > {
+    ok: true,
+    data: sales_orders,
+    meta,
+  }))
+}
+
+#[debug_handler]
+pub async fn create_sales_order(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateSalesOrderPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateSalesOrderError> {
+  let usecase = CreateSalesOrderUsecase {
+    partner_id: payload.partner_id,
+    invoice_address_id: payload.invoice_address_id,
+    delivery_address_id: payload.delivery_address_id,
+    order_date: payload.order_date,
+    currency_id: payload.currency_id,
+    payment_term_days: payload.payment_term_days,
+    note: payload.note,
+    lines: payload.lines,
+  };
+
+  let sales_order = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: sales_order.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_sales_order(
+  Reader(db): Reader,
+  Path(path): Path<FindSalesOrderParams>,
+) -> Result<FindOneResponse<SalesOrderDTO>, FindSalesOrderError> {
+  let usecase = FindSalesOrderUsecase { id: path.id };
+
+  let sales_order = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<SalesOrderDTO> {
+    ok: true,
+    data: sales_order,
+  })
+}
+
+#[debug_handler]
+pub async fn update_sales_order(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdateSalesOrderPayload>,
+) -> Result<OkResponse, UpdateSalesOrderError> {
+  let usecase = UpdateSalesOrderUsecase {
+    id: payload.id,
+    partner_id: payload.partner_id,
+    invoice_address_id: payload.invoice_address_id,
+    delivery_address_id: payload.delivery_address_id,
+    order_date: payload.order_date,
+    currency_id: payload.currency_id,
+    payment_term_days: payload.payment_term_days,
+    note: payload.note,
+    lines: payload.lines,
+  };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn confirm_sales_order(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<ConfirmSalesOrderPayload>,
+) -> Result<OkResponse, SalesOrderStateError> {
+  let usecase = ConfirmSalesOrderUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn cancel_sales_order(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<CancelSalesOrderPayload>,
+) -> Result<OkResponse, SalesOrderStateError> {
+  let usecase = CancelSalesOrderUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler]
+pub async fn mark_sales_order_done(
+  State(state): State<Arc<AppState>>,
+  Json(payload): Json<MarkSalesOrderDonePayload>,
+) -> Result<OkResponse, SalesOrderStateError> {
+  let usecase = MarkSalesOrderDoneUsecase { id: payload.id };
+
+  usecase.invoke(state.write_db.clone()).await?;
+
+  Ok(OkResponse { ok: true })
+}
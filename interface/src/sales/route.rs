@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use infra::state::AppState;
+
+use super::handler::{
+  cancel_sales_order, confirm_sales_order, create_sales_order, find_sales_order,
+  list_paginated_sales_orders, mark_sales_order_done, update_sales_order,
+};
+pub struct SalesRouter {}
+
+impl SalesRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/sales_orders.list", get(list_paginated_sales_orders))
+      .route("/sales_orders.create", post(create_sales_order))
+      .route("/sales_orders.find/:id", get(find_sales_order))
+      .route("/sales_orders.update", post(update_sales_order))
+      .route("/sales_orders.confirm", post(confirm_sales_order))
+      .route("/sales_orders.cancel", post(cancel_sales_order))
+      .route("/sales_orders.done", post(mark_sales_order_done))
+  }
+}
@@ -0,0 +1,138 @@
+use axum::{
+  extract::{Path, Query, State},
+  http::StatusCode,
+};
+use axum_macros::debug_handler;
+use domain::tax::tax::{self, TaxComputationDTO, TaxDTO};
+use infra::{
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse,
+  },
+  state::AppState,
+  uuid::Uuid,
+  validation::ValidatedJson,
+};
+use service::list_query::uses_cursor;
+use service::tax::{
+  ComputeTaxesError, ComputeTaxesPayload, ComputeTaxesUsecase, CreateTaxError, CreateTaxPayload,
+  CreateTaxUsecase, FindTaxError, FindTaxUsecase, ListPaginatedTaxesError,
+  ListPaginatedTaxesParams, ListPaginatedTaxesUsecase, UpdateTaxError, UpdateTaxPayload,
+  UpdateTaxUsecase,
+};
+use std::sync::Arc;
+
+#[debug_handler]
+pub async fn create_tax(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<CreateTaxPayload>,
+) -> Result<(StatusCode, CreateResponse), CreateTaxError> {
+  let usecase = CreateTaxUsecase {
+    name: payload.name,
+    tax_use: payload.tax_use,
+    amount_type: payload.amount_type,
+    amount: payload.amount,
+    price_include: payload.price_include,
+    vat_group: payload.vat_group,
+  };
+
+  let created_tax = usecase.invoke(state.write_db.clone()).await?;
+
+  Ok((
+    StatusCode::CREATED,
+    CreateResponse {
+      id: created_tax.id,
+      ok: true,
+    },
+  ))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn list_paginated_taxes(
+  Reader(db): Reader,
+  Query(query): Query<ListPaginatedTaxesParams>,
+) -> Result<ListResponse<tax::PartialModel>, ListPaginatedTaxesError> {
+  let usecase = ListPaginatedTaxesUsecase {
+    page: query.page,
+    per_page: query.per_page,
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    tax_use: query.tax_use,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
+  };
+
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (taxes, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse {
+      ok: true,
+      data: taxes,
+      meta,
+    }));
+  }
+
+  let (taxes, meta) = usecase.invoke(db).await?;
+
+  Ok(ListResponse::Paginated(PaginatedResponse {
+    ok: true,
+    data: taxes,
+    meta,
+  }))
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn find_tax(
+  Reader(db): Reader,
+  Path(id): Path<Uuid>,
+) -> Result<FindOneResponse<TaxDTO>, FindTaxError> {
+  let usecase = FindTaxUsecase { id };
+  let tax = usecase.invoke(db).await?;
+  Ok(FindOneResponse::<TaxDTO> {
+    ok: true,
+    data: tax,
+  })
+}
+
+#[debug_handler]
+pub async fn update_tax(
+  State(state): State<Arc<AppState>>,
+  ValidatedJson(payload): ValidatedJson<UpdateTaxPayload>,
+) -> Result<OkResponse, UpdateTaxError> {
+  let usecase = UpdateTaxUsecase {
+    id: payload.id,
+    name: payload.name,
+    tax_use: payload.tax_use,
+    amount_type: payload.amount_type,
+    amount: payload.amount,
+    price_include: payload.price_include,
+    vat_group: payload.vat_group,
+  };
+  usecase.invoke(state.write_db.clone()).await?;
+  Ok(OkResponse { ok: true })
+}
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn compute_taxes(
+  Reader(db): Reader,
+  ValidatedJson(payload): ValidatedJson<ComputeTaxesPayload>,
+) -> Result<FindOneResponse<TaxComputationDTO>, ComputeTaxesError> {
+  let usecase = ComputeTaxesUsecase {
+    tax_use: payload.tax_use,
+    currency_id: payload.currency_id,
+    date: payload.date,
+    lines: payload.lines,
+  };
+
+  let computation = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<TaxComputationDTO> {
+    ok: true,
+    data: computation,
+  })
+}
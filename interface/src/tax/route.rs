@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use axum::{
+  routing::{get, post},
+  Router,
+};
+use domain::tax::tax::Entity as Tax;
+use infra::state::AppState;
+
+use crate::archive::{archive, unarchive};
+
+use super::handler::{compute_taxes, create_tax, find_tax, list_paginated_taxes, update_tax};
+pub struct TaxRouter {}
+
+impl TaxRouter {
+  #[allow(clippy::new_ret_no_self)]
+  pub fn new() -> Router<Arc<AppState>> {
+    Router::new()
+      .route("/taxes.create", post(create_tax))
+      .route("/taxes.list", get(list_paginated_taxes))
+      .route("/taxes.find/:id", get(find_tax))
+      .route("/taxes.update", post(update_tax))
+      .route("/taxes.archive", post(archive::<Tax>))
+      .route("/taxes.unarchive", post(unarchive::<Tax>))
+      .route("/taxes.compute", post(compute_taxes))
+  }
+}
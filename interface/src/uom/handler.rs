@@ -1,47 +1,80 @@
 use axum::{
-  extract::{Json, Path, Query, State},
+  extract::{Path, Query, State},
   http::StatusCode,
 };
 use axum_macros::debug_handler;
 use domain::measurement::uom::PartialModel as Uom;
 use infra::{
-  response::{CreateResponse, FindOneResponse, OkResponse, PaginatedResponse},
+  db::Reader,
+  response::{
+    CreateResponse, CursorPaginatedResponse, FindOneResponse, ListResponse, OkResponse,
+    PaginatedResponse,
+  },
   state::AppState,
+  validation::ValidatedJson,
 };
+use service::list_query::uses_cursor;
 use service::measurement::{
-  CreateUomError, CreateUomParams, CreateUomUsecase, FindUomError, FindUomParams, FindUomUsecase,
+  ConvertUomError, ConvertUomParams, ConvertUomUsecase, ConvertedQuantity, CreateUomError,
+  CreateUomParams, CreateUomUsecase, FindUomError, FindUomParams, FindUomUsecase,
   ListPaginatedUomsError, ListPaginatedUomsParams, ListPaginatedUomsUsecase, UpdateUomError,
   UpdateUomParams, UpdateUomUsecase,
 };
 use std::sync::Arc;
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn list_paginated_uoms(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Query(query): Query<ListPaginatedUomsParams>,
-) -> Result<PaginatedResponse<Uom>, ListPaginatedUomsError> {
+) -> Result<ListResponse<Uom>, ListPaginatedUomsError> {
   let usecase = ListPaginatedUomsUsecase {
     page: Some(query.page.unwrap_or(1)),
     per_page: Some(query.per_page.unwrap_or(30)),
+    include_archived: query.include_archived,
+    only_archived: query.only_archived,
+    name: query.name,
+    sort_by: query.sort_by,
+    order: query.order,
+    pagination: query.pagination,
+    cursor: query.cursor,
+    with_total: query.with_total,
   };
 
-  let (uoms, meta) = usecase.invoke(state.read_db.clone()).await?;
+  if uses_cursor(usecase.pagination, usecase.cursor.as_ref()) {
+    let (uoms, meta) = usecase.invoke_cursor(db).await?;
+
+    return Ok(ListResponse::CursorPaginated(CursorPaginatedResponse::<
+      Uom,
+    > {
+      ok: true,
+      data: uoms,
+      meta,
+    }));
+  }
+
+  let (uoms, meta) = usecase.invoke(db).await?;
 
-  Ok(PaginatedResponse::<Uom> {
+  Ok(ListResponse::Paginated(PaginatedResponse::<Uom> {
     ok: true,
     data: uoms,
     meta,
-  })
+  }))
 }
 
 #[debug_handler]
 pub async fn create_uom(
   State(state): State<Arc<AppState>>,
-  Json(body): Json<CreateUomParams>,
+  ValidatedJson(body): ValidatedJson<CreateUomParams>,
 ) -> Result<(StatusCode, CreateResponse), CreateUomError> {
-  let usecase = CreateUomUsecase { name: body.name };
+  let usecase = CreateUomUsecase {
+    name: body.name,
+    category: body.category,
+    ratio: body.ratio,
+    rounding: body.rounding,
+    is_reference: body.is_reference,
+  };
 
-  let uom = usecase.invoke(state.read_db.clone()).await?;
+  let uom = usecase.invoke(state.write_db.clone()).await?;
 
   Ok((
     StatusCode::CREATED,
@@ -52,14 +85,14 @@ pub async fn create_uom(
   ))
 }
 
-#[debug_handler]
+#[debug_handler(state = Arc<AppState>)]
 pub async fn find_uom(
-  State(state): State<Arc<AppState>>,
+  Reader(db): Reader,
   Path(path): Path<FindUomParams>,
 ) -> Result<FindOneResponse<Uom>, FindUomError> {
   let usecase = FindUomUsecase { id: path.id };
 
-  let uom = usecase.invoke(state.read_db.clone()).await?;
+  let uom = usecase.invoke(db).await?;
 
   Ok(FindOneResponse::<Uom> {
     ok: true,
@@ -70,14 +103,37 @@ pub async fn find_uom(
 #[debug_handler]
 pub async fn update_uom(
   State(state): State<Arc<AppState>>,
-  Json(body): Json<UpdateUomParams>,
+  ValidatedJson(body): ValidatedJson<UpdateUomParams>,
 ) -> Result<OkResponse, UpdateUomError> {
   let usecase = UpdateUomUsecase {
     id: body.id,
     name: body.name,
+    category: body.category,
+    ratio: body.ratio,
+    rounding: body.rounding,
+    is_reference: body.is_reference,
   };
 
-  usecase.invoke(state.read_db.clone()).await?;
+  usecase.invoke(state.write_db.clone()).await?;
 
   Ok(OkResponse { ok: true })
 }
+
+#[debug_handler(state = Arc<AppState>)]
+pub async fn convert_uom(
+  Reader(db): Reader,
+  Query(query): Query<ConvertUomParams>,
+) -> Result<FindOneResponse<ConvertedQuantity>, ConvertUomError> {
+  let usecase = ConvertUomUsecase {
+    quantity: query.quantity,
+    from_uom_id: query.from_uom_id,
+    to_uom_id: query.to_uom_id,
+  };
+
+  let converted = usecase.invoke(db).await?;
+
+  Ok(FindOneResponse::<ConvertedQuantity> {
+    ok: true,
+    data: converted,
+  })
+}
@@ -4,17 +4,24 @@ use axum::{
   routing::{get, post},
   Router,
 };
+use domain::measurement::uom::Entity as Uom;
 use infra::state::AppState;
 
-use super::handler::{create_uom, find_uom, list_paginated_uoms, update_uom};
+use crate::archive::{archive, unarchive};
+
+use super::handler::{convert_uom, create_uom, find_uom, list_paginated_uoms, update_uom};
 pub struct UomRouter {}
 
 impl UomRouter {
+  #[allow(clippy::new_ret_no_self)]
   pub fn new() -> Router<Arc<AppState>> {
     Router::new()
       .route("/uoms.list", get(list_paginated_uoms))
       .route("/uoms.create", post(create_uom))
       .route("/uoms.find/:id", get(find_uom))
       .route("/uoms.update", post(update_uom))
+      .route("/uoms.archive", post(archive::<Uom>))
+      .route("/uoms.unarchive", post(unarchive::<Uom>))
+      .route("/uoms.convert", get(convert_uom))
   }
 }
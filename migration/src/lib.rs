@@ -9,6 +9,24 @@ mod m20241212_025826_create_attribute_option_table;
 mod m20241216_120454_create_product_template_table;
 mod m20241216_143112_create_product_table;
 mod m20241222_055121_create_product_combination_table;
+mod m20241224_031245_add_archived_at_to_uom_category_attribute;
+mod m20241226_082410_create_product_mould_table;
+mod m20241228_094530_add_name_search_indexes;
+mod m20241229_101500_add_unaccent_unique_indexes;
+mod m20241230_083000_add_archived_at_to_attribute_option;
+mod m20241231_090000_add_category_and_ratio_to_uom;
+mod m20250102_084500_add_purchase_sales_price_uoms;
+mod m20250104_091500_create_inventory_tables;
+mod m20250106_100000_add_stock_valuation;
+mod m20250108_090000_add_lot_tracking;
+mod m20250110_090000_create_partner_tables;
+mod m20250112_090000_create_sales_tables;
+mod m20250114_090000_create_purchase_tables;
+mod m20250116_090000_create_pricelist_tables;
+mod m20250118_090000_create_tax_tables;
+mod m20250120_090000_create_currency_tables;
+mod m20250122_090000_add_sales_order_line_to_stock_move;
+mod m20250124_090000_add_template_and_validity_to_supplier_info;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
@@ -21,6 +39,24 @@ impl MigratorTrait for Migrator {
             Box::new(m20241216_120454_create_product_template_table::Migration),
             Box::new(m20241216_143112_create_product_table::Migration),
             Box::new(m20241222_055121_create_product_combination_table::Migration),
+            Box::new(m20241224_031245_add_archived_at_to_uom_category_attribute::Migration),
+            Box::new(m20241226_082410_create_product_mould_table::Migration),
+            Box::new(m20241228_094530_add_name_search_indexes::Migration),
+            Box::new(m20241229_101500_add_unaccent_unique_indexes::Migration),
+            Box::new(m20241230_083000_add_archived_at_to_attribute_option::Migration),
+            Box::new(m20241231_090000_add_category_and_ratio_to_uom::Migration),
+            Box::new(m20250102_084500_add_purchase_sales_price_uoms::Migration),
+            Box::new(m20250104_091500_create_inventory_tables::Migration),
+            Box::new(m20250106_100000_add_stock_valuation::Migration),
+            Box::new(m20250108_090000_add_lot_tracking::Migration),
+            Box::new(m20250110_090000_create_partner_tables::Migration),
+            Box::new(m20250112_090000_create_sales_tables::Migration),
+            Box::new(m20250114_090000_create_purchase_tables::Migration),
+            Box::new(m20250116_090000_create_pricelist_tables::Migration),
+            Box::new(m20250118_090000_create_tax_tables::Migration),
+            Box::new(m20250120_090000_create_currency_tables::Migration),
+            Box::new(m20250122_090000_add_sales_order_line_to_stock_move::Migration),
+            Box::new(m20250124_090000_add_template_and_validity_to_supplier_info::Migration),
         ]
   }
 }
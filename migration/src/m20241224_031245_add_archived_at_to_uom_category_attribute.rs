@@ -0,0 +1,79 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Uom::Table)
+          .add_column(timestamp_with_time_zone_null(Uom::ArchivedAt))
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Category::Table)
+          .add_column(timestamp_with_time_zone_null(Category::ArchivedAt))
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Attribute::Table)
+          .add_column(timestamp_with_time_zone_null(Attribute::ArchivedAt))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Attribute::Table)
+          .drop_column(Attribute::ArchivedAt)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Category::Table)
+          .drop_column(Category::ArchivedAt)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Uom::Table)
+          .drop_column(Uom::ArchivedAt)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Uom {
+  Table,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum Category {
+  Table,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum Attribute {
+  Table,
+  ArchivedAt,
+}
@@ -0,0 +1,73 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(ProductMould::Table)
+          .if_not_exists()
+          .col(uuid(ProductMould::ProductId))
+          .col(uuid(ProductMould::MouldTemplateId))
+          .primary_key(
+            Index::create()
+              .name("pk-product_mould")
+              .col(ProductMould::ProductId)
+              .col(ProductMould::MouldTemplateId),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-product_mould-product_id")
+              .from(ProductMould::Table, ProductMould::ProductId)
+              .to(Product::Table, Product::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-product_mould-mould_template_id")
+              .from(ProductMould::Table, ProductMould::MouldTemplateId)
+              .to(ProductTemplate::Table, ProductTemplate::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-product_mould-mould_template_id")
+          .table(ProductMould::Table)
+          .col(ProductMould::MouldTemplateId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(ProductMould::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum ProductMould {
+  Table,
+  ProductId,
+  MouldTemplateId,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+  Id,
+}
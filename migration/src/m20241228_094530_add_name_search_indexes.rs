@@ -0,0 +1,35 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const TABLES: [&str; 4] = ["product_template", "uom", "attribute", "category"];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    db.execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+      .await?;
+
+    for table in TABLES {
+      db.execute_unprepared(&format!(
+        r#"CREATE INDEX IF NOT EXISTS "idx-{table}-name_trgm" ON "{table}" USING gin (lower(name) gin_trgm_ops)"#
+      ))
+      .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+
+    for table in TABLES {
+      db.execute_unprepared(&format!(r#"DROP INDEX IF EXISTS "idx-{table}-name_trgm""#))
+        .await?;
+    }
+
+    Ok(())
+  }
+}
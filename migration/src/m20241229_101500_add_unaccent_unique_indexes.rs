@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Names are compared with `lower(f_unaccent(..))` so "Cái", "cai" and "CÁI"
+/// collide. `unaccent` itself is only STABLE, hence the IMMUTABLE wrapper that
+/// expression indexes require.
+const UNIQUE_INDEXES: [(&str, &str, &str); 4] = [
+  ("idx-uom-name_unique", "uom", "lower(f_unaccent(name))"),
+  (
+    "idx-attribute-name_unique",
+    "attribute",
+    "lower(f_unaccent(name))",
+  ),
+  (
+    "idx-attribute_option-attribute_id-value_unique",
+    "attribute_option",
+    "attribute_id, lower(f_unaccent(value))",
+  ),
+  (
+    "idx-category-parent_category_id-name_unique",
+    "category",
+    "COALESCE(parent_category_id, '00000000-0000-0000-0000-000000000000'::uuid), lower(f_unaccent(name))",
+  ),
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    db.execute_unprepared("CREATE EXTENSION IF NOT EXISTS unaccent")
+      .await?;
+    db.execute_unprepared(
+      r#"CREATE OR REPLACE FUNCTION f_unaccent(text) RETURNS text
+        AS $func$ SELECT public.unaccent('public.unaccent', $1) $func$
+        LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT"#,
+    )
+    .await?;
+
+    for (index, table, columns) in UNIQUE_INDEXES {
+      db.execute_unprepared(&format!(
+        r#"CREATE UNIQUE INDEX "{index}" ON "{table}" ({columns})"#
+      ))
+      .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+
+    for (index, _, _) in UNIQUE_INDEXES {
+      db.execute_unprepared(&format!(r#"DROP INDEX IF EXISTS "{index}""#))
+        .await?;
+    }
+    db.execute_unprepared("DROP FUNCTION IF EXISTS f_unaccent(text)")
+      .await?;
+
+    Ok(())
+  }
+}
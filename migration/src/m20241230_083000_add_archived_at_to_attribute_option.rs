@@ -0,0 +1,35 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(AttributeOption::Table)
+          .add_column(timestamp_with_time_zone_null(AttributeOption::ArchivedAt))
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(AttributeOption::Table)
+          .drop_column(AttributeOption::ArchivedAt)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum AttributeOption {
+  Table,
+  ArchivedAt,
+}
@@ -0,0 +1,100 @@
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(UomCategory::Enum)
+          .values([
+            UomCategory::Unit,
+            UomCategory::Weight,
+            UomCategory::Length,
+            UomCategory::Area,
+            UomCategory::Volume,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Uom::Table)
+          .add_column(
+            ColumnDef::new(Uom::Category)
+              .custom(UomCategory::Enum)
+              .not_null()
+              .default(UomCategory::Unit.to_string()),
+          )
+          .add_column(decimal_len(Uom::Ratio, 20, 10).default(1))
+          .add_column(decimal_len(Uom::Rounding, 20, 10).default(0.01))
+          .add_column(boolean(Uom::IsReference).default(false))
+          .to_owned(),
+      )
+      .await?;
+
+    // Ratios are expressed against the reference unit of the category, which
+    // therefore always has a ratio of exactly 1.
+    let db = manager.get_connection();
+    db.execute_unprepared(
+      r#"ALTER TABLE "uom"
+        ADD CONSTRAINT "chk-uom-ratio" CHECK (ratio > 0 AND (NOT is_reference OR ratio = 1)),
+        ADD CONSTRAINT "chk-uom-rounding" CHECK (rounding > 0)"#,
+    )
+    .await?;
+    db.execute_unprepared(
+      r#"CREATE UNIQUE INDEX "idx-uom-category_reference_unique" ON "uom" (category) WHERE is_reference"#,
+    )
+    .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Uom::Table)
+          .drop_column(Uom::IsReference)
+          .drop_column(Uom::Rounding)
+          .drop_column(Uom::Ratio)
+          .drop_column(Uom::Category)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_type(Type::drop().name(UomCategory::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Uom {
+  Table,
+  Category,
+  Ratio,
+  Rounding,
+  IsReference,
+}
+
+#[derive(DeriveIden)]
+enum UomCategory {
+  #[sea_orm(iden = "uom_category")]
+  Enum,
+  #[sea_orm(iden = "unit")]
+  Unit,
+  #[sea_orm(iden = "weight")]
+  Weight,
+  #[sea_orm(iden = "length")]
+  Length,
+  #[sea_orm(iden = "area")]
+  Area,
+  #[sea_orm(iden = "volume")]
+  Volume,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+
+    // Existing templates buy and sell in their stock unit, and existing prices
+    // refer to it as well.
+    db.execute_unprepared(
+      r#"ALTER TABLE "product_template"
+        ADD COLUMN "purchase_uom_id" uuid,
+        ADD COLUMN "sales_uom_id" uuid"#,
+    )
+    .await?;
+    db.execute_unprepared(
+      r#"UPDATE "product_template" SET "purchase_uom_id" = "uom_id", "sales_uom_id" = "uom_id""#,
+    )
+    .await?;
+    db.execute_unprepared(
+      r#"ALTER TABLE "product_template"
+        ALTER COLUMN "purchase_uom_id" SET NOT NULL,
+        ALTER COLUMN "sales_uom_id" SET NOT NULL,
+        ADD CONSTRAINT "fk-product_template-purchase_uom_id" FOREIGN KEY ("purchase_uom_id") REFERENCES "uom" ("id"),
+        ADD CONSTRAINT "fk-product_template-sales_uom_id" FOREIGN KEY ("sales_uom_id") REFERENCES "uom" ("id")"#,
+    )
+    .await?;
+
+    db.execute_unprepared(r#"ALTER TABLE "product" ADD COLUMN "price_uom_id" uuid"#)
+      .await?;
+    db.execute_unprepared(
+      r#"UPDATE "product" SET "price_uom_id" = "product_template"."uom_id"
+        FROM "product_template"
+        WHERE "product"."product_template_id" = "product_template"."id""#,
+    )
+    .await?;
+    db.execute_unprepared(
+      r#"ALTER TABLE "product"
+        ALTER COLUMN "price_uom_id" SET NOT NULL,
+        ADD CONSTRAINT "fk-product-price_uom_id" FOREIGN KEY ("price_uom_id") REFERENCES "uom" ("id")"#,
+    )
+    .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Product::Table)
+          .drop_column(Product::PriceUomId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(ProductTemplate::Table)
+          .drop_column(ProductTemplate::SalesUomId)
+          .drop_column(ProductTemplate::PurchaseUomId)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+  PurchaseUomId,
+  SalesUomId,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  PriceUomId,
+}
@@ -0,0 +1,264 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(LocationType::Enum)
+          .values([
+            LocationType::Internal,
+            LocationType::Supplier,
+            LocationType::Customer,
+            LocationType::Scrap,
+            LocationType::Adjustment,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(StockMoveState::Enum)
+          .values([
+            StockMoveState::Draft,
+            StockMoveState::Done,
+            StockMoveState::Cancelled,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Warehouse::Table)
+          .if_not_exists()
+          .col(uuid(Warehouse::Id).primary_key())
+          .col(text(Warehouse::Name).default(""))
+          .col(text(Warehouse::Code).unique_key())
+          .col(timestamp_with_time_zone(Warehouse::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Warehouse::UpdatedAt))
+          .col(timestamp_with_time_zone_null(Warehouse::ArchivedAt))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Location::Table)
+          .if_not_exists()
+          .col(uuid(Location::Id).primary_key())
+          .col(text(Location::Name).default(""))
+          .col(
+            ColumnDef::new(Location::LocationType)
+              .custom(LocationType::Enum)
+              .not_null()
+              .default(LocationType::Internal.to_string()),
+          )
+          .col(uuid_null(Location::ParentLocationId))
+          .col(uuid_null(Location::WarehouseId))
+          .col(timestamp_with_time_zone(Location::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Location::UpdatedAt))
+          .col(timestamp_with_time_zone_null(Location::ArchivedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-location-parent_location_id")
+              .from(Location::Table, Location::ParentLocationId)
+              .to(Location::Table, Location::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-location-warehouse_id")
+              .from(Location::Table, Location::WarehouseId)
+              .to(Warehouse::Table, Warehouse::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(StockMove::Table)
+          .if_not_exists()
+          .col(uuid(StockMove::Id).primary_key())
+          .col(text(StockMove::Reference).default(""))
+          .col(uuid(StockMove::ProductId))
+          .col(uuid(StockMove::SourceLocationId))
+          .col(uuid(StockMove::DestinationLocationId))
+          .col(decimal_len(StockMove::Quantity, 20, 10))
+          .col(uuid(StockMove::UomId))
+          .col(decimal_len(StockMove::ProductQuantity, 20, 10))
+          .col(
+            ColumnDef::new(StockMove::State)
+              .custom(StockMoveState::Enum)
+              .not_null()
+              .default(StockMoveState::Draft.to_string()),
+          )
+          .col(timestamp_with_time_zone_null(StockMove::DoneAt))
+          .col(timestamp_with_time_zone(StockMove::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(StockMove::UpdatedAt))
+          .check(Expr::col(StockMove::Quantity).gt(0))
+          .check(
+            Expr::col(StockMove::SourceLocationId).ne(Expr::col(StockMove::DestinationLocationId)),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_move-product_id")
+              .from(StockMove::Table, StockMove::ProductId)
+              .to(Product::Table, Product::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_move-source_location_id")
+              .from(StockMove::Table, StockMove::SourceLocationId)
+              .to(Location::Table, Location::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_move-destination_location_id")
+              .from(StockMove::Table, StockMove::DestinationLocationId)
+              .to(Location::Table, Location::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_move-uom_id")
+              .from(StockMove::Table, StockMove::UomId)
+              .to(Uom::Table, Uom::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    // On-hand quantities are summed over done moves per product and location.
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-stock_move-product_id-destination_location_id")
+          .table(StockMove::Table)
+          .col(StockMove::ProductId)
+          .col(StockMove::DestinationLocationId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-stock_move-product_id-source_location_id")
+          .table(StockMove::Table)
+          .col(StockMove::ProductId)
+          .col(StockMove::SourceLocationId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(StockMove::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Location::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Warehouse::Table).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(StockMoveState::Enum).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(LocationType::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Warehouse {
+  Table,
+  Id,
+  Name,
+  Code,
+  CreatedAt,
+  UpdatedAt,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+#[allow(clippy::enum_variant_names)]
+enum Location {
+  Table,
+  Id,
+  Name,
+  LocationType,
+  ParentLocationId,
+  WarehouseId,
+  CreatedAt,
+  UpdatedAt,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum StockMove {
+  Table,
+  Id,
+  Reference,
+  ProductId,
+  SourceLocationId,
+  DestinationLocationId,
+  Quantity,
+  UomId,
+  ProductQuantity,
+  State,
+  DoneAt,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Uom {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum LocationType {
+  #[sea_orm(iden = "location_type")]
+  Enum,
+  #[sea_orm(iden = "internal")]
+  Internal,
+  #[sea_orm(iden = "supplier")]
+  Supplier,
+  #[sea_orm(iden = "customer")]
+  Customer,
+  #[sea_orm(iden = "scrap")]
+  Scrap,
+  #[sea_orm(iden = "adjustment")]
+  Adjustment,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum StockMoveState {
+  #[sea_orm(iden = "stock_move_state")]
+  Enum,
+  #[sea_orm(iden = "draft")]
+  Draft,
+  #[sea_orm(iden = "done")]
+  Done,
+  #[sea_orm(iden = "cancelled")]
+  Cancelled,
+}
@@ -0,0 +1,174 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(CostingMethod::Enum)
+          .values([
+            CostingMethod::Standard,
+            CostingMethod::Average,
+            CostingMethod::Fifo,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Category::Table)
+          .add_column(
+            ColumnDef::new(Category::CostingMethod)
+              .custom(CostingMethod::Enum)
+              .not_null()
+              .default(CostingMethod::Standard.to_string()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(StockMove::Table)
+          .add_column(decimal_len_null(StockMove::UnitCost, 20, 10))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(StockValuationLayer::Table)
+          .if_not_exists()
+          .col(uuid(StockValuationLayer::Id).primary_key())
+          .col(uuid(StockValuationLayer::ProductId))
+          .col(uuid(StockValuationLayer::StockMoveId))
+          .col(uuid_null(StockValuationLayer::WarehouseId))
+          .col(decimal_len(StockValuationLayer::Quantity, 20, 10))
+          .col(decimal_len(StockValuationLayer::UnitCost, 20, 10))
+          .col(decimal_len(StockValuationLayer::Value, 20, 10))
+          .col(decimal_len(StockValuationLayer::RemainingQuantity, 20, 10).default(0))
+          .col(
+            timestamp_with_time_zone(StockValuationLayer::CreatedAt)
+              .default(Expr::current_timestamp()),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_valuation_layer-product_id")
+              .from(StockValuationLayer::Table, StockValuationLayer::ProductId)
+              .to(Product::Table, Product::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_valuation_layer-stock_move_id")
+              .from(StockValuationLayer::Table, StockValuationLayer::StockMoveId)
+              .to(StockMove::Table, StockMove::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-stock_valuation_layer-warehouse_id")
+              .from(StockValuationLayer::Table, StockValuationLayer::WarehouseId)
+              .to(Warehouse::Table, Warehouse::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-stock_valuation_layer-product_id-warehouse_id-created_at")
+          .table(StockValuationLayer::Table)
+          .col(StockValuationLayer::ProductId)
+          .col(StockValuationLayer::WarehouseId)
+          .col(StockValuationLayer::CreatedAt)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(StockValuationLayer::Table).to_owned())
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(StockMove::Table)
+          .drop_column(StockMove::UnitCost)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Category::Table)
+          .drop_column(Category::CostingMethod)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_type(Type::drop().name(CostingMethod::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Category {
+  Table,
+  CostingMethod,
+}
+
+#[derive(DeriveIden)]
+enum StockMove {
+  Table,
+  Id,
+  UnitCost,
+}
+
+#[derive(DeriveIden)]
+enum StockValuationLayer {
+  Table,
+  Id,
+  ProductId,
+  StockMoveId,
+  WarehouseId,
+  Quantity,
+  UnitCost,
+  Value,
+  RemainingQuantity,
+  CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Warehouse {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum CostingMethod {
+  #[sea_orm(iden = "costing_method")]
+  Enum,
+  #[sea_orm(iden = "standard")]
+  Standard,
+  #[sea_orm(iden = "average")]
+  Average,
+  #[sea_orm(iden = "fifo")]
+  Fifo,
+}
@@ -0,0 +1,174 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(Tracking::Enum)
+          .values([Tracking::None, Tracking::Lot, Tracking::Serial])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(ProductTemplate::Table)
+          .add_column(
+            ColumnDef::new(ProductTemplate::Tracking)
+              .custom(Tracking::Enum)
+              .not_null()
+              .default(Tracking::None.to_string()),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .get_connection()
+      .execute_unprepared("ALTER TYPE location_type ADD VALUE IF NOT EXISTS 'production'")
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Lot::Table)
+          .if_not_exists()
+          .col(uuid(Lot::Id).primary_key())
+          .col(uuid(Lot::ProductId))
+          .col(text(Lot::Name))
+          .col(timestamp_with_time_zone(Lot::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Lot::UpdatedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-lot-product_id")
+              .from(Lot::Table, Lot::ProductId)
+              .to(Product::Table, Product::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"CREATE UNIQUE INDEX "idx-lot-product_id-name" ON lot (product_id, lower(name))"#,
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(StockMove::Table)
+          .add_column(uuid_null(StockMove::LotId))
+          .add_column(uuid_null(StockMove::ProducedLotId))
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk-stock_move-lot_id")
+              .from_tbl(StockMove::Table)
+              .from_col(StockMove::LotId)
+              .to_tbl(Lot::Table)
+              .to_col(Lot::Id),
+          )
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk-stock_move-produced_lot_id")
+              .from_tbl(StockMove::Table)
+              .from_col(StockMove::ProducedLotId)
+              .to_tbl(Lot::Table)
+              .to_col(Lot::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    for column in [StockMove::LotId, StockMove::ProducedLotId] {
+      manager
+        .create_index(
+          Index::create()
+            .name(format!("idx-stock_move-{}", column.to_string()))
+            .table(StockMove::Table)
+            .col(column)
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  /// `production` stays in `location_type`: Postgres cannot drop enum values.
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(StockMove::Table)
+          .drop_column(StockMove::LotId)
+          .drop_column(StockMove::ProducedLotId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Lot::Table).to_owned())
+      .await?;
+    manager
+      .alter_table(
+        Table::alter()
+          .table(ProductTemplate::Table)
+          .drop_column(ProductTemplate::Tracking)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_type(Type::drop().name(Tracking::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+  Tracking,
+}
+
+#[derive(DeriveIden)]
+enum Lot {
+  Table,
+  Id,
+  ProductId,
+  Name,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum StockMove {
+  Table,
+  LotId,
+  ProducedLotId,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum Tracking {
+  #[sea_orm(iden = "tracking")]
+  Enum,
+  #[sea_orm(iden = "none")]
+  None,
+  #[sea_orm(iden = "lot")]
+  Lot,
+  #[sea_orm(iden = "serial")]
+  Serial,
+}
@@ -0,0 +1,240 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PartnerType::Enum)
+          .values([PartnerType::Company, PartnerType::Individual])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(AddressType::Enum)
+          .values([AddressType::Billing, AddressType::Delivery])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Partner::Table)
+          .if_not_exists()
+          .col(uuid(Partner::Id).primary_key())
+          .col(text(Partner::Name))
+          .col(
+            ColumnDef::new(Partner::PartnerType)
+              .custom(PartnerType::Enum)
+              .not_null()
+              .default(PartnerType::Company.to_string()),
+          )
+          .col(text_null(Partner::TaxCode))
+          .col(boolean(Partner::IsCustomer).default(false))
+          .col(boolean(Partner::IsSupplier).default(false))
+          .col(text_null(Partner::Email))
+          .col(text_null(Partner::Phone))
+          .col(integer(Partner::PaymentTermDays).default(0))
+          .col(timestamp_with_time_zone(Partner::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Partner::UpdatedAt))
+          .col(timestamp_with_time_zone_null(Partner::ArchivedAt))
+          .check(Expr::col(Partner::PaymentTermDays).gte(0))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"CREATE UNIQUE INDEX "idx-partner-tax_code" ON partner (tax_code) WHERE tax_code IS NOT NULL"#,
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PartnerAddress::Table)
+          .if_not_exists()
+          .col(uuid(PartnerAddress::Id).primary_key())
+          .col(uuid(PartnerAddress::PartnerId))
+          .col(
+            ColumnDef::new(PartnerAddress::AddressType)
+              .custom(AddressType::Enum)
+              .not_null(),
+          )
+          .col(text(PartnerAddress::Street))
+          .col(text(PartnerAddress::Ward).default(""))
+          .col(text(PartnerAddress::District).default(""))
+          .col(text(PartnerAddress::City).default(""))
+          .col(text(PartnerAddress::CountryCode).default("VN"))
+          .col(boolean(PartnerAddress::IsDefault).default(false))
+          .col(
+            timestamp_with_time_zone(PartnerAddress::CreatedAt).default(Expr::current_timestamp()),
+          )
+          .col(timestamp_with_time_zone_null(PartnerAddress::UpdatedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-partner_address-partner_id")
+              .from(PartnerAddress::Table, PartnerAddress::PartnerId)
+              .to(Partner::Table, Partner::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"CREATE UNIQUE INDEX "idx-partner_address-partner_id-address_type-is_default"
+          ON partner_address (partner_id, address_type) WHERE is_default"#,
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PartnerContact::Table)
+          .if_not_exists()
+          .col(uuid(PartnerContact::Id).primary_key())
+          .col(uuid(PartnerContact::PartnerId))
+          .col(text(PartnerContact::Name))
+          .col(text(PartnerContact::JobTitle).default(""))
+          .col(text_null(PartnerContact::Email))
+          .col(text_null(PartnerContact::Phone))
+          .col(
+            timestamp_with_time_zone(PartnerContact::CreatedAt).default(Expr::current_timestamp()),
+          )
+          .col(timestamp_with_time_zone_null(PartnerContact::UpdatedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-partner_contact-partner_id")
+              .from(PartnerContact::Table, PartnerContact::PartnerId)
+              .to(Partner::Table, Partner::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    for (name, table, column) in [
+      (
+        "idx-partner_address-partner_id",
+        PartnerAddress::Table.into_iden(),
+        PartnerAddress::PartnerId.into_iden(),
+      ),
+      (
+        "idx-partner_contact-partner_id",
+        PartnerContact::Table.into_iden(),
+        PartnerContact::PartnerId.into_iden(),
+      ),
+    ] {
+      manager
+        .create_index(
+          Index::create()
+            .name(name)
+            .table(table)
+            .col(column)
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PartnerContact::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(PartnerAddress::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Partner::Table).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(AddressType::Enum).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(PartnerType::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+#[allow(clippy::enum_variant_names)]
+enum Partner {
+  Table,
+  Id,
+  Name,
+  PartnerType,
+  TaxCode,
+  IsCustomer,
+  IsSupplier,
+  Email,
+  Phone,
+  PaymentTermDays,
+  CreatedAt,
+  UpdatedAt,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum PartnerAddress {
+  Table,
+  Id,
+  PartnerId,
+  AddressType,
+  Street,
+  Ward,
+  District,
+  City,
+  CountryCode,
+  IsDefault,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PartnerContact {
+  Table,
+  Id,
+  PartnerId,
+  Name,
+  JobTitle,
+  Email,
+  Phone,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum PartnerType {
+  #[sea_orm(iden = "partner_type")]
+  Enum,
+  #[sea_orm(iden = "company")]
+  Company,
+  #[sea_orm(iden = "individual")]
+  Individual,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum AddressType {
+  #[sea_orm(iden = "address_type")]
+  Enum,
+  #[sea_orm(iden = "billing")]
+  Billing,
+  #[sea_orm(iden = "delivery")]
+  Delivery,
+}
@@ -0,0 +1,249 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(SalesOrderState::Enum)
+          .values([
+            SalesOrderState::Quotation,
+            SalesOrderState::Confirmed,
+            SalesOrderState::Done,
+            SalesOrderState::Cancelled,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .get_connection()
+      .execute_unprepared("CREATE SEQUENCE IF NOT EXISTS sales_order_reference_seq")
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(SalesOrder::Table)
+          .if_not_exists()
+          .col(uuid(SalesOrder::Id).primary_key())
+          .col(text(SalesOrder::Reference).unique_key().default(Expr::cust(
+            "'SO' || lpad(nextval('sales_order_reference_seq')::text, 5, '0')",
+          )))
+          .col(uuid(SalesOrder::PartnerId))
+          .col(uuid_null(SalesOrder::InvoiceAddressId))
+          .col(uuid_null(SalesOrder::DeliveryAddressId))
+          .col(
+            ColumnDef::new(SalesOrder::State)
+              .custom(SalesOrderState::Enum)
+              .not_null()
+              .default(SalesOrderState::Quotation.to_string()),
+          )
+          .col(timestamp_with_time_zone(SalesOrder::OrderDate).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(SalesOrder::ConfirmedAt))
+          .col(integer(SalesOrder::PaymentTermDays).default(0))
+          .col(text(SalesOrder::Note).default(""))
+          .col(decimal_len(SalesOrder::AmountTotal, 20, 3).default(0))
+          .col(timestamp_with_time_zone(SalesOrder::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(SalesOrder::UpdatedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-sales_order-partner_id")
+              .from(SalesOrder::Table, SalesOrder::PartnerId)
+              .to(Partner::Table, Partner::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-sales_order-invoice_address_id")
+              .from(SalesOrder::Table, SalesOrder::InvoiceAddressId)
+              .to(PartnerAddress::Table, PartnerAddress::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-sales_order-delivery_address_id")
+              .from(SalesOrder::Table, SalesOrder::DeliveryAddressId)
+              .to(PartnerAddress::Table, PartnerAddress::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .check(Expr::col(SalesOrder::PaymentTermDays).gte(0))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(SalesOrderLine::Table)
+          .if_not_exists()
+          .col(uuid(SalesOrderLine::Id).primary_key())
+          .col(uuid(SalesOrderLine::SalesOrderId))
+          .col(integer(SalesOrderLine::Sequence).default(0))
+          .col(uuid(SalesOrderLine::ProductId))
+          .col(text(SalesOrderLine::Description).default(""))
+          .col(decimal_len(SalesOrderLine::Quantity, 20, 10))
+          .col(uuid(SalesOrderLine::UomId))
+          .col(decimal_len(SalesOrderLine::ProductQuantity, 20, 10))
+          .col(decimal_len(SalesOrderLine::UnitPrice, 20, 6))
+          .col(decimal_len(SalesOrderLine::Discount, 5, 2).default(0))
+          .col(decimal_len(SalesOrderLine::Subtotal, 20, 3))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-sales_order_line-sales_order_id")
+              .from(SalesOrderLine::Table, SalesOrderLine::SalesOrderId)
+              .to(SalesOrder::Table, SalesOrder::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-sales_order_line-product_id")
+              .from(SalesOrderLine::Table, SalesOrderLine::ProductId)
+              .to(Product::Table, Product::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-sales_order_line-uom_id")
+              .from(SalesOrderLine::Table, SalesOrderLine::UomId)
+              .to(Uom::Table, Uom::Id),
+          )
+          .check(Expr::col(SalesOrderLine::Quantity).gt(0))
+          .check(Expr::col(SalesOrderLine::UnitPrice).gte(0))
+          .check(
+            Expr::col(SalesOrderLine::Discount)
+              .gte(0)
+              .and(Expr::col(SalesOrderLine::Discount).lte(100)),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    for (name, table, column) in [
+      (
+        "idx-sales_order-partner_id",
+        SalesOrder::Table.into_iden(),
+        SalesOrder::PartnerId.into_iden(),
+      ),
+      (
+        "idx-sales_order-order_date",
+        SalesOrder::Table.into_iden(),
+        SalesOrder::OrderDate.into_iden(),
+      ),
+      (
+        "idx-sales_order_line-sales_order_id",
+        SalesOrderLine::Table.into_iden(),
+        SalesOrderLine::SalesOrderId.into_iden(),
+      ),
+      (
+        "idx-sales_order_line-product_id",
+        SalesOrderLine::Table.into_iden(),
+        SalesOrderLine::ProductId.into_iden(),
+      ),
+    ] {
+      manager
+        .create_index(
+          Index::create()
+            .name(name)
+            .table(table)
+            .col(column)
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(SalesOrderLine::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(SalesOrder::Table).to_owned())
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared("DROP SEQUENCE IF EXISTS sales_order_reference_seq")
+      .await?;
+    manager
+      .drop_type(Type::drop().name(SalesOrderState::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum SalesOrder {
+  Table,
+  Id,
+  Reference,
+  PartnerId,
+  InvoiceAddressId,
+  DeliveryAddressId,
+  State,
+  OrderDate,
+  ConfirmedAt,
+  PaymentTermDays,
+  Note,
+  AmountTotal,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum SalesOrderLine {
+  Table,
+  Id,
+  SalesOrderId,
+  Sequence,
+  ProductId,
+  Description,
+  Quantity,
+  UomId,
+  ProductQuantity,
+  UnitPrice,
+  Discount,
+  Subtotal,
+}
+
+#[derive(DeriveIden)]
+enum Partner {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum PartnerAddress {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Uom {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum SalesOrderState {
+  #[sea_orm(iden = "sales_order_state")]
+  Enum,
+  #[sea_orm(iden = "quotation")]
+  Quotation,
+  #[sea_orm(iden = "confirmed")]
+  Confirmed,
+  #[sea_orm(iden = "done")]
+  Done,
+  #[sea_orm(iden = "cancelled")]
+  Cancelled,
+}
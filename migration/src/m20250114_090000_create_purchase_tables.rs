@@ -0,0 +1,322 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(SupplierInfo::Table)
+          .if_not_exists()
+          .col(uuid(SupplierInfo::Id).primary_key())
+          .col(uuid(SupplierInfo::PartnerId))
+          .col(uuid(SupplierInfo::ProductId))
+          .col(text_null(SupplierInfo::SupplierSku))
+          .col(decimal_len(SupplierInfo::Price, 20, 6))
+          .col(uuid(SupplierInfo::UomId))
+          .col(decimal_len(SupplierInfo::MinQuantity, 20, 10).default(0))
+          .col(integer(SupplierInfo::LeadTimeDays).default(0))
+          .col(timestamp_with_time_zone(SupplierInfo::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(SupplierInfo::UpdatedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-supplier_info-partner_id")
+              .from(SupplierInfo::Table, SupplierInfo::PartnerId)
+              .to(Partner::Table, Partner::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-supplier_info-product_id")
+              .from(SupplierInfo::Table, SupplierInfo::ProductId)
+              .to(Product::Table, Product::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-supplier_info-uom_id")
+              .from(SupplierInfo::Table, SupplierInfo::UomId)
+              .to(Uom::Table, Uom::Id),
+          )
+          .check(Expr::col(SupplierInfo::Price).gte(0))
+          .check(Expr::col(SupplierInfo::MinQuantity).gte(0))
+          .check(Expr::col(SupplierInfo::LeadTimeDays).gte(0))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PurchaseOrderState::Enum)
+          .values([
+            PurchaseOrderState::Rfq,
+            PurchaseOrderState::Confirmed,
+            PurchaseOrderState::Received,
+            PurchaseOrderState::Cancelled,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .get_connection()
+      .execute_unprepared("CREATE SEQUENCE IF NOT EXISTS purchase_order_reference_seq")
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PurchaseOrder::Table)
+          .if_not_exists()
+          .col(uuid(PurchaseOrder::Id).primary_key())
+          .col(
+            text(PurchaseOrder::Reference)
+              .unique_key()
+              .default(Expr::cust(
+                "'PO' || lpad(nextval('purchase_order_reference_seq')::text, 5, '0')",
+              )),
+          )
+          .col(uuid(PurchaseOrder::PartnerId))
+          .col(
+            ColumnDef::new(PurchaseOrder::State)
+              .custom(PurchaseOrderState::Enum)
+              .not_null()
+              .default(PurchaseOrderState::Rfq.to_string()),
+          )
+          .col(
+            timestamp_with_time_zone(PurchaseOrder::OrderDate).default(Expr::current_timestamp()),
+          )
+          .col(timestamp_with_time_zone_null(PurchaseOrder::ExpectedDate))
+          .col(timestamp_with_time_zone_null(PurchaseOrder::ConfirmedAt))
+          .col(timestamp_with_time_zone_null(PurchaseOrder::ReceivedAt))
+          .col(text(PurchaseOrder::Note).default(""))
+          .col(decimal_len(PurchaseOrder::AmountTotal, 20, 3).default(0))
+          .col(
+            timestamp_with_time_zone(PurchaseOrder::CreatedAt).default(Expr::current_timestamp()),
+          )
+          .col(timestamp_with_time_zone_null(PurchaseOrder::UpdatedAt))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-purchase_order-partner_id")
+              .from(PurchaseOrder::Table, PurchaseOrder::PartnerId)
+              .to(Partner::Table, Partner::Id),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PurchaseOrderLine::Table)
+          .if_not_exists()
+          .col(uuid(PurchaseOrderLine::Id).primary_key())
+          .col(uuid(PurchaseOrderLine::PurchaseOrderId))
+          .col(integer(PurchaseOrderLine::Sequence).default(0))
+          .col(uuid(PurchaseOrderLine::ProductId))
+          .col(uuid_null(PurchaseOrderLine::SupplierInfoId))
+          .col(text(PurchaseOrderLine::Description).default(""))
+          .col(decimal_len(PurchaseOrderLine::Quantity, 20, 10))
+          .col(uuid(PurchaseOrderLine::UomId))
+          .col(decimal_len(PurchaseOrderLine::ProductQuantity, 20, 10))
+          .col(decimal_len(PurchaseOrderLine::UnitPrice, 20, 6))
+          .col(decimal_len(PurchaseOrderLine::Subtotal, 20, 3))
+          .col(uuid_null(PurchaseOrderLine::StockMoveId))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-purchase_order_line-purchase_order_id")
+              .from(PurchaseOrderLine::Table, PurchaseOrderLine::PurchaseOrderId)
+              .to(PurchaseOrder::Table, PurchaseOrder::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-purchase_order_line-product_id")
+              .from(PurchaseOrderLine::Table, PurchaseOrderLine::ProductId)
+              .to(Product::Table, Product::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-purchase_order_line-supplier_info_id")
+              .from(PurchaseOrderLine::Table, PurchaseOrderLine::SupplierInfoId)
+              .to(SupplierInfo::Table, SupplierInfo::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-purchase_order_line-uom_id")
+              .from(PurchaseOrderLine::Table, PurchaseOrderLine::UomId)
+              .to(Uom::Table, Uom::Id),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-purchase_order_line-stock_move_id")
+              .from(PurchaseOrderLine::Table, PurchaseOrderLine::StockMoveId)
+              .to(StockMove::Table, StockMove::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .check(Expr::col(PurchaseOrderLine::Quantity).gt(0))
+          .check(Expr::col(PurchaseOrderLine::UnitPrice).gte(0))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-supplier_info-product_id-partner_id")
+          .table(SupplierInfo::Table)
+          .col(SupplierInfo::ProductId)
+          .col(SupplierInfo::PartnerId)
+          .to_owned(),
+      )
+      .await?;
+
+    for (name, table, column) in [
+      (
+        "idx-purchase_order-partner_id",
+        PurchaseOrder::Table.into_iden(),
+        PurchaseOrder::PartnerId.into_iden(),
+      ),
+      (
+        "idx-purchase_order-order_date",
+        PurchaseOrder::Table.into_iden(),
+        PurchaseOrder::OrderDate.into_iden(),
+      ),
+      (
+        "idx-purchase_order_line-purchase_order_id",
+        PurchaseOrderLine::Table.into_iden(),
+        PurchaseOrderLine::PurchaseOrderId.into_iden(),
+      ),
+      (
+        "idx-purchase_order_line-product_id",
+        PurchaseOrderLine::Table.into_iden(),
+        PurchaseOrderLine::ProductId.into_iden(),
+      ),
+    ] {
+      manager
+        .create_index(
+          Index::create()
+            .name(name)
+            .table(table)
+            .col(column)
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .drop_table(Table::drop().table(PurchaseOrderLine::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(PurchaseOrder::Table).to_owned())
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared("DROP SEQUENCE IF EXISTS purchase_order_reference_seq")
+      .await?;
+    manager
+      .drop_type(Type::drop().name(PurchaseOrderState::Enum).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(SupplierInfo::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum SupplierInfo {
+  Table,
+  Id,
+  PartnerId,
+  ProductId,
+  SupplierSku,
+  Price,
+  UomId,
+  MinQuantity,
+  LeadTimeDays,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrder {
+  Table,
+  Id,
+  Reference,
+  PartnerId,
+  State,
+  OrderDate,
+  ExpectedDate,
+  ConfirmedAt,
+  ReceivedAt,
+  Note,
+  AmountTotal,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrderLine {
+  Table,
+  Id,
+  PurchaseOrderId,
+  Sequence,
+  ProductId,
+  SupplierInfoId,
+  Description,
+  Quantity,
+  UomId,
+  ProductQuantity,
+  UnitPrice,
+  Subtotal,
+  StockMoveId,
+}
+
+#[derive(DeriveIden)]
+enum Partner {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Uom {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum StockMove {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum PurchaseOrderState {
+  #[sea_orm(iden = "purchase_order_state")]
+  Enum,
+  #[sea_orm(iden = "rfq")]
+  Rfq,
+  #[sea_orm(iden = "confirmed")]
+  Confirmed,
+  #[sea_orm(iden = "received")]
+  Received,
+  #[sea_orm(iden = "cancelled")]
+  Cancelled,
+}
@@ -0,0 +1,264 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Pricelist::Table)
+          .if_not_exists()
+          .col(uuid(Pricelist::Id).primary_key())
+          .col(text(Pricelist::Name))
+          .col(timestamp_with_time_zone(Pricelist::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Pricelist::UpdatedAt))
+          .col(timestamp_with_time_zone_null(Pricelist::ArchivedAt))
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"CREATE UNIQUE INDEX "idx-pricelist-name_unique" ON pricelist (lower(f_unaccent(name)))"#,
+      )
+      .await?;
+
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PricelistRuleScope::Enum)
+          .values([
+            PricelistRuleScope::Variant,
+            PricelistRuleScope::Template,
+            PricelistRuleScope::Category,
+            PricelistRuleScope::Global,
+          ])
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(PricelistComputeType::Enum)
+          .values([
+            PricelistComputeType::Fixed,
+            PricelistComputeType::Discount,
+            PricelistComputeType::CostPlusMargin,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(PricelistRule::Table)
+          .if_not_exists()
+          .col(uuid(PricelistRule::Id).primary_key())
+          .col(uuid(PricelistRule::PricelistId))
+          .col(integer(PricelistRule::Sequence).default(0))
+          .col(
+            ColumnDef::new(PricelistRule::Scope)
+              .custom(PricelistRuleScope::Enum)
+              .not_null(),
+          )
+          .col(uuid_null(PricelistRule::ProductId))
+          .col(uuid_null(PricelistRule::ProductTemplateId))
+          .col(uuid_null(PricelistRule::CategoryId))
+          .col(decimal_len(PricelistRule::MinQuantity, 20, 10).default(0))
+          .col(timestamp_with_time_zone_null(PricelistRule::DateStart))
+          .col(timestamp_with_time_zone_null(PricelistRule::DateEnd))
+          .col(
+            ColumnDef::new(PricelistRule::ComputeType)
+              .custom(PricelistComputeType::Enum)
+              .not_null(),
+          )
+          .col(decimal_len_null(PricelistRule::FixedPrice, 20, 6))
+          .col(decimal_len_null(PricelistRule::Discount, 7, 4))
+          .col(decimal_len_null(PricelistRule::Margin, 9, 4))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-pricelist_rule-pricelist_id")
+              .from(PricelistRule::Table, PricelistRule::PricelistId)
+              .to(Pricelist::Table, Pricelist::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-pricelist_rule-product_id")
+              .from(PricelistRule::Table, PricelistRule::ProductId)
+              .to(Product::Table, Product::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-pricelist_rule-product_template_id")
+              .from(PricelistRule::Table, PricelistRule::ProductTemplateId)
+              .to(ProductTemplate::Table, ProductTemplate::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-pricelist_rule-category_id")
+              .from(PricelistRule::Table, PricelistRule::CategoryId)
+              .to(Category::Table, Category::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .check(Expr::col(PricelistRule::MinQuantity).gte(0))
+          .check(Expr::col(PricelistRule::FixedPrice).gte(0))
+          .check(Expr::col(PricelistRule::Discount).lte(100))
+          .check(Expr::col(PricelistRule::Margin).gt(-100))
+          .check(Expr::cust(
+            "(scope = 'variant') = (product_id IS NOT NULL) \
+             AND (scope = 'template') = (product_template_id IS NOT NULL) \
+             AND (scope = 'category') = (category_id IS NOT NULL)",
+          ))
+          .check(Expr::cust(
+            "(compute_type <> 'fixed' OR fixed_price IS NOT NULL) \
+             AND (compute_type <> 'discount' OR discount IS NOT NULL) \
+             AND (compute_type <> 'cost_plus_margin' OR margin IS NOT NULL)",
+          ))
+          .check(Expr::cust(
+            "date_start IS NULL OR date_end IS NULL OR date_start < date_end",
+          ))
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-pricelist_rule-pricelist_id")
+          .table(PricelistRule::Table)
+          .col(PricelistRule::PricelistId)
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Partner::Table)
+          .add_column(uuid_null(Partner::PricelistId))
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk-partner-pricelist_id")
+              .from_tbl(Partner::Table)
+              .from_col(Partner::PricelistId)
+              .to_tbl(Pricelist::Table)
+              .to_col(Pricelist::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(Partner::Table)
+          .drop_foreign_key(Alias::new("fk-partner-pricelist_id"))
+          .drop_column(Partner::PricelistId)
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .drop_table(Table::drop().table(PricelistRule::Table).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(PricelistComputeType::Enum).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(PricelistRuleScope::Enum).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Pricelist::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Pricelist {
+  Table,
+  Id,
+  Name,
+  CreatedAt,
+  UpdatedAt,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum PricelistRule {
+  Table,
+  Id,
+  PricelistId,
+  Sequence,
+  Scope,
+  ProductId,
+  ProductTemplateId,
+  CategoryId,
+  MinQuantity,
+  DateStart,
+  DateEnd,
+  ComputeType,
+  FixedPrice,
+  Discount,
+  Margin,
+}
+
+#[derive(DeriveIden)]
+enum Partner {
+  Table,
+  PricelistId,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden)]
+enum Category {
+  Table,
+  Id,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum PricelistRuleScope {
+  #[sea_orm(iden = "pricelist_rule_scope")]
+  Enum,
+  #[sea_orm(iden = "variant")]
+  Variant,
+  #[sea_orm(iden = "template")]
+  Template,
+  #[sea_orm(iden = "category")]
+  Category,
+  #[sea_orm(iden = "global")]
+  Global,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum PricelistComputeType {
+  #[sea_orm(iden = "pricelist_compute_type")]
+  Enum,
+  #[sea_orm(iden = "fixed")]
+  Fixed,
+  #[sea_orm(iden = "discount")]
+  Discount,
+  #[sea_orm(iden = "cost_plus_margin")]
+  CostPlusMargin,
+}
@@ -0,0 +1,305 @@
+use sea_orm::EnumIter;
+use sea_orm_migration::prelude::{sea_query::extension::postgres::Type, *};
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(TaxUse::Enum)
+          .values([TaxUse::Sales, TaxUse::Purchase])
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(TaxAmountType::Enum)
+          .values([TaxAmountType::Percent, TaxAmountType::Fixed])
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .create_type(
+        Type::create()
+          .as_enum(VatGroup::Enum)
+          .values([
+            VatGroup::Vat0,
+            VatGroup::Vat5,
+            VatGroup::Vat8,
+            VatGroup::Vat10,
+            VatGroup::Exempt,
+          ])
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(Tax::Table)
+          .if_not_exists()
+          .col(uuid(Tax::Id).primary_key())
+          .col(text(Tax::Name))
+          .col(ColumnDef::new(Tax::TaxUse).custom(TaxUse::Enum).not_null())
+          .col(
+            ColumnDef::new(Tax::AmountType)
+              .custom(TaxAmountType::Enum)
+              .not_null(),
+          )
+          .col(decimal_len(Tax::Amount, 20, 6))
+          .col(boolean(Tax::PriceInclude).default(false))
+          .col(ColumnDef::new(Tax::VatGroup).custom(VatGroup::Enum).null())
+          .col(timestamp_with_time_zone(Tax::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Tax::UpdatedAt))
+          .col(timestamp_with_time_zone_null(Tax::ArchivedAt))
+          .check(Expr::col(Tax::Amount).gte(0))
+          .check(Expr::cust("amount_type <> 'percent' OR amount <= 100"))
+          .check(Expr::cust(
+            "vat_group IS NULL OR (amount_type = 'percent' AND amount = CASE vat_group \
+             WHEN 'vat_5' THEN 5 WHEN 'vat_8' THEN 8 WHEN 'vat_10' THEN 10 ELSE 0 END)",
+          ))
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"CREATE UNIQUE INDEX "idx-tax-tax_use-name_unique" ON tax (tax_use, lower(f_unaccent(name)))"#,
+      )
+      .await?;
+
+    for table in [
+      ProductTemplate::Table.into_iden(),
+      Category::Table.into_iden(),
+    ] {
+      let table_name = table.to_string();
+      let mut alter = Table::alter();
+      alter.table(table.clone());
+      for column in [DefaultTax::SalesTaxId, DefaultTax::PurchaseTaxId] {
+        alter.add_column(uuid_null(column)).add_foreign_key(
+          TableForeignKey::new()
+            .name(format!("fk-{}-{}", table_name, column.to_string()))
+            .from_tbl(table.clone())
+            .from_col(column)
+            .to_tbl(Tax::Table)
+            .to_col(Tax::Id)
+            .on_delete(ForeignKeyAction::SetNull),
+        );
+      }
+      manager.alter_table(alter.to_owned()).await?;
+    }
+
+    for table in [
+      SalesOrderLine::Table.into_iden(),
+      PurchaseOrderLine::Table.into_iden(),
+    ] {
+      let table_name = table.to_string();
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table.clone())
+            .add_column(uuid_null(OrderLineTax::TaxId))
+            .add_column(decimal_len(OrderLineTax::TaxAmount, 20, 3).default(0))
+            .add_foreign_key(
+              TableForeignKey::new()
+                .name(format!("fk-{}-tax_id", table_name))
+                .from_tbl(table.clone())
+                .from_col(OrderLineTax::TaxId)
+                .to_tbl(Tax::Table)
+                .to_col(Tax::Id),
+            )
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    for table in [
+      SalesOrder::Table.into_iden(),
+      PurchaseOrder::Table.into_iden(),
+    ] {
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table.clone())
+            .add_column(decimal_len(OrderAmount::AmountUntaxed, 20, 3).default(0))
+            .add_column(decimal_len(OrderAmount::AmountTax, 20, 3).default(0))
+            .to_owned(),
+        )
+        .await?;
+    }
+    // Orders placed before taxes existed are entirely untaxed.
+    manager
+      .get_connection()
+      .execute_unprepared(
+        "UPDATE sales_order SET amount_untaxed = amount_total; \
+         UPDATE purchase_order SET amount_untaxed = amount_total",
+      )
+      .await?;
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    for table in [
+      SalesOrder::Table.into_iden(),
+      PurchaseOrder::Table.into_iden(),
+    ] {
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table)
+            .drop_column(OrderAmount::AmountUntaxed)
+            .drop_column(OrderAmount::AmountTax)
+            .to_owned(),
+        )
+        .await?;
+    }
+    for table in [
+      SalesOrderLine::Table.into_iden(),
+      PurchaseOrderLine::Table.into_iden(),
+    ] {
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table)
+            .drop_column(OrderLineTax::TaxId)
+            .drop_column(OrderLineTax::TaxAmount)
+            .to_owned(),
+        )
+        .await?;
+    }
+    for table in [
+      ProductTemplate::Table.into_iden(),
+      Category::Table.into_iden(),
+    ] {
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table)
+            .drop_column(DefaultTax::SalesTaxId)
+            .drop_column(DefaultTax::PurchaseTaxId)
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    manager
+      .drop_table(Table::drop().table(Tax::Table).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(VatGroup::Enum).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(TaxAmountType::Enum).to_owned())
+      .await?;
+    manager
+      .drop_type(Type::drop().name(TaxUse::Enum).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+#[allow(clippy::enum_variant_names)]
+enum Tax {
+  Table,
+  Id,
+  Name,
+  TaxUse,
+  AmountType,
+  Amount,
+  PriceInclude,
+  VatGroup,
+  CreatedAt,
+  UpdatedAt,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum DefaultTax {
+  SalesTaxId,
+  PurchaseTaxId,
+}
+
+#[derive(DeriveIden)]
+enum OrderLineTax {
+  TaxId,
+  TaxAmount,
+}
+
+#[derive(DeriveIden)]
+enum OrderAmount {
+  AmountUntaxed,
+  AmountTax,
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum Category {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum SalesOrder {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum SalesOrderLine {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrder {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrderLine {
+  Table,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum TaxUse {
+  #[sea_orm(iden = "tax_use")]
+  Enum,
+  #[sea_orm(iden = "sales")]
+  Sales,
+  #[sea_orm(iden = "purchase")]
+  Purchase,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum TaxAmountType {
+  #[sea_orm(iden = "tax_amount_type")]
+  Enum,
+  #[sea_orm(iden = "percent")]
+  Percent,
+  #[sea_orm(iden = "fixed")]
+  Fixed,
+}
+
+#[derive(DeriveIden, EnumIter)]
+enum VatGroup {
+  #[sea_orm(iden = "vat_group")]
+  Enum,
+  #[sea_orm(iden = "vat_0")]
+  Vat0,
+  #[sea_orm(iden = "vat_5")]
+  Vat5,
+  #[sea_orm(iden = "vat_8")]
+  Vat8,
+  #[sea_orm(iden = "vat_10")]
+  Vat10,
+  #[sea_orm(iden = "exempt")]
+  Exempt,
+}
@@ -0,0 +1,211 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .create_table(
+        Table::create()
+          .table(Currency::Table)
+          .if_not_exists()
+          .col(uuid(Currency::Id).primary_key())
+          .col(text(Currency::Code))
+          .col(text(Currency::Name))
+          .col(text(Currency::Symbol))
+          .col(integer(Currency::DecimalPlaces))
+          .col(decimal_len(Currency::Rounding, 20, 10))
+          .col(boolean(Currency::IsBase).default(false))
+          .col(timestamp_with_time_zone(Currency::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(Currency::UpdatedAt))
+          .col(timestamp_with_time_zone_null(Currency::ArchivedAt))
+          .check(Expr::cust("code ~ '^[A-Z]{3}$'"))
+          .check(Expr::col(Currency::DecimalPlaces).between(0, 6))
+          .check(Expr::col(Currency::Rounding).gt(0))
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-currency-code_unique")
+          .table(Currency::Table)
+          .col(Currency::Code)
+          .unique()
+          .to_owned(),
+      )
+      .await?;
+    // Amounts without a currency of their own are in the base currency, so
+    // there is exactly one.
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"CREATE UNIQUE INDEX "idx-currency-is_base_unique" ON currency (is_base) WHERE is_base"#,
+      )
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared(
+        "INSERT INTO currency (id, code, name, symbol, decimal_places, rounding, is_base) VALUES \
+         (gen_random_uuid(), 'VND', 'Vietnamese dong', '₫', 0, 1, true), \
+         (gen_random_uuid(), 'USD', 'US dollar', '$', 2, 0.01, false)",
+      )
+      .await?;
+
+    manager
+      .create_table(
+        Table::create()
+          .table(CurrencyRate::Table)
+          .if_not_exists()
+          .col(uuid(CurrencyRate::Id).primary_key())
+          .col(uuid(CurrencyRate::CurrencyId))
+          .col(decimal_len(CurrencyRate::Rate, 20, 10))
+          .col(timestamp_with_time_zone(CurrencyRate::DateStart))
+          .col(timestamp_with_time_zone(CurrencyRate::CreatedAt).default(Expr::current_timestamp()))
+          .col(timestamp_with_time_zone_null(CurrencyRate::UpdatedAt))
+          .check(Expr::col(CurrencyRate::Rate).gt(0))
+          .foreign_key(
+            ForeignKey::create()
+              .name("fk-currency_rate-currency_id")
+              .from(CurrencyRate::Table, CurrencyRate::CurrencyId)
+              .to(Currency::Table, Currency::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-currency_rate-currency_id-date_start_unique")
+          .table(CurrencyRate::Table)
+          .col(CurrencyRate::CurrencyId)
+          .col(CurrencyRate::DateStart)
+          .unique()
+          .to_owned(),
+      )
+      .await?;
+
+    for table in [
+      ProductTemplate::Table.into_iden(),
+      SupplierInfo::Table.into_iden(),
+      Pricelist::Table.into_iden(),
+      SalesOrder::Table.into_iden(),
+      PurchaseOrder::Table.into_iden(),
+    ] {
+      let table_name = table.to_string();
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table.clone())
+            .add_column(uuid_null(CurrencyAmount::CurrencyId))
+            .add_foreign_key(
+              TableForeignKey::new()
+                .name(format!("fk-{}-currency_id", table_name))
+                .from_tbl(table.clone())
+                .from_col(CurrencyAmount::CurrencyId)
+                .to_tbl(Currency::Table)
+                .to_col(Currency::Id),
+            )
+            .to_owned(),
+        )
+        .await?;
+      // Everything entered so far was implicitly in dong.
+      manager
+        .get_connection()
+        .execute_unprepared(&format!(
+          "UPDATE {0} SET currency_id = (SELECT id FROM currency WHERE is_base); \
+           ALTER TABLE {0} ALTER COLUMN currency_id SET NOT NULL",
+          table_name
+        ))
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    for table in [
+      PurchaseOrder::Table.into_iden(),
+      SalesOrder::Table.into_iden(),
+      Pricelist::Table.into_iden(),
+      SupplierInfo::Table.into_iden(),
+      ProductTemplate::Table.into_iden(),
+    ] {
+      manager
+        .alter_table(
+          Table::alter()
+            .table(table)
+            .drop_column(CurrencyAmount::CurrencyId)
+            .to_owned(),
+        )
+        .await?;
+    }
+
+    manager
+      .drop_table(Table::drop().table(CurrencyRate::Table).to_owned())
+      .await?;
+    manager
+      .drop_table(Table::drop().table(Currency::Table).to_owned())
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum Currency {
+  Table,
+  Id,
+  Code,
+  Name,
+  Symbol,
+  DecimalPlaces,
+  Rounding,
+  IsBase,
+  CreatedAt,
+  UpdatedAt,
+  ArchivedAt,
+}
+
+#[derive(DeriveIden)]
+enum CurrencyRate {
+  Table,
+  Id,
+  CurrencyId,
+  Rate,
+  DateStart,
+  CreatedAt,
+  UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum CurrencyAmount {
+  CurrencyId,
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum SupplierInfo {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum Pricelist {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum SalesOrder {
+  Table,
+}
+
+#[derive(DeriveIden)]
+enum PurchaseOrder {
+  Table,
+}
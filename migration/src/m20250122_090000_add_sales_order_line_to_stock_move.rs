@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(StockMove::Table)
+          .add_column(uuid_null(StockMove::SalesOrderLineId))
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk-stock_move-sales_order_line_id")
+              .from_tbl(StockMove::Table)
+              .from_col(StockMove::SalesOrderLineId)
+              .to_tbl(SalesOrderLine::Table)
+              .to_col(SalesOrderLine::Id)
+              .on_delete(ForeignKeyAction::SetNull),
+          )
+          .to_owned(),
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-stock_move-sales_order_line_id")
+          .table(StockMove::Table)
+          .col(StockMove::SalesOrderLineId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(StockMove::Table)
+          .drop_column(StockMove::SalesOrderLineId)
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum StockMove {
+  Table,
+  SalesOrderLineId,
+}
+
+#[derive(DeriveIden)]
+enum SalesOrderLine {
+  Table,
+  Id,
+}
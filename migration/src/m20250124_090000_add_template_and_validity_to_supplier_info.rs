@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::schema::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+  async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .alter_table(
+        Table::alter()
+          .table(SupplierInfo::Table)
+          .modify_column(ColumnDef::new(SupplierInfo::ProductId).uuid().null())
+          .add_column(uuid_null(SupplierInfo::ProductTemplateId))
+          .add_column(timestamp_with_time_zone_null(SupplierInfo::DateStart))
+          .add_column(timestamp_with_time_zone_null(SupplierInfo::DateEnd))
+          .add_foreign_key(
+            TableForeignKey::new()
+              .name("fk-supplier_info-product_template_id")
+              .from_tbl(SupplierInfo::Table)
+              .from_col(SupplierInfo::ProductTemplateId)
+              .to_tbl(ProductTemplate::Table)
+              .to_col(ProductTemplate::Id)
+              .on_delete(ForeignKeyAction::Cascade),
+          )
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"ALTER TABLE supplier_info
+             ADD CONSTRAINT "chk-supplier_info-product" CHECK ((product_id IS NULL) <> (product_template_id IS NULL)),
+             ADD CONSTRAINT "chk-supplier_info-dates" CHECK (date_start IS NULL OR date_end IS NULL OR date_start < date_end)"#,
+      )
+      .await?;
+
+    manager
+      .create_index(
+        Index::create()
+          .name("idx-supplier_info-product_template_id-partner_id")
+          .table(SupplierInfo::Table)
+          .col(SupplierInfo::ProductTemplateId)
+          .col(SupplierInfo::PartnerId)
+          .to_owned(),
+      )
+      .await
+  }
+
+  async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+    manager
+      .exec_stmt(
+        Query::delete()
+          .from_table(SupplierInfo::Table)
+          .and_where(Expr::col(SupplierInfo::ProductId).is_null())
+          .to_owned(),
+      )
+      .await?;
+    manager
+      .get_connection()
+      .execute_unprepared(
+        r#"ALTER TABLE supplier_info
+             DROP CONSTRAINT "chk-supplier_info-product",
+             DROP CONSTRAINT "chk-supplier_info-dates""#,
+      )
+      .await?;
+
+    manager
+      .alter_table(
+        Table::alter()
+          .table(SupplierInfo::Table)
+          .drop_column(SupplierInfo::ProductTemplateId)
+          .drop_column(SupplierInfo::DateStart)
+          .drop_column(SupplierInfo::DateEnd)
+          .modify_column(ColumnDef::new(SupplierInfo::ProductId).uuid().not_null())
+          .to_owned(),
+      )
+      .await
+  }
+}
+
+#[derive(DeriveIden)]
+enum SupplierInfo {
+  Table,
+  PartnerId,
+  ProductId,
+  ProductTemplateId,
+  DateStart,
+  DateEnd,
+}
+
+#[derive(DeriveIden)]
+enum ProductTemplate {
+  Table,
+  Id,
+}
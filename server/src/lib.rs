@@ -1,11 +1,17 @@
 use axum::{
-  http::{header::CONTENT_TYPE, Method},
-  Router,
+  http::{header::CONTENT_TYPE, HeaderName, Method},
+  middleware, Router,
+};
+use infra::{
+  db::{attach_consistency_token, ReadDb, WriteDb, CONSISTENCY_TOKEN_HEADER},
+  state::AppState,
 };
-use infra::state::AppState;
 use interface::{
   attribute::route::AttributeRouter, category::route::CategoryRouter,
-  product::route::ProductRouter, uom::route::UomRouter,
+  currency::route::CurrencyRouter, inventory::route::InventoryRouter,
+  partner::route::PartnerRouter, pricelist::route::PricelistRouter, product::route::ProductRouter,
+  purchase::route::PurchaseRouter, sales::route::SalesRouter, tax::route::TaxRouter,
+  uom::route::UomRouter,
 };
 use sea_orm::{Database, DatabaseConnection, DbErr};
 use std::{net::SocketAddr, sync::Arc};
@@ -29,7 +35,7 @@ pub async fn start() {
   let write_db = match get_db_connection("DATABASE_URL").await {
     Ok(db) => {
       tracing::info!("Connected to write database!");
-      db
+      WriteDb::new(db)
     }
     Err(_) => {
       tracing::error!("Failed to connect to write database!");
@@ -39,14 +45,14 @@ pub async fn start() {
   let read_db = match get_db_connection("DATABASE_URL_READ").await {
     Ok(db) => {
       tracing::info!("Connected to read database!");
-      db
+      ReadDb::new(db)
     }
     Err(_) => {
       tracing::error!("Failed to connect to read database!");
       return;
     }
   };
-  let app_state = Arc::new(AppState { write_db, read_db });
+  let app_state = Arc::new(AppState::new(write_db, read_db));
   let port: u16 = std::env::var("PORT")
     .unwrap_or("3000".into())
     .parse()
@@ -65,13 +71,28 @@ pub async fn start() {
       Method::DELETE,
     ])
     .allow_origin(Any)
-    .allow_headers([CONTENT_TYPE]);
+    .allow_headers([
+      CONTENT_TYPE,
+      HeaderName::from_static(CONSISTENCY_TOKEN_HEADER),
+    ])
+    .expose_headers([HeaderName::from_static(CONSISTENCY_TOKEN_HEADER)]);
 
   let router = Router::new()
     .merge(UomRouter::new())
     .merge(CategoryRouter::new())
     .merge(AttributeRouter::new())
     .merge(ProductRouter::new())
+    .merge(InventoryRouter::new())
+    .merge(PartnerRouter::new())
+    .merge(SalesRouter::new())
+    .merge(PurchaseRouter::new())
+    .merge(PricelistRouter::new())
+    .merge(TaxRouter::new())
+    .merge(CurrencyRouter::new())
+    .layer(middleware::from_fn_with_state(
+      app_state.clone(),
+      attach_consistency_token,
+    ))
     .layer(cors)
     .layer(
       TraceLayer::new_for_http().make_span_with(trace::DefaultMakeSpan::new().level(Level::INFO)),
@@ -0,0 +1,303 @@
+use std::{future::Future, marker::PhantomData};
+
+use axum::response::{IntoResponse, Response};
+use domain::{
+  currency::currency,
+  measurement::uom,
+  partner::partner,
+  pricelist::pricelist,
+  product::{attribute, category, product_template},
+  tax::tax,
+};
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{
+  prelude::Expr, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Restricts a list query to active rows unless archived ones are requested.
+pub fn archived_condition<C>(
+  archived_at: C,
+  include_archived: Option<bool>,
+  only_archived: Option<bool>,
+) -> Condition
+where
+  C: ColumnTrait,
+{
+  if only_archived.unwrap_or(false) {
+    Condition::all().add(archived_at.is_not_null())
+  } else if include_archived.unwrap_or(false) {
+    Condition::all()
+  } else {
+    Condition::all().add(archived_at.is_null())
+  }
+}
+
+/// Sets or clears `archived_at` on a single row. Archiving an already archived
+/// row keeps its original timestamp. Returns `false` when no row matched `id`.
+pub async fn set_archived<E, C>(
+  db: &C,
+  id: Uuid,
+  id_column: E::Column,
+  archived_at: E::Column,
+  updated_at: E::Column,
+  archived: bool,
+) -> Result<bool, DbErr>
+where
+  E: EntityTrait,
+  C: ConnectionTrait,
+{
+  let archived_at_expr = if archived {
+    Expr::cust("COALESCE(archived_at, CURRENT_TIMESTAMP)")
+  } else {
+    Expr::cust("NULL")
+  };
+
+  let result = E::update_many()
+    .col_expr(archived_at, archived_at_expr)
+    .col_expr(updated_at, Expr::cust("CURRENT_TIMESTAMP"))
+    .filter(id_column.eq(id))
+    .exec(db)
+    .await?;
+
+  Ok(result.rows_affected > 0)
+}
+
+/// Entity archived through `archived_at` by `ArchiveUsecase` and
+/// `UnarchiveUsecase`.
+pub trait Archivable: EntityTrait {
+  /// Source reported on errors, e.g. `archive_product`.
+  const SOURCE: &'static str;
+  const ID: Self::Column;
+  const ARCHIVED_AT: Self::Column;
+  const UPDATED_AT: Self::Column;
+
+  /// Runs before row `id` is archived and may refuse it.
+  fn before_archive<C>(
+    db: &C,
+    id: Uuid,
+  ) -> impl Future<Output = Result<(), ArchiveError<Self>>> + Send
+  where
+    C: ConnectionTrait,
+  {
+    let _ = (db, id);
+    async { Ok(()) }
+  }
+}
+
+impl Archivable for product_template::Entity {
+  const SOURCE: &'static str = "archive_product";
+  const ID: Self::Column = product_template::Column::Id;
+  const ARCHIVED_AT: Self::Column = product_template::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = product_template::Column::UpdatedAt;
+}
+
+impl Archivable for category::Entity {
+  const SOURCE: &'static str = "archive_category";
+  const ID: Self::Column = category::Column::Id;
+  const ARCHIVED_AT: Self::Column = category::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = category::Column::UpdatedAt;
+}
+
+impl Archivable for attribute::Entity {
+  const SOURCE: &'static str = "archive_attribute";
+  const ID: Self::Column = attribute::Column::Id;
+  const ARCHIVED_AT: Self::Column = attribute::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = attribute::Column::UpdatedAt;
+}
+
+impl Archivable for uom::Entity {
+  const SOURCE: &'static str = "archive_uom";
+  const ID: Self::Column = uom::Column::Id;
+  const ARCHIVED_AT: Self::Column = uom::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = uom::Column::UpdatedAt;
+}
+
+impl Archivable for partner::Entity {
+  const SOURCE: &'static str = "archive_partner";
+  const ID: Self::Column = partner::Column::Id;
+  const ARCHIVED_AT: Self::Column = partner::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = partner::Column::UpdatedAt;
+}
+
+impl Archivable for pricelist::Entity {
+  const SOURCE: &'static str = "archive_pricelist";
+  const ID: Self::Column = pricelist::Column::Id;
+  const ARCHIVED_AT: Self::Column = pricelist::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = pricelist::Column::UpdatedAt;
+}
+
+impl Archivable for tax::Entity {
+  const SOURCE: &'static str = "archive_tax";
+  const ID: Self::Column = tax::Column::Id;
+  const ARCHIVED_AT: Self::Column = tax::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = tax::Column::UpdatedAt;
+}
+
+/// Documents already in an archived currency keep it; it is only no longer
+/// offered for new ones. The base currency cannot be archived.
+impl Archivable for currency::Entity {
+  const SOURCE: &'static str = "archive_currency";
+  const ID: Self::Column = currency::Column::Id;
+  const ARCHIVED_AT: Self::Column = currency::Column::ArchivedAt;
+  const UPDATED_AT: Self::Column = currency::Column::UpdatedAt;
+
+  async fn before_archive<C>(db: &C, id: Uuid) -> Result<(), ArchiveError<Self>>
+  where
+    C: ConnectionTrait,
+  {
+    let currency = currency::Entity::find_by_id(id)
+      .one(db)
+      .await?
+      .ok_or(ArchiveError::RecordNotFound(PhantomData))?;
+    if currency.is_base {
+      return Err(ArchiveError::Refused("base_currency_cannot_be_archived"));
+    }
+
+    Ok(())
+  }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ArchivePayload {
+  pub id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct ArchiveUsecase<E> {
+  pub id: Uuid,
+  entity: PhantomData<E>,
+}
+
+#[derive(Debug)]
+pub struct UnarchiveUsecase<E> {
+  pub id: Uuid,
+  entity: PhantomData<E>,
+}
+
+#[derive(Error, Debug)]
+pub enum ArchiveError<E> {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound(PhantomData<E>),
+
+  /// Refused by `Archivable::before_archive`.
+  #[error("{0}")]
+  Refused(&'static str),
+}
+
+impl<E> IntoResponse for ArchiveError<E>
+where
+  E: Archivable,
+{
+  fn into_response(self) -> Response {
+    let error = match self {
+      ArchiveError::Database(err) => AppError::from(err),
+      ArchiveError::RecordNotFound(_) => AppError::not_found(self.to_string()),
+      ArchiveError::Refused(_) => AppError::conflict(self.to_string()),
+    };
+
+    error.with_source(E::SOURCE).into_response()
+  }
+}
+
+impl<E> ArchiveUsecase<E>
+where
+  E: Archivable,
+{
+  pub fn new(id: Uuid) -> Self {
+    Self {
+      id,
+      entity: PhantomData,
+    }
+  }
+
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), ArchiveError<E>> {
+    E::before_archive(&db, self.id).await?;
+    set_archived_or_not_found::<E, _>(&db, self.id, true).await
+  }
+}
+
+impl<E> UnarchiveUsecase<E>
+where
+  E: Archivable,
+{
+  pub fn new(id: Uuid) -> Self {
+    Self {
+      id,
+      entity: PhantomData,
+    }
+  }
+
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), ArchiveError<E>> {
+    set_archived_or_not_found::<E, _>(&db, self.id, false).await
+  }
+}
+
+async fn set_archived_or_not_found<E, C>(
+  db: &C,
+  id: Uuid,
+  archived: bool,
+) -> Result<(), ArchiveError<E>>
+where
+  E: Archivable,
+  C: ConnectionTrait,
+{
+  let updated =
+    set_archived::<E, _>(db, id, E::ID, E::ARCHIVED_AT, E::UPDATED_AT, archived).await?;
+
+  if updated {
+    Ok(())
+  } else {
+    Err(ArchiveError::RecordNotFound(PhantomData))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use infra::db::WriteDb;
+  use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
+
+  use super::*;
+  use crate::test_support::currency;
+
+  fn archive(id: Uuid) -> ArchiveUsecase<currency::Entity> {
+    ArchiveUsecase::new(id)
+  }
+
+  #[tokio::test]
+  async fn base_currency_is_not_archived() {
+    let base = currency("VND", "1", true);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![base.clone()]])
+      .into_connection();
+
+    let err = archive(base.id)
+      .invoke(WriteDb::new(db))
+      .await
+      .err()
+      .unwrap();
+
+    assert!(matches!(
+      err,
+      ArchiveError::Refused("base_currency_cannot_be_archived")
+    ));
+  }
+
+  #[tokio::test]
+  async fn other_currencies_are_archived() {
+    let usd = currency("USD", "0.01", false);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![usd.clone()]])
+      .append_exec_results([MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 1,
+      }])
+      .into_connection();
+
+    assert!(archive(usd.id).invoke(WriteDb::new(db)).await.is_ok());
+  }
+}
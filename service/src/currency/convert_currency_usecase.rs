@@ -0,0 +1,61 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::currency::currency::ConvertedAmountDTO;
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  DbErr,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::currency_conversion::{convert_amount, CurrencyConversionError};
+
+/// Converts an amount at the rates in effect on `date`, now by default.
+#[derive(Debug, Deserialize)]
+pub struct ConvertCurrencyUsecase {
+  pub amount: Decimal,
+  pub from_currency_id: Uuid,
+  pub to_currency_id: Uuid,
+  pub date: Option<DateTimeWithTimeZone>,
+}
+
+pub type ConvertCurrencyParams = ConvertCurrencyUsecase;
+
+#[derive(Error, Debug)]
+pub enum ConvertCurrencyError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl IntoResponse for ConvertCurrencyError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ConvertCurrencyError::Database(err) => AppError::from(err),
+      ConvertCurrencyError::Currency(err) => AppError::from(err),
+    };
+
+    error.with_source("convert_currency").into_response()
+  }
+}
+
+impl ConvertCurrencyUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<ConvertedAmountDTO, ConvertCurrencyError> {
+    let converted = convert_amount(
+      &db,
+      self.amount,
+      self.from_currency_id,
+      self.to_currency_id,
+      self.date.unwrap_or_else(|| Utc::now().into()),
+    )
+    .await?;
+
+    Ok(converted)
+  }
+}
@@ -0,0 +1,152 @@
+use axum::response::{IntoResponse, Response};
+use domain::currency::currency;
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::Decimal, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait,
+  QueryFilter, Set,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreateCurrencyUsecase {
+  pub code: String,
+  pub name: String,
+  #[serde(default)]
+  pub symbol: String,
+  #[serde(rename(deserialize = "decimalPlaces"))]
+  pub decimal_places: i32,
+  /// Defaults to the smallest amount `decimal_places` can show.
+  #[serde(default)]
+  pub rounding: Option<Decimal>,
+}
+
+pub type CreateCurrencyPayload = CreateCurrencyUsecase;
+
+/// Finest precision the amount columns can hold with room to spare.
+const MAX_DECIMAL_PLACES: i32 = 6;
+
+impl Validate for CreateCurrencyUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_currency(&self.code, &self.name, self.decimal_places, self.rounding).into_result()
+  }
+}
+
+/// Amounts are never rounded finer than the currency can display.
+pub(crate) fn validate_currency(
+  code: &str,
+  name: &str,
+  decimal_places: i32,
+  rounding: Option<Decimal>,
+) -> ValidationErrors {
+  let code = normalize_code(code);
+  ValidationErrors::new()
+    .field(
+      "code",
+      [
+        rules::required(&code),
+        rules::reject_if(
+          !code.is_empty() && (code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase())),
+          "must_be_iso_4217",
+        ),
+      ],
+    )
+    .field("name", [rules::required(name)])
+    .field(
+      "decimalPlaces",
+      [rules::reject_if(
+        !(0..=MAX_DECIMAL_PLACES).contains(&decimal_places),
+        "must_be_between_0_and_6",
+      )],
+    )
+    .field(
+      "rounding",
+      rounding.map(|rounding| {
+        rules::positive(rounding).and(rules::reject_if(
+          rounding.normalize().scale() > decimal_places.max(0) as u32,
+          "must_not_exceed_decimal_places",
+        ))
+      }),
+    )
+}
+
+pub(crate) fn normalize_code(code: &str) -> String {
+  code.trim().to_uppercase()
+}
+
+/// `rounding` when given, otherwise one unit of the last displayed digit.
+pub(crate) fn rounding_or_default(rounding: Option<Decimal>, decimal_places: i32) -> Decimal {
+  rounding.unwrap_or_else(|| Decimal::new(1, decimal_places.clamp(0, MAX_DECIMAL_PLACES) as u32))
+}
+
+pub(crate) async fn find_by_code<C>(
+  db: &C,
+  code: &str,
+  except_id: Option<Uuid>,
+) -> Result<Option<currency::Model>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  currency::Entity::find()
+    .filter(currency::Column::Code.eq(normalize_code(code)))
+    .filter(Condition::all().add_option(except_id.map(|id| currency::Column::Id.ne(id))))
+    .one(db)
+    .await
+}
+
+pub(crate) fn code_conflict(code: String, existing_id: Uuid) -> AppError {
+  AppError::conflict(code)
+    .with_field("code", "already_exists")
+    .with_details(json!({ "existingId": existing_id }))
+}
+
+#[derive(Error, Debug)]
+pub enum CreateCurrencyError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("code_already_exists")]
+  CodeConflict(Uuid),
+}
+
+impl IntoResponse for CreateCurrencyError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateCurrencyError::Database(err) => AppError::from(err),
+      CreateCurrencyError::CodeConflict(existing_id) => {
+        code_conflict(self.to_string(), existing_id)
+      }
+    };
+
+    error.with_source("create_currency").into_response()
+  }
+}
+
+impl CreateCurrencyUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<currency::Model, CreateCurrencyError> {
+    if let Some(existing) = find_by_code(&db, &self.code, None).await? {
+      return Err(CreateCurrencyError::CodeConflict(existing.id));
+    }
+
+    let currency = currency::ActiveModel {
+      code: Set(normalize_code(&self.code)),
+      name: Set(self.name.trim().to_string()),
+      symbol: Set(self.symbol.trim().to_string()),
+      decimal_places: Set(self.decimal_places),
+      rounding: Set(rounding_or_default(self.rounding, self.decimal_places)),
+      ..Default::default()
+    };
+    let currency = currency.insert(&db).await?;
+
+    Ok(currency)
+  }
+}
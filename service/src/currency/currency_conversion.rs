@@ -0,0 +1,407 @@
+use domain::currency::{
+  currency::{self, ConvertedAmountDTO},
+  currency_rate,
+};
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+};
+use serde_json::json;
+use thiserror::Error;
+
+use crate::measurement::{round_to, UomConversionError};
+
+#[derive(Error, Debug)]
+pub enum CurrencyConversionError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("currency_not_found")]
+  CurrencyNotFound(Uuid),
+
+  #[error("exchange_rate_not_found")]
+  RateNotFound {
+    currency_id: Uuid,
+    at: DateTimeWithTimeZone,
+  },
+}
+
+impl From<CurrencyConversionError> for AppError {
+  fn from(err: CurrencyConversionError) -> Self {
+    match err {
+      CurrencyConversionError::Database(err) => AppError::from(err),
+      CurrencyConversionError::CurrencyNotFound(_) => {
+        AppError::validation(err.to_string()).with_field("currencyId", "not_found")
+      }
+      CurrencyConversionError::RateNotFound { currency_id, at } => {
+        AppError::validation(err.to_string())
+          .with_details(json!({ "currencyId": currency_id, "date": at }))
+      }
+    }
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum PriceConversionError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Uom(UomConversionError),
+
+  #[error(transparent)]
+  Currency(CurrencyConversionError),
+}
+
+impl From<UomConversionError> for PriceConversionError {
+  fn from(err: UomConversionError) -> Self {
+    match err {
+      UomConversionError::Database(err) => PriceConversionError::Database(err),
+      err => PriceConversionError::Uom(err),
+    }
+  }
+}
+
+impl From<CurrencyConversionError> for PriceConversionError {
+  fn from(err: CurrencyConversionError) -> Self {
+    match err {
+      CurrencyConversionError::Database(err) => PriceConversionError::Database(err),
+      err => PriceConversionError::Currency(err),
+    }
+  }
+}
+
+impl From<PriceConversionError> for AppError {
+  fn from(err: PriceConversionError) -> Self {
+    match err {
+      PriceConversionError::Database(err) => AppError::from(err),
+      PriceConversionError::Uom(err) => AppError::from(err),
+      PriceConversionError::Currency(err) => AppError::from(err),
+    }
+  }
+}
+
+/// The company currency, seeded by the migration that introduced currencies.
+pub async fn base_currency<C>(db: &C) -> Result<currency::Model, DbErr>
+where
+  C: ConnectionTrait,
+{
+  currency::Entity::find()
+    .filter(currency::Column::IsBase.eq(true))
+    .one(db)
+    .await?
+    .ok_or_else(|| DbErr::RecordNotFound("base currency".to_string()))
+}
+
+/// Id of the active currency `currency_id`, or of the base currency when
+/// `None`. Meant for prices entered together with their currency.
+pub async fn currency_or_base<C>(
+  db: &C,
+  currency_id: Option<Uuid>,
+) -> Result<Uuid, CurrencyConversionError>
+where
+  C: ConnectionTrait,
+{
+  let Some(currency_id) = currency_id else {
+    return Ok(base_currency(db).await?.id);
+  };
+
+  currency::Entity::find_by_id(currency_id)
+    .filter(currency::Column::ArchivedAt.is_null())
+    .one(db)
+    .await?
+    .map(|currency| currency.id)
+    .ok_or(CurrencyConversionError::CurrencyNotFound(currency_id))
+}
+
+/// Base-currency value of one unit of `currency` at `at`: the latest rate
+/// starting on or before it, or one for the base currency itself.
+pub async fn rate_at<C>(
+  db: &C,
+  currency: &currency::Model,
+  at: DateTimeWithTimeZone,
+) -> Result<Decimal, CurrencyConversionError>
+where
+  C: ConnectionTrait,
+{
+  if currency.is_base {
+    return Ok(Decimal::ONE);
+  }
+
+  let rate = currency_rate::Entity::find()
+    .filter(currency_rate::Column::CurrencyId.eq(currency.id))
+    .filter(currency_rate::Column::DateStart.lte(at))
+    .order_by_desc(currency_rate::Column::DateStart)
+    .one(db)
+    .await?
+    .ok_or(CurrencyConversionError::RateNotFound {
+      currency_id: currency.id,
+      at,
+    })?;
+
+  Ok(rate.rate)
+}
+
+/// Currency `currency_id`, archived or not.
+pub async fn find_currency<C>(
+  db: &C,
+  currency_id: Uuid,
+) -> Result<currency::Model, CurrencyConversionError>
+where
+  C: ConnectionTrait,
+{
+  currency::Entity::find_by_id(currency_id)
+    .one(db)
+    .await?
+    .ok_or(CurrencyConversionError::CurrencyNotFound(currency_id))
+}
+
+/// Converts `amount` from `from_currency_id` into `to_currency_id` at the rates
+/// in effect at `at`, rounded to the precision of the target currency.
+pub async fn convert_amount<C>(
+  db: &C,
+  amount: Decimal,
+  from_currency_id: Uuid,
+  to_currency_id: Uuid,
+  at: DateTimeWithTimeZone,
+) -> Result<ConvertedAmountDTO, CurrencyConversionError>
+where
+  C: ConnectionTrait,
+{
+  let to = find_currency(db, to_currency_id).await?;
+  let rate = if from_currency_id == to_currency_id {
+    Decimal::ONE
+  } else {
+    let from = find_currency(db, from_currency_id).await?;
+    (rate_at(db, &from, at).await? / rate_at(db, &to, at).await?).normalize()
+  };
+
+  Ok(ConvertedAmountDTO {
+    amount: round_to(amount * rate, to.rounding),
+    currency_id: to.id,
+    rate,
+  })
+}
+
+/// Currency a document is in, with its rate on the document date.
+pub struct DocumentCurrency {
+  pub currency: currency::Model,
+  /// Base-currency value of one unit of `currency` at `at`.
+  pub rate: Decimal,
+  pub at: DateTimeWithTimeZone,
+}
+
+impl DocumentCurrency {
+  pub async fn load<C>(
+    db: &C,
+    currency_id: Option<Uuid>,
+    at: DateTimeWithTimeZone,
+  ) -> Result<Self, CurrencyConversionError>
+  where
+    C: ConnectionTrait,
+  {
+    let currency = match currency_id {
+      Some(currency_id) => currency::Entity::find_by_id(currency_id)
+        .filter(currency::Column::ArchivedAt.is_null())
+        .one(db)
+        .await?
+        .ok_or(CurrencyConversionError::CurrencyNotFound(currency_id))?,
+      None => base_currency(db).await?,
+    };
+    let rate = rate_at(db, &currency, at).await?;
+
+    Ok(Self { currency, rate, at })
+  }
+
+  pub fn round(&self, amount: Decimal) -> Decimal {
+    round_to(amount, self.currency.rounding)
+  }
+
+  /// Converts a base-currency amount, e.g. a cost or a fixed tax, unrounded.
+  pub fn from_base(&self, amount: Decimal) -> Decimal {
+    (amount / self.rate).normalize()
+  }
+
+  /// Converts a unit price quoted in `currency_id` into the document's
+  /// currency. Prices are not rounded.
+  pub async fn convert_price<C>(
+    &self,
+    db: &C,
+    price: Decimal,
+    currency_id: Uuid,
+  ) -> Result<Decimal, CurrencyConversionError>
+  where
+    C: ConnectionTrait,
+  {
+    if currency_id == self.currency.id {
+      return Ok(price);
+    }
+    let from = find_currency(db, currency_id).await?;
+
+    Ok(self.from_base(price * rate_at(db, &from, self.at).await?))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use sea_orm::{DatabaseBackend, MockDatabase, QuerySelect, QueryTrait, Transaction};
+
+  use super::*;
+  use crate::test_support::{at, currency, dec};
+
+  fn rate(currency: &currency::Model, rate: &str, date_start: &str) -> currency_rate::Model {
+    currency_rate::Model {
+      id: Uuid::new(),
+      currency_id: currency.id,
+      rate: dec(rate),
+      date_start: at(date_start),
+      created_at: Utc::now().into(),
+      updated_at: None,
+    }
+  }
+
+  fn document_currency(currency: currency::Model, rate: &str) -> DocumentCurrency {
+    DocumentCurrency {
+      currency,
+      rate: dec(rate),
+      at: at("2025-01-15T12:00:00Z"),
+    }
+  }
+
+  #[tokio::test]
+  async fn rate_at_takes_latest_rate_starting_by_the_date() {
+    let usd = currency("USD", "0.01", false);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![rate(&usd, "25000", "2025-01-15T00:00:00Z")]])
+      .into_connection();
+    let date = at("2025-01-15T12:00:00Z");
+
+    assert_eq!(rate_at(&db, &usd, date).await.unwrap(), dec("25000"));
+    assert_eq!(
+      db.into_transaction_log(),
+      [Transaction::one(
+        currency_rate::Entity::find()
+          .filter(currency_rate::Column::CurrencyId.eq(usd.id))
+          .filter(currency_rate::Column::DateStart.lte(date))
+          .order_by_desc(currency_rate::Column::DateStart)
+          .limit(1)
+          .build(DatabaseBackend::Postgres)
+      )]
+    );
+  }
+
+  #[tokio::test]
+  async fn base_currency_rate_is_one_without_lookup() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let vnd = currency("VND", "1", true);
+
+    assert_eq!(
+      rate_at(&db, &vnd, at("2025-01-15T00:00:00Z"))
+        .await
+        .unwrap(),
+      Decimal::ONE
+    );
+    assert!(db.into_transaction_log().is_empty());
+  }
+
+  #[tokio::test]
+  async fn missing_rate_names_currency_and_date() {
+    let usd = currency("USD", "0.01", false);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![usd.clone()]])
+      .append_query_results([Vec::<currency_rate::Model>::new()])
+      .into_connection();
+    let date = at("2024-12-31T00:00:00Z");
+
+    let err = DocumentCurrency::load(&db, Some(usd.id), date)
+      .await
+      .err()
+      .unwrap();
+
+    assert!(matches!(
+      err,
+      CurrencyConversionError::RateNotFound { currency_id, at }
+        if currency_id == usd.id && at == date
+    ));
+  }
+
+  #[tokio::test]
+  async fn convert_amount_rounds_to_the_target_currency() {
+    let vnd = currency("VND", "1", true);
+    let usd = currency("USD", "0.01", false);
+    let db = |results: Vec<Vec<currency::Model>>, rates: Vec<currency_rate::Model>| {
+      MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(results)
+        .append_query_results([rates])
+        .into_connection()
+    };
+    let date = at("2025-01-15T00:00:00Z");
+    let usd_rate = rate(&usd, "25000", "2025-01-01T00:00:00Z");
+
+    let to_base = convert_amount(
+      &db(
+        vec![vec![vnd.clone()], vec![usd.clone()]],
+        vec![usd_rate.clone()],
+      ),
+      dec("1.23"),
+      usd.id,
+      vnd.id,
+      date,
+    )
+    .await
+    .unwrap();
+    assert_eq!(to_base.amount, dec("30750"));
+    assert_eq!(to_base.rate, dec("25000"));
+
+    let from_base = convert_amount(
+      &db(vec![vec![usd.clone()], vec![vnd.clone()]], vec![usd_rate]),
+      dec("123456"),
+      vnd.id,
+      usd.id,
+      date,
+    )
+    .await
+    .unwrap();
+    assert_eq!(from_base.amount, dec("4.94"));
+    assert_eq!(from_base.rate, dec("0.00004"));
+  }
+
+  #[tokio::test]
+  async fn convert_price_is_unrounded() {
+    let vnd = currency("VND", "1", true);
+    let usd = currency("USD", "0.01", false);
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![usd.clone()]])
+      .append_query_results([vec![rate(&usd, "25000", "2025-01-01T00:00:00Z")]])
+      .into_connection();
+
+    let price = document_currency(vnd, "1")
+      .convert_price(&db, dec("1.2345"), usd.id)
+      .await
+      .unwrap();
+
+    assert_eq!(price, dec("30862.5"));
+  }
+
+  #[tokio::test]
+  async fn convert_price_keeps_prices_already_in_the_document_currency() {
+    let db = MockDatabase::new(DatabaseBackend::Postgres).into_connection();
+    let usd = document_currency(currency("USD", "0.01", false), "25000");
+    let usd_id = usd.currency.id;
+
+    assert_eq!(
+      usd.convert_price(&db, dec("1.2345"), usd_id).await.unwrap(),
+      dec("1.2345")
+    );
+  }
+
+  #[test]
+  fn from_base_divides_by_the_rate_unrounded() {
+    let usd = document_currency(currency("USD", "0.01", false), "25000");
+
+    assert_eq!(usd.from_base(dec("30862")), dec("1.23448"));
+    assert_eq!(usd.round(dec("1.23448")), dec("1.23"));
+  }
+}
@@ -0,0 +1,49 @@
+use axum::response::{IntoResponse, Response};
+use domain::currency::currency_rate;
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{DbErr, EntityTrait};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Removes a rate; the previous one of the currency then applies until the
+/// next. Documents already converted with it keep their amounts.
+#[derive(Debug, Deserialize)]
+pub struct DeleteCurrencyRateUsecase {
+  pub id: Uuid,
+}
+
+pub type DeleteCurrencyRatePayload = DeleteCurrencyRateUsecase;
+
+#[derive(Error, Debug)]
+pub enum DeleteCurrencyRateError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for DeleteCurrencyRateError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      DeleteCurrencyRateError::Database(err) => AppError::from(err),
+      DeleteCurrencyRateError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("delete_currency_rate").into_response()
+  }
+}
+
+impl DeleteCurrencyRateUsecase {
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), DeleteCurrencyRateError> {
+    let result = currency_rate::Entity::delete_by_id(self.id)
+      .exec(&db)
+      .await?;
+
+    if result.rows_affected > 0 {
+      Ok(())
+    } else {
+      Err(DeleteCurrencyRateError::RecordNotFound)
+    }
+  }
+}
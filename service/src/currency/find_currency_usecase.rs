@@ -0,0 +1,74 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::currency::{
+  currency::{self, CurrencyDTO},
+  currency_rate,
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct FindCurrencyUsecase {
+  pub id: Uuid,
+}
+
+pub type FindCurrencyParams = FindCurrencyUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindCurrencyError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindCurrencyError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindCurrencyError::Database(err) => AppError::from(err),
+      FindCurrencyError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_currency").into_response()
+  }
+}
+
+impl FindCurrencyUsecase {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<CurrencyDTO, FindCurrencyError> {
+    let currency = currency::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindCurrencyError::RecordNotFound)?;
+
+    // Rates dated in the future are entered ahead of time and not yet in
+    // effect.
+    let current_rate = if currency.is_base {
+      None
+    } else {
+      currency_rate::Entity::find()
+        .filter(currency_rate::Column::CurrencyId.eq(currency.id))
+        .filter(currency_rate::Column::DateStart.lte(Utc::now()))
+        .order_by_desc(currency_rate::Column::DateStart)
+        .one(&db)
+        .await?
+        .map(currency_rate::PartialModel::from)
+    };
+
+    Ok(CurrencyDTO {
+      id: currency.id,
+      code: currency.code,
+      name: currency.name,
+      symbol: currency.symbol,
+      decimal_places: currency.decimal_places,
+      rounding: currency.rounding,
+      is_base: currency.is_base,
+      current_rate,
+      created_at: currency.created_at,
+      updated_at: currency.updated_at,
+      archived_at: currency.archived_at,
+    })
+  }
+}
@@ -0,0 +1,266 @@
+use std::{
+  collections::{HashMap, HashSet},
+  str::FromStr,
+};
+
+use axum::response::{IntoResponse, Response};
+use chrono::DateTime;
+use domain::currency::{
+  currency,
+  currency_rate::{self, CurrencyRateImportDTO},
+};
+use infra::{db::WriteConnection, error::AppError};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use thiserror::Error;
+
+use super::{create_currency_usecase::normalize_code, set_currency_rate_usecase::upsert_rates};
+
+/// Imports rates from CSV text with one `code,date,rate` row per line, e.g.
+/// `USD,2025-01-20T00:00:00+07:00,25400`, dates in RFC 3339. A leading
+/// `code,...` header and blank lines are skipped. Rates starting at the same
+/// moment as an existing one replace it, and nothing is imported unless every
+/// row is valid.
+#[derive(Debug, Clone)]
+pub struct ImportCurrencyRatesUsecase {
+  pub csv: String,
+}
+
+/// Problem with a CSV row. Fields are named `lines[<line number>].<column>`,
+/// counting lines from one.
+#[derive(Debug)]
+pub struct RateRowViolation {
+  pub field: String,
+  pub message: &'static str,
+}
+
+#[derive(Error, Debug)]
+pub enum ImportCurrencyRatesError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("no_rates_to_import")]
+  Empty,
+
+  #[error("invalid_rows")]
+  InvalidRows(Vec<RateRowViolation>),
+}
+
+impl IntoResponse for ImportCurrencyRatesError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ImportCurrencyRatesError::Database(err) => AppError::from(err),
+      ImportCurrencyRatesError::Empty => {
+        AppError::validation(self.to_string()).with_field("csv", "required")
+      }
+      ImportCurrencyRatesError::InvalidRows(ref violations) => violations.iter().fold(
+        AppError::validation(self.to_string()),
+        |error, violation| error.with_field(violation.field.clone(), violation.message),
+      ),
+    };
+
+    error.with_source("import_currency_rates").into_response()
+  }
+}
+
+struct RateRow {
+  line: usize,
+  code: String,
+  date_start: DateTimeWithTimeZone,
+  rate: Decimal,
+}
+
+impl ImportCurrencyRatesUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<CurrencyRateImportDTO, ImportCurrencyRatesError> {
+    let (rows, mut violations) = parse_rows(&self.csv);
+
+    let codes = rows
+      .iter()
+      .map(|row| row.code.clone())
+      .collect::<HashSet<_>>();
+    let currencies = currency::Entity::find()
+      .filter(currency::Column::Code.is_in(codes))
+      .all(&db)
+      .await?
+      .into_iter()
+      .map(|currency| (currency.code.clone(), currency))
+      .collect::<HashMap<_, _>>();
+
+    let mut seen = HashSet::new();
+    let mut rates = Vec::with_capacity(rows.len());
+    for row in rows {
+      let violation = |column: &str, message| RateRowViolation {
+        field: format!("lines[{}].{}", row.line, column),
+        message,
+      };
+      let Some(currency) = currencies.get(&row.code) else {
+        violations.push(violation("code", "not_found"));
+        continue;
+      };
+      if currency.is_base {
+        violations.push(violation("code", "base_currency"));
+        continue;
+      }
+      if !seen.insert((currency.id, row.date_start)) {
+        violations.push(violation("date", "duplicate"));
+        continue;
+      }
+
+      rates.push(currency_rate::ActiveModel {
+        currency_id: Set(currency.id),
+        rate: Set(row.rate),
+        date_start: Set(row.date_start),
+        ..Default::default()
+      });
+    }
+
+    if !violations.is_empty() {
+      return Err(ImportCurrencyRatesError::InvalidRows(violations));
+    }
+    if rates.is_empty() {
+      return Err(ImportCurrencyRatesError::Empty);
+    }
+
+    let imported = rates.len();
+    upsert_rates(&db, rates).await?;
+
+    Ok(CurrencyRateImportDTO { imported })
+  }
+}
+
+fn parse_rows(csv: &str) -> (Vec<RateRow>, Vec<RateRowViolation>) {
+  let mut rows = Vec::new();
+  let mut violations = Vec::new();
+  let mut is_first = true;
+
+  for (index, text) in csv.lines().enumerate() {
+    let text = text.trim();
+    if text.is_empty() {
+      continue;
+    }
+    let columns = text.split(',').map(str::trim).collect::<Vec<_>>();
+    if std::mem::take(&mut is_first) && columns[0].eq_ignore_ascii_case("code") {
+      continue;
+    }
+
+    let line = index + 1;
+    let violation = |column: &str, message| RateRowViolation {
+      field: format!("lines[{}].{}", line, column),
+      message,
+    };
+    let [code, date, rate] = columns[..] else {
+      violations.push(violation("columns", "must_be_code_date_rate"));
+      continue;
+    };
+
+    let date_start = DateTime::parse_from_rfc3339(date).ok();
+    if date_start.is_none() {
+      violations.push(violation("date", "must_be_rfc3339"));
+    }
+    let rate = Decimal::from_str(rate)
+      .ok()
+      .filter(|rate| *rate > Decimal::ZERO);
+    if rate.is_none() {
+      violations.push(violation("rate", "must_be_positive"));
+    }
+
+    if let (Some(date_start), Some(rate)) = (date_start, rate) {
+      rows.push(RateRow {
+        line,
+        code: normalize_code(code),
+        date_start,
+        rate,
+      });
+    }
+  }
+
+  (rows, violations)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn violations(csv: &str) -> Vec<(String, &'static str)> {
+    parse_rows(csv)
+      .1
+      .into_iter()
+      .map(|violation| (violation.field, violation.message))
+      .collect()
+  }
+
+  #[test]
+  fn parses_rows_and_normalizes_codes() {
+    let (rows, violations) =
+      parse_rows(" usd , 2025-01-20T00:00:00+07:00 , 25400\nEUR,2025-01-20T00:00:00Z,26500.5\n");
+
+    assert!(violations.is_empty());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].line, 1);
+    assert_eq!(rows[0].code, "USD");
+    assert_eq!(
+      rows[0].date_start,
+      DateTime::parse_from_rfc3339("2025-01-20T00:00:00+07:00").unwrap()
+    );
+    assert_eq!(rows[0].rate, Decimal::from(25400));
+    assert_eq!(rows[1].code, "EUR");
+    assert_eq!(rows[1].rate, Decimal::from_str("26500.5").unwrap());
+  }
+
+  #[test]
+  fn skips_header_and_blank_lines_but_keeps_line_numbers() {
+    let (rows, violations) = parse_rows("Code,Date,Rate\n\nUSD,2025-01-20T00:00:00Z,25400\n");
+
+    assert!(violations.is_empty());
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].line, 3);
+  }
+
+  #[test]
+  fn header_is_only_skipped_on_first_row() {
+    assert_eq!(
+      violations("USD,2025-01-20T00:00:00Z,25400\ncode,date,rate"),
+      vec![
+        ("lines[2].date".to_string(), "must_be_rfc3339"),
+        ("lines[2].rate".to_string(), "must_be_positive"),
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_rows_with_wrong_column_count() {
+    assert_eq!(
+      violations("USD,2025-01-20T00:00:00Z\nUSD,2025-01-20T00:00:00Z,1,2"),
+      vec![
+        ("lines[1].columns".to_string(), "must_be_code_date_rate"),
+        ("lines[2].columns".to_string(), "must_be_code_date_rate"),
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_invalid_date_and_rate() {
+    assert_eq!(
+      violations("USD,2025-01-20,0\nEUR,2025-01-20T00:00:00Z,-1\nJPY,2025-01-20T00:00:00Z,abc"),
+      vec![
+        ("lines[1].date".to_string(), "must_be_rfc3339"),
+        ("lines[1].rate".to_string(), "must_be_positive"),
+        ("lines[2].rate".to_string(), "must_be_positive"),
+        ("lines[3].rate".to_string(), "must_be_positive"),
+      ]
+    );
+  }
+
+  #[test]
+  fn invalid_rows_are_left_out() {
+    let (rows, _) = parse_rows("USD,2025-01-20,25400\nEUR,2025-01-20T00:00:00Z,26500");
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].code, "EUR");
+  }
+}
@@ -0,0 +1,83 @@
+use axum::response::{IntoResponse, Response};
+use domain::currency::currency_rate::{self, Column, Entity as CurrencyRate};
+use infra::{db::ReadConnection, error::AppError, response::PaginationMeta, uuid::Uuid};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone, ColumnTrait, Condition, DbErr, EntityTrait, PaginatorTrait,
+  QueryFilter, QueryOrder,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::list_query::{page_index, page_size, PageError};
+
+/// Rate history of a currency, latest first, restricted to rates starting in
+/// `[date_from, date_to)` when given.
+#[derive(Debug, Deserialize)]
+pub struct ListCurrencyRatesUsecase {
+  pub currency_id: Uuid,
+  pub date_from: Option<DateTimeWithTimeZone>,
+  pub date_to: Option<DateTimeWithTimeZone>,
+  pub page: Option<u64>,
+  pub per_page: Option<u64>,
+}
+
+pub type ListCurrencyRatesParams = ListCurrencyRatesUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListCurrencyRatesError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Page(#[from] PageError),
+}
+
+impl IntoResponse for ListCurrencyRatesError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListCurrencyRatesError::Database(err) => AppError::from(err),
+      ListCurrencyRatesError::Page(err) => AppError::from(err),
+    };
+
+    error.with_source("list_currency_rates").into_response()
+  }
+}
+
+impl ListCurrencyRatesUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<currency_rate::PartialModel>, PaginationMeta), ListCurrencyRatesError> {
+    let per_page = page_size(self.per_page)?;
+    let page = page_index(self.page)?;
+
+    let rate_pages = CurrencyRate::find()
+      .filter(
+        Condition::all()
+          .add(Column::CurrencyId.eq(self.currency_id))
+          .add_option(
+            self
+              .date_from
+              .map(|date_from| Column::DateStart.gte(date_from)),
+          )
+          .add_option(self.date_to.map(|date_to| Column::DateStart.lt(date_to))),
+      )
+      .order_by_desc(Column::DateStart)
+      .into_partial_model::<currency_rate::PartialModel>()
+      .paginate(&db, per_page);
+    let rates = rate_pages.fetch_page(page).await?;
+    let items_and_pages = rate_pages.num_items_and_pages().await?;
+    let total = items_and_pages.number_of_items;
+    let total_pages = items_and_pages.number_of_pages;
+
+    Ok((
+      rates,
+      PaginationMeta {
+        total,
+        total_pages,
+        page: page + 1,
+        per_page,
+      },
+    ))
+  }
+}
@@ -0,0 +1,28 @@
+pub mod currency_conversion;
+
+pub mod create_currency_usecase;
+pub use create_currency_usecase::*;
+
+pub mod update_currency_usecase;
+pub use update_currency_usecase::*;
+
+pub mod find_currency_usecase;
+pub use find_currency_usecase::*;
+
+pub mod list_paginated_currencies_usecase;
+pub use list_paginated_currencies_usecase::*;
+
+pub mod set_currency_rate_usecase;
+pub use set_currency_rate_usecase::*;
+
+pub mod delete_currency_rate_usecase;
+pub use delete_currency_rate_usecase::*;
+
+pub mod list_currency_rates_usecase;
+pub use list_currency_rates_usecase::*;
+
+pub mod import_currency_rates_usecase;
+pub use import_currency_rates_usecase::*;
+
+pub mod convert_currency_usecase;
+pub use convert_currency_usecase::*;
@@ -0,0 +1,118 @@
+use axum::response::{IntoResponse, Response};
+use domain::currency::{currency, currency_rate};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal, Expr},
+  sea_query::OnConflict,
+  ConnectionTrait, DbErr, EntityTrait, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Records the rate of a currency from `date_start` on, replacing the rate
+/// already starting at that exact moment.
+#[derive(Debug, Deserialize, Clone)]
+pub struct SetCurrencyRateUsecase {
+  #[serde(rename(deserialize = "currencyId"))]
+  pub currency_id: Uuid,
+  /// Base-currency value of one unit of the currency.
+  pub rate: Decimal,
+  #[serde(rename(deserialize = "dateStart"))]
+  pub date_start: DateTimeWithTimeZone,
+}
+
+pub type SetCurrencyRatePayload = SetCurrencyRateUsecase;
+
+impl Validate for SetCurrencyRateUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("rate", [rules::positive(self.rate)])
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum SetCurrencyRateError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("currency_not_found")]
+  CurrencyNotFound,
+
+  #[error("base_currency_has_no_rate")]
+  BaseCurrency,
+}
+
+impl IntoResponse for SetCurrencyRateError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      SetCurrencyRateError::Database(err) => AppError::from(err),
+      SetCurrencyRateError::CurrencyNotFound => {
+        AppError::validation(self.to_string()).with_field("currencyId", "not_found")
+      }
+      SetCurrencyRateError::BaseCurrency => {
+        AppError::validation(self.to_string()).with_field("currencyId", "base_currency")
+      }
+    };
+
+    error.with_source("set_currency_rate").into_response()
+  }
+}
+
+pub(crate) async fn upsert_rates<C, I>(db: &C, rates: I) -> Result<(), DbErr>
+where
+  C: ConnectionTrait,
+  I: IntoIterator<Item = currency_rate::ActiveModel>,
+{
+  currency_rate::Entity::insert_many(rates)
+    .on_empty_do_nothing()
+    .on_conflict(rate_conflict())
+    .exec(db)
+    .await?;
+
+  Ok(())
+}
+
+fn rate_conflict() -> OnConflict {
+  OnConflict::columns([
+    currency_rate::Column::CurrencyId,
+    currency_rate::Column::DateStart,
+  ])
+  .update_column(currency_rate::Column::Rate)
+  .value(currency_rate::Column::UpdatedAt, Expr::current_timestamp())
+  .to_owned()
+}
+
+impl SetCurrencyRateUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<currency_rate::Model, SetCurrencyRateError> {
+    let currency = currency::Entity::find_by_id(self.currency_id)
+      .one(&db)
+      .await?
+      .ok_or(SetCurrencyRateError::CurrencyNotFound)?;
+    // Rates are expressed in the base currency, whose own rate is always one.
+    if currency.is_base {
+      return Err(SetCurrencyRateError::BaseCurrency);
+    }
+
+    let rate = currency_rate::ActiveModel {
+      currency_id: Set(self.currency_id),
+      rate: Set(self.rate),
+      date_start: Set(self.date_start),
+      ..Default::default()
+    };
+    let rate = currency_rate::Entity::insert(rate)
+      .on_conflict(rate_conflict())
+      .exec_with_returning(&db)
+      .await?;
+
+    Ok(rate)
+  }
+}
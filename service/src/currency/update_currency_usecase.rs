@@ -0,0 +1,92 @@
+use axum::response::{IntoResponse, Response};
+use domain::currency::currency;
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{prelude::Decimal, ActiveModelTrait, DbErr, EntityTrait, Set};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::create_currency_usecase::{
+  code_conflict, find_by_code, normalize_code, rounding_or_default, validate_currency,
+};
+
+/// Changing the precision of a currency leaves the amounts already rounded on
+/// documents as they are.
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateCurrencyUsecase {
+  pub id: Uuid,
+  pub code: String,
+  pub name: String,
+  #[serde(default)]
+  pub symbol: String,
+  #[serde(rename(deserialize = "decimalPlaces"))]
+  pub decimal_places: i32,
+  #[serde(default)]
+  pub rounding: Option<Decimal>,
+}
+
+pub type UpdateCurrencyPayload = UpdateCurrencyUsecase;
+
+impl Validate for UpdateCurrencyUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_currency(&self.code, &self.name, self.decimal_places, self.rounding).into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateCurrencyError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("code_already_exists")]
+  CodeConflict(Uuid),
+}
+
+impl IntoResponse for UpdateCurrencyError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdateCurrencyError::Database(err) => AppError::from(err),
+      UpdateCurrencyError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateCurrencyError::CodeConflict(existing_id) => {
+        code_conflict(self.to_string(), existing_id)
+      }
+    };
+
+    error.with_source("update_currency").into_response()
+  }
+}
+
+impl UpdateCurrencyUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<currency::Model, UpdateCurrencyError> {
+    currency::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(UpdateCurrencyError::RecordNotFound)?;
+    if let Some(existing) = find_by_code(&db, &self.code, Some(self.id)).await? {
+      return Err(UpdateCurrencyError::CodeConflict(existing.id));
+    }
+
+    let currency = currency::ActiveModel {
+      id: Set(self.id),
+      code: Set(normalize_code(&self.code)),
+      name: Set(self.name.trim().to_string()),
+      symbol: Set(self.symbol.trim().to_string()),
+      decimal_places: Set(self.decimal_places),
+      rounding: Set(rounding_or_default(self.rounding, self.decimal_places)),
+      ..Default::default()
+    };
+    let currency = currency.update(&db).await?;
+
+    Ok(currency)
+  }
+}
@@ -0,0 +1,114 @@
+use axum::response::{IntoResponse, Response};
+use domain::inventory::{
+  location::{self, LocationType},
+  warehouse,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, Set};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLocationUsecase {
+  pub name: String,
+  #[serde(rename(deserialize = "locationType"))]
+  pub location_type: LocationType,
+  #[serde(rename(deserialize = "parentLocationId"), default)]
+  pub parent_location_id: Option<Uuid>,
+  #[serde(rename(deserialize = "warehouseId"), default)]
+  pub warehouse_id: Option<Uuid>,
+}
+
+pub type CreateLocationPayload = CreateLocationUsecase;
+
+impl Validate for CreateLocationUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateLocationError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("parent_location_not_found")]
+  ParentLocationNotFound,
+
+  #[error("warehouse_not_found")]
+  WarehouseNotFound,
+
+  #[error("warehouse_mismatch")]
+  WarehouseMismatch,
+}
+
+impl IntoResponse for CreateLocationError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateLocationError::Database(err) => AppError::from(err),
+      CreateLocationError::ParentLocationNotFound => {
+        AppError::validation(self.to_string()).with_field("parentLocationId", "not_found")
+      }
+      CreateLocationError::WarehouseNotFound => {
+        AppError::validation(self.to_string()).with_field("warehouseId", "not_found")
+      }
+      CreateLocationError::WarehouseMismatch => {
+        AppError::validation(self.to_string()).with_field("warehouseId", self.to_string())
+      }
+    };
+
+    error.with_source("create_location").into_response()
+  }
+}
+
+impl CreateLocationUsecase {
+  /// Child locations belong to the warehouse of their parent unless one is
+  /// given, in which case both must agree.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<location::Model, CreateLocationError> {
+    let parent_warehouse_id = match self.parent_location_id {
+      Some(parent_location_id) => {
+        let parent = location::Entity::find_by_id(parent_location_id)
+          .one(&db)
+          .await?
+          .ok_or(CreateLocationError::ParentLocationNotFound)?;
+        parent.warehouse_id
+      }
+      None => None,
+    };
+
+    let warehouse_id = match (self.warehouse_id, parent_warehouse_id) {
+      (Some(warehouse_id), Some(parent_warehouse_id)) if warehouse_id != parent_warehouse_id => {
+        return Err(CreateLocationError::WarehouseMismatch);
+      }
+      (Some(warehouse_id), _) => {
+        let warehouse = warehouse::Entity::find_by_id(warehouse_id).one(&db).await?;
+        if warehouse.is_none() {
+          return Err(CreateLocationError::WarehouseNotFound);
+        }
+        Some(warehouse_id)
+      }
+      (None, parent_warehouse_id) => parent_warehouse_id,
+    };
+
+    let location = location::ActiveModel {
+      name: Set(self.name.trim().to_string()),
+      location_type: Set(self.location_type),
+      parent_location_id: Set(self.parent_location_id),
+      warehouse_id: Set(warehouse_id),
+      ..Default::default()
+    };
+    let location = location.insert(&db).await?;
+
+    Ok(location)
+  }
+}
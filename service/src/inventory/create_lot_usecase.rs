@@ -0,0 +1,94 @@
+use axum::response::{IntoResponse, Response};
+use domain::{inventory::lot, product::product_template::Tracking};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  sea_query::{Expr, Func},
+  ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::stockable_product::{find_stockable_product, StockableProductError};
+use crate::unique_name::name_conflict;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLotUsecase {
+  #[serde(rename(deserialize = "productId"))]
+  pub product_id: Uuid,
+  pub name: String,
+}
+
+pub type CreateLotPayload = CreateLotUsecase;
+
+impl Validate for CreateLotUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateLotError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Product(#[from] StockableProductError),
+
+  #[error("product_not_lot_tracked")]
+  NotLotTracked,
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+}
+
+impl IntoResponse for CreateLotError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateLotError::Database(err) => AppError::from(err),
+      CreateLotError::Product(err) => AppError::from(err),
+      CreateLotError::NotLotTracked => {
+        AppError::validation(self.to_string()).with_field("productId", "not_tracked")
+      }
+      CreateLotError::NameConflict(existing_id) => name_conflict(self.to_string(), existing_id),
+    };
+
+    error.with_source("create_lot").into_response()
+  }
+}
+
+impl CreateLotUsecase {
+  /// Registers a lot or serial number for a product tracked by either. Names
+  /// are unique per product, ignoring case.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<lot::Model, CreateLotError> {
+    let stockable = find_stockable_product(&db, self.product_id).await?;
+    if stockable.template.tracking == Tracking::None {
+      return Err(CreateLotError::NotLotTracked);
+    }
+
+    let name = self.name.trim().to_string();
+    let existing = lot::Entity::find()
+      .filter(lot::Column::ProductId.eq(self.product_id))
+      .filter(Expr::expr(Func::lower(Expr::col(lot::Column::Name))).eq(name.to_lowercase()))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(CreateLotError::NameConflict(existing.id));
+    }
+
+    let lot = lot::ActiveModel {
+      product_id: Set(self.product_id),
+      name: Set(name),
+      ..Default::default()
+    };
+    let lot = lot.insert(&db).await?;
+
+    Ok(lot)
+  }
+}
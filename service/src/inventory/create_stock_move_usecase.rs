@@ -0,0 +1,204 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  inventory::{
+    location::{self, LocationType},
+    lot, stock_move,
+  },
+  sales::sales_order_line,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{prelude::Decimal, ActiveModelTrait, DbErr, EntityTrait, Set};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+  lot_assignment::{check_lot_assignment, LotAssignmentError},
+  stockable_product::{find_stockable_product, StockableProductError},
+};
+use crate::measurement::{convert_quantity, UomConversionError};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateStockMoveUsecase {
+  #[serde(default)]
+  pub reference: String,
+  #[serde(rename(deserialize = "productId"))]
+  pub product_id: Uuid,
+  #[serde(rename(deserialize = "sourceLocationId"))]
+  pub source_location_id: Uuid,
+  #[serde(rename(deserialize = "destinationLocationId"))]
+  pub destination_location_id: Uuid,
+  pub quantity: Decimal,
+  #[serde(rename(deserialize = "uomId"), default)]
+  pub uom_id: Option<Uuid>,
+  /// Price paid per `uom_id` unit. Receipts without one are valued at the
+  /// product's current cost.
+  #[serde(rename(deserialize = "unitCost"), default)]
+  pub unit_cost: Option<Decimal>,
+  #[serde(rename(deserialize = "lotId"), default)]
+  pub lot_id: Option<Uuid>,
+  /// Finished lot the components are consumed into. Only for moves into a
+  /// production location.
+  #[serde(rename(deserialize = "producedLotId"), default)]
+  pub produced_lot_id: Option<Uuid>,
+  /// Sales order line the move delivers. Only for moves into a customer
+  /// location, and the line must sell the moved product.
+  #[serde(rename(deserialize = "salesOrderLineId"), default)]
+  pub sales_order_line_id: Option<Uuid>,
+}
+
+pub type CreateStockMovePayload = CreateStockMoveUsecase;
+
+impl Validate for CreateStockMoveUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("quantity", [rules::positive(self.quantity)])
+      .field("unitCost", self.unit_cost.map(rules::non_negative))
+      .field(
+        "destinationLocationId",
+        [rules::reject_if(
+          self.source_location_id == self.destination_location_id,
+          "same_as_source",
+        )],
+      )
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateStockMoveError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Product(#[from] StockableProductError),
+
+  #[error(transparent)]
+  Conversion(#[from] UomConversionError),
+
+  #[error(transparent)]
+  Lot(#[from] LotAssignmentError),
+
+  #[error("location_not_found")]
+  LocationNotFound(&'static str),
+
+  #[error("produced_lot_requires_production_destination")]
+  ProducedLotOutsideProduction,
+
+  #[error("sales_order_line_not_found")]
+  SalesOrderLineNotFound,
+
+  #[error("sales_order_line_requires_customer_destination")]
+  SalesOrderLineOutsideCustomer,
+
+  #[error("sales_order_line_product_mismatch")]
+  SalesOrderLineProductMismatch,
+}
+
+impl IntoResponse for CreateStockMoveError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateStockMoveError::Database(err) => AppError::from(err),
+      CreateStockMoveError::Product(err) => AppError::from(err),
+      CreateStockMoveError::Conversion(err) => AppError::from(err).with_field("uomId", "invalid"),
+      CreateStockMoveError::Lot(err) => AppError::from(err),
+      CreateStockMoveError::LocationNotFound(field) => {
+        AppError::validation(self.to_string()).with_field(field, "not_found")
+      }
+      CreateStockMoveError::ProducedLotOutsideProduction => {
+        AppError::validation(self.to_string()).with_field("producedLotId", "invalid")
+      }
+      CreateStockMoveError::SalesOrderLineNotFound => {
+        AppError::validation(self.to_string()).with_field("salesOrderLineId", "not_found")
+      }
+      CreateStockMoveError::SalesOrderLineOutsideCustomer
+      | CreateStockMoveError::SalesOrderLineProductMismatch => {
+        AppError::validation(self.to_string()).with_field("salesOrderLineId", "invalid")
+      }
+    };
+
+    error.with_source("create_stock_move").into_response()
+  }
+}
+
+impl CreateStockMoveUsecase {
+  /// Records a draft move. The quantity may be entered in any unit of the
+  /// template's UoM category and is converted to the stock unit up front.
+  /// Products tracked by lot or serial must name the lot they move, and
+  /// deliveries may name the sales order line they fulfil.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<stock_move::Model, CreateStockMoveError> {
+    let stockable = find_stockable_product(&db, self.product_id).await?;
+
+    let mut locations = vec![];
+    for (field, location_id) in [
+      ("sourceLocationId", self.source_location_id),
+      ("destinationLocationId", self.destination_location_id),
+    ] {
+      let location = location::Entity::find_by_id(location_id)
+        .one(&db)
+        .await?
+        .ok_or(CreateStockMoveError::LocationNotFound(field))?;
+      locations.push(location);
+    }
+
+    let uom_id = self.uom_id.unwrap_or(stockable.template.uom_id);
+    let product_quantity =
+      convert_quantity(&db, self.quantity, uom_id, stockable.template.uom_id).await?;
+
+    check_lot_assignment(
+      &db,
+      &stockable.template,
+      self.product_id,
+      self.lot_id,
+      product_quantity,
+    )
+    .await?;
+    if let Some(produced_lot_id) = self.produced_lot_id {
+      if locations[1].location_type != LocationType::Production {
+        return Err(CreateStockMoveError::ProducedLotOutsideProduction);
+      }
+      let produced_lot = lot::Entity::find_by_id(produced_lot_id).one(&db).await?;
+      if produced_lot.is_none() {
+        return Err(LotAssignmentError::LotNotFound("producedLotId").into());
+      }
+    }
+    if let Some(sales_order_line_id) = self.sales_order_line_id {
+      if locations[1].location_type != LocationType::Customer {
+        return Err(CreateStockMoveError::SalesOrderLineOutsideCustomer);
+      }
+      let line = sales_order_line::Entity::find_by_id(sales_order_line_id)
+        .one(&db)
+        .await?
+        .ok_or(CreateStockMoveError::SalesOrderLineNotFound)?;
+      if line.product_id != self.product_id {
+        return Err(CreateStockMoveError::SalesOrderLineProductMismatch);
+      }
+    }
+
+    let stock_move = stock_move::ActiveModel {
+      reference: Set(self.reference.trim().to_string()),
+      product_id: Set(self.product_id),
+      source_location_id: Set(self.source_location_id),
+      destination_location_id: Set(self.destination_location_id),
+      quantity: Set(self.quantity),
+      uom_id: Set(uom_id),
+      product_quantity: Set(product_quantity),
+      unit_cost: Set(self.unit_cost),
+      lot_id: Set(self.lot_id),
+      produced_lot_id: Set(self.produced_lot_id),
+      sales_order_line_id: Set(self.sales_order_line_id),
+      state: Set(stock_move::StockMoveState::Draft),
+      ..Default::default()
+    };
+    let stock_move = stock_move.insert(&db).await?;
+
+    Ok(stock_move)
+  }
+}
@@ -0,0 +1,103 @@
+use axum::response::{IntoResponse, Response};
+use domain::inventory::{
+  location::{self, LocationType},
+  warehouse::{self, WarehouseDTO},
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{ActiveModelTrait, DbErr, Set, TransactionError};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreateWarehouseUsecase {
+  pub name: String,
+  pub code: String,
+}
+
+pub type CreateWarehousePayload = CreateWarehouseUsecase;
+
+impl Validate for CreateWarehouseUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .field("code", [rules::required(&self.code)])
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateWarehouseError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl From<TransactionError<DbErr>> for CreateWarehouseError {
+  fn from(err: TransactionError<DbErr>) -> Self {
+    match err {
+      TransactionError::Connection(err) | TransactionError::Transaction(err) => {
+        CreateWarehouseError::Database(err)
+      }
+    }
+  }
+}
+
+impl IntoResponse for CreateWarehouseError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateWarehouseError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("create_warehouse").into_response()
+  }
+}
+
+impl CreateWarehouseUsecase {
+  /// Creates the warehouse together with its root internal location, the
+  /// parent of every shelf or bin added to it later.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<WarehouseDTO, CreateWarehouseError> {
+    let payload = self.clone();
+
+    let warehouse = db
+      .transaction::<_, WarehouseDTO, DbErr>(move |txn| {
+        Box::pin(async move {
+          let warehouse = warehouse::ActiveModel {
+            name: Set(payload.name.trim().to_string()),
+            code: Set(payload.code.trim().to_uppercase()),
+            ..Default::default()
+          };
+          let warehouse = warehouse.insert(txn).await?;
+
+          let stock_location = location::ActiveModel {
+            name: Set(format!("{}/Stock", warehouse.code)),
+            location_type: Set(LocationType::Internal),
+            warehouse_id: Set(Some(warehouse.id)),
+            ..Default::default()
+          };
+          let stock_location = stock_location.insert(txn).await?;
+
+          Ok(WarehouseDTO {
+            id: warehouse.id,
+            name: warehouse.name,
+            code: warehouse.code,
+            stock_location: location::PartialModel {
+              id: stock_location.id,
+              name: stock_location.name,
+              location_type: stock_location.location_type,
+              parent_location_id: stock_location.parent_location_id,
+              warehouse_id: stock_location.warehouse_id,
+            },
+          })
+        })
+      })
+      .await?;
+
+    Ok(warehouse)
+  }
+}
@@ -0,0 +1,51 @@
+use axum::response::{IntoResponse, Response};
+use domain::inventory::stock_move::OnHandDTO;
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::DbErr;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::on_hand::{descendant_location_ids, on_hand};
+
+#[derive(Debug, Deserialize)]
+pub struct FindOnHandUsecase {
+  pub product_id: Option<Uuid>,
+  pub location_id: Option<Uuid>,
+  pub lot_id: Option<Uuid>,
+  /// Also report the locations below `location_id`. Defaults to `true`.
+  pub include_children: Option<bool>,
+}
+
+pub type FindOnHandParams = FindOnHandUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindOnHandError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl IntoResponse for FindOnHandError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindOnHandError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("find_on_hand").into_response()
+  }
+}
+
+impl FindOnHandUsecase {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<Vec<OnHandDTO>, FindOnHandError> {
+    let location_ids = match self.location_id {
+      Some(location_id) if self.include_children.unwrap_or(true) => {
+        Some(descendant_location_ids(&db, location_id).await?)
+      }
+      Some(location_id) => Some(vec![location_id]),
+      None => None,
+    };
+
+    let quantities = on_hand(&db, self.product_id, location_ids, self.lot_id).await?;
+
+    Ok(quantities)
+  }
+}
@@ -0,0 +1,62 @@
+use axum::response::{IntoResponse, Response};
+use domain::inventory::location::{self, Column, Entity as Location, LocationType};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::archive::archived_condition;
+
+#[derive(Debug, Deserialize)]
+pub struct ListLocationsUsecase {
+  pub warehouse_id: Option<Uuid>,
+  pub location_type: Option<LocationType>,
+  pub include_archived: Option<bool>,
+}
+
+pub type ListLocationsParams = ListLocationsUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListLocationsError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl IntoResponse for ListLocationsError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListLocationsError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("list_locations").into_response()
+  }
+}
+
+impl ListLocationsUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<Vec<location::PartialModel>, ListLocationsError> {
+    let locations = Location::find()
+      .filter(
+        Condition::all()
+          .add(archived_condition(
+            Column::ArchivedAt,
+            self.include_archived,
+            None,
+          ))
+          .add_option(self.warehouse_id.map(|id| Column::WarehouseId.eq(id)))
+          .add_option(
+            self
+              .location_type
+              .map(|location_type| Column::LocationType.eq(location_type)),
+          ),
+      )
+      .order_by_asc(Column::Name)
+      .into_partial_model::<location::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(locations)
+  }
+}
@@ -0,0 +1,45 @@
+use axum::response::{IntoResponse, Response};
+use domain::inventory::lot::{self, Column, Entity as Lot};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct ListLotsUsecase {
+  pub product_id: Option<Uuid>,
+}
+
+pub type ListLotsParams = ListLotsUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListLotsError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl IntoResponse for ListLotsError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListLotsError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("list_lots").into_response()
+  }
+}
+
+impl ListLotsUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<Vec<lot::PartialModel>, ListLotsError> {
+    let lots = Lot::find()
+      .filter(Condition::all().add_option(self.product_id.map(|id| Column::ProductId.eq(id))))
+      .order_by_asc(Column::Name)
+      .into_partial_model::<lot::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(lots)
+  }
+}
@@ -0,0 +1,85 @@
+use domain::{
+  inventory::lot,
+  product::product_template::{self, Tracking},
+};
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{prelude::Decimal, ConnectionTrait, DbErr, EntityTrait};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LotAssignmentError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("lot_required")]
+  LotRequired,
+
+  #[error("product_not_lot_tracked")]
+  NotLotTracked,
+
+  #[error("lot_not_found")]
+  LotNotFound(&'static str),
+
+  #[error("lot_belongs_to_other_product")]
+  ForeignLot,
+
+  #[error("serial_quantity_must_be_one")]
+  SerialQuantity,
+}
+
+impl From<LotAssignmentError> for AppError {
+  fn from(err: LotAssignmentError) -> Self {
+    match err {
+      LotAssignmentError::Database(err) => AppError::from(err),
+      LotAssignmentError::LotRequired => {
+        AppError::validation(err.to_string()).with_field("lotId", "required")
+      }
+      LotAssignmentError::NotLotTracked => {
+        AppError::validation(err.to_string()).with_field("lotId", "not_tracked")
+      }
+      LotAssignmentError::LotNotFound(field) => {
+        AppError::validation(err.to_string()).with_field(field, "not_found")
+      }
+      LotAssignmentError::ForeignLot => {
+        AppError::validation(err.to_string()).with_field("lotId", "belongs_to_other_product")
+      }
+      LotAssignmentError::SerialQuantity => {
+        AppError::validation(err.to_string()).with_field("quantity", "must_be_one")
+      }
+    }
+  }
+}
+
+/// Checks the lot a move of `product_id` carries against the template's
+/// tracking: tracked products need a lot of their own, untracked ones take
+/// none, and a serial covers exactly one stock unit.
+pub async fn check_lot_assignment<C>(
+  db: &C,
+  template: &product_template::Model,
+  product_id: Uuid,
+  lot_id: Option<Uuid>,
+  product_quantity: Decimal,
+) -> Result<(), LotAssignmentError>
+where
+  C: ConnectionTrait,
+{
+  let lot_id = match (template.tracking, lot_id) {
+    (Tracking::None, None) => return Ok(()),
+    (Tracking::None, Some(_)) => return Err(LotAssignmentError::NotLotTracked),
+    (Tracking::Lot | Tracking::Serial, None) => return Err(LotAssignmentError::LotRequired),
+    (Tracking::Lot | Tracking::Serial, Some(lot_id)) => lot_id,
+  };
+
+  let lot = lot::Entity::find_by_id(lot_id)
+    .one(db)
+    .await?
+    .ok_or(LotAssignmentError::LotNotFound("lotId"))?;
+  if lot.product_id != product_id {
+    return Err(LotAssignmentError::ForeignLot);
+  }
+  if template.tracking == Tracking::Serial && product_quantity != Decimal::ONE {
+    return Err(LotAssignmentError::SerialQuantity);
+  }
+
+  Ok(())
+}
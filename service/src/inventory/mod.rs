@@ -0,0 +1,37 @@
+pub mod stockable_product;
+
+pub mod lot_assignment;
+
+pub mod on_hand;
+
+pub mod valuation;
+
+pub mod create_warehouse_usecase;
+pub use create_warehouse_usecase::*;
+
+pub mod create_location_usecase;
+pub use create_location_usecase::*;
+
+pub mod list_locations_usecase;
+pub use list_locations_usecase::*;
+
+pub mod create_stock_move_usecase;
+pub use create_stock_move_usecase::*;
+
+pub mod validate_stock_move_usecase;
+pub use validate_stock_move_usecase::*;
+
+pub mod find_on_hand_usecase;
+pub use find_on_hand_usecase::*;
+
+pub mod stock_valuation_report_usecase;
+pub use stock_valuation_report_usecase::*;
+
+pub mod create_lot_usecase;
+pub use create_lot_usecase::*;
+
+pub mod list_lots_usecase;
+pub use list_lots_usecase::*;
+
+pub mod trace_lot_usecase;
+pub use trace_lot_usecase::*;
@@ -0,0 +1,131 @@
+use domain::inventory::{
+  location::{self, LocationType},
+  stock_move::{self, OnHandDTO, StockMoveState},
+};
+use infra::uuid::Uuid;
+use sea_orm::{
+  prelude::Decimal,
+  sea_query::{Alias, Expr, Query, SelectStatement, SimpleExpr, UnionType},
+  ColumnTrait, ConnectionTrait, DbBackend, DbErr, FromQueryResult, Statement,
+};
+
+/// Done quantities per product, internal location and lot: inflows through
+/// `destination_location_id` minus outflows through `source_location_id`.
+/// Restricted to `product_id`, `location_ids` and `lot_id` when given.
+pub async fn on_hand<C>(
+  db: &C,
+  product_id: Option<Uuid>,
+  location_ids: Option<Vec<Uuid>>,
+  lot_id: Option<Uuid>,
+) -> Result<Vec<OnHandDTO>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let moves = Alias::new("moves");
+  let quantity = Expr::col((moves.clone(), Alias::new("quantity"))).sum();
+
+  let mut query = Query::select();
+  query
+    .column((moves.clone(), Alias::new("product_id")))
+    .column((moves.clone(), Alias::new("location_id")))
+    .column((moves.clone(), Alias::new("lot_id")))
+    .expr_as(quantity.clone(), Alias::new("quantity"))
+    .from_subquery(
+      move_legs(
+        stock_move::Column::DestinationLocationId,
+        Expr::col(stock_move::Column::ProductQuantity).into(),
+        product_id,
+        lot_id,
+      )
+      .union(
+        UnionType::All,
+        move_legs(
+          stock_move::Column::SourceLocationId,
+          Expr::col(stock_move::Column::ProductQuantity).mul(-1),
+          product_id,
+          lot_id,
+        ),
+      )
+      .to_owned(),
+      moves.clone(),
+    )
+    .inner_join(
+      location::Entity,
+      Expr::col((location::Entity, location::Column::Id))
+        .equals((moves.clone(), Alias::new("location_id"))),
+    )
+    .and_where(location::Column::LocationType.eq(LocationType::Internal))
+    .group_by_col((moves.clone(), Alias::new("product_id")))
+    .group_by_col((moves.clone(), Alias::new("location_id")))
+    .group_by_col((moves.clone(), Alias::new("lot_id")))
+    .and_having(Expr::expr(quantity).ne(0));
+  if let Some(location_ids) = location_ids {
+    query.and_where(Expr::col((moves, Alias::new("location_id"))).is_in(location_ids));
+  }
+
+  OnHandDTO::find_by_statement(db.get_database_backend().build(&query))
+    .all(db)
+    .await
+}
+
+/// On-hand quantity of one product at exactly `location_id`, of `lot_id` only
+/// when given.
+pub async fn on_hand_quantity<C>(
+  db: &C,
+  product_id: Uuid,
+  location_id: Uuid,
+  lot_id: Option<Uuid>,
+) -> Result<Decimal, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let rows = on_hand(db, Some(product_id), Some(vec![location_id]), lot_id).await?;
+
+  Ok(rows.into_iter().map(|row| row.quantity).sum())
+}
+
+/// `location_id` followed by every location below it.
+pub async fn descendant_location_ids<C>(db: &C, location_id: Uuid) -> Result<Vec<Uuid>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let statement = Statement::from_sql_and_values(
+    DbBackend::Postgres,
+    r#"WITH RECURSIVE tree AS (
+      SELECT id FROM location WHERE id = $1
+      UNION
+      SELECT location.id FROM location JOIN tree ON location.parent_location_id = tree.id
+    )
+    SELECT id FROM tree"#,
+    [location_id.into()],
+  );
+
+  db.query_all(statement)
+    .await?
+    .into_iter()
+    .map(|row| row.try_get("", "id"))
+    .collect()
+}
+
+/// One side of every done move: the location it touches and the signed
+/// quantity it adds there.
+fn move_legs(
+  location_column: stock_move::Column,
+  quantity: SimpleExpr,
+  product_id: Option<Uuid>,
+  lot_id: Option<Uuid>,
+) -> SelectStatement {
+  Query::select()
+    .expr_as(
+      Expr::col(stock_move::Column::ProductId),
+      Alias::new("product_id"),
+    )
+    .expr_as(Expr::col(location_column), Alias::new("location_id"))
+    .expr_as(Expr::col(stock_move::Column::LotId), Alias::new("lot_id"))
+    .expr_as(quantity, Alias::new("quantity"))
+    .from(stock_move::Entity)
+    .and_where(stock_move::Column::State.eq(StockMoveState::Done))
+    .and_where_option(product_id.map(|product_id| stock_move::Column::ProductId.eq(product_id)))
+    .and_where_option(lot_id.map(|lot_id| stock_move::Column::LotId.eq(lot_id)))
+    .to_owned()
+}
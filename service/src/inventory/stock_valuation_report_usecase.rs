@@ -0,0 +1,52 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::inventory::stock_valuation_layer::StockValuationDTO;
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{prelude::DateTimeWithTimeZone, DbErr};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::valuation::{stock_valuation, ValuationGrouping};
+
+#[derive(Debug, Deserialize)]
+pub struct StockValuationReportUsecase {
+  /// Report the value as of this instant. Defaults to now.
+  pub at: Option<DateTimeWithTimeZone>,
+  pub product_id: Option<Uuid>,
+  pub warehouse_id: Option<Uuid>,
+  /// Defaults to one row per product and warehouse.
+  #[serde(default)]
+  pub group_by: ValuationGrouping,
+}
+
+pub type StockValuationReportParams = StockValuationReportUsecase;
+
+#[derive(Error, Debug)]
+pub enum StockValuationReportError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl IntoResponse for StockValuationReportError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      StockValuationReportError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("stock_valuation_report").into_response()
+  }
+}
+
+impl StockValuationReportUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<Vec<StockValuationDTO>, StockValuationReportError> {
+    let at = self.at.unwrap_or_else(|| Utc::now().into());
+
+    let valuation =
+      stock_valuation(&db, at, self.group_by, self.product_id, self.warehouse_id).await?;
+
+    Ok(valuation)
+  }
+}
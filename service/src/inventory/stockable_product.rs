@@ -0,0 +1,62 @@
+use domain::product::{
+  product,
+  product_template::{self, ProductType},
+};
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
+use thiserror::Error;
+
+/// Variant together with the template that decides whether it is stocked.
+pub struct StockableProduct {
+  pub product: product::Model,
+  pub template: product_template::Model,
+}
+
+#[derive(Error, Debug)]
+pub enum StockableProductError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("product_not_found")]
+  ProductNotFound,
+
+  #[error("product_not_tracked")]
+  ProductNotTracked,
+}
+
+impl From<StockableProductError> for AppError {
+  fn from(err: StockableProductError) -> Self {
+    match err {
+      StockableProductError::Database(err) => AppError::from(err),
+      StockableProductError::ProductNotFound => {
+        AppError::validation(err.to_string()).with_field("productId", "not_found")
+      }
+      StockableProductError::ProductNotTracked => {
+        AppError::validation(err.to_string()).with_field("productId", err.to_string())
+      }
+    }
+  }
+}
+
+/// Loads `product_id` and rejects services and goods whose template does not
+/// track inventory, the only products stock moves may carry.
+pub async fn find_stockable_product<C>(
+  db: &C,
+  product_id: Uuid,
+) -> Result<StockableProduct, StockableProductError>
+where
+  C: ConnectionTrait,
+{
+  let (product, template) = product::Entity::find_by_id(product_id)
+    .find_also_related(product_template::Entity)
+    .one(db)
+    .await?
+    .ok_or(StockableProductError::ProductNotFound)?;
+  let template = template.ok_or(StockableProductError::ProductNotFound)?;
+
+  if template.product_type != ProductType::Goods || !template.is_track_inventory {
+    return Err(StockableProductError::ProductNotTracked);
+  }
+
+  Ok(StockableProduct { product, template })
+}
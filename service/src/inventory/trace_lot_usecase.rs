@@ -0,0 +1,158 @@
+use axum::response::{IntoResponse, Response};
+use domain::inventory::lot::{self, LotDeliveryDTO, LotLinkDTO, LotTraceDTO};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{DbBackend, DbErr, EntityTrait, FromQueryResult, Statement};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct TraceLotUsecase {
+  pub id: Uuid,
+}
+
+pub type TraceLotParams = TraceLotUsecase;
+
+#[derive(Error, Debug)]
+pub enum TraceLotError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for TraceLotError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      TraceLotError::Database(err) => AppError::from(err),
+      TraceLotError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("trace_lot").into_response()
+  }
+}
+
+impl TraceLotUsecase {
+  /// Where a lot went and what it was made of: its done moves to customer
+  /// locations, with the customer when a move fulfils a sales order, the
+  /// component lots consumed into it and the finished lots it was consumed
+  /// into. Each side goes one production step deep; trace a linked lot to
+  /// follow the chain further.
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<LotTraceDTO, TraceLotError> {
+    let lot = lot::Entity::find_by_id(self.id)
+      .into_partial_model::<lot::PartialModel>()
+      .one(&db)
+      .await?
+      .ok_or(TraceLotError::RecordNotFound)?;
+
+    let deliveries = LotDeliveryDTO::find_by_statement(Statement::from_sql_and_values(
+      DbBackend::Postgres,
+      r#"SELECT stock_move.id AS stock_move_id, stock_move.reference,
+        location.id AS location_id, location.name AS location_name,
+        partner.id AS partner_id, partner.name AS partner_name,
+        sales_order.id AS sales_order_id, sales_order.reference AS sales_order_reference,
+        stock_move.product_quantity AS quantity, stock_move.done_at
+      FROM stock_move
+      JOIN location ON location.id = stock_move.destination_location_id
+      LEFT JOIN sales_order_line ON sales_order_line.id = stock_move.sales_order_line_id
+      LEFT JOIN sales_order ON sales_order.id = sales_order_line.sales_order_id
+      LEFT JOIN partner ON partner.id = sales_order.partner_id
+      WHERE stock_move.lot_id = $1
+        AND stock_move.state = 'done'
+        AND location.location_type = 'customer'
+      ORDER BY stock_move.done_at"#,
+      [self.id.into()],
+    ))
+    .all(&db)
+    .await?;
+
+    let components = linked_lots(&db, "lot_id", "produced_lot_id", self.id).await?;
+    let produced_lots = linked_lots(&db, "produced_lot_id", "lot_id", self.id).await?;
+
+    Ok(LotTraceDTO {
+      lot,
+      deliveries,
+      components,
+      produced_lots,
+    })
+  }
+}
+
+/// Lots found through `linked_column` on done moves whose `lot_column` is
+/// `lot_id`, with the component quantity those moves consumed.
+async fn linked_lots(
+  db: &impl ReadConnection,
+  linked_column: &str,
+  lot_column: &str,
+  lot_id: Uuid,
+) -> Result<Vec<LotLinkDTO>, DbErr> {
+  LotLinkDTO::find_by_statement(Statement::from_sql_and_values(
+    DbBackend::Postgres,
+    format!(
+      r#"SELECT lot.id AS lot_id, lot.name AS lot_name, lot.product_id,
+        SUM(stock_move.product_quantity) AS quantity
+      FROM stock_move
+      JOIN lot ON lot.id = stock_move.{linked_column}
+      WHERE stock_move.{lot_column} = $1 AND stock_move.state = 'done'
+      GROUP BY lot.id, lot.name, lot.product_id
+      ORDER BY lot.name"#
+    ),
+    [lot_id.into()],
+  ))
+  .all(db)
+  .await
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use infra::db::ReadDb;
+  use sea_orm::{prelude::Decimal, DatabaseBackend, MockDatabase, Value};
+
+  use super::*;
+
+  #[tokio::test]
+  async fn traces_lot_shipped_without_sales_order_line() {
+    let lot_id = Uuid::new();
+    let stock_move_id = Uuid::new();
+    let location_id = Uuid::new();
+    let lot_row = BTreeMap::from([
+      ("id", Value::from(lot_id)),
+      ("product_id", Value::from(Uuid::new())),
+      ("name", Value::from("L-001")),
+    ]);
+    let delivery_row = BTreeMap::from([
+      ("stock_move_id", Value::from(stock_move_id)),
+      ("reference", Value::from("OUT/001")),
+      ("location_id", Value::from(location_id)),
+      ("location_name", Value::from("Customers")),
+      ("partner_id", Value::Uuid(None)),
+      ("partner_name", Value::String(None)),
+      ("sales_order_id", Value::Uuid(None)),
+      ("sales_order_reference", Value::String(None)),
+      ("quantity", Value::from(Decimal::from(5))),
+      ("done_at", Value::ChronoDateTimeWithTimeZone(None)),
+    ]);
+    let no_links: Vec<BTreeMap<&str, Value>> = vec![];
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![lot_row]])
+      .append_query_results([vec![delivery_row]])
+      .append_query_results([no_links.clone(), no_links])
+      .into_connection();
+
+    let trace = TraceLotUsecase { id: lot_id }
+      .invoke(ReadDb::new(db))
+      .await
+      .unwrap();
+
+    assert_eq!(trace.deliveries.len(), 1);
+    let delivery = &trace.deliveries[0];
+    assert_eq!(delivery.stock_move_id, stock_move_id);
+    assert_eq!(delivery.location_id, location_id);
+    assert_eq!(delivery.location_name, "Customers");
+    assert_eq!(delivery.partner_id, None);
+    assert_eq!(delivery.sales_order_reference, None);
+    assert_eq!(delivery.quantity, Decimal::from(5));
+  }
+}
@@ -0,0 +1,181 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::{
+  inventory::{
+    location::{self, LocationType},
+    stock_move::{self, StockMoveState},
+  },
+  product::{product, product_template::Tracking},
+};
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{
+  prelude::Decimal, ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, QuerySelect, Set,
+  TransactionError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use super::{
+  on_hand::{on_hand, on_hand_quantity},
+  stockable_product::{find_stockable_product, StockableProductError},
+  valuation::value_stock_move,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ValidateStockMoveUsecase {
+  pub id: Uuid,
+}
+
+pub type ValidateStockMovePayload = ValidateStockMoveUsecase;
+
+#[derive(Error, Debug)]
+pub enum ValidateStockMoveError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Product(#[from] StockableProductError),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("stock_move_not_draft")]
+  NotDraft(StockMoveState),
+
+  #[error("insufficient_stock")]
+  InsufficientStock { available: Decimal },
+
+  #[error("serial_already_in_stock")]
+  SerialInStock,
+}
+
+impl From<TransactionError<ValidateStockMoveError>> for ValidateStockMoveError {
+  fn from(err: TransactionError<ValidateStockMoveError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => ValidateStockMoveError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl From<ValidateStockMoveError> for AppError {
+  fn from(err: ValidateStockMoveError) -> Self {
+    match err {
+      ValidateStockMoveError::Database(err) => AppError::from(err),
+      ValidateStockMoveError::Product(err) => AppError::from(err),
+      ValidateStockMoveError::RecordNotFound => AppError::not_found(err.to_string()),
+      ValidateStockMoveError::NotDraft(state) => {
+        AppError::conflict(err.to_string()).with_details(json!({ "state": state }))
+      }
+      ValidateStockMoveError::InsufficientStock { available } => {
+        AppError::conflict(err.to_string()).with_details(json!({ "available": available }))
+      }
+      ValidateStockMoveError::SerialInStock => {
+        AppError::conflict(err.to_string()).with_field("lotId", "already_in_stock")
+      }
+    }
+  }
+}
+
+impl IntoResponse for ValidateStockMoveError {
+  fn into_response(self) -> Response {
+    AppError::from(self)
+      .with_source("validate_stock_move")
+      .into_response()
+  }
+}
+
+impl ValidateStockMoveUsecase {
+  /// Marks a draft move as done, which is when it starts counting towards
+  /// on-hand quantities and gets valued. Moves out of an internal location may
+  /// not take more than it holds of the lot, and a serial cannot be received
+  /// while it is still in stock.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<stock_move::Model, ValidateStockMoveError> {
+    let id = self.id;
+
+    let stock_move = db
+      .transaction::<_, stock_move::Model, ValidateStockMoveError>(move |txn| {
+        Box::pin(async move {
+          let stock_move = stock_move::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(txn)
+            .await?
+            .ok_or(ValidateStockMoveError::RecordNotFound)?;
+          if stock_move.state != StockMoveState::Draft {
+            return Err(ValidateStockMoveError::NotDraft(stock_move.state));
+          }
+
+          complete_stock_move(txn, stock_move).await
+        })
+      })
+      .await?;
+
+    Ok(stock_move)
+  }
+}
+
+/// Marks the draft `stock_move` as done inside the caller's transaction,
+/// checking availability and serials and writing its valuation layers first.
+pub(crate) async fn complete_stock_move<C>(
+  db: &C,
+  stock_move: stock_move::Model,
+) -> Result<stock_move::Model, ValidateStockMoveError>
+where
+  C: ConnectionTrait,
+{
+  // Serializes validations of the same product so two moves cannot both
+  // consume the last units, and reads the cost under that lock.
+  product::Entity::find_by_id(stock_move.product_id)
+    .lock_exclusive()
+    .one(db)
+    .await?;
+  let stockable = find_stockable_product(db, stock_move.product_id).await?;
+
+  let source = location::Entity::find_by_id(stock_move.source_location_id)
+    .one(db)
+    .await?
+    .ok_or(ValidateStockMoveError::RecordNotFound)?;
+  let destination = location::Entity::find_by_id(stock_move.destination_location_id)
+    .one(db)
+    .await?
+    .ok_or(ValidateStockMoveError::RecordNotFound)?;
+  if source.location_type == LocationType::Internal {
+    let available = on_hand_quantity(
+      db,
+      stock_move.product_id,
+      stock_move.source_location_id,
+      stock_move.lot_id,
+    )
+    .await?;
+    if available < stock_move.product_quantity {
+      return Err(ValidateStockMoveError::InsufficientStock { available });
+    }
+  } else if stockable.template.tracking == Tracking::Serial
+    && destination.location_type == LocationType::Internal
+  {
+    let in_stock = on_hand(db, Some(stock_move.product_id), None, stock_move.lot_id)
+      .await?
+      .into_iter()
+      .any(|row| row.quantity > Decimal::ZERO);
+    if in_stock {
+      return Err(ValidateStockMoveError::SerialInStock);
+    }
+  }
+
+  let done_at = Utc::now().into();
+  value_stock_move(db, &stock_move, &stockable, &source, &destination, done_at).await?;
+
+  let stock_move = stock_move::ActiveModel {
+    id: Set(stock_move.id),
+    state: Set(StockMoveState::Done),
+    done_at: Set(Some(done_at)),
+    ..Default::default()
+  };
+  let stock_move = stock_move.update(db).await?;
+
+  Ok(stock_move)
+}
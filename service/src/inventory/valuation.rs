@@ -0,0 +1,426 @@
+use domain::{
+  inventory::{
+    location::{self, LocationType},
+    stock_move,
+    stock_valuation_layer::{self, StockValuationDTO},
+  },
+  product::{
+    category::{self, CostingMethod},
+    product,
+  },
+};
+use infra::uuid::Uuid;
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  sea_query::{Alias, Expr, Query, SelectStatement},
+  ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, QueryFilter,
+  QueryOrder, QuerySelect, Set,
+};
+use serde::Deserialize;
+
+use super::{on_hand::on_hand, stockable_product::StockableProduct};
+use crate::measurement::round_to;
+
+/// Precision of `product.cost`.
+const COST_ROUNDING: Decimal = Decimal::from_parts(1, 0, 0, false, 3);
+
+/// Writes the valuation layers of `stock_move`, which is about to be marked
+/// done at `done_at`, and refreshes `product.cost` for average costing. Must
+/// run before the move counts towards on-hand quantities.
+///
+/// Moves between two internal locations of the same warehouse leave its value
+/// unchanged and produce no layer; any other move produces an outgoing layer
+/// for an internal source and an incoming one for an internal destination.
+pub async fn value_stock_move<C>(
+  db: &C,
+  stock_move: &stock_move::Model,
+  stockable: &StockableProduct,
+  source: &location::Model,
+  destination: &location::Model,
+  done_at: DateTimeWithTimeZone,
+) -> Result<(), DbErr>
+where
+  C: ConnectionTrait,
+{
+  let is_source_internal = source.location_type == LocationType::Internal;
+  let is_destination_internal = destination.location_type == LocationType::Internal;
+  if is_source_internal
+    && is_destination_internal
+    && source.warehouse_id == destination.warehouse_id
+  {
+    return Ok(());
+  }
+
+  let costing_method = costing_method(db, stockable.template.category_id).await?;
+  let product = &stockable.product;
+  let quantity = stock_move.product_quantity;
+
+  let mut outgoing_unit_cost = None;
+  if is_source_internal {
+    let consumed_value = consume_layers(db, product, source.warehouse_id, quantity).await?;
+    let value = match costing_method {
+      CostingMethod::Fifo => consumed_value,
+      CostingMethod::Standard | CostingMethod::Average => quantity * product.cost,
+    };
+    let unit_cost = value / quantity;
+    outgoing_unit_cost = Some(unit_cost);
+
+    insert_layer(
+      db,
+      stock_move,
+      source.warehouse_id,
+      -quantity,
+      unit_cost,
+      Decimal::ZERO,
+      done_at,
+    )
+    .await?;
+  }
+
+  if is_destination_internal {
+    // A transfer between warehouses carries the cost it left with, a receipt
+    // the price it was bought at.
+    let unit_cost = match (costing_method, outgoing_unit_cost) {
+      (_, Some(unit_cost)) => unit_cost,
+      (CostingMethod::Standard, None) => product.cost,
+      (CostingMethod::Average | CostingMethod::Fifo, None) => stock_move
+        .unit_cost
+        .map(|unit_cost| unit_cost * stock_move.quantity / quantity)
+        .unwrap_or(product.cost),
+    };
+
+    if costing_method == CostingMethod::Average && outgoing_unit_cost.is_none() {
+      update_average_cost(db, product, quantity, unit_cost).await?;
+    }
+
+    insert_layer(
+      db,
+      stock_move,
+      destination.warehouse_id,
+      quantity,
+      unit_cost,
+      quantity,
+      done_at,
+    )
+    .await?;
+  }
+
+  Ok(())
+}
+
+/// Costing method of the template's category; uncategorised products use
+/// standard costing.
+async fn costing_method<C>(db: &C, category_id: Option<Uuid>) -> Result<CostingMethod, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let Some(category_id) = category_id else {
+    return Ok(CostingMethod::default());
+  };
+  let category = category::Entity::find_by_id(category_id).one(db).await?;
+
+  Ok(
+    category
+      .map(|category| category.costing_method)
+      .unwrap_or_default(),
+  )
+}
+
+/// Draws `quantity` from the oldest incoming layers of the warehouse that still
+/// have some left and returns what it was worth. Stock received before
+/// valuation existed has no layer and is valued at the current cost.
+async fn consume_layers<C>(
+  db: &C,
+  product: &product::Model,
+  warehouse_id: Option<Uuid>,
+  quantity: Decimal,
+) -> Result<Decimal, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let layers = stock_valuation_layer::Entity::find()
+    .filter(stock_valuation_layer::Column::ProductId.eq(product.id))
+    .filter(match warehouse_id {
+      Some(warehouse_id) => stock_valuation_layer::Column::WarehouseId.eq(warehouse_id),
+      None => stock_valuation_layer::Column::WarehouseId.is_null(),
+    })
+    .filter(stock_valuation_layer::Column::RemainingQuantity.gt(Decimal::ZERO))
+    .order_by_asc(stock_valuation_layer::Column::CreatedAt)
+    .order_by_asc(stock_valuation_layer::Column::Id)
+    .lock_exclusive()
+    .all(db)
+    .await?;
+
+  let available = layers
+    .iter()
+    .map(|layer| (layer.remaining_quantity, layer.unit_cost))
+    .collect::<Vec<_>>();
+  let (taken, value) = fifo_consumption(&available, quantity, product.cost);
+
+  for (layer, taken) in layers.into_iter().zip(taken) {
+    if taken.is_zero() {
+      continue;
+    }
+    let layer = stock_valuation_layer::ActiveModel {
+      id: Set(layer.id),
+      remaining_quantity: Set(layer.remaining_quantity - taken),
+      ..Default::default()
+    };
+    layer.update(db).await?;
+  }
+
+  Ok(value)
+}
+
+/// Splits `quantity` over `(remaining quantity, unit cost)` layers, oldest
+/// first. Returns the quantity taken from each layer and the value consumed,
+/// with whatever the layers cannot cover valued at `fallback_cost`.
+fn fifo_consumption(
+  layers: &[(Decimal, Decimal)],
+  quantity: Decimal,
+  fallback_cost: Decimal,
+) -> (Vec<Decimal>, Decimal) {
+  let mut left = quantity;
+  let mut value = Decimal::ZERO;
+  let taken = layers
+    .iter()
+    .map(|&(remaining_quantity, unit_cost)| {
+      let taken = left.min(remaining_quantity);
+      left -= taken;
+      value += taken * unit_cost;
+      taken
+    })
+    .collect();
+
+  (taken, value + left * fallback_cost)
+}
+
+/// Blends a receipt into the weighted average cost of the stock on hand in
+/// every warehouse.
+async fn update_average_cost<C>(
+  db: &C,
+  product: &product::Model,
+  quantity: Decimal,
+  unit_cost: Decimal,
+) -> Result<(), DbErr>
+where
+  C: ConnectionTrait,
+{
+  let on_hand = on_hand(db, Some(product.id), None, None)
+    .await?
+    .into_iter()
+    .map(|row| row.quantity)
+    .sum::<Decimal>()
+    .max(Decimal::ZERO);
+
+  let product = product::ActiveModel {
+    id: Set(product.id),
+    cost: Set(average_cost(on_hand, product.cost, quantity, unit_cost)),
+    ..Default::default()
+  };
+  product.update(db).await?;
+
+  Ok(())
+}
+
+/// Weighted average of `on_hand` units at `cost` and a receipt of `quantity`
+/// units at `unit_cost`, rounded to the precision of `product.cost`.
+fn average_cost(on_hand: Decimal, cost: Decimal, quantity: Decimal, unit_cost: Decimal) -> Decimal {
+  let cost = (on_hand * cost + quantity * unit_cost) / (on_hand + quantity);
+
+  round_to(cost, COST_ROUNDING)
+}
+
+async fn insert_layer<C>(
+  db: &C,
+  stock_move: &stock_move::Model,
+  warehouse_id: Option<Uuid>,
+  quantity: Decimal,
+  unit_cost: Decimal,
+  remaining_quantity: Decimal,
+  created_at: DateTimeWithTimeZone,
+) -> Result<(), DbErr>
+where
+  C: ConnectionTrait,
+{
+  let layer = stock_valuation_layer::ActiveModel {
+    product_id: Set(stock_move.product_id),
+    stock_move_id: Set(stock_move.id),
+    warehouse_id: Set(warehouse_id),
+    quantity: Set(quantity),
+    unit_cost: Set(unit_cost),
+    value: Set(quantity * unit_cost),
+    remaining_quantity: Set(remaining_quantity),
+    created_at: Set(created_at),
+    ..Default::default()
+  };
+  layer.insert(db).await?;
+
+  Ok(())
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValuationGrouping {
+  #[serde(rename = "product")]
+  Product,
+  #[serde(rename = "warehouse")]
+  Warehouse,
+  #[default]
+  #[serde(rename = "product_warehouse")]
+  ProductWarehouse,
+}
+
+/// Sum of the layers created up to `at`, grouped by `grouping` and restricted
+/// to `product_id` and `warehouse_id` when given.
+pub async fn stock_valuation<C>(
+  db: &C,
+  at: DateTimeWithTimeZone,
+  grouping: ValuationGrouping,
+  product_id: Option<Uuid>,
+  warehouse_id: Option<Uuid>,
+) -> Result<Vec<StockValuationDTO>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let query = stock_valuation_query(at, grouping, product_id, warehouse_id);
+
+  StockValuationDTO::find_by_statement(db.get_database_backend().build(&query))
+    .all(db)
+    .await
+}
+
+fn stock_valuation_query(
+  at: DateTimeWithTimeZone,
+  grouping: ValuationGrouping,
+  product_id: Option<Uuid>,
+  warehouse_id: Option<Uuid>,
+) -> SelectStatement {
+  let quantity = Expr::col(stock_valuation_layer::Column::Quantity).sum();
+  let value = Expr::col(stock_valuation_layer::Column::Value).sum();
+
+  let mut query = Query::select();
+  query
+    .expr_as(quantity.clone(), Alias::new("quantity"))
+    .expr_as(value, Alias::new("value"))
+    .from(stock_valuation_layer::Entity)
+    .and_where(stock_valuation_layer::Column::CreatedAt.lte(at))
+    .and_where_option(product_id.map(|id| stock_valuation_layer::Column::ProductId.eq(id)))
+    .and_where_option(warehouse_id.map(|id| stock_valuation_layer::Column::WarehouseId.eq(id)))
+    .and_having(Expr::expr(quantity).ne(0));
+
+  for (column, alias, grouped) in [
+    (
+      stock_valuation_layer::Column::ProductId,
+      "product_id",
+      grouping != ValuationGrouping::Warehouse,
+    ),
+    (
+      stock_valuation_layer::Column::WarehouseId,
+      "warehouse_id",
+      grouping != ValuationGrouping::Product,
+    ),
+  ] {
+    if grouped {
+      query
+        .expr_as(Expr::col(column), Alias::new(alias))
+        .group_by_col(column)
+        .order_by(column, sea_orm::Order::Asc);
+    } else {
+      query.expr_as(Expr::cust("NULL::uuid"), Alias::new(alias));
+    }
+  }
+
+  query
+}
+
+#[cfg(test)]
+mod tests {
+  use sea_orm::sea_query::PostgresQueryBuilder;
+
+  use super::*;
+  use crate::test_support::dec;
+
+  #[test]
+  fn fifo_consumes_oldest_layers_first() {
+    let layers = [
+      (dec("5"), dec("10")),
+      (dec("5"), dec("12")),
+      (dec("5"), dec("15")),
+    ];
+
+    let (taken, value) = fifo_consumption(&layers, dec("7"), dec("99"));
+
+    assert_eq!(taken, vec![dec("5"), dec("2"), dec("0")]);
+    assert_eq!(value, dec("74"));
+  }
+
+  #[test]
+  fn fifo_values_uncovered_quantity_at_fallback_cost() {
+    let layers = [(dec("2"), dec("10"))];
+
+    let (taken, value) = fifo_consumption(&layers, dec("5"), dec("11"));
+
+    assert_eq!(taken, vec![dec("2")]);
+    assert_eq!(value, dec("53"));
+  }
+
+  #[test]
+  fn fifo_without_layers_uses_fallback_cost() {
+    let (taken, value) = fifo_consumption(&[], dec("3"), dec("4.5"));
+
+    assert!(taken.is_empty());
+    assert_eq!(value, dec("13.5"));
+  }
+
+  #[test]
+  fn average_cost_weights_receipt_against_stock_on_hand() {
+    assert_eq!(
+      average_cost(dec("10"), dec("4"), dec("30"), dec("8")),
+      dec("7")
+    );
+  }
+
+  #[test]
+  fn average_cost_without_stock_takes_receipt_cost() {
+    assert_eq!(
+      average_cost(dec("0"), dec("4"), dec("3"), dec("2.5")),
+      dec("2.5")
+    );
+  }
+
+  #[test]
+  fn average_cost_rounds_to_cost_precision() {
+    assert_eq!(
+      average_cost(dec("1"), dec("1"), dec("2"), dec("2")),
+      dec("1.667")
+    );
+  }
+
+  #[test]
+  fn stock_valuation_only_counts_layers_up_to_date() {
+    let at = DateTimeWithTimeZone::parse_from_rfc3339("2025-01-31T23:59:59+07:00").unwrap();
+
+    let sql = stock_valuation_query(at, ValuationGrouping::ProductWarehouse, None, None)
+      .to_string(PostgresQueryBuilder);
+
+    assert!(
+      sql.contains(r#""created_at" <= '2025-01-31 23:59:59 +07:00'"#),
+      "{sql}"
+    );
+    assert!(
+      sql.contains(r#"GROUP BY "product_id", "warehouse_id""#),
+      "{sql}"
+    );
+  }
+
+  #[test]
+  fn stock_valuation_grouping_blanks_ungrouped_column() {
+    let at = DateTimeWithTimeZone::parse_from_rfc3339("2025-01-31T00:00:00Z").unwrap();
+
+    let sql = stock_valuation_query(at, ValuationGrouping::Warehouse, None, None)
+      .to_string(PostgresQueryBuilder);
+
+    assert!(sql.contains(r#"NULL::uuid AS "product_id""#), "{sql}");
+    assert!(sql.contains(r#"GROUP BY "warehouse_id""#), "{sql}");
+  }
+}
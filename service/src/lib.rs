@@ -1,2 +1,15 @@
+pub mod archive;
+pub mod currency;
+pub mod inventory;
+pub mod list_query;
 pub mod measurement;
+pub mod partner;
+pub mod pricelist;
 pub mod product;
+pub mod purchase;
+pub mod sales;
+pub mod tax;
+pub mod unique_name;
+
+#[cfg(test)]
+mod test_support;
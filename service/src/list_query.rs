@@ -0,0 +1,360 @@
+use std::str::FromStr;
+
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{
+  sea_query::{BinOper, Expr, Func, LikeExpr, SelectStatement, SimpleExpr},
+  ColumnTrait, ConnectionTrait, DbErr, Order,
+};
+use serde::{Deserialize, Deserializer};
+use thiserror::Error;
+
+/// Sort direction accepted by every `*.list` endpoint as `order=asc|desc`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+  #[default]
+  Asc,
+  Desc,
+}
+
+impl From<SortOrder> for Order {
+  fn from(order: SortOrder) -> Self {
+    match order {
+      SortOrder::Asc => Order::Asc,
+      SortOrder::Desc => Order::Desc,
+    }
+  }
+}
+
+/// Sort keys shared by the uom, attribute and category lists.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+  Name,
+  CreatedAt,
+}
+
+/// Case-insensitive substring match on a text column. `%`, `_` and `\` in the
+/// search term are matched literally.
+pub fn name_contains<C>(column: C, term: &str) -> SimpleExpr
+where
+  C: ColumnTrait,
+{
+  let escaped = term
+    .trim()
+    .to_lowercase()
+    .replace('\\', "\\\\")
+    .replace('%', "\\%")
+    .replace('_', "\\_");
+
+  Expr::expr(Func::lower(Expr::col((column.entity_name(), column))))
+    .like(LikeExpr::new(format!("%{}%", escaped)).escape('\\'))
+}
+
+/// Deserializes a comma-separated query parameter such as `ids=a,b,c`.
+pub fn comma_separated<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: FromStr,
+  T::Err: std::fmt::Display,
+{
+  let value = Option::<String>::deserialize(deserializer)?;
+
+  match value {
+    Some(value) => value
+      .split(',')
+      .map(str::trim)
+      .filter(|item| !item.is_empty())
+      .map(|item| item.parse::<T>().map_err(serde::de::Error::custom))
+      .collect::<Result<Vec<_>, _>>()
+      .map(Some),
+    None => Ok(None),
+  }
+}
+
+/// Paging strategy of a `*.list` endpoint, `pagination=offset|cursor`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PaginationMode {
+  #[default]
+  Offset,
+  Cursor,
+}
+
+/// Page size of a list when `per_page` is left out.
+pub const DEFAULT_PER_PAGE: u64 = 30;
+
+/// Largest `per_page` a list accepts.
+pub const MAX_PER_PAGE: u64 = 100;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PageError {
+  #[error("invalid_page")]
+  Page,
+
+  #[error("invalid_per_page")]
+  PerPage,
+}
+
+impl From<PageError> for AppError {
+  fn from(err: PageError) -> Self {
+    match err {
+      PageError::Page => {
+        AppError::validation(err.to_string()).with_field("page", "must_be_positive")
+      }
+      PageError::PerPage => AppError::validation(err.to_string()).with_field(
+        "per_page",
+        format!("must_be_between_1_and_{}", MAX_PER_PAGE),
+      ),
+    }
+  }
+}
+
+/// Zero-based index of the requested page. Pages count from 1.
+pub fn page_index(page: Option<u64>) -> Result<u64, PageError> {
+  match page.unwrap_or(1) {
+    0 => Err(PageError::Page),
+    page => Ok(page - 1),
+  }
+}
+
+/// Rows per page, `DEFAULT_PER_PAGE` by default and at most `MAX_PER_PAGE`.
+pub fn page_size(per_page: Option<u64>) -> Result<u64, PageError> {
+  match per_page.unwrap_or(DEFAULT_PER_PAGE) {
+    per_page @ 1..=MAX_PER_PAGE => Ok(per_page),
+    _ => Err(PageError::PerPage),
+  }
+}
+
+/// Whether a list request should be served with cursor pagination. Passing a
+/// cursor implies cursor mode.
+pub fn uses_cursor(pagination: Option<PaginationMode>, cursor: Option<&Cursor>) -> bool {
+  pagination == Some(PaginationMode::Cursor) || cursor.is_some()
+}
+
+#[derive(Error, Debug)]
+pub enum CursorError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  /// The row the cursor points at was deleted (or no longer matches), so
+  /// there is nothing to page from.
+  #[error("invalid_cursor")]
+  AnchorNotFound,
+}
+
+impl From<CursorError> for AppError {
+  fn from(err: CursorError) -> Self {
+    match err {
+      CursorError::Database(err) => AppError::from(err),
+      CursorError::AnchorNotFound => {
+        AppError::bad_request(err.to_string()).with_field("cursor", "not_found")
+      }
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorDirection {
+  Next,
+  Prev,
+}
+
+/// Opaque keyset cursor pointing at the row a page starts after (`Next`) or
+/// ends before (`Prev`). Only the row id is carried; the sort key is looked up
+/// from the row itself, so cursors stay valid for every sort order but not
+/// once that row is deleted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+  pub id: Uuid,
+  pub direction: CursorDirection,
+}
+
+impl Cursor {
+  pub fn next(id: Uuid) -> Self {
+    Self {
+      id,
+      direction: CursorDirection::Next,
+    }
+  }
+
+  pub fn prev(id: Uuid) -> Self {
+    Self {
+      id,
+      direction: CursorDirection::Prev,
+    }
+  }
+
+  pub fn encode(&self) -> String {
+    let prefix = match self.direction {
+      CursorDirection::Next => 'n',
+      CursorDirection::Prev => 'p',
+    };
+
+    format!("{}:{}", prefix, self.id)
+      .bytes()
+      .map(|byte| format!("{:02x}", byte))
+      .collect()
+  }
+
+  pub fn decode(value: &str) -> Option<Self> {
+    if !value.len().is_multiple_of(2) {
+      return None;
+    }
+    let bytes = (0..value.len())
+      .step_by(2)
+      .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+      .collect::<Option<Vec<_>>>()?;
+    let decoded = String::from_utf8(bytes).ok()?;
+    let (prefix, id) = decoded.split_once(':')?;
+    let direction = match prefix {
+      "n" => CursorDirection::Next,
+      "p" => CursorDirection::Prev,
+      _ => return None,
+    };
+
+    Some(Self {
+      id: id.parse().ok()?,
+      direction,
+    })
+  }
+
+  /// Order the page query has to run in; `Prev` pages are read backwards and
+  /// flipped afterwards.
+  pub fn query_order(cursor: Option<&Cursor>, order: Order) -> Order {
+    match (cursor.map(|cursor| cursor.direction), order) {
+      (Some(CursorDirection::Prev), Order::Asc) => Order::Desc,
+      (Some(CursorDirection::Prev), _) => Order::Asc,
+      (_, order) => order,
+    }
+  }
+
+  /// Row-value comparison `(keys) > (anchor)` selecting the rows after the
+  /// cursor in `query_order`. `anchor` must select the same keys for the
+  /// cursor row; when it finds no row the cursor is rejected, since the
+  /// comparison would silently match nothing.
+  pub async fn keyset_condition<C>(
+    &self,
+    db: &C,
+    keys: Vec<SimpleExpr>,
+    anchor: SelectStatement,
+    query_order: Order,
+  ) -> Result<SimpleExpr, CursorError>
+  where
+    C: ConnectionTrait,
+  {
+    let builder = db.get_database_backend();
+    if db.query_one(builder.build(&anchor)).await?.is_none() {
+      return Err(CursorError::AnchorNotFound);
+    }
+
+    let operator = match query_order {
+      Order::Desc => BinOper::SmallerThan,
+      _ => BinOper::GreaterThan,
+    };
+
+    Ok(Expr::tuple(keys).binary(
+      operator,
+      SimpleExpr::SubQuery(None, Box::new(anchor.into_sub_query_statement())),
+    ))
+  }
+}
+
+impl<'de> Deserialize<'de> for Cursor {
+  fn deserialize<D>(deserializer: D) -> Result<Cursor, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let value = String::deserialize(deserializer)?;
+    Cursor::decode(&value).ok_or_else(|| serde::de::Error::custom("invalid_cursor"))
+  }
+}
+
+/// Turns the `per_page + 1` rows fetched for a cursor page into the page
+/// itself plus its `next`/`prev` cursors.
+pub fn cursor_page<T, F>(
+  mut rows: Vec<T>,
+  per_page: u64,
+  cursor: Option<&Cursor>,
+  id_of: F,
+) -> (Vec<T>, Option<String>, Option<String>)
+where
+  F: Fn(&T) -> Uuid,
+{
+  let has_more = rows.len() as u64 > per_page;
+  rows.truncate(per_page as usize);
+
+  let direction = cursor.map(|cursor| cursor.direction);
+  if direction == Some(CursorDirection::Prev) {
+    rows.reverse();
+  }
+
+  let first = rows.first().map(&id_of);
+  let last = rows.last().map(&id_of);
+  let (has_next, has_prev) = match direction {
+    None => (has_more, false),
+    Some(CursorDirection::Next) => (has_more, true),
+    Some(CursorDirection::Prev) => (true, has_more),
+  };
+
+  let next_cursor = last
+    .filter(|_| has_next)
+    .map(|id| Cursor::next(id).encode());
+  let prev_cursor = first
+    .filter(|_| has_prev)
+    .map(|id| Cursor::prev(id).encode());
+
+  (rows, next_cursor, prev_cursor)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn page_index_counts_pages_from_one() {
+    assert_eq!(page_index(None), Ok(0));
+    assert_eq!(page_index(Some(1)), Ok(0));
+    assert_eq!(page_index(Some(3)), Ok(2));
+    assert_eq!(page_index(Some(0)), Err(PageError::Page));
+  }
+
+  #[test]
+  fn page_size_is_bounded() {
+    assert_eq!(page_size(None), Ok(DEFAULT_PER_PAGE));
+    assert_eq!(page_size(Some(1)), Ok(1));
+    assert_eq!(page_size(Some(MAX_PER_PAGE)), Ok(MAX_PER_PAGE));
+    assert_eq!(page_size(Some(0)), Err(PageError::PerPage));
+    assert_eq!(page_size(Some(MAX_PER_PAGE + 1)), Err(PageError::PerPage));
+  }
+
+  #[test]
+  fn cursor_round_trips_through_encode_and_decode() {
+    let id = Uuid::new();
+
+    for cursor in [Cursor::next(id), Cursor::prev(id)] {
+      assert_eq!(Cursor::decode(&cursor.encode()), Some(cursor));
+    }
+  }
+
+  #[test]
+  fn cursor_encodes_direction() {
+    let id = Uuid::new();
+
+    assert_ne!(Cursor::next(id).encode(), Cursor::prev(id).encode());
+  }
+
+  #[test]
+  fn decode_rejects_malformed_cursors() {
+    let id = Uuid::new();
+    let hex =
+      |value: String| -> String { value.bytes().map(|byte| format!("{:02x}", byte)).collect() };
+
+    assert_eq!(Cursor::decode(""), None);
+    assert_eq!(Cursor::decode("abc"), None);
+    assert_eq!(Cursor::decode("zz"), None);
+    assert_eq!(Cursor::decode(&hex(format!("x:{}", id))), None);
+    assert_eq!(Cursor::decode(&hex("n:not-a-uuid".to_string())), None);
+    assert_eq!(Cursor::decode(&hex(id.to_string())), None);
+  }
+}
@@ -0,0 +1,53 @@
+use axum::response::{IntoResponse, Response};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::uom_conversion::{convert_quantity, UomConversionError};
+
+#[derive(Debug, Deserialize)]
+pub struct ConvertUomUsecase {
+  pub quantity: Decimal,
+  pub from_uom_id: Uuid,
+  pub to_uom_id: Uuid,
+}
+
+pub type ConvertUomParams = ConvertUomUsecase;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedQuantity {
+  pub quantity: Decimal,
+  pub uom_id: Uuid,
+}
+
+#[derive(Error, Debug)]
+pub enum ConvertUomError {
+  #[error(transparent)]
+  Conversion(#[from] UomConversionError),
+}
+
+impl IntoResponse for ConvertUomError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ConvertUomError::Conversion(err) => AppError::from(err),
+    };
+
+    error.with_source("convert_uom").into_response()
+  }
+}
+
+impl ConvertUomUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<ConvertedQuantity, ConvertUomError> {
+    let quantity = convert_quantity(&db, self.quantity, self.from_uom_id, self.to_uom_id).await?;
+
+    Ok(ConvertedQuantity {
+      quantity,
+      uom_id: self.to_uom_id,
+    })
+  }
+}
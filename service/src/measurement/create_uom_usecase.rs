@@ -1,53 +1,101 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
+use axum::response::{IntoResponse, Response};
+use domain::measurement::uom::{self, ActiveModel as Uom, UomCategory};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
 };
-use domain::measurement::uom::{self, ActiveModel as Uom};
-use infra::util::error;
-use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, Set};
+use sea_orm::{prelude::Decimal, ActiveModelTrait, DbErr, EntityTrait, QueryFilter, Set};
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::unique_name::{name_conflict, same_name};
+
 #[derive(Debug, Deserialize)]
 pub struct CreateUomUsecase {
   pub name: String,
+  #[serde(default)]
+  pub category: UomCategory,
+  #[serde(default = "default_ratio")]
+  pub ratio: Decimal,
+  #[serde(default = "default_rounding")]
+  pub rounding: Decimal,
+  #[serde(rename(deserialize = "isReference"), default)]
+  pub is_reference: bool,
 }
 
 pub type CreateUomParams = CreateUomUsecase;
 
+fn default_ratio() -> Decimal {
+  Decimal::ONE
+}
+
+fn default_rounding() -> Decimal {
+  Decimal::new(1, 2)
+}
+
+impl Validate for CreateUomUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .field(
+        "ratio",
+        [
+          rules::positive(self.ratio),
+          rules::reject_if(
+            self.is_reference && self.ratio != Decimal::ONE,
+            "must_be_one_for_reference_unit",
+          ),
+        ],
+      )
+      .field("rounding", [rules::positive(self.rounding)])
+      .into_result()
+  }
+}
+
 #[derive(Error, Debug)]
 pub enum CreateUomError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
 }
 
 impl IntoResponse for CreateUomError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      CreateUomError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
-      }
+    let error = match self {
+      CreateUomError::Database(err) => AppError::from(err),
+      CreateUomError::NameConflict(existing_id) => name_conflict(self.to_string(), existing_id),
     };
 
-    (status, error(code, Some("create_uom".to_string()))).into_response()
+    error.with_source("create_uom").into_response()
   }
 }
 
 impl CreateUomUsecase {
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait,
+    db: impl WriteConnection,
   ) -> Result<uom::PartialModel, CreateUomError> {
+    let existing = uom::Entity::find()
+      .filter(same_name(uom::Column::Name, &self.name))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(CreateUomError::NameConflict(existing.id));
+    }
+
     let uom = Uom {
       name: Set(self.name.to_owned()),
+      category: Set(self.category),
+      ratio: Set(self.ratio),
+      rounding: Set(self.rounding),
+      is_reference: Set(self.is_reference),
       ..Default::default()
     };
     let uom = uom.insert(&db).await?;
-    let partial_uom = uom::PartialModel {
-      id: uom.id,
-      name: uom.name,
-    };
-
-    Ok(partial_uom)
+    Ok(uom.into())
   }
 }
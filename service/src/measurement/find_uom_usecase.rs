@@ -1,10 +1,7 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
-};
+use axum::response::{IntoResponse, Response};
 use domain::measurement::uom::{self, Entity as Uom};
-use infra::{util::error, uuid::Uuid};
-use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{DbErr, EntityTrait};
 use serde::Deserialize;
 use thiserror::Error;
 
@@ -17,8 +14,8 @@ pub type FindUomParams = FindUomUsecase;
 
 #[derive(Error, Debug)]
 pub enum FindUomError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
 
   #[error("record_not_found")]
   RecordNotFound,
@@ -26,17 +23,17 @@ pub enum FindUomError {
 
 impl IntoResponse for FindUomError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      FindUomError::InternalServerError(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
-      FindUomError::RecordNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+    let error = match self {
+      FindUomError::Database(err) => AppError::from(err),
+      FindUomError::RecordNotFound => AppError::not_found(self.to_string()),
     };
 
-    (status, error(code, Some("create_uom".to_string()))).into_response()
+    error.with_source("find_uom").into_response()
   }
 }
 
 impl FindUomUsecase {
-  pub async fn invoke(&self, db: impl ConnectionTrait) -> Result<uom::PartialModel, FindUomError> {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<uom::PartialModel, FindUomError> {
     let uom = Uom::find_by_id(self.id)
       .into_partial_model::<uom::PartialModel>()
       .one(&db)
@@ -1,48 +1,79 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
+use axum::response::{IntoResponse, Response};
+use domain::measurement::uom::{self, Column, Entity as Uom};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  response::{CursorPaginationMeta, PaginationMeta},
+};
+use sea_orm::{
+  sea_query::{Expr, Query},
+  ColumnTrait, Condition, DbErr, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+  QuerySelect,
 };
-use domain::measurement::uom::{self, Entity as Uom};
-use infra::{response::PaginationMeta, util::error};
-use sea_orm::{ConnectionTrait, DbErr, EntityTrait, PaginatorTrait};
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::{
+  archive::archived_condition,
+  list_query::{
+    cursor_page, name_contains, page_index, page_size, Cursor, CursorError, PageError,
+    PaginationMode, SortBy, SortOrder,
+  },
+};
+
 #[derive(Debug, Deserialize)]
 pub struct ListPaginatedUomsUsecase {
   pub page: Option<u64>,
   pub per_page: Option<u64>,
+  pub include_archived: Option<bool>,
+  pub only_archived: Option<bool>,
+  pub name: Option<String>,
+  pub sort_by: Option<SortBy>,
+  pub order: Option<SortOrder>,
+  pub pagination: Option<PaginationMode>,
+  pub cursor: Option<Cursor>,
+  pub with_total: Option<bool>,
 }
 
 pub type ListPaginatedUomsParams = ListPaginatedUomsUsecase;
 
 #[derive(Error, Debug)]
 pub enum ListPaginatedUomsError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Page(#[from] PageError),
+
+  #[error(transparent)]
+  Cursor(#[from] CursorError),
 }
 
 impl IntoResponse for ListPaginatedUomsError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      ListPaginatedUomsError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
-      }
+    let error = match self {
+      ListPaginatedUomsError::Database(err) => AppError::from(err),
+      ListPaginatedUomsError::Page(err) => AppError::from(err),
+      ListPaginatedUomsError::Cursor(err) => AppError::from(err),
     };
 
-    (status, error(code, Some("list_paginated_uoms".to_string()))).into_response()
+    error.with_source("list_paginated_uoms").into_response()
   }
 }
 
 impl ListPaginatedUomsUsecase {
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait,
+    db: impl ReadConnection,
   ) -> Result<(Vec<uom::PartialModel>, PaginationMeta), ListPaginatedUomsError> {
-    let per_page = self.per_page.unwrap_or(30);
-    let page = self.page.unwrap_or(1) - 1;
+    let per_page = page_size(self.per_page)?;
+    let page = page_index(self.page)?;
+    let order = Order::from(self.order.unwrap_or_default());
 
     let uom_pages = Uom::find()
+      .filter(self.filter_condition())
+      .order_by(self.sort_column(), order)
+      .order_by_asc(Column::Id)
       .into_partial_model::<uom::PartialModel>()
       .paginate(&db, per_page);
     let uoms = uom_pages.fetch_page(page).await?;
@@ -60,4 +91,92 @@ impl ListPaginatedUomsUsecase {
       },
     ))
   }
+
+  /// Keyset variant of `invoke`: reads `per_page` rows after (or before) the
+  /// cursor and only counts the whole result set when `with_total` is set.
+  pub async fn invoke_cursor(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<uom::PartialModel>, CursorPaginationMeta), ListPaginatedUomsError> {
+    let per_page = page_size(self.per_page)?;
+    let cursor = self.cursor.as_ref();
+    let query_order = Cursor::query_order(cursor, self.order.unwrap_or_default().into());
+    let sort_column = self.sort_column();
+
+    let keyset_condition = match cursor {
+      Some(cursor) => Some(
+        cursor
+          .keyset_condition(
+            &db,
+            vec![
+              Expr::col((Uom, sort_column)).into(),
+              Expr::col((Uom, Column::Id)).into(),
+            ],
+            Query::select()
+              .column(sort_column)
+              .column(Column::Id)
+              .from(Uom)
+              .and_where(Column::Id.eq(cursor.id))
+              .to_owned(),
+            query_order.clone(),
+          )
+          .await?,
+      ),
+      None => None,
+    };
+
+    let rows = Uom::find()
+      .filter(self.filter_condition())
+      .filter(Condition::all().add_option(keyset_condition))
+      .order_by(sort_column, query_order.clone())
+      .order_by(Column::Id, query_order)
+      .limit(per_page + 1)
+      .into_partial_model::<uom::PartialModel>()
+      .all(&db)
+      .await?;
+    let (uoms, next_cursor, prev_cursor) = cursor_page(rows, per_page, cursor, |uom| uom.id);
+
+    let total = match self.with_total {
+      Some(true) => Some(
+        Uom::find()
+          .filter(self.filter_condition())
+          .count(&db)
+          .await?,
+      ),
+      _ => None,
+    };
+
+    Ok((
+      uoms,
+      CursorPaginationMeta {
+        per_page,
+        next_cursor,
+        prev_cursor,
+        total,
+      },
+    ))
+  }
+
+  fn filter_condition(&self) -> Condition {
+    Condition::all()
+      .add(archived_condition(
+        Column::ArchivedAt,
+        self.include_archived,
+        self.only_archived,
+      ))
+      .add_option(
+        self
+          .name
+          .as_deref()
+          .filter(|name| !name.trim().is_empty())
+          .map(|name| name_contains(Column::Name, name)),
+      )
+  }
+
+  fn sort_column(&self) -> Column {
+    match self.sort_by {
+      Some(SortBy::Name) => Column::Name,
+      Some(SortBy::CreatedAt) | None => Column::CreatedAt,
+    }
+  }
 }
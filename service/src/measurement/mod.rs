@@ -9,3 +9,9 @@ pub use find_uom_usecase::*;
 
 pub mod update_uom_usecase;
 pub use update_uom_usecase::*;
+
+pub mod uom_conversion;
+pub use uom_conversion::*;
+
+pub mod convert_uom_usecase;
+pub use convert_uom_usecase::*;
@@ -0,0 +1,223 @@
+use domain::measurement::uom;
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{prelude::Decimal, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum UomConversionError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("uom_not_found")]
+  UomNotFound(Uuid),
+
+  #[error("incompatible_uom_categories")]
+  IncompatibleCategories {
+    from: uom::UomCategory,
+    to: uom::UomCategory,
+  },
+}
+
+impl From<UomConversionError> for AppError {
+  fn from(err: UomConversionError) -> Self {
+    match err {
+      UomConversionError::Database(err) => AppError::from(err),
+      UomConversionError::UomNotFound(uom_id) => {
+        AppError::validation(err.to_string()).with_details(json!({ "uomId": uom_id }))
+      }
+      UomConversionError::IncompatibleCategories { from, to } => {
+        AppError::validation(err.to_string()).with_details(json!({ "from": from, "to": to }))
+      }
+    }
+  }
+}
+
+/// Converts `quantity` expressed in `from` into `to`, rounded to the
+/// precision of `to`. Both units must belong to the same category.
+pub fn convert(
+  quantity: Decimal,
+  from: &uom::Model,
+  to: &uom::Model,
+) -> Result<Decimal, UomConversionError> {
+  if from.category != to.category {
+    return Err(UomConversionError::IncompatibleCategories {
+      from: from.category,
+      to: to.category,
+    });
+  }
+  if from.id == to.id {
+    return Ok(quantity);
+  }
+
+  Ok(round_to(quantity * from.ratio / to.ratio, to.rounding))
+}
+
+/// Converts a unit price quoted per `from` into a price per `to`, e.g. a price
+/// per box into a price per piece. Prices are not rounded.
+pub fn convert_price(
+  price: Decimal,
+  from: &uom::Model,
+  to: &uom::Model,
+) -> Result<Decimal, UomConversionError> {
+  if from.category != to.category {
+    return Err(UomConversionError::IncompatibleCategories {
+      from: from.category,
+      to: to.category,
+    });
+  }
+
+  Ok((price * to.ratio / from.ratio).normalize())
+}
+
+/// Rounds half away from zero to a multiple of `rounding`.
+pub fn round_to(quantity: Decimal, rounding: Decimal) -> Decimal {
+  if rounding.is_zero() {
+    return quantity;
+  }
+
+  let half = Decimal::new(5, 1);
+  let steps = quantity / rounding;
+  let steps = if steps.is_sign_negative() {
+    (steps - half).ceil()
+  } else {
+    (steps + half).floor()
+  };
+
+  (steps * rounding).normalize()
+}
+
+/// Loads both units and converts `quantity` between them. Meant for usecases
+/// that receive unit ids, e.g. order lines priced in another unit.
+pub async fn convert_quantity<C>(
+  db: &C,
+  quantity: Decimal,
+  from_uom_id: Uuid,
+  to_uom_id: Uuid,
+) -> Result<Decimal, UomConversionError>
+where
+  C: ConnectionTrait,
+{
+  let (from, to) = find_uom_pair(db, from_uom_id, to_uom_id).await?;
+
+  convert(quantity, &from, &to)
+}
+
+/// Loads both units and converts a price per `from_uom_id` into a price per
+/// `to_uom_id`.
+pub async fn convert_unit_price<C>(
+  db: &C,
+  price: Decimal,
+  from_uom_id: Uuid,
+  to_uom_id: Uuid,
+) -> Result<Decimal, UomConversionError>
+where
+  C: ConnectionTrait,
+{
+  let (from, to) = find_uom_pair(db, from_uom_id, to_uom_id).await?;
+
+  convert_price(price, &from, &to)
+}
+
+async fn find_uom_pair<C>(
+  db: &C,
+  from_uom_id: Uuid,
+  to_uom_id: Uuid,
+) -> Result<(uom::Model, uom::Model), UomConversionError>
+where
+  C: ConnectionTrait,
+{
+  let uoms = uom::Entity::find()
+    .filter(uom::Column::Id.is_in([from_uom_id, to_uom_id]))
+    .all(db)
+    .await?;
+  let find = |uom_id: Uuid| {
+    uoms
+      .iter()
+      .find(|uom| uom.id == uom_id)
+      .cloned()
+      .ok_or(UomConversionError::UomNotFound(uom_id))
+  };
+
+  Ok((find(from_uom_id)?, find(to_uom_id)?))
+}
+
+#[cfg(test)]
+mod tests {
+  use domain::measurement::uom::UomCategory;
+
+  use super::*;
+  use crate::test_support::{dec, uom};
+
+  #[test]
+  fn round_to_rounds_half_away_from_zero() {
+    assert_eq!(round_to(dec("1.25"), dec("0.1")), dec("1.3"));
+    assert_eq!(round_to(dec("1.24"), dec("0.1")), dec("1.2"));
+    assert_eq!(round_to(dec("-1.25"), dec("0.1")), dec("-1.3"));
+    assert_eq!(round_to(dec("7"), dec("5")), dec("5"));
+    assert_eq!(round_to(dec("7.5"), dec("5")), dec("10"));
+  }
+
+  #[test]
+  fn round_to_leaves_quantity_alone_without_rounding() {
+    assert_eq!(round_to(dec("1.23456"), Decimal::ZERO), dec("1.23456"));
+  }
+
+  #[test]
+  fn convert_goes_through_the_reference_unit() {
+    let piece = uom(UomCategory::Unit, "1", "1");
+    let box_of_12 = uom(UomCategory::Unit, "12", "0.01");
+
+    assert_eq!(convert(dec("3"), &box_of_12, &piece).unwrap(), dec("36"));
+    assert_eq!(convert(dec("18"), &piece, &box_of_12).unwrap(), dec("1.5"));
+  }
+
+  #[test]
+  fn convert_rounds_to_target_unit() {
+    let piece = uom(UomCategory::Unit, "1", "1");
+    let box_of_12 = uom(UomCategory::Unit, "12", "0.01");
+
+    assert_eq!(convert(dec("1"), &piece, &box_of_12).unwrap(), dec("0.08"));
+    assert_eq!(convert(dec("0.1"), &box_of_12, &piece).unwrap(), dec("1"));
+  }
+
+  #[test]
+  fn convert_into_same_unit_is_not_rounded() {
+    let piece = uom(UomCategory::Unit, "1", "1");
+
+    assert_eq!(convert(dec("2.5"), &piece, &piece).unwrap(), dec("2.5"));
+  }
+
+  #[test]
+  fn convert_rejects_units_of_other_categories() {
+    let piece = uom(UomCategory::Unit, "1", "1");
+    let kilogram = uom(UomCategory::Weight, "1", "0.001");
+
+    assert!(matches!(
+      convert(dec("1"), &piece, &kilogram),
+      Err(UomConversionError::IncompatibleCategories {
+        from: UomCategory::Unit,
+        to: UomCategory::Weight,
+      })
+    ));
+    assert!(matches!(
+      convert_price(dec("1"), &kilogram, &piece),
+      Err(UomConversionError::IncompatibleCategories { .. })
+    ));
+  }
+
+  #[test]
+  fn convert_price_scales_inversely_and_is_not_rounded() {
+    let piece = uom(UomCategory::Unit, "1", "1");
+    let box_of_12 = uom(UomCategory::Unit, "12", "0.01");
+
+    assert_eq!(
+      convert_price(dec("9"), &box_of_12, &piece).unwrap(),
+      dec("0.75")
+    );
+    assert_eq!(
+      convert_price(dec("2"), &piece, &box_of_12).unwrap(),
+      dec("24")
+    );
+  }
+}
@@ -1,55 +1,185 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
+use axum::response::{IntoResponse, Response};
+use domain::{
+  inventory::stock_move,
+  measurement::uom::{self, ActiveModel as Uom, UomCategory},
+  product::{product, product_template},
+  purchase::{purchase_order_line, supplier_info},
+  sales::sales_order_line,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::Decimal, ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, Condition, ConnectionTrait,
+  DbErr, EntityTrait, PaginatorTrait, QueryFilter, Set,
 };
-use domain::measurement::uom::{self, ActiveModel as Uom};
-use infra::{util::error, uuid::Uuid};
-use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, Set};
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::unique_name::{name_conflict, same_name};
+
+/// Fields left out of the payload keep their current value. The category and
+/// ratio of a unit are fixed once anything records quantities in it.
 #[derive(Debug, Deserialize)]
 pub struct UpdateUomUsecase {
   pub id: Uuid,
   pub name: String,
+  #[serde(default)]
+  pub category: Option<UomCategory>,
+  #[serde(default)]
+  pub ratio: Option<Decimal>,
+  #[serde(default)]
+  pub rounding: Option<Decimal>,
+  #[serde(rename(deserialize = "isReference"), default)]
+  pub is_reference: Option<bool>,
 }
 
 pub type UpdateUomParams = UpdateUomUsecase;
 
+impl Validate for UpdateUomUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .field(
+        "ratio",
+        self.ratio.into_iter().flat_map(|ratio| {
+          [
+            rules::positive(ratio),
+            rules::reject_if(
+              self.is_reference == Some(true) && ratio != Decimal::ONE,
+              "must_be_one_for_reference_unit",
+            ),
+          ]
+        }),
+      )
+      .field("rounding", self.rounding.map(rules::positive))
+      .into_result()
+  }
+}
+
 #[derive(Error, Debug)]
 pub enum UpdateUomError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+
+  #[error("uom_locked_by_references")]
+  Locked(&'static str),
 }
 
 impl IntoResponse for UpdateUomError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      UpdateUomError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+    let error = match self {
+      UpdateUomError::Database(err) => AppError::from(err),
+      UpdateUomError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateUomError::NameConflict(existing_id) => name_conflict(self.to_string(), existing_id),
+      UpdateUomError::Locked(field) => {
+        AppError::conflict(self.to_string()).with_field(field, "locked")
       }
     };
 
-    (status, error(code, Some("create_uom".to_string()))).into_response()
+    error.with_source("update_uom").into_response()
   }
 }
 
 impl UpdateUomUsecase {
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait,
+    db: impl WriteConnection,
   ) -> Result<uom::PartialModel, UpdateUomError> {
+    let current = uom::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(UpdateUomError::RecordNotFound)?;
+
+    let existing = uom::Entity::find()
+      .filter(same_name(uom::Column::Name, &self.name))
+      .filter(uom::Column::Id.ne(self.id))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(UpdateUomError::NameConflict(existing.id));
+    }
+
+    // Stored quantities were converted with the current ratio and category.
+    let locked_field = if self
+      .category
+      .is_some_and(|category| category != current.category)
+    {
+      Some("category")
+    } else if self.ratio.is_some_and(|ratio| ratio != current.ratio) {
+      Some("ratio")
+    } else {
+      None
+    };
+    if let Some(field) = locked_field {
+      if is_referenced(&db, self.id).await? {
+        return Err(UpdateUomError::Locked(field));
+      }
+    }
+
     let uom = Uom {
       id: Set(self.id),
       name: Set(self.name.to_string()),
+      category: self.category.map_or(NotSet, Set),
+      ratio: self.ratio.map_or(NotSet, Set),
+      rounding: self.rounding.map_or(NotSet, Set),
+      is_reference: self.is_reference.map_or(NotSet, Set),
       ..Default::default()
     };
-    let updated_uom = uom.update(&db).await?;
-    let partial_uom = uom::PartialModel {
-      id: updated_uom.id,
-      name: updated_uom.name,
-    };
-
-    Ok(partial_uom)
+    let updated_uom = uom.update(&db).await.map_err(|err| match err {
+      DbErr::RecordNotUpdated => UpdateUomError::RecordNotFound,
+      err => UpdateUomError::Database(err),
+    })?;
+    Ok(updated_uom.into())
   }
 }
+
+/// Whether a template, variant price, stock move, order line or supplier
+/// price is expressed in the unit.
+async fn is_referenced<C>(db: &C, uom_id: Uuid) -> Result<bool, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let counts = [
+    product_template::Entity::find()
+      .filter(
+        Condition::any()
+          .add(product_template::Column::UomId.eq(uom_id))
+          .add(product_template::Column::PurchaseUomId.eq(uom_id))
+          .add(product_template::Column::SalesUomId.eq(uom_id)),
+      )
+      .count(db)
+      .await?,
+    product::Entity::find()
+      .filter(product::Column::PriceUomId.eq(uom_id))
+      .count(db)
+      .await?,
+    stock_move::Entity::find()
+      .filter(stock_move::Column::UomId.eq(uom_id))
+      .count(db)
+      .await?,
+    sales_order_line::Entity::find()
+      .filter(sales_order_line::Column::UomId.eq(uom_id))
+      .count(db)
+      .await?,
+    purchase_order_line::Entity::find()
+      .filter(purchase_order_line::Column::UomId.eq(uom_id))
+      .count(db)
+      .await?,
+    supplier_info::Entity::find()
+      .filter(supplier_info::Column::UomId.eq(uom_id))
+      .count(db)
+      .await?,
+  ];
+
+  Ok(counts.into_iter().any(|count| count > 0))
+}
@@ -0,0 +1,372 @@
+use std::collections::HashSet;
+
+use axum::response::{IntoResponse, Response};
+use domain::{
+  partner::{
+    partner::{self, PartnerType},
+    partner_address::{self, AddressType},
+    partner_contact,
+  },
+  pricelist::pricelist,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+  TransactionError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use super::tax_code::{normalize_tax_code, tax_code};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PartnerAddress {
+  pub id: Option<Uuid>,
+  #[serde(rename(deserialize = "addressType"))]
+  pub address_type: AddressType,
+  pub street: String,
+  #[serde(default)]
+  pub ward: String,
+  #[serde(default)]
+  pub district: String,
+  #[serde(default)]
+  pub city: String,
+  #[serde(rename(deserialize = "countryCode"), default = "default_country_code")]
+  pub country_code: String,
+  #[serde(rename(deserialize = "isDefault"), default)]
+  pub is_default: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PartnerContact {
+  pub id: Option<Uuid>,
+  pub name: String,
+  #[serde(rename(deserialize = "jobTitle"), default)]
+  pub job_title: String,
+  pub email: Option<String>,
+  pub phone: Option<String>,
+}
+
+fn default_country_code() -> String {
+  "VN".to_string()
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreatePartnerUsecase {
+  pub name: String,
+  #[serde(rename(deserialize = "partnerType"))]
+  pub partner_type: PartnerType,
+  #[serde(rename(deserialize = "taxCode"), default)]
+  pub tax_code: Option<String>,
+  #[serde(rename(deserialize = "isCustomer"), default)]
+  pub is_customer: bool,
+  #[serde(rename(deserialize = "isSupplier"), default)]
+  pub is_supplier: bool,
+  pub email: Option<String>,
+  pub phone: Option<String>,
+  #[serde(rename(deserialize = "paymentTermDays"), default)]
+  pub payment_term_days: i32,
+  #[serde(rename(deserialize = "pricelistId"), default)]
+  pub pricelist_id: Option<Uuid>,
+  #[serde(default)]
+  pub addresses: Vec<PartnerAddress>,
+  #[serde(default)]
+  pub contacts: Vec<PartnerContact>,
+}
+
+pub type CreatePartnerPayload = CreatePartnerUsecase;
+
+impl Validate for CreatePartnerUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_partner(
+      &self.name,
+      self.partner_type,
+      self.tax_code.as_deref(),
+      self.is_customer,
+      self.is_supplier,
+      self.email.as_deref(),
+      self.payment_term_days,
+      &self.addresses,
+      &self.contacts,
+    )
+    .into_result()
+  }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn validate_partner(
+  name: &str,
+  partner_type: PartnerType,
+  tax_code_value: Option<&str>,
+  is_customer: bool,
+  is_supplier: bool,
+  email: Option<&str>,
+  payment_term_days: i32,
+  addresses: &[PartnerAddress],
+  contacts: &[PartnerContact],
+) -> ValidationErrors {
+  let mut default_types = HashSet::new();
+  let duplicate_defaults = addresses
+    .iter()
+    .enumerate()
+    .filter(|(_, address)| address.is_default && !default_types.insert(address.address_type))
+    .map(|(index, _)| index)
+    .collect::<Vec<_>>();
+
+  let errors = ValidationErrors::new()
+    .field("name", [rules::required(name)])
+    .field(
+      "taxCode",
+      normalize_tax_code(tax_code_value).map(|value| tax_code(&value, partner_type)),
+    )
+    .field(
+      "isCustomer",
+      [rules::reject_if(
+        !is_customer && !is_supplier,
+        "customer_or_supplier_required",
+      )],
+    )
+    .field("email", email.map(email_rule))
+    .field(
+      "paymentTermDays",
+      [rules::reject_if(
+        payment_term_days < 0,
+        "must_not_be_negative",
+      )],
+    )
+    .each("addresses", addresses, |address| {
+      ValidationErrors::new()
+        .field("street", [rules::required(&address.street)])
+        .field(
+          "countryCode",
+          [rules::reject_if(
+            address.country_code.len() != 2
+              || !address
+                .country_code
+                .bytes()
+                .all(|byte| byte.is_ascii_alphabetic()),
+            "invalid",
+          )],
+        )
+    })
+    .each("contacts", contacts, |contact| {
+      ValidationErrors::new()
+        .field("name", [rules::required(&contact.name)])
+        .field("email", contact.email.as_deref().map(email_rule))
+    });
+
+  duplicate_defaults
+    .into_iter()
+    .fold(errors, |errors, index| {
+      errors.field(
+        format!("addresses[{}].isDefault", index),
+        [Err("duplicate")],
+      )
+    })
+}
+
+fn email_rule(email: &str) -> infra::validation::Rule {
+  let email = email.trim();
+  rules::reject_if(
+    !email.is_empty()
+      && email
+        .split_once('@')
+        .is_none_or(|(local, domain)| local.is_empty() || !domain.contains('.')),
+    "invalid_email",
+  )
+}
+
+/// Trimmed optional text, `None` when blank.
+pub(crate) fn optional_text(value: Option<&str>) -> Option<String> {
+  value
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(str::to_string)
+}
+
+#[derive(Error, Debug)]
+pub enum CreatePartnerError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("tax_code_already_exists")]
+  TaxCodeConflict(Uuid),
+
+  #[error("pricelist_not_found")]
+  PricelistNotFound,
+}
+
+impl From<TransactionError<DbErr>> for CreatePartnerError {
+  fn from(err: TransactionError<DbErr>) -> Self {
+    match err {
+      TransactionError::Connection(err) | TransactionError::Transaction(err) => {
+        CreatePartnerError::Database(err)
+      }
+    }
+  }
+}
+
+impl IntoResponse for CreatePartnerError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreatePartnerError::Database(err) => AppError::from(err),
+      CreatePartnerError::TaxCodeConflict(existing_id) => {
+        tax_code_conflict(self.to_string(), existing_id)
+      }
+      CreatePartnerError::PricelistNotFound => {
+        AppError::validation(self.to_string()).with_field("pricelistId", "not_found")
+      }
+    };
+
+    error.with_source("create_partner").into_response()
+  }
+}
+
+/// 409 raised when a tax code is already registered to `existing_id`.
+pub(crate) fn tax_code_conflict(code: String, existing_id: Uuid) -> AppError {
+  AppError::conflict(code)
+    .with_field("taxCode", "already_exists")
+    .with_details(json!({ "existingId": existing_id }))
+}
+
+/// Partner other than `except_id` already registered under `tax_code`.
+pub(crate) async fn find_by_tax_code<C>(
+  db: &C,
+  tax_code: Option<&str>,
+  except_id: Option<Uuid>,
+) -> Result<Option<partner::Model>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let Some(tax_code) = tax_code else {
+    return Ok(None);
+  };
+
+  partner::Entity::find()
+    .filter(partner::Column::TaxCode.eq(tax_code))
+    .filter(Condition::all().add_option(except_id.map(|id| partner::Column::Id.ne(id))))
+    .one(db)
+    .await
+}
+
+/// Whether `pricelist_id`, when given, names an active pricelist.
+pub(crate) async fn pricelist_exists<C>(db: &C, pricelist_id: Option<Uuid>) -> Result<bool, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let Some(pricelist_id) = pricelist_id else {
+    return Ok(true);
+  };
+  let pricelist = pricelist::Entity::find_by_id(pricelist_id)
+    .filter(pricelist::Column::ArchivedAt.is_null())
+    .one(db)
+    .await?;
+
+  Ok(pricelist.is_some())
+}
+
+pub(crate) fn address_model(
+  partner_id: Uuid,
+  address: PartnerAddress,
+) -> partner_address::ActiveModel {
+  partner_address::ActiveModel {
+    id: Set(address.id.unwrap_or_default()),
+    partner_id: Set(partner_id),
+    address_type: Set(address.address_type),
+    street: Set(address.street.trim().to_string()),
+    ward: Set(address.ward.trim().to_string()),
+    district: Set(address.district.trim().to_string()),
+    city: Set(address.city.trim().to_string()),
+    country_code: Set(address.country_code.trim().to_uppercase()),
+    is_default: Set(address.is_default),
+    ..Default::default()
+  }
+}
+
+pub(crate) fn contact_model(
+  partner_id: Uuid,
+  contact: PartnerContact,
+) -> partner_contact::ActiveModel {
+  partner_contact::ActiveModel {
+    id: Set(contact.id.unwrap_or_default()),
+    partner_id: Set(partner_id),
+    name: Set(contact.name.trim().to_string()),
+    job_title: Set(contact.job_title.trim().to_string()),
+    email: Set(optional_text(contact.email.as_deref())),
+    phone: Set(optional_text(contact.phone.as_deref())),
+    ..Default::default()
+  }
+}
+
+impl CreatePartnerUsecase {
+  /// Creates the partner with its addresses and contact persons.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<partner::Model, CreatePartnerError> {
+    let tax_code = normalize_tax_code(self.tax_code.as_deref());
+    if let Some(existing) = find_by_tax_code(&db, tax_code.as_deref(), None).await? {
+      return Err(CreatePartnerError::TaxCodeConflict(existing.id));
+    }
+    if !pricelist_exists(&db, self.pricelist_id).await? {
+      return Err(CreatePartnerError::PricelistNotFound);
+    }
+
+    let payload = self.clone();
+
+    let partner = db
+      .transaction::<_, partner::Model, DbErr>(move |txn| {
+        Box::pin(async move {
+          let partner = partner::ActiveModel {
+            name: Set(payload.name.trim().to_string()),
+            partner_type: Set(payload.partner_type),
+            tax_code: Set(tax_code),
+            is_customer: Set(payload.is_customer),
+            is_supplier: Set(payload.is_supplier),
+            email: Set(optional_text(payload.email.as_deref())),
+            phone: Set(optional_text(payload.phone.as_deref())),
+            payment_term_days: Set(payload.payment_term_days),
+            pricelist_id: Set(payload.pricelist_id),
+            ..Default::default()
+          };
+          let partner = partner.insert(txn).await?;
+
+          partner_address::Entity::insert_many(payload.addresses.into_iter().map(|address| {
+            address_model(
+              partner.id,
+              PartnerAddress {
+                id: None,
+                ..address
+              },
+            )
+          }))
+          .on_empty_do_nothing()
+          .exec(txn)
+          .await?;
+          partner_contact::Entity::insert_many(payload.contacts.into_iter().map(|contact| {
+            contact_model(
+              partner.id,
+              PartnerContact {
+                id: None,
+                ..contact
+              },
+            )
+          }))
+          .on_empty_do_nothing()
+          .exec(txn)
+          .await?;
+
+          Ok(partner)
+        })
+      })
+      .await?;
+
+    Ok(partner)
+  }
+}
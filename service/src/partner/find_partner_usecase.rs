@@ -0,0 +1,78 @@
+use axum::response::{IntoResponse, Response};
+use domain::partner::{
+  partner::{self, PartnerDTO},
+  partner_address, partner_contact,
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct FindPartnerUsecase {
+  pub id: Uuid,
+}
+
+pub type FindPartnerParams = FindPartnerUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindPartnerError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindPartnerError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindPartnerError::Database(err) => AppError::from(err),
+      FindPartnerError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_partner").into_response()
+  }
+}
+
+impl FindPartnerUsecase {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<PartnerDTO, FindPartnerError> {
+    let partner = partner::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindPartnerError::RecordNotFound)?;
+
+    let addresses = partner_address::Entity::find()
+      .filter(partner_address::Column::PartnerId.eq(partner.id))
+      .order_by_asc(partner_address::Column::AddressType)
+      .order_by_desc(partner_address::Column::IsDefault)
+      .order_by_asc(partner_address::Column::CreatedAt)
+      .into_partial_model::<partner_address::PartialModel>()
+      .all(&db)
+      .await?;
+    let contacts = partner_contact::Entity::find()
+      .filter(partner_contact::Column::PartnerId.eq(partner.id))
+      .order_by_asc(partner_contact::Column::Name)
+      .into_partial_model::<partner_contact::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(PartnerDTO {
+      id: partner.id,
+      name: partner.name,
+      partner_type: partner.partner_type,
+      tax_code: partner.tax_code,
+      is_customer: partner.is_customer,
+      is_supplier: partner.is_supplier,
+      email: partner.email,
+      phone: partner.phone,
+      payment_term_days: partner.payment_term_days,
+      pricelist_id: partner.pricelist_id,
+      addresses,
+      contacts,
+      created_at: partner.created_at,
+      updated_at: partner.updated_at,
+      archived_at: partner.archived_at,
+    })
+  }
+}
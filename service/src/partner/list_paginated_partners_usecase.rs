@@ -0,0 +1,202 @@
+use axum::response::{IntoResponse, Response};
+use domain::partner::partner::{self, Column, Entity as Partner, PartnerType};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  response::{CursorPaginationMeta, PaginationMeta},
+};
+use sea_orm::{
+  sea_query::{Expr, Query},
+  ColumnTrait, Condition, DbErr, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+  QuerySelect,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  archive::archived_condition,
+  list_query::{
+    cursor_page, name_contains, page_index, page_size, Cursor, CursorError, PageError,
+    PaginationMode, SortBy, SortOrder,
+  },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ListPaginatedPartnersUsecase {
+  pub page: Option<u64>,
+  pub per_page: Option<u64>,
+  pub include_archived: Option<bool>,
+  pub only_archived: Option<bool>,
+  /// Matches name, tax code, email or phone.
+  pub q: Option<String>,
+  pub partner_type: Option<PartnerType>,
+  pub is_customer: Option<bool>,
+  pub is_supplier: Option<bool>,
+  pub sort_by: Option<SortBy>,
+  pub order: Option<SortOrder>,
+  pub pagination: Option<PaginationMode>,
+  pub cursor: Option<Cursor>,
+  pub with_total: Option<bool>,
+}
+
+pub type ListPaginatedPartnersParams = ListPaginatedPartnersUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListPaginatedPartnersError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Page(#[from] PageError),
+
+  #[error(transparent)]
+  Cursor(#[from] CursorError),
+}
+
+impl IntoResponse for ListPaginatedPartnersError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListPaginatedPartnersError::Database(err) => AppError::from(err),
+      ListPaginatedPartnersError::Page(err) => AppError::from(err),
+      ListPaginatedPartnersError::Cursor(err) => AppError::from(err),
+    };
+
+    error.with_source("list_paginated_partners").into_response()
+  }
+}
+
+impl ListPaginatedPartnersUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<partner::PartialModel>, PaginationMeta), ListPaginatedPartnersError> {
+    let per_page = page_size(self.per_page)?;
+    let page = page_index(self.page)?;
+    let order = Order::from(self.order.unwrap_or_default());
+
+    let partner_pages = Partner::find()
+      .filter(self.filter_condition())
+      .order_by(self.sort_column(), order)
+      .order_by_asc(Column::Id)
+      .into_partial_model::<partner::PartialModel>()
+      .paginate(&db, per_page);
+    let partners = partner_pages.fetch_page(page).await?;
+    let items_and_pages = partner_pages.num_items_and_pages().await?;
+    let total = items_and_pages.number_of_items;
+    let total_pages = items_and_pages.number_of_pages;
+
+    Ok((
+      partners,
+      PaginationMeta {
+        total,
+        total_pages,
+        page: page + 1,
+        per_page,
+      },
+    ))
+  }
+
+  /// Keyset variant of `invoke`: reads `per_page` rows after (or before) the
+  /// cursor and only counts the whole result set when `with_total` is set.
+  pub async fn invoke_cursor(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<partner::PartialModel>, CursorPaginationMeta), ListPaginatedPartnersError> {
+    let per_page = page_size(self.per_page)?;
+    let cursor = self.cursor.as_ref();
+    let query_order = Cursor::query_order(cursor, self.order.unwrap_or_default().into());
+    let sort_column = self.sort_column();
+
+    let keyset_condition = match cursor {
+      Some(cursor) => Some(
+        cursor
+          .keyset_condition(
+            &db,
+            vec![
+              Expr::col((Partner, sort_column)).into(),
+              Expr::col((Partner, Column::Id)).into(),
+            ],
+            Query::select()
+              .column(sort_column)
+              .column(Column::Id)
+              .from(Partner)
+              .and_where(Column::Id.eq(cursor.id))
+              .to_owned(),
+            query_order.clone(),
+          )
+          .await?,
+      ),
+      None => None,
+    };
+
+    let rows = Partner::find()
+      .filter(self.filter_condition())
+      .filter(Condition::all().add_option(keyset_condition))
+      .order_by(sort_column, query_order.clone())
+      .order_by(Column::Id, query_order)
+      .limit(per_page + 1)
+      .into_partial_model::<partner::PartialModel>()
+      .all(&db)
+      .await?;
+    let (partners, next_cursor, prev_cursor) =
+      cursor_page(rows, per_page, cursor, |partner| partner.id);
+
+    let total = match self.with_total {
+      Some(true) => Some(
+        Partner::find()
+          .filter(self.filter_condition())
+          .count(&db)
+          .await?,
+      ),
+      _ => None,
+    };
+
+    Ok((
+      partners,
+      CursorPaginationMeta {
+        per_page,
+        next_cursor,
+        prev_cursor,
+        total,
+      },
+    ))
+  }
+
+  fn filter_condition(&self) -> Condition {
+    Condition::all()
+      .add(archived_condition(
+        Column::ArchivedAt,
+        self.include_archived,
+        self.only_archived,
+      ))
+      .add_option(self.q.as_deref().filter(|q| !q.trim().is_empty()).map(|q| {
+        Condition::any()
+          .add(name_contains(Column::Name, q))
+          .add(name_contains(Column::TaxCode, q))
+          .add(name_contains(Column::Email, q))
+          .add(name_contains(Column::Phone, q))
+      }))
+      .add_option(
+        self
+          .partner_type
+          .map(|partner_type| Column::PartnerType.eq(partner_type)),
+      )
+      .add_option(
+        self
+          .is_customer
+          .map(|is_customer| Column::IsCustomer.eq(is_customer)),
+      )
+      .add_option(
+        self
+          .is_supplier
+          .map(|is_supplier| Column::IsSupplier.eq(is_supplier)),
+      )
+  }
+
+  fn sort_column(&self) -> Column {
+    match self.sort_by {
+      Some(SortBy::Name) => Column::Name,
+      Some(SortBy::CreatedAt) | None => Column::CreatedAt,
+    }
+  }
+}
@@ -0,0 +1,13 @@
+pub mod tax_code;
+
+pub mod list_paginated_partners_usecase;
+pub use list_paginated_partners_usecase::*;
+
+pub mod create_partner_usecase;
+pub use create_partner_usecase::*;
+
+pub mod find_partner_usecase;
+pub use find_partner_usecase::*;
+
+pub mod update_partner_usecase;
+pub use update_partner_usecase::*;
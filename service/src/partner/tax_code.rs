@@ -0,0 +1,127 @@
+use domain::partner::partner::PartnerType;
+use infra::validation::Rule;
+
+/// Weights of the first nine digits in the MST check digit.
+const WEIGHTS: [u32; 9] = [31, 29, 23, 19, 17, 13, 7, 5, 3];
+
+/// Tax code as stored: surrounding and inner whitespace removed, `None` when
+/// nothing is left.
+pub fn normalize_tax_code(value: Option<&str>) -> Option<String> {
+  value
+    .map(|value| value.split_whitespace().collect::<String>())
+    .filter(|value| !value.is_empty())
+}
+
+/// Vietnamese tax code (MST): ten digits whose tenth is a check digit over the
+/// first nine, optionally followed by `-` and a three-digit branch number.
+/// Individuals may also use their twelve-digit citizen ID, which carries no
+/// check digit.
+pub fn tax_code(value: &str, partner_type: PartnerType) -> Rule {
+  let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+
+  if partner_type == PartnerType::Individual && value.len() == 12 && is_digits(value) {
+    return Ok(());
+  }
+
+  let (head, branch) = match value.split_once('-') {
+    Some((head, branch)) => (head, Some(branch)),
+    None => (value, None),
+  };
+  if head.len() != 10 || !is_digits(head) {
+    return Err("invalid_format");
+  }
+  if let Some(branch) = branch {
+    if branch.len() != 3 || !is_digits(branch) || branch == "000" {
+      return Err("invalid_format");
+    }
+  }
+
+  let digits = head
+    .bytes()
+    .map(|byte| u32::from(byte - b'0'))
+    .collect::<Vec<_>>();
+  let sum = WEIGHTS
+    .iter()
+    .zip(&digits)
+    .map(|(weight, digit)| weight * digit)
+    .sum::<u32>();
+  let check = 10 - sum % 11;
+  if check == digits[9] {
+    Ok(())
+  } else {
+    Err("invalid_checksum")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn accepts_tax_code_with_valid_check_digit() {
+    assert_eq!(tax_code("0100109106", PartnerType::Company), Ok(()));
+    assert_eq!(tax_code("0301234562", PartnerType::Company), Ok(()));
+  }
+
+  #[test]
+  fn accepts_branch_suffix() {
+    assert_eq!(tax_code("0100109106-001", PartnerType::Company), Ok(()));
+  }
+
+  #[test]
+  fn rejects_wrong_check_digit() {
+    assert_eq!(
+      tax_code("0100109107", PartnerType::Company),
+      Err("invalid_checksum")
+    );
+  }
+
+  #[test]
+  fn rejects_head_whose_check_digit_would_be_ten() {
+    for check in 0..10 {
+      assert_eq!(
+        tax_code(&format!("000000000{check}"), PartnerType::Company),
+        Err("invalid_checksum")
+      );
+    }
+  }
+
+  #[test]
+  fn rejects_malformed_tax_codes() {
+    for value in [
+      "",
+      "010010910",
+      "01001091060",
+      "01001091a6",
+      "0100109106-",
+      "0100109106-01",
+      "0100109106-000",
+      "0100109106-0a1",
+    ] {
+      assert_eq!(
+        tax_code(value, PartnerType::Company),
+        Err("invalid_format"),
+        "{value}"
+      );
+    }
+  }
+
+  #[test]
+  fn accepts_citizen_id_only_for_individuals() {
+    assert_eq!(tax_code("001099012345", PartnerType::Individual), Ok(()));
+    assert_eq!(
+      tax_code("001099012345", PartnerType::Company),
+      Err("invalid_format")
+    );
+  }
+
+  #[test]
+  fn normalize_strips_whitespace_and_drops_blank_values() {
+    assert_eq!(
+      normalize_tax_code(Some(" 0100 109 106 ")),
+      Some("0100109106".to_string())
+    );
+    assert_eq!(normalize_tax_code(Some("   ")), None);
+    assert_eq!(normalize_tax_code(None), None);
+  }
+}
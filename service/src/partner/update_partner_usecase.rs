@@ -0,0 +1,294 @@
+use axum::response::{IntoResponse, Response};
+use domain::partner::{
+  partner::{self, PartnerType},
+  partner_address, partner_contact,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::Expr, sea_query::OnConflict, ActiveModelTrait, ColumnTrait, DbErr, EntityTrait,
+  QueryFilter, QuerySelect, Set, TransactionError,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+  create_partner_usecase::{
+    address_model, contact_model, find_by_tax_code, optional_text, pricelist_exists,
+    tax_code_conflict, validate_partner, PartnerAddress, PartnerContact,
+  },
+  tax_code::normalize_tax_code,
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdatePartnerUsecase {
+  pub id: Uuid,
+  pub name: String,
+  #[serde(rename(deserialize = "partnerType"))]
+  pub partner_type: PartnerType,
+  #[serde(rename(deserialize = "taxCode"), default)]
+  pub tax_code: Option<String>,
+  #[serde(rename(deserialize = "isCustomer"), default)]
+  pub is_customer: bool,
+  #[serde(rename(deserialize = "isSupplier"), default)]
+  pub is_supplier: bool,
+  pub email: Option<String>,
+  pub phone: Option<String>,
+  #[serde(rename(deserialize = "paymentTermDays"), default)]
+  pub payment_term_days: i32,
+  #[serde(rename(deserialize = "pricelistId"), default)]
+  pub pricelist_id: Option<Uuid>,
+  #[serde(default)]
+  pub addresses: Vec<PartnerAddress>,
+  #[serde(default)]
+  pub contacts: Vec<PartnerContact>,
+}
+
+pub type UpdatePartnerPayload = UpdatePartnerUsecase;
+
+impl Validate for UpdatePartnerUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_partner(
+      &self.name,
+      self.partner_type,
+      self.tax_code.as_deref(),
+      self.is_customer,
+      self.is_supplier,
+      self.email.as_deref(),
+      self.payment_term_days,
+      &self.addresses,
+      &self.contacts,
+    )
+    .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdatePartnerError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("tax_code_already_exists")]
+  TaxCodeConflict(Uuid),
+
+  #[error("pricelist_not_found")]
+  PricelistNotFound,
+
+  #[error("belongs_to_other_partner")]
+  ForeignItems(Vec<String>),
+}
+
+impl From<TransactionError<UpdatePartnerError>> for UpdatePartnerError {
+  fn from(err: TransactionError<UpdatePartnerError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdatePartnerError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for UpdatePartnerError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdatePartnerError::Database(err) => AppError::from(err),
+      UpdatePartnerError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdatePartnerError::TaxCodeConflict(existing_id) => {
+        tax_code_conflict(self.to_string(), existing_id)
+      }
+      UpdatePartnerError::PricelistNotFound => {
+        AppError::validation(self.to_string()).with_field("pricelistId", "not_found")
+      }
+      UpdatePartnerError::ForeignItems(ref fields) => fields
+        .iter()
+        .fold(AppError::validation(self.to_string()), |error, field| {
+          error.with_field(field.clone(), "belongs_to_other_partner")
+        }),
+    };
+
+    error.with_source("update_partner").into_response()
+  }
+}
+
+impl UpdatePartnerUsecase {
+  /// Updates the partner and syncs its addresses and contacts: items with an
+  /// `id` are updated, items without one are created, and items missing from
+  /// the payload are deleted.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<partner::Model, UpdatePartnerError> {
+    let tax_code = normalize_tax_code(self.tax_code.as_deref());
+    if let Some(existing) = find_by_tax_code(&db, tax_code.as_deref(), Some(self.id)).await? {
+      return Err(UpdatePartnerError::TaxCodeConflict(existing.id));
+    }
+    if !pricelist_exists(&db, self.pricelist_id).await? {
+      return Err(UpdatePartnerError::PricelistNotFound);
+    }
+
+    let payload = self.clone();
+
+    let partner = db
+      .transaction::<_, partner::Model, UpdatePartnerError>(move |txn| {
+        Box::pin(async move {
+          let id = payload.id;
+          partner::Entity::find_by_id(id)
+            .one(txn)
+            .await?
+            .ok_or(UpdatePartnerError::RecordNotFound)?;
+
+          let address_ids = payload
+            .addresses
+            .iter()
+            .filter_map(|address| address.id)
+            .collect::<Vec<_>>();
+          let contact_ids = payload
+            .contacts
+            .iter()
+            .filter_map(|contact| contact.id)
+            .collect::<Vec<_>>();
+
+          let foreign_address_ids = partner_address::Entity::find()
+            .select_only()
+            .column(partner_address::Column::Id)
+            .filter(partner_address::Column::Id.is_in(address_ids.clone()))
+            .filter(partner_address::Column::PartnerId.ne(id))
+            .into_tuple::<Uuid>()
+            .all(txn)
+            .await?;
+          let foreign_contact_ids = partner_contact::Entity::find()
+            .select_only()
+            .column(partner_contact::Column::Id)
+            .filter(partner_contact::Column::Id.is_in(contact_ids.clone()))
+            .filter(partner_contact::Column::PartnerId.ne(id))
+            .into_tuple::<Uuid>()
+            .all(txn)
+            .await?;
+          let foreign_fields = foreign_indexes(
+            "addresses",
+            payload.addresses.iter().map(|address| address.id),
+            &foreign_address_ids,
+          )
+          .chain(foreign_indexes(
+            "contacts",
+            payload.contacts.iter().map(|contact| contact.id),
+            &foreign_contact_ids,
+          ))
+          .collect::<Vec<_>>();
+          if !foreign_fields.is_empty() {
+            return Err(UpdatePartnerError::ForeignItems(foreign_fields));
+          }
+
+          let partner = partner::ActiveModel {
+            id: Set(id),
+            name: Set(payload.name.trim().to_string()),
+            partner_type: Set(payload.partner_type),
+            tax_code: Set(tax_code),
+            is_customer: Set(payload.is_customer),
+            is_supplier: Set(payload.is_supplier),
+            email: Set(optional_text(payload.email.as_deref())),
+            phone: Set(optional_text(payload.phone.as_deref())),
+            payment_term_days: Set(payload.payment_term_days),
+            pricelist_id: Set(payload.pricelist_id),
+            ..Default::default()
+          };
+          let partner = partner.update(txn).await?;
+
+          partner_address::Entity::delete_many()
+            .filter(partner_address::Column::PartnerId.eq(id))
+            .filter(partner_address::Column::Id.is_not_in(address_ids))
+            .exec(txn)
+            .await?;
+          partner_contact::Entity::delete_many()
+            .filter(partner_contact::Column::PartnerId.eq(id))
+            .filter(partner_contact::Column::Id.is_not_in(contact_ids))
+            .exec(txn)
+            .await?;
+
+          // Cleared first so moving the default between two addresses does
+          // not trip the one-default-per-type index halfway through.
+          partner_address::Entity::update_many()
+            .col_expr(partner_address::Column::IsDefault, Expr::value(false))
+            .filter(partner_address::Column::PartnerId.eq(id))
+            .exec(txn)
+            .await?;
+
+          partner_address::Entity::insert_many(
+            payload
+              .addresses
+              .into_iter()
+              .map(|address| address_model(id, address)),
+          )
+          .on_empty_do_nothing()
+          .on_conflict(
+            OnConflict::column(partner_address::Column::Id)
+              .update_columns([
+                partner_address::Column::AddressType,
+                partner_address::Column::Street,
+                partner_address::Column::Ward,
+                partner_address::Column::District,
+                partner_address::Column::City,
+                partner_address::Column::CountryCode,
+                partner_address::Column::IsDefault,
+              ])
+              .value(
+                partner_address::Column::UpdatedAt,
+                Expr::current_timestamp(),
+              )
+              .to_owned(),
+          )
+          .exec(txn)
+          .await?;
+          partner_contact::Entity::insert_many(
+            payload
+              .contacts
+              .into_iter()
+              .map(|contact| contact_model(id, contact)),
+          )
+          .on_empty_do_nothing()
+          .on_conflict(
+            OnConflict::column(partner_contact::Column::Id)
+              .update_columns([
+                partner_contact::Column::Name,
+                partner_contact::Column::JobTitle,
+                partner_contact::Column::Email,
+                partner_contact::Column::Phone,
+              ])
+              .value(
+                partner_contact::Column::UpdatedAt,
+                Expr::current_timestamp(),
+              )
+              .to_owned(),
+          )
+          .exec(txn)
+          .await?;
+
+          Ok(partner)
+        })
+      })
+      .await?;
+
+    Ok(partner)
+  }
+}
+
+/// `field[index].id` paths of the items whose id is in `foreign_ids`.
+fn foreign_indexes<'a, I>(
+  field: &'a str,
+  ids: I,
+  foreign_ids: &'a [Uuid],
+) -> impl Iterator<Item = String> + 'a
+where
+  I: Iterator<Item = Option<Uuid>> + 'a,
+{
+  ids
+    .enumerate()
+    .filter(|(_, id)| id.is_some_and(|id| foreign_ids.contains(&id)))
+    .map(move |(index, _)| format!("{}[{}].id", field, index))
+}
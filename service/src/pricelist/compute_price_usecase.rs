@@ -0,0 +1,153 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::{
+  partner::partner,
+  pricelist::pricelist::{self, ComputedPriceDTO},
+  product::{product, product_template},
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, DbErr, EntityTrait, QueryFilter,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::price_resolution::resolve_price;
+use crate::{
+  currency::currency_conversion::{DocumentCurrency, PriceConversionError},
+  measurement::convert_quantity,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ComputePriceUsecase {
+  /// Defaults to the pricelist of `partner_id`.
+  pub pricelist_id: Option<Uuid>,
+  pub partner_id: Option<Uuid>,
+  pub product_id: Uuid,
+  /// Quantity in `uom_id`, one unit by default.
+  pub quantity: Option<Decimal>,
+  /// Defaults to the template's sales unit.
+  pub uom_id: Option<Uuid>,
+  /// Date the price applies on, now by default.
+  pub date: Option<DateTimeWithTimeZone>,
+  /// Currency to quote the price in. Defaults to the pricelist's currency, or
+  /// the base currency without a pricelist.
+  pub currency_id: Option<Uuid>,
+}
+
+pub type ComputePriceParams = ComputePriceUsecase;
+
+#[derive(Error, Debug)]
+pub enum ComputePriceError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Conversion(#[from] PriceConversionError),
+
+  #[error("quantity_must_be_positive")]
+  InvalidQuantity,
+
+  #[error("product_not_found")]
+  ProductNotFound,
+
+  #[error("partner_not_found")]
+  PartnerNotFound,
+
+  #[error("pricelist_not_found")]
+  PricelistNotFound,
+}
+
+impl IntoResponse for ComputePriceError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ComputePriceError::Database(err) => AppError::from(err),
+      ComputePriceError::Conversion(err) => AppError::from(err),
+      ComputePriceError::InvalidQuantity => {
+        AppError::validation(self.to_string()).with_field("quantity", "must_be_positive")
+      }
+      ComputePriceError::ProductNotFound => AppError::not_found(self.to_string()),
+      ComputePriceError::PartnerNotFound => AppError::not_found(self.to_string()),
+      ComputePriceError::PricelistNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("compute_price").into_response()
+  }
+}
+
+impl ComputePriceUsecase {
+  /// Resolves the unit price of the product for the quantity, returning the
+  /// pricelist rule it came from.
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<ComputedPriceDTO, ComputePriceError> {
+    let quantity = self.quantity.unwrap_or(Decimal::ONE);
+    if quantity <= Decimal::ZERO {
+      return Err(ComputePriceError::InvalidQuantity);
+    }
+
+    let (product, template) = product::Entity::find_by_id(self.product_id)
+      .find_also_related(product_template::Entity)
+      .one(&db)
+      .await?
+      .ok_or(ComputePriceError::ProductNotFound)?;
+    let template = template.ok_or(ComputePriceError::ProductNotFound)?;
+
+    let pricelist_id = match (self.pricelist_id, self.partner_id) {
+      (Some(pricelist_id), _) => Some(pricelist_id),
+      (None, Some(partner_id)) => {
+        partner::Entity::find_by_id(partner_id)
+          .one(&db)
+          .await?
+          .ok_or(ComputePriceError::PartnerNotFound)?
+          .pricelist_id
+      }
+      (None, None) => None,
+    };
+    let pricelist = match pricelist_id {
+      Some(pricelist_id) => Some(
+        pricelist::Entity::find_by_id(pricelist_id)
+          .filter(pricelist::Column::ArchivedAt.is_null())
+          .one(&db)
+          .await?
+          .ok_or(ComputePriceError::PricelistNotFound)?,
+      ),
+      None => None,
+    };
+    let currency = DocumentCurrency::load(
+      &db,
+      self
+        .currency_id
+        .or(pricelist.as_ref().map(|pricelist| pricelist.currency_id)),
+      self.date.unwrap_or_else(|| Utc::now().into()),
+    )
+    .await
+    .map_err(PriceConversionError::from)?;
+
+    let uom_id = self.uom_id.unwrap_or(template.sales_uom_id);
+    let product_quantity = convert_quantity(&db, quantity, uom_id, template.uom_id)
+      .await
+      .map_err(PriceConversionError::from)?;
+    let price = resolve_price(
+      &db,
+      pricelist.as_ref(),
+      &product,
+      &template,
+      product_quantity,
+      uom_id,
+      &currency,
+    )
+    .await?;
+
+    Ok(ComputedPriceDTO {
+      product_id: product.id,
+      quantity,
+      uom_id,
+      unit_price: price.unit_price,
+      currency_id: currency.currency.id,
+      rule: price.rule.map(Into::into),
+    })
+  }
+}
@@ -0,0 +1,252 @@
+use axum::response::{IntoResponse, Response};
+use domain::pricelist::{
+  pricelist,
+  pricelist_rule::{self, PricelistComputeType, PricelistRuleScope},
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Rule, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ActiveModelTrait, DbErr, EntityTrait, QueryFilter, Set, TransactionError,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  currency::currency_conversion::{currency_or_base, CurrencyConversionError},
+  unique_name::{name_conflict, same_name},
+};
+
+/// Rule of a pricelist. Rules are sequenced in the order they are sent.
+#[derive(Debug, Deserialize, Clone)]
+pub struct PricelistRule {
+  pub scope: PricelistRuleScope,
+  #[serde(rename(deserialize = "productId"), default)]
+  pub product_id: Option<Uuid>,
+  #[serde(rename(deserialize = "productTemplateId"), default)]
+  pub product_template_id: Option<Uuid>,
+  #[serde(rename(deserialize = "categoryId"), default)]
+  pub category_id: Option<Uuid>,
+  /// Stock-unit quantity from which the rule applies.
+  #[serde(rename(deserialize = "minQuantity"), default)]
+  pub min_quantity: Decimal,
+  #[serde(rename(deserialize = "dateStart"), default)]
+  pub date_start: Option<DateTimeWithTimeZone>,
+  #[serde(rename(deserialize = "dateEnd"), default)]
+  pub date_end: Option<DateTimeWithTimeZone>,
+  #[serde(rename(deserialize = "computeType"))]
+  pub compute_type: PricelistComputeType,
+  #[serde(rename(deserialize = "fixedPrice"), default)]
+  pub fixed_price: Option<Decimal>,
+  #[serde(default)]
+  pub discount: Option<Decimal>,
+  #[serde(default)]
+  pub margin: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreatePricelistUsecase {
+  pub name: String,
+  /// Defaults to the base currency.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  #[serde(default)]
+  pub rules: Vec<PricelistRule>,
+}
+
+pub type CreatePricelistPayload = CreatePricelistUsecase;
+
+impl Validate for CreatePricelistUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_pricelist(&self.name, &self.rules).into_result()
+  }
+}
+
+pub(crate) fn validate_pricelist(
+  name: &str,
+  pricelist_rules: &[PricelistRule],
+) -> ValidationErrors {
+  ValidationErrors::new()
+    .field("name", [rules::required(name)])
+    .each("rules", pricelist_rules, |rule| {
+      let is_fixed = rule.compute_type == PricelistComputeType::Fixed;
+      let is_discount = rule.compute_type == PricelistComputeType::Discount;
+      let is_cost_plus_margin = rule.compute_type == PricelistComputeType::CostPlusMargin;
+
+      ValidationErrors::new()
+        .field(
+          "productId",
+          [expected_when(
+            rule.scope == PricelistRuleScope::Variant,
+            rule.product_id.is_some(),
+          )],
+        )
+        .field(
+          "productTemplateId",
+          [expected_when(
+            rule.scope == PricelistRuleScope::Template,
+            rule.product_template_id.is_some(),
+          )],
+        )
+        .field(
+          "categoryId",
+          [expected_when(
+            rule.scope == PricelistRuleScope::Category,
+            rule.category_id.is_some(),
+          )],
+        )
+        .field("minQuantity", [rules::non_negative(rule.min_quantity)])
+        .field(
+          "dateEnd",
+          [rules::reject_if(
+            rule
+              .date_start
+              .zip(rule.date_end)
+              .is_some_and(|(start, end)| end <= start),
+            "must_be_after_date_start",
+          )],
+        )
+        .field(
+          "fixedPrice",
+          [expected_when(is_fixed, rule.fixed_price.is_some())],
+        )
+        .field("fixedPrice", rule.fixed_price.map(rules::non_negative))
+        .field(
+          "discount",
+          [expected_when(is_discount, rule.discount.is_some())],
+        )
+        .field(
+          "discount",
+          rule.discount.map(|discount| {
+            rules::reject_if(discount > Decimal::ONE_HUNDRED, "must_not_exceed_100")
+          }),
+        )
+        .field(
+          "margin",
+          [expected_when(is_cost_plus_margin, rule.margin.is_some())],
+        )
+        .field(
+          "margin",
+          rule.margin.map(|margin| {
+            rules::reject_if(margin <= -Decimal::ONE_HUNDRED, "must_exceed_minus_100")
+          }),
+        )
+    })
+}
+
+/// A field that must be set exactly when `expected` holds.
+fn expected_when(expected: bool, is_set: bool) -> Rule {
+  match (expected, is_set) {
+    (true, false) => Err("required"),
+    (false, true) => Err("not_allowed"),
+    _ => Ok(()),
+  }
+}
+
+/// Rows of `pricelist_id` for the payload rules.
+pub(crate) fn rule_models(
+  pricelist_id: Uuid,
+  rules: Vec<PricelistRule>,
+) -> impl Iterator<Item = pricelist_rule::ActiveModel> {
+  rules
+    .into_iter()
+    .enumerate()
+    .map(move |(index, rule)| pricelist_rule::ActiveModel {
+      pricelist_id: Set(pricelist_id),
+      sequence: Set(index as i32),
+      scope: Set(rule.scope),
+      product_id: Set(rule.product_id),
+      product_template_id: Set(rule.product_template_id),
+      category_id: Set(rule.category_id),
+      min_quantity: Set(rule.min_quantity),
+      date_start: Set(rule.date_start),
+      date_end: Set(rule.date_end),
+      compute_type: Set(rule.compute_type),
+      fixed_price: Set(rule.fixed_price),
+      discount: Set(rule.discount),
+      margin: Set(rule.margin),
+      ..Default::default()
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum CreatePricelistError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl From<TransactionError<DbErr>> for CreatePricelistError {
+  fn from(err: TransactionError<DbErr>) -> Self {
+    match err {
+      TransactionError::Connection(err) | TransactionError::Transaction(err) => {
+        CreatePricelistError::Database(err)
+      }
+    }
+  }
+}
+
+impl IntoResponse for CreatePricelistError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreatePricelistError::Database(err) => AppError::from(err),
+      CreatePricelistError::NameConflict(existing_id) => {
+        name_conflict(self.to_string(), existing_id)
+      }
+      CreatePricelistError::Currency(err) => AppError::from(err),
+    };
+
+    error.with_source("create_pricelist").into_response()
+  }
+}
+
+impl CreatePricelistUsecase {
+  /// Creates the pricelist with its rules. Rule targets that do not exist are
+  /// rejected by their foreign keys.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<pricelist::Model, CreatePricelistError> {
+    let existing = pricelist::Entity::find()
+      .filter(same_name(pricelist::Column::Name, &self.name))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(CreatePricelistError::NameConflict(existing.id));
+    }
+    let currency_id = currency_or_base(&db, self.currency_id).await?;
+
+    let payload = self.clone();
+
+    let pricelist = db
+      .transaction::<_, pricelist::Model, DbErr>(move |txn| {
+        Box::pin(async move {
+          let pricelist = pricelist::ActiveModel {
+            name: Set(payload.name.trim().to_string()),
+            currency_id: Set(currency_id),
+            ..Default::default()
+          };
+          let pricelist = pricelist.insert(txn).await?;
+
+          pricelist_rule::Entity::insert_many(rule_models(pricelist.id, payload.rules))
+            .on_empty_do_nothing()
+            .exec(txn)
+            .await?;
+
+          Ok(pricelist)
+        })
+      })
+      .await?;
+
+    Ok(pricelist)
+  }
+}
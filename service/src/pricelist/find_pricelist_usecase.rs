@@ -0,0 +1,62 @@
+use axum::response::{IntoResponse, Response};
+use domain::pricelist::{
+  pricelist::{self, PricelistDTO},
+  pricelist_rule,
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct FindPricelistUsecase {
+  pub id: Uuid,
+}
+
+pub type FindPricelistParams = FindPricelistUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindPricelistError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindPricelistError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindPricelistError::Database(err) => AppError::from(err),
+      FindPricelistError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_pricelist").into_response()
+  }
+}
+
+impl FindPricelistUsecase {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<PricelistDTO, FindPricelistError> {
+    let pricelist = pricelist::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindPricelistError::RecordNotFound)?;
+
+    let rules = pricelist_rule::Entity::find()
+      .filter(pricelist_rule::Column::PricelistId.eq(pricelist.id))
+      .order_by_asc(pricelist_rule::Column::Sequence)
+      .into_partial_model::<pricelist_rule::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(PricelistDTO {
+      id: pricelist.id,
+      name: pricelist.name,
+      currency_id: pricelist.currency_id,
+      rules,
+      created_at: pricelist.created_at,
+      updated_at: pricelist.updated_at,
+      archived_at: pricelist.archived_at,
+    })
+  }
+}
@@ -0,0 +1,16 @@
+pub mod price_resolution;
+
+pub mod create_pricelist_usecase;
+pub use create_pricelist_usecase::*;
+
+pub mod update_pricelist_usecase;
+pub use update_pricelist_usecase::*;
+
+pub mod find_pricelist_usecase;
+pub use find_pricelist_usecase::*;
+
+pub mod list_paginated_pricelists_usecase;
+pub use list_paginated_pricelists_usecase::*;
+
+pub mod compute_price_usecase;
+pub use compute_price_usecase::*;
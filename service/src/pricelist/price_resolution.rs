@@ -0,0 +1,364 @@
+use std::cmp::Reverse;
+
+use domain::{
+  pricelist::{
+    pricelist,
+    pricelist_rule::{self, PricelistComputeType, PricelistRuleScope},
+  },
+  product::{product, product_template},
+};
+use infra::uuid::Uuid;
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter,
+};
+
+use crate::{
+  currency::currency_conversion::{DocumentCurrency, PriceConversionError},
+  measurement::convert_unit_price,
+  product::category_hierarchy::ancestor_category_ids,
+};
+
+/// Unit price of a product together with the rule it was computed from.
+pub struct ResolvedPrice {
+  /// Price of one unit of the requested unit of measure, in the requested
+  /// currency.
+  pub unit_price: Decimal,
+  pub rule: Option<pricelist_rule::Model>,
+}
+
+/// Prices `product_quantity` stock units of `product` under `pricelist` on the
+/// date of `currency`, quoted per `uom_id` in `currency`. Without a pricelist,
+/// or when none of its rules applies, the product's own price is used. List
+/// prices, costs and fixed prices are converted from their own currencies at
+/// the rates of that date before any rule is applied.
+///
+/// The applicable rule is the most specific one: a variant rule beats a
+/// template rule, which beats a category rule (the closest category first),
+/// which beats a global rule. Among equally specific rules the highest
+/// minimum quantity wins, then the lowest sequence.
+pub async fn resolve_price<C>(
+  db: &C,
+  pricelist: Option<&pricelist::Model>,
+  product: &product::Model,
+  template: &product_template::Model,
+  product_quantity: Decimal,
+  uom_id: Uuid,
+  currency: &DocumentCurrency,
+) -> Result<ResolvedPrice, PriceConversionError>
+where
+  C: ConnectionTrait,
+{
+  let list_price =
+    convert_unit_price(db, product.price, product.price_uom_id, template.uom_id).await?;
+  let list_price = currency
+    .convert_price(db, list_price, template.currency_id)
+    .await?;
+
+  let rule = match pricelist {
+    Some(pricelist) => {
+      find_rule(
+        db,
+        pricelist.id,
+        product,
+        template,
+        product_quantity,
+        currency.at,
+      )
+      .await?
+    }
+    None => None,
+  };
+  let stock_price = match (&rule, pricelist) {
+    (Some(rule), Some(pricelist)) => {
+      let fixed_price = match rule.fixed_price {
+        Some(fixed_price) => Some(
+          currency
+            .convert_price(db, fixed_price, pricelist.currency_id)
+            .await?,
+        ),
+        None => None,
+      };
+      rule_price(
+        rule,
+        fixed_price,
+        list_price,
+        currency.from_base(product.cost),
+      )
+    }
+    _ => list_price,
+  };
+  let unit_price = convert_unit_price(db, stock_price, template.uom_id, uom_id).await?;
+
+  Ok(ResolvedPrice { unit_price, rule })
+}
+
+/// Price per stock unit set by `rule`, from prices already in the document's
+/// currency.
+fn rule_price(
+  rule: &pricelist_rule::Model,
+  fixed_price: Option<Decimal>,
+  list_price: Decimal,
+  cost: Decimal,
+) -> Decimal {
+  let percent = |value: Option<Decimal>| value.unwrap_or_default() / Decimal::ONE_HUNDRED;
+
+  match rule.compute_type {
+    PricelistComputeType::Fixed => fixed_price.unwrap_or(list_price),
+    PricelistComputeType::Discount => list_price - list_price * percent(rule.discount),
+    PricelistComputeType::CostPlusMargin => cost + cost * percent(rule.margin),
+  }
+  .normalize()
+}
+
+async fn find_rule<C>(
+  db: &C,
+  pricelist_id: Uuid,
+  product: &product::Model,
+  template: &product_template::Model,
+  product_quantity: Decimal,
+  at: DateTimeWithTimeZone,
+) -> Result<Option<pricelist_rule::Model>, sea_orm::DbErr>
+where
+  C: ConnectionTrait,
+{
+  // Closest category first, so a rule on the product's own category beats
+  // one on a parent.
+  let category_ids = match template.category_id {
+    Some(category_id) => ancestor_category_ids(db, category_id).await?,
+    None => vec![],
+  };
+
+  let rules = pricelist_rule::Entity::find()
+    .filter(pricelist_rule::Column::PricelistId.eq(pricelist_id))
+    .filter(
+      Condition::any()
+        .add(pricelist_rule::Column::ProductId.eq(product.id))
+        .add(pricelist_rule::Column::ProductTemplateId.eq(template.id))
+        .add(pricelist_rule::Column::CategoryId.is_in(category_ids.clone()))
+        .add(pricelist_rule::Column::Scope.eq(PricelistRuleScope::Global)),
+    )
+    .all(db)
+    .await?;
+
+  Ok(best_rule(rules, &category_ids, product_quantity, at))
+}
+
+/// Most specific of `rules` that covers `product_quantity` at `at`, ranked as
+/// described on `resolve_price`. `category_ids` lists the template's category
+/// and its ancestors, closest first.
+fn best_rule(
+  rules: Vec<pricelist_rule::Model>,
+  category_ids: &[Uuid],
+  product_quantity: Decimal,
+  at: DateTimeWithTimeZone,
+) -> Option<pricelist_rule::Model> {
+  let specificity = |rule: &pricelist_rule::Model| match rule.scope {
+    PricelistRuleScope::Variant => (0, 0),
+    PricelistRuleScope::Template => (1, 0),
+    PricelistRuleScope::Category => (
+      2,
+      category_ids
+        .iter()
+        .position(|id| Some(*id) == rule.category_id)
+        .unwrap_or(usize::MAX),
+    ),
+    PricelistRuleScope::Global => (3, 0),
+  };
+
+  rules
+    .into_iter()
+    .filter(|rule| rule.min_quantity <= product_quantity)
+    .filter(|rule| rule.date_start.is_none_or(|date_start| date_start <= at))
+    .filter(|rule| rule.date_end.is_none_or(|date_end| date_end > at))
+    .min_by_key(|rule| (specificity(rule), Reverse(rule.min_quantity), rule.sequence))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::test_support::{at, dec};
+
+  fn rule(scope: PricelistRuleScope) -> pricelist_rule::Model {
+    pricelist_rule::Model {
+      id: Uuid::new(),
+      pricelist_id: Uuid::new(),
+      sequence: 10,
+      scope,
+      product_id: None,
+      product_template_id: None,
+      category_id: None,
+      min_quantity: Decimal::ZERO,
+      date_start: None,
+      date_end: None,
+      compute_type: PricelistComputeType::Discount,
+      fixed_price: None,
+      discount: None,
+      margin: None,
+    }
+  }
+
+  fn category_rule(category_id: Uuid) -> pricelist_rule::Model {
+    pricelist_rule::Model {
+      category_id: Some(category_id),
+      ..rule(PricelistRuleScope::Category)
+    }
+  }
+
+  fn best(rules: &[pricelist_rule::Model], category_ids: &[Uuid], quantity: &str) -> Option<Uuid> {
+    best_rule(
+      rules.to_vec(),
+      category_ids,
+      dec(quantity),
+      at("2025-01-15T00:00:00Z"),
+    )
+    .map(|rule| rule.id)
+  }
+
+  #[test]
+  fn variant_beats_template_beats_category_beats_global() {
+    let category_id = Uuid::new();
+    let variant = rule(PricelistRuleScope::Variant);
+    let template = rule(PricelistRuleScope::Template);
+    let category = category_rule(category_id);
+    let global = rule(PricelistRuleScope::Global);
+
+    let mut rules = vec![
+      global.clone(),
+      category.clone(),
+      template.clone(),
+      variant.clone(),
+    ];
+    assert_eq!(best(&rules, &[category_id], "1"), Some(variant.id));
+    rules.pop();
+    assert_eq!(best(&rules, &[category_id], "1"), Some(template.id));
+    rules.pop();
+    assert_eq!(best(&rules, &[category_id], "1"), Some(category.id));
+    rules.pop();
+    assert_eq!(best(&rules, &[category_id], "1"), Some(global.id));
+  }
+
+  #[test]
+  fn closest_category_wins() {
+    let [child, parent] = [Uuid::new(), Uuid::new()];
+    let on_parent = category_rule(parent);
+    let on_child = category_rule(child);
+
+    assert_eq!(
+      best(&[on_parent, on_child.clone()], &[child, parent], "1"),
+      Some(on_child.id)
+    );
+  }
+
+  #[test]
+  fn highest_reached_min_quantity_then_lowest_sequence_wins() {
+    let tier_1 = rule(PricelistRuleScope::Global);
+    let tier_10 = pricelist_rule::Model {
+      min_quantity: dec("10"),
+      ..rule(PricelistRuleScope::Global)
+    };
+    let tier_100 = pricelist_rule::Model {
+      min_quantity: dec("100"),
+      ..rule(PricelistRuleScope::Global)
+    };
+    let tier_10_first = pricelist_rule::Model {
+      id: Uuid::new(),
+      sequence: 1,
+      ..tier_10.clone()
+    };
+    let rules = [
+      tier_1.clone(),
+      tier_10.clone(),
+      tier_100,
+      tier_10_first.clone(),
+    ];
+
+    assert_eq!(best(&rules, &[], "5"), Some(tier_1.id));
+    assert_eq!(best(&rules, &[], "10"), Some(tier_10_first.id));
+    assert_eq!(best(&rules[..3], &[], "50"), Some(tier_10.id));
+  }
+
+  #[test]
+  fn rules_only_apply_within_their_dates() {
+    let expired = pricelist_rule::Model {
+      date_end: Some(at("2025-01-15T00:00:00Z")),
+      ..rule(PricelistRuleScope::Variant)
+    };
+    let upcoming = pricelist_rule::Model {
+      date_start: Some(at("2025-01-16T00:00:00Z")),
+      ..rule(PricelistRuleScope::Template)
+    };
+    let current = pricelist_rule::Model {
+      date_start: Some(at("2025-01-15T00:00:00Z")),
+      date_end: Some(at("2025-01-16T00:00:00Z")),
+      ..rule(PricelistRuleScope::Global)
+    };
+
+    assert_eq!(
+      best(&[expired, upcoming, current.clone()], &[], "1"),
+      Some(current.id)
+    );
+  }
+
+  #[test]
+  fn no_rule_applies_below_every_min_quantity() {
+    let tier = pricelist_rule::Model {
+      min_quantity: dec("10"),
+      ..rule(PricelistRuleScope::Global)
+    };
+
+    assert_eq!(best(&[tier], &[], "9.5"), None);
+  }
+
+  #[test]
+  fn fixed_rule_uses_fixed_price_or_falls_back_to_list_price() {
+    let fixed = pricelist_rule::Model {
+      compute_type: PricelistComputeType::Fixed,
+      ..rule(PricelistRuleScope::Global)
+    };
+
+    assert_eq!(
+      rule_price(&fixed, Some(dec("7.50")), dec("10"), dec("4")),
+      dec("7.5")
+    );
+    assert_eq!(rule_price(&fixed, None, dec("10"), dec("4")), dec("10"));
+  }
+
+  #[test]
+  fn discount_rule_takes_percentage_off_list_price_unrounded() {
+    let discount = pricelist_rule::Model {
+      compute_type: PricelistComputeType::Discount,
+      discount: Some(dec("12.5")),
+      ..rule(PricelistRuleScope::Global)
+    };
+
+    assert_eq!(
+      rule_price(&discount, None, dec("9.99"), dec("4")),
+      dec("8.74125")
+    );
+  }
+
+  #[test]
+  fn margin_rule_adds_percentage_on_top_of_cost_unrounded() {
+    let margin = pricelist_rule::Model {
+      compute_type: PricelistComputeType::CostPlusMargin,
+      margin: Some(dec("33")),
+      ..rule(PricelistRuleScope::Global)
+    };
+
+    assert_eq!(
+      rule_price(&margin, None, dec("10"), dec("3.33")),
+      dec("4.4289")
+    );
+  }
+
+  #[test]
+  fn missing_percentages_count_as_zero() {
+    let discount = rule(PricelistRuleScope::Global);
+
+    assert_eq!(
+      rule_price(&discount, None, dec("10.00"), dec("4")),
+      dec("10")
+    );
+  }
+}
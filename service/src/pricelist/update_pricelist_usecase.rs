@@ -0,0 +1,130 @@
+use axum::response::{IntoResponse, Response};
+use domain::pricelist::{pricelist, pricelist_rule};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{
+  ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, Set, TransactionError,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::create_pricelist_usecase::{rule_models, validate_pricelist, PricelistRule};
+use crate::{
+  currency::currency_conversion::{currency_or_base, CurrencyConversionError},
+  unique_name::{name_conflict, same_name},
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdatePricelistUsecase {
+  pub id: Uuid,
+  pub name: String,
+  /// Defaults to the base currency. Fixed prices are kept as they are when
+  /// the currency changes.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  /// Replaces every rule of the pricelist.
+  #[serde(default)]
+  pub rules: Vec<PricelistRule>,
+}
+
+pub type UpdatePricelistPayload = UpdatePricelistUsecase;
+
+impl Validate for UpdatePricelistUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_pricelist(&self.name, &self.rules).into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdatePricelistError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl From<TransactionError<UpdatePricelistError>> for UpdatePricelistError {
+  fn from(err: TransactionError<UpdatePricelistError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdatePricelistError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for UpdatePricelistError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdatePricelistError::Database(err) => AppError::from(err),
+      UpdatePricelistError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdatePricelistError::NameConflict(existing_id) => {
+        name_conflict(self.to_string(), existing_id)
+      }
+      UpdatePricelistError::Currency(err) => AppError::from(err),
+    };
+
+    error.with_source("update_pricelist").into_response()
+  }
+}
+
+impl UpdatePricelistUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<pricelist::Model, UpdatePricelistError> {
+    let existing = pricelist::Entity::find()
+      .filter(same_name(pricelist::Column::Name, &self.name))
+      .filter(pricelist::Column::Id.ne(self.id))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(UpdatePricelistError::NameConflict(existing.id));
+    }
+    let currency_id = currency_or_base(&db, self.currency_id).await?;
+
+    let payload = self.clone();
+
+    let pricelist = db
+      .transaction::<_, pricelist::Model, UpdatePricelistError>(move |txn| {
+        Box::pin(async move {
+          pricelist::Entity::find_by_id(payload.id)
+            .one(txn)
+            .await?
+            .ok_or(UpdatePricelistError::RecordNotFound)?;
+
+          let pricelist = pricelist::ActiveModel {
+            id: Set(payload.id),
+            name: Set(payload.name.trim().to_string()),
+            currency_id: Set(currency_id),
+            ..Default::default()
+          };
+          let pricelist = pricelist.update(txn).await?;
+
+          pricelist_rule::Entity::delete_many()
+            .filter(pricelist_rule::Column::PricelistId.eq(pricelist.id))
+            .exec(txn)
+            .await?;
+          pricelist_rule::Entity::insert_many(rule_models(pricelist.id, payload.rules))
+            .on_empty_do_nothing()
+            .exec(txn)
+            .await?;
+
+          Ok(pricelist)
+        })
+      })
+      .await?;
+
+    Ok(pricelist)
+  }
+}
@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+
+use domain::product::category::{self, CategoryTreeNode, Entity as Category};
+use infra::uuid::Uuid;
+use sea_orm::{
+  sea_query::SimpleExpr, ColumnTrait, ConnectionTrait, DbBackend, DbErr, EntityTrait, QuerySelect,
+  Statement,
+};
+
+pub const PATH_SEPARATOR: &str = " / ";
+
+/// Categories sharing the parent `parent_category_id`, the scope category
+/// names have to be unique in.
+pub fn parent_scope(parent_category_id: Option<Uuid>) -> SimpleExpr {
+  match parent_category_id {
+    Some(parent_category_id) => category::Column::ParentCategoryId.eq(parent_category_id),
+    None => category::Column::ParentCategoryId.is_null(),
+  }
+}
+
+/// `category_id` followed by its ancestors, closest first. Reads only that
+/// branch, for callers that need one category rather than the whole tree.
+pub async fn ancestor_category_ids<C>(db: &C, category_id: Uuid) -> Result<Vec<Uuid>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let statement = Statement::from_sql_and_values(
+    DbBackend::Postgres,
+    r#"WITH RECURSIVE branch AS (
+      SELECT id, parent_category_id, 0 AS depth FROM category WHERE id = $1
+      UNION ALL
+      SELECT category.id, category.parent_category_id, branch.depth + 1
+      FROM category JOIN branch ON category.id = branch.parent_category_id
+    )
+    SELECT id FROM branch ORDER BY depth"#,
+    [category_id.into()],
+  );
+
+  db.query_all(statement)
+    .await?
+    .into_iter()
+    .map(|row| row.try_get("", "id"))
+    .collect()
+}
+
+/// In-memory view of the whole category table, used to resolve breadcrumbs,
+/// descendants and parent cycles without issuing one query per level.
+pub struct CategoryHierarchy {
+  categories: HashMap<Uuid, category::Model>,
+}
+
+impl CategoryHierarchy {
+  pub fn new(categories: Vec<category::Model>) -> Self {
+    Self {
+      categories: categories
+        .into_iter()
+        .map(|category| (category.id, category))
+        .collect(),
+    }
+  }
+
+  pub async fn load<C>(db: &C) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let categories = Category::find().all(db).await?;
+    Ok(Self::new(categories))
+  }
+
+  /// Like `load`, but locks every category until the transaction ends so a
+  /// concurrent re-parenting cannot invalidate a cycle check.
+  pub async fn load_for_update<C>(db: &C) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let categories = Category::find().lock_exclusive().all(db).await?;
+    Ok(Self::new(categories))
+  }
+
+  pub fn get(&self, id: Uuid) -> Option<&category::Model> {
+    self.categories.get(&id)
+  }
+
+  /// Ancestors of `id` ordered from the root down to `id` itself.
+  pub fn breadcrumbs(&self, id: Uuid) -> Vec<category::PartialModel> {
+    let mut breadcrumbs = vec![];
+    let mut visited = HashSet::new();
+    let mut current = self.categories.get(&id);
+
+    while let Some(category) = current {
+      if !visited.insert(category.id) {
+        break;
+      }
+      breadcrumbs.push(category::PartialModel {
+        id: category.id,
+        name: category.name.clone(),
+      });
+      current = category
+        .parent_category_id
+        .and_then(|parent_id| self.categories.get(&parent_id));
+    }
+
+    breadcrumbs.reverse();
+    breadcrumbs
+  }
+
+  pub fn path(&self, id: Uuid) -> String {
+    self
+      .breadcrumbs(id)
+      .into_iter()
+      .map(|category| category.name)
+      .collect::<Vec<_>>()
+      .join(PATH_SEPARATOR)
+  }
+
+  /// Whether attaching `id` under `parent_id` would make `id` its own ancestor.
+  pub fn would_create_cycle(&self, id: Uuid, parent_id: Uuid) -> bool {
+    let mut visited = HashSet::new();
+    let mut current = Some(parent_id);
+
+    while let Some(current_id) = current {
+      if current_id == id {
+        return true;
+      }
+      if !visited.insert(current_id) {
+        return false;
+      }
+      current = self
+        .categories
+        .get(&current_id)
+        .and_then(|category| category.parent_category_id);
+    }
+
+    false
+  }
+
+  /// `id` followed by every category below it.
+  pub fn descendant_ids(&self, id: Uuid) -> Vec<Uuid> {
+    let children = self.children_by_parent();
+    let mut ids = vec![];
+    let mut visited = HashSet::new();
+    let mut stack = vec![id];
+
+    while let Some(current_id) = stack.pop() {
+      if !visited.insert(current_id) {
+        continue;
+      }
+      ids.push(current_id);
+      if let Some(child_ids) = children.get(&Some(current_id)) {
+        stack.extend(child_ids.iter().rev());
+      }
+    }
+
+    ids
+  }
+
+  /// Nested category tree sorted by name. Archived categories and everything
+  /// below them are left out unless `include_archived` is set.
+  pub fn tree(&self, include_archived: bool) -> Vec<CategoryTreeNode> {
+    let children = self.children_by_parent();
+    let mut visited = HashSet::new();
+    let mut roots = self
+      .categories
+      .values()
+      .filter(|category| match category.parent_category_id {
+        Some(parent_id) => !self.categories.contains_key(&parent_id),
+        None => true,
+      })
+      .collect::<Vec<_>>();
+    roots.sort_by(|a, b| a.name.cmp(&b.name));
+
+    roots
+      .into_iter()
+      .filter_map(|root| self.build_node(root.id, None, include_archived, &children, &mut visited))
+      .collect()
+  }
+
+  fn build_node(
+    &self,
+    id: Uuid,
+    parent_path: Option<&str>,
+    include_archived: bool,
+    children: &HashMap<Option<Uuid>, Vec<Uuid>>,
+    visited: &mut HashSet<Uuid>,
+  ) -> Option<CategoryTreeNode> {
+    if !visited.insert(id) {
+      return None;
+    }
+    let category = self.categories.get(&id)?;
+    if !include_archived && category.archived_at.is_some() {
+      return None;
+    }
+    let path = match parent_path {
+      Some(parent_path) => format!("{}{}{}", parent_path, PATH_SEPARATOR, category.name),
+      None => category.name.clone(),
+    };
+    let child_nodes = children
+      .get(&Some(id))
+      .map(|child_ids| {
+        child_ids
+          .iter()
+          .filter_map(|child_id| {
+            self.build_node(*child_id, Some(&path), include_archived, children, visited)
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    Some(CategoryTreeNode {
+      id,
+      name: category.name.clone(),
+      parent_category_id: category.parent_category_id,
+      path,
+      children: child_nodes,
+    })
+  }
+
+  fn children_by_parent(&self) -> HashMap<Option<Uuid>, Vec<Uuid>> {
+    let mut categories = self.categories.values().collect::<Vec<_>>();
+    categories.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut children: HashMap<Option<Uuid>, Vec<Uuid>> = HashMap::new();
+    for category in categories {
+      children
+        .entry(category.parent_category_id)
+        .or_default()
+        .push(category.id);
+    }
+    children
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use domain::product::category::CostingMethod;
+
+  use super::*;
+
+  fn category(id: Uuid, parent_category_id: Option<Uuid>) -> category::Model {
+    category::Model {
+      id,
+      name: id.to_string(),
+      parent_category_id,
+      costing_method: CostingMethod::default(),
+      sales_tax_id: None,
+      purchase_tax_id: None,
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    }
+  }
+
+  /// `root > child > grandchild`, plus `sibling` under `root`.
+  fn hierarchy() -> (CategoryHierarchy, [Uuid; 4]) {
+    let [root, child, grandchild, sibling] = [Uuid::new(), Uuid::new(), Uuid::new(), Uuid::new()];
+    let hierarchy = CategoryHierarchy::new(vec![
+      category(root, None),
+      category(child, Some(root)),
+      category(grandchild, Some(child)),
+      category(sibling, Some(root)),
+    ]);
+
+    (hierarchy, [root, child, grandchild, sibling])
+  }
+
+  #[test]
+  fn category_cannot_be_its_own_parent() {
+    let (hierarchy, [root, ..]) = hierarchy();
+
+    assert!(hierarchy.would_create_cycle(root, root));
+  }
+
+  #[test]
+  fn category_cannot_move_under_its_descendants() {
+    let (hierarchy, [root, child, grandchild, _]) = hierarchy();
+
+    assert!(hierarchy.would_create_cycle(root, grandchild));
+    assert!(hierarchy.would_create_cycle(child, grandchild));
+  }
+
+  #[test]
+  fn category_can_move_under_unrelated_branch_or_ancestor() {
+    let (hierarchy, [root, child, grandchild, sibling]) = hierarchy();
+
+    assert!(!hierarchy.would_create_cycle(child, sibling));
+    assert!(!hierarchy.would_create_cycle(grandchild, root));
+    assert!(!hierarchy.would_create_cycle(sibling, grandchild));
+  }
+
+  #[test]
+  fn unknown_parent_does_not_create_cycle() {
+    let (hierarchy, [root, ..]) = hierarchy();
+
+    assert!(!hierarchy.would_create_cycle(root, Uuid::new()));
+  }
+
+  #[test]
+  fn existing_cycle_above_parent_terminates() {
+    let [a, b, c] = [Uuid::new(), Uuid::new(), Uuid::new()];
+    let hierarchy = CategoryHierarchy::new(vec![
+      category(a, Some(b)),
+      category(b, Some(a)),
+      category(c, None),
+    ]);
+
+    assert!(!hierarchy.would_create_cycle(c, a));
+  }
+}
@@ -1,16 +1,20 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
-};
+use axum::response::{IntoResponse, Response};
 use domain::product::{
   attribute::{self, ActiveModel as Attribute},
   attribute_option,
 };
-use infra::util::error;
-use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, Set, TransactionError, TransactionTrait};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, QueryFilter, Set, TransactionError};
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::unique_name::{name_conflict, same_name};
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct CreateAttributeUsecase {
   pub name: String,
@@ -25,29 +29,78 @@ pub struct AttributeOption {
 
 pub type CreateAttributePayload = CreateAttributeUsecase;
 
+impl Validate for CreateAttributeUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let duplicates = rules::duplicate_indexes(
+      self
+        .attribute_options
+        .iter()
+        .map(|option| option.value.as_str()),
+    );
+
+    let errors = ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .each("attributeOptions", &self.attribute_options, |option| {
+        ValidationErrors::new().field("value", [rules::required(&option.value)])
+      });
+
+    duplicates
+      .into_iter()
+      .fold(errors, |errors, index| {
+        errors.field(
+          format!("attributeOptions[{}].value", index),
+          [Err("duplicate")],
+        )
+      })
+      .into_result()
+  }
+}
+
 #[derive(Error, Debug)]
 pub enum CreateAttributeError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] TransactionError<DbErr>),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+}
+
+impl From<TransactionError<DbErr>> for CreateAttributeError {
+  fn from(err: TransactionError<DbErr>) -> Self {
+    match err {
+      TransactionError::Connection(err) | TransactionError::Transaction(err) => {
+        CreateAttributeError::Database(err)
+      }
+    }
+  }
 }
 
 impl IntoResponse for CreateAttributeError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      CreateAttributeError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+    let error = match self {
+      CreateAttributeError::Database(err) => AppError::from(err),
+      CreateAttributeError::NameConflict(existing_id) => {
+        name_conflict(self.to_string(), existing_id)
       }
     };
 
-    (status, error(code, Some("create_uom".to_string()))).into_response()
+    error.with_source("create_attribute").into_response()
   }
 }
 
 impl CreateAttributeUsecase {
   pub async fn invoke(
     &self,
-    db: impl TransactionTrait,
+    db: impl WriteConnection,
   ) -> Result<attribute::Model, CreateAttributeError> {
+    let existing = attribute::Entity::find()
+      .filter(same_name(attribute::Column::Name, &self.name))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(CreateAttributeError::NameConflict(existing.id));
+    }
+
     let name = self.name.to_owned();
     let attribute_options = self.attribute_options.to_owned();
     let attribute = db
@@ -67,7 +120,7 @@ impl CreateAttributeUsecase {
             })
             .collect::<Vec<_>>();
 
-          if options.len() > 0 {
+          if !options.is_empty() {
             attribute_option::Entity::insert_many(options)
               .exec(txn)
               .await?;
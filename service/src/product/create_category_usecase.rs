@@ -0,0 +1,120 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  product::category::{
+    self, ActiveModel as CategoryActiveModel, CostingMethod, Entity as Category,
+  },
+  tax::tax::TaxUse,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, QueryFilter, Set};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::category_hierarchy::parent_scope;
+use crate::{
+  tax::line_taxes::{check_default_taxes, TaxInputError},
+  unique_name::{name_conflict, same_name},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryUsecase {
+  pub name: String,
+  #[serde(rename(deserialize = "parentCategoryId"))]
+  pub parent_category_id: Option<Uuid>,
+  #[serde(rename(deserialize = "salesTaxId"), default)]
+  pub sales_tax_id: Option<Uuid>,
+  #[serde(rename(deserialize = "purchaseTaxId"), default)]
+  pub purchase_tax_id: Option<Uuid>,
+  #[serde(rename(deserialize = "costingMethod"), default)]
+  pub costing_method: CostingMethod,
+}
+
+pub type CreateCategoryPayload = CreateCategoryUsecase;
+
+impl Validate for CreateCategoryUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateCategoryError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("parent_category_not_found")]
+  ParentCategoryNotFound,
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+}
+
+impl IntoResponse for CreateCategoryError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateCategoryError::Database(err) => AppError::from(err),
+      CreateCategoryError::ParentCategoryNotFound => {
+        AppError::validation(self.to_string()).with_field("parentCategoryId", self.to_string())
+      }
+      CreateCategoryError::NameConflict(existing_id) => {
+        name_conflict(self.to_string(), existing_id)
+      }
+      CreateCategoryError::Tax(err) => AppError::from(err),
+    };
+
+    error.with_source("create_category").into_response()
+  }
+}
+
+impl CreateCategoryUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<category::Model, CreateCategoryError> {
+    if let Some(parent_category_id) = self.parent_category_id {
+      let parent = Category::find_by_id(parent_category_id).one(&db).await?;
+      if parent.is_none() {
+        return Err(CreateCategoryError::ParentCategoryNotFound);
+      }
+    }
+
+    let existing = Category::find()
+      .filter(same_name(category::Column::Name, &self.name))
+      .filter(parent_scope(self.parent_category_id))
+      .one(&db)
+      .await?;
+    if let Some(existing) = existing {
+      return Err(CreateCategoryError::NameConflict(existing.id));
+    }
+    check_default_taxes(
+      &db,
+      [
+        ("salesTaxId", self.sales_tax_id, TaxUse::Sales),
+        ("purchaseTaxId", self.purchase_tax_id, TaxUse::Purchase),
+      ],
+    )
+    .await?;
+
+    let category = CategoryActiveModel {
+      name: Set(self.name.to_owned()),
+      parent_category_id: Set(self.parent_category_id),
+      costing_method: Set(self.costing_method),
+      sales_tax_id: Set(self.sales_tax_id),
+      purchase_tax_id: Set(self.purchase_tax_id),
+      ..Default::default()
+    };
+    let category = category.insert(&db).await?;
+
+    Ok(category)
+  }
+}
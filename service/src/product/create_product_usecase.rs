@@ -1,30 +1,62 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
+use axum::response::{IntoResponse, Response};
+use domain::{
+  product::{
+    attribute::{self},
+    attribute_option, product, product_combination, product_mould, product_template,
+  },
+  tax::tax::TaxUse,
 };
-use domain::product::{
-  attribute::{self},
-  attribute_option, product, product_combination, product_template,
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Rule, Validate, ValidationErrors},
 };
-use infra::{util::error, uuid::Uuid};
 use sea_orm::{
-  prelude::Decimal, ActiveModelTrait, DbErr, EntityTrait, Set, TransactionError, TransactionTrait,
+  prelude::Decimal, ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, Set, TransactionError,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
 
-#[derive(Debug, Deserialize, Clone)]
+use super::generate_variants_usecase::{
+  generate_variants, validate_selection, AttributeSelection, GenerateVariantsError,
+};
+use super::template_uoms::{uom_violations_error, validate_template_uoms, UomViolation};
+use super::variant_validation::{
+  load_option_attributes, validate_variant_combinations, VariantViolation,
+};
+use crate::{
+  currency::currency_conversion::{currency_or_base, CurrencyConversionError},
+  tax::line_taxes::{check_default_taxes, TaxInputError},
+};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct VariantAttributeOption {
   pub attribute: attribute::PartialModel,
   pub option: attribute_option::PartialModel,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Variant {
   pub price: Decimal,
-  #[serde(rename(deserialize = "variantAttributeOptions"))]
+  #[serde(default)]
+  pub cost: Option<Decimal>,
+  #[serde(rename = "priceUomId", default)]
+  pub price_uom_id: Option<Uuid>,
+  #[serde(rename = "variantAttributeOptions")]
   pub attribute_options: Vec<VariantAttributeOption>,
 }
 
+/// Whether `createCorrespondingMoulds` produces one mould for the whole
+/// template or a separate mould for every variant.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MouldMode {
+  #[default]
+  PerTemplate,
+  PerVariant,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct CreateProductUsecase {
   pub name: String,
@@ -34,68 +66,252 @@ pub struct CreateProductUsecase {
   pub product_subtype: product_template::ProductSubtype,
   #[serde(rename(deserialize = "isTrackInventory"))]
   pub is_track_inventory: bool,
+  #[serde(default)]
+  pub tracking: product_template::Tracking,
   pub price: Decimal,
   pub cost: Decimal,
   #[serde(rename(deserialize = "uomId"))]
   pub uom_id: Uuid,
+  #[serde(rename(deserialize = "purchaseUomId"), default)]
+  pub purchase_uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "salesUomId"), default)]
+  pub sales_uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "priceUomId"), default)]
+  pub price_uom_id: Option<Uuid>,
   #[serde(rename(deserialize = "categoryId"))]
   pub category_id: Option<Uuid>,
+  #[serde(rename(deserialize = "salesTaxId"), default)]
+  pub sales_tax_id: Option<Uuid>,
+  #[serde(rename(deserialize = "purchaseTaxId"), default)]
+  pub purchase_tax_id: Option<Uuid>,
+  /// Currency of the variant prices, the base currency by default.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
   #[serde(rename(deserialize = "createCorrespondingMoulds"))]
   pub create_corresponding_moulds: bool,
+  #[serde(rename(deserialize = "mouldMode"), default)]
+  pub mould_mode: MouldMode,
   #[serde(rename(deserialize = "isMultipleVariants"))]
   pub is_multiple_variants: bool,
+  #[serde(default)]
   pub variants: Vec<Variant>,
+  #[serde(rename(deserialize = "attributeSelections"), default)]
+  pub attribute_selections: Option<Vec<AttributeSelection>>,
 }
 
 pub type CreateProductPayload = CreateProductUsecase;
 
+impl Validate for CreateProductUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let has_selections = self
+      .attribute_selections
+      .as_ref()
+      .is_some_and(|selections| !selections.is_empty());
+
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .field("price", [rules::non_negative(self.price)])
+      .field("cost", [rules::non_negative(self.cost)])
+      .field(
+        "tracking",
+        [tracking_rule(
+          self.tracking,
+          &self.product_type,
+          self.is_track_inventory,
+        )],
+      )
+      .field(
+        "variants",
+        [rules::reject_if(
+          self.is_multiple_variants && self.variants.is_empty() && !has_selections,
+          "required",
+        )],
+      )
+      .each("variants", &self.variants, |variant| {
+        ValidationErrors::new()
+          .field("price", [rules::non_negative(variant.price)])
+          .field("cost", variant.cost.map(rules::non_negative))
+      })
+      // Selections generate the variants, so they can neither be combined
+      // with explicit ones nor apply to a single-variant product.
+      .field(
+        "attributeSelections",
+        [
+          rules::reject_if(
+            self.attribute_selections.is_some() && !self.variants.is_empty(),
+            "not_allowed_with_variants",
+          ),
+          rules::reject_if(
+            self.attribute_selections.is_some() && !self.is_multiple_variants,
+            "requires_multiple_variants",
+          ),
+        ],
+      )
+      .each(
+        "attributeSelections",
+        self.attribute_selections.as_deref().unwrap_or_default(),
+        validate_selection,
+      )
+      .into_result()
+  }
+}
+
+/// Lots and serials only make sense on goods whose inventory is tracked.
+pub(crate) fn tracking_rule(
+  tracking: product_template::Tracking,
+  product_type: &product_template::ProductType,
+  is_track_inventory: bool,
+) -> Rule {
+  rules::reject_if(
+    tracking != product_template::Tracking::None
+      && (*product_type != product_template::ProductType::Goods || !is_track_inventory),
+    "requires_inventory_tracking",
+  )
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum CreateProductError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] TransactionError<DbErr>),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("invalid_variant_combinations")]
+  InvalidVariants(Vec<VariantViolation>),
+
+  #[error(transparent)]
+  VariantGeneration(#[from] GenerateVariantsError),
+
+  #[error("moulds_require_packaging_with_print")]
+  MouldsRequirePackagingWithPrint,
+
+  #[error("invalid_uoms")]
+  InvalidUoms(Vec<UomViolation>),
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl From<TransactionError<CreateProductError>> for CreateProductError {
+  fn from(err: TransactionError<CreateProductError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => CreateProductError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
 }
 
 impl IntoResponse for CreateProductError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      CreateProductError::InternalServerError(e) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+    let error = match self {
+      CreateProductError::Database(err) => AppError::from(err),
+      CreateProductError::InvalidVariants(ref violations) => {
+        AppError::validation(self.to_string()).with_details(json!({ "variants": violations }))
+      }
+      CreateProductError::VariantGeneration(err) => AppError::from(err),
+      CreateProductError::MouldsRequirePackagingWithPrint => {
+        AppError::validation(self.to_string()).with_field("productSubtype", self.to_string())
+      }
+      CreateProductError::InvalidUoms(ref violations) => {
+        uom_violations_error(self.to_string(), violations)
       }
+      CreateProductError::Tax(err) => AppError::from(err),
+      CreateProductError::Currency(err) => AppError::from(err),
     };
 
-    (status, error(code, Some("create_uom".to_string()))).into_response()
+    error.with_source("create_product").into_response()
   }
 }
 
 impl CreateProductUsecase {
   pub async fn invoke(
     &self,
-    db: impl TransactionTrait,
+    db: impl WriteConnection,
   ) -> Result<Vec<product::Model>, CreateProductError> {
     let payload = self.clone();
 
     let products = db
-      .transaction::<_, Vec<product::Model>, DbErr>(move |txn| {
+      .transaction::<_, Vec<product::Model>, CreateProductError>(move |txn| {
         Box::pin(async move {
+          if payload.create_corresponding_moulds
+            && payload.product_subtype != product_template::ProductSubtype::PackagingWithPrint
+          {
+            return Err(CreateProductError::MouldsRequirePackagingWithPrint);
+          }
+
+          let variants = match payload.attribute_selections {
+            Some(ref selections) => {
+              generate_variants(txn, selections, payload.price, payload.cost).await?
+            }
+            None => payload.variants,
+          };
+
+          if payload.is_multiple_variants {
+            let option_attributes = load_option_attributes(txn, &variants).await?;
+            let violations = validate_variant_combinations(&variants, &option_attributes);
+            if !violations.is_empty() {
+              return Err(CreateProductError::InvalidVariants(violations));
+            }
+          }
+
+          // Units default to the stock unit, prices to the sales unit.
+          let purchase_uom_id = payload.purchase_uom_id.unwrap_or(payload.uom_id);
+          let sales_uom_id = payload.sales_uom_id.unwrap_or(payload.uom_id);
+          let price_uom_id = payload.price_uom_id.unwrap_or(sales_uom_id);
+          let related_uoms = [
+            ("purchaseUomId".to_string(), purchase_uom_id),
+            ("salesUomId".to_string(), sales_uom_id),
+            ("priceUomId".to_string(), price_uom_id),
+          ]
+          .into_iter()
+          .chain(variants.iter().enumerate().filter_map(|(index, variant)| {
+            variant
+              .price_uom_id
+              .map(|uom_id| (format!("variants[{}].priceUomId", index), uom_id))
+          }))
+          .collect::<Vec<_>>();
+          let violations = validate_template_uoms(txn, payload.uom_id, &related_uoms).await?;
+          if !violations.is_empty() {
+            return Err(CreateProductError::InvalidUoms(violations));
+          }
+          check_default_taxes(
+            txn,
+            [
+              ("salesTaxId", payload.sales_tax_id, TaxUse::Sales),
+              ("purchaseTaxId", payload.purchase_tax_id, TaxUse::Purchase),
+            ],
+          )
+          .await?;
+          let currency_id = currency_or_base(txn, payload.currency_id).await?;
+
           let product_template = product_template::ActiveModel {
             name: Set(payload.name),
             product_type: Set(payload.product_type),
             product_subtype: Set(payload.product_subtype),
             is_track_inventory: Set(payload.is_track_inventory),
+            tracking: Set(payload.tracking),
             uom_id: Set(payload.uom_id),
+            purchase_uom_id: Set(purchase_uom_id),
+            sales_uom_id: Set(sales_uom_id),
             category_id: Set(payload.category_id),
+            sales_tax_id: Set(payload.sales_tax_id),
+            purchase_tax_id: Set(payload.purchase_tax_id),
+            currency_id: Set(currency_id),
             ..Default::default()
           };
           let product_template = product_template.insert(txn).await?;
           let mut products = vec![];
+          let mut variant_labels = vec![];
 
           if payload.is_multiple_variants {
-            for variant in payload.variants.iter() {
+            for variant in variants.iter() {
               let attribute_options = variant.attribute_options.iter().collect::<Vec<_>>();
               let product = product::ActiveModel {
                 product_template_id: Set(product_template.id),
                 price: Set(variant.price),
-                cost: Set(payload.cost),
+                price_uom_id: Set(variant.price_uom_id.unwrap_or(price_uom_id)),
+                cost: Set(variant.cost.unwrap_or(payload.cost)),
                 is_product_variant: Set(true),
                 ..Default::default()
               };
@@ -116,21 +332,64 @@ impl CreateProductUsecase {
                 .exec(txn)
                 .await?;
 
+              variant_labels.push(
+                attribute_options
+                  .iter()
+                  .map(|option| option.option.value.to_owned())
+                  .collect::<Vec<_>>()
+                  .join(", "),
+              );
               products.push(product);
             }
           } else {
             let product = product::ActiveModel {
               product_template_id: Set(product_template.id),
               price: Set(payload.price),
+              price_uom_id: Set(price_uom_id),
               cost: Set(payload.cost),
               is_product_variant: Set(false),
               ..Default::default()
             };
 
             let product = product.insert(txn).await?;
+            variant_labels.push(String::new());
             products.push(product);
           }
 
+          if payload.create_corresponding_moulds {
+            let mut links = vec![];
+
+            match payload.mould_mode {
+              MouldMode::PerTemplate => {
+                let mould = create_mould_template(txn, &product_template, None).await?;
+                for product in products.iter() {
+                  links.push((product.id, mould.id));
+                }
+              }
+              MouldMode::PerVariant => {
+                for (product, label) in products.iter().zip(variant_labels.iter()) {
+                  let mould =
+                    create_mould_template(txn, &product_template, Some(label.as_str())).await?;
+                  links.push((product.id, mould.id));
+                }
+              }
+            }
+
+            let product_moulds = links
+              .into_iter()
+              .map(
+                |(product_id, mould_template_id)| product_mould::ActiveModel {
+                  product_id: Set(product_id),
+                  mould_template_id: Set(mould_template_id),
+                },
+              )
+              .collect::<Vec<_>>();
+            product_mould::Entity::insert_many(product_moulds)
+              .on_empty_do_nothing()
+              .exec(txn)
+              .await?;
+          }
+
           Ok(products)
         })
       })
@@ -139,3 +398,116 @@ impl CreateProductUsecase {
     Ok(products)
   }
 }
+
+/// Creates a single-product `Mould` template mirroring the packaging template's
+/// unit and category. `variant_label` names the variant the mould belongs to.
+async fn create_mould_template<C>(
+  db: &C,
+  packaging: &product_template::Model,
+  variant_label: Option<&str>,
+) -> Result<product_template::Model, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let name = match variant_label {
+    Some(label) if !label.is_empty() => format!("{} ({}) - Mould", packaging.name, label),
+    _ => format!("{} - Mould", packaging.name),
+  };
+  let mould_template = product_template::ActiveModel {
+    name: Set(name),
+    product_type: Set(product_template::ProductType::Goods),
+    product_subtype: Set(product_template::ProductSubtype::Mould),
+    is_track_inventory: Set(true),
+    uom_id: Set(packaging.uom_id),
+    purchase_uom_id: Set(packaging.uom_id),
+    sales_uom_id: Set(packaging.uom_id),
+    category_id: Set(packaging.category_id),
+    currency_id: Set(packaging.currency_id),
+    ..Default::default()
+  };
+  let mould_template = mould_template.insert(db).await?;
+
+  let mould = product::ActiveModel {
+    product_template_id: Set(mould_template.id),
+    price: Set(Decimal::ZERO),
+    price_uom_id: Set(packaging.uom_id),
+    cost: Set(Decimal::ZERO),
+    is_product_variant: Set(false),
+    ..Default::default()
+  };
+  mould.insert(db).await?;
+
+  Ok(mould_template)
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  fn payload(fields: serde_json::Value) -> CreateProductUsecase {
+    let mut payload = json!({
+      "name": "Carton box",
+      "productType": "goods",
+      "productSubtype": "normal",
+      "isTrackInventory": true,
+      "price": 1,
+      "cost": 0,
+      "uomId": Uuid::new(),
+      "categoryId": null,
+      "createCorrespondingMoulds": false,
+      "isMultipleVariants": true,
+    });
+    payload
+      .as_object_mut()
+      .unwrap()
+      .extend(fields.as_object().unwrap().clone());
+
+    serde_json::from_value(payload).unwrap()
+  }
+
+  fn selections() -> serde_json::Value {
+    json!([{ "attributeId": Uuid::new(), "options": [{ "optionId": Uuid::new() }] }])
+  }
+
+  fn selection_errors(payload: CreateProductUsecase) -> Option<Vec<String>> {
+    payload
+      .validate()
+      .err()
+      .and_then(|errors| errors.fields().get("attributeSelections").cloned())
+  }
+
+  #[test]
+  fn selections_alone_are_accepted() {
+    let payload = payload(json!({ "attributeSelections": selections() }));
+
+    assert!(payload.validate().is_ok());
+  }
+
+  #[test]
+  fn selections_with_explicit_variants_are_rejected() {
+    let payload = payload(json!({
+      "attributeSelections": selections(),
+      "variants": [{ "price": 1, "variantAttributeOptions": [] }],
+    }));
+
+    assert_eq!(
+      selection_errors(payload),
+      Some(vec!["not_allowed_with_variants".to_string()])
+    );
+  }
+
+  #[test]
+  fn selections_on_single_variant_product_are_rejected() {
+    let payload = payload(json!({
+      "isMultipleVariants": false,
+      "attributeSelections": selections(),
+    }));
+
+    assert_eq!(
+      selection_errors(payload),
+      Some(vec!["requires_multiple_variants".to_string()])
+    );
+  }
+}
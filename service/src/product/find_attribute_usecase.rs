@@ -1,13 +1,10 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
-};
+use axum::response::{IntoResponse, Response};
 use domain::product::{
   attribute::{self, Entity as Attribute},
   attribute_option,
 };
-use infra::{util::error, uuid::Uuid};
-use sea_orm::{ConnectionTrait, DbErr, EntityTrait};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{DbErr, EntityTrait};
 use serde::Deserialize;
 use thiserror::Error;
 
@@ -20,8 +17,8 @@ pub type FindAttributeParams = FindAttributeUsecase;
 
 #[derive(Error, Debug)]
 pub enum FindAttributeError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
 
   #[error("record_not_found")]
   RecordNotFound,
@@ -29,21 +26,19 @@ pub enum FindAttributeError {
 
 impl IntoResponse for FindAttributeError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      FindAttributeError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
-      }
-      FindAttributeError::RecordNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+    let error = match self {
+      FindAttributeError::Database(err) => AppError::from(err),
+      FindAttributeError::RecordNotFound => AppError::not_found(self.to_string()),
     };
 
-    (status, error(code, Some("create_attribute".to_string()))).into_response()
+    error.with_source("find_attribute").into_response()
   }
 }
 
 impl FindAttributeUsecase {
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait,
+    db: impl ReadConnection,
   ) -> Result<attribute::AttributeDTO, FindAttributeError> {
     let attribute = Attribute::find_by_id(self.id)
       .find_with_related(attribute_option::Entity)
@@ -57,6 +52,7 @@ impl FindAttributeUsecase {
         name: attribute.name,
         attribute_options: attribute_options
           .into_iter()
+          .filter(|option| option.archived_at.is_none())
           .map(|option| attribute_option::PartialModel {
             id: option.id,
             value: option.value,
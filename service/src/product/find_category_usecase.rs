@@ -0,0 +1,55 @@
+use axum::response::{IntoResponse, Response};
+use domain::product::category::CategoryDTO;
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::DbErr;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::category_hierarchy::CategoryHierarchy;
+
+#[derive(Debug, Deserialize)]
+pub struct FindCategoryUsecase {
+  pub id: Uuid,
+}
+
+pub type FindCategoryParams = FindCategoryUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindCategoryError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindCategoryError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindCategoryError::Database(err) => AppError::from(err),
+      FindCategoryError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_category").into_response()
+  }
+}
+
+impl FindCategoryUsecase {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<CategoryDTO, FindCategoryError> {
+    let hierarchy = CategoryHierarchy::load(&db).await?;
+    let category = hierarchy
+      .get(self.id)
+      .ok_or(FindCategoryError::RecordNotFound)?;
+
+    Ok(CategoryDTO {
+      id: category.id,
+      name: category.name.clone(),
+      parent_category_id: category.parent_category_id,
+      costing_method: category.costing_method,
+      sales_tax_id: category.sales_tax_id,
+      purchase_tax_id: category.purchase_tax_id,
+      path: hierarchy.path(category.id),
+      breadcrumbs: hierarchy.breadcrumbs(category.id),
+    })
+  }
+}
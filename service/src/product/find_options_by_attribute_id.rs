@@ -1,10 +1,7 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
-};
+use axum::response::{IntoResponse, Response};
 use domain::product::attribute_option;
-use infra::{util::error, uuid::Uuid};
-use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter};
 use serde::Deserialize;
 use thiserror::Error;
 
@@ -17,8 +14,8 @@ pub type FindOptionsByAttributeIdParams = FindOptionsByAttributeIdUsecase;
 
 #[derive(Error, Debug)]
 pub enum FindOptionsByAttributeIdError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
 
   #[error("record_not_found")]
   RecordNotFound,
@@ -26,24 +23,25 @@ pub enum FindOptionsByAttributeIdError {
 
 impl IntoResponse for FindOptionsByAttributeIdError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      FindOptionsByAttributeIdError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
-      }
-      FindOptionsByAttributeIdError::RecordNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+    let error = match self {
+      FindOptionsByAttributeIdError::Database(err) => AppError::from(err),
+      FindOptionsByAttributeIdError::RecordNotFound => AppError::not_found(self.to_string()),
     };
 
-    (status, error(code, Some("create_attribute".to_string()))).into_response()
+    error
+      .with_source("find_options_by_attribute_id")
+      .into_response()
   }
 }
 
 impl FindOptionsByAttributeIdUsecase {
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait,
+    db: impl ReadConnection,
   ) -> Result<Vec<attribute_option::PartialModel>, FindOptionsByAttributeIdError> {
     let options = attribute_option::Entity::find()
       .filter(attribute_option::Column::AttributeId.eq(self.attribute_id))
+      .filter(attribute_option::Column::ArchivedAt.is_null())
       .into_partial_model::<attribute_option::PartialModel>()
       .all(&db)
       .await?;
@@ -0,0 +1,173 @@
+use axum::response::{IntoResponse, Response};
+use std::collections::{HashMap, HashSet};
+
+use domain::{
+  measurement::uom,
+  product::{
+    category,
+    product::{self, ProductVariantDTO},
+    product_mould,
+    product_template::{self, ProductTemplateDTO},
+  },
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::product_combinations::load_product_combinations;
+
+#[derive(Debug, Deserialize)]
+pub struct FindProductUsecase {
+  pub id: Uuid,
+}
+
+pub type FindProductParams = FindProductUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindProductError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindProductError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindProductError::Database(err) => AppError::from(err),
+      FindProductError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_product").into_response()
+  }
+}
+
+impl FindProductUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<ProductTemplateDTO, FindProductError> {
+    let template = product_template::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindProductError::RecordNotFound)?;
+
+    let uoms = uom::Entity::find()
+      .filter(uom::Column::Id.is_in([
+        template.uom_id,
+        template.purchase_uom_id,
+        template.sales_uom_id,
+      ]))
+      .all(&db)
+      .await?;
+    let find_uom = |uom_id: Uuid| {
+      uoms
+        .iter()
+        .find(|uom| uom.id == uom_id)
+        .cloned()
+        .map(uom::PartialModel::from)
+        .ok_or(FindProductError::RecordNotFound)
+    };
+    let uom = find_uom(template.uom_id)?;
+    let purchase_uom = find_uom(template.purchase_uom_id)?;
+    let sales_uom = find_uom(template.sales_uom_id)?;
+
+    let category = match template.category_id {
+      Some(category_id) => {
+        category::Entity::find_by_id(category_id)
+          .into_partial_model::<category::PartialModel>()
+          .one(&db)
+          .await?
+      }
+      None => None,
+    };
+
+    let products = product::Entity::find()
+      .filter(product::Column::ProductTemplateId.eq(template.id))
+      .filter(product::Column::ArchivedAt.is_null())
+      .order_by_asc(product::Column::Id)
+      .all(&db)
+      .await?;
+    let product_ids = products
+      .iter()
+      .map(|product| product.id)
+      .collect::<Vec<_>>();
+    let mut combinations = load_product_combinations(&db, &product_ids).await?;
+
+    let mut moulds: HashMap<_, Vec<product_template::PartialModel>> = HashMap::new();
+    if !product_ids.is_empty() {
+      let product_moulds = product_mould::Entity::find()
+        .filter(product_mould::Column::ProductId.is_in(product_ids.clone()))
+        .find_also_related(product_template::Entity)
+        .all(&db)
+        .await?;
+      for (link, mould_template) in product_moulds {
+        if let Some(mould_template) = mould_template {
+          moulds
+            .entry(link.product_id)
+            .or_default()
+            .push(product_template::PartialModel {
+              id: mould_template.id,
+              name: mould_template.name,
+            });
+        }
+      }
+    }
+
+    let packaging_template_ids = product_mould::Entity::find()
+      .filter(product_mould::Column::MouldTemplateId.eq(template.id))
+      .find_also_related(product::Entity)
+      .all(&db)
+      .await?
+      .into_iter()
+      .filter_map(|(_, product)| product.map(|product| product.product_template_id))
+      .collect::<HashSet<_>>();
+    let packagings = if packaging_template_ids.is_empty() {
+      vec![]
+    } else {
+      product_template::Entity::find()
+        .filter(product_template::Column::Id.is_in(packaging_template_ids))
+        .order_by_asc(product_template::Column::Name)
+        .into_partial_model::<product_template::PartialModel>()
+        .all(&db)
+        .await?
+    };
+
+    let variants = products
+      .into_iter()
+      .map(|product| ProductVariantDTO {
+        id: product.id,
+        price: product.price,
+        price_uom_id: product.price_uom_id,
+        cost: product.cost,
+        is_product_variant: product.is_product_variant,
+        combinations: combinations.remove(&product.id).unwrap_or_default(),
+        moulds: moulds.remove(&product.id).unwrap_or_default(),
+      })
+      .collect();
+
+    Ok(ProductTemplateDTO {
+      id: template.id,
+      name: template.name,
+      description: template.description,
+      product_type: template.product_type,
+      product_subtype: template.product_subtype,
+      is_track_inventory: template.is_track_inventory,
+      tracking: template.tracking,
+      uom,
+      purchase_uom,
+      sales_uom,
+      category,
+      sales_tax_id: template.sales_tax_id,
+      purchase_tax_id: template.purchase_tax_id,
+      currency_id: template.currency_id,
+      variants,
+      packagings,
+      created_at: template.created_at,
+      updated_at: template.updated_at,
+      archived_at: template.archived_at,
+    })
+  }
+}
@@ -0,0 +1,346 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::response::{IntoResponse, Response};
+use domain::product::{attribute, attribute_option};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{prelude::Decimal, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use super::create_product_usecase::{Variant, VariantAttributeOption};
+
+/// Upper bound on the number of combinations a single selection may expand to.
+pub const MAX_GENERATED_VARIANTS: usize = 1000;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct OptionSelection {
+  #[serde(rename(deserialize = "optionId"))]
+  pub option_id: Uuid,
+  #[serde(rename(deserialize = "priceExtra"), default)]
+  pub price_extra: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AttributeSelection {
+  #[serde(rename(deserialize = "attributeId"))]
+  pub attribute_id: Uuid,
+  pub options: Vec<OptionSelection>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GenerateVariantsUsecase {
+  pub price: Decimal,
+  pub cost: Decimal,
+  pub attributes: Vec<AttributeSelection>,
+}
+
+pub type GenerateVariantsPayload = GenerateVariantsUsecase;
+
+impl Validate for GenerateVariantsUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("price", [rules::non_negative(self.price)])
+      .field("cost", [rules::non_negative(self.cost)])
+      .field("attributes", [rules::not_empty(&self.attributes)])
+      .each("attributes", &self.attributes, validate_selection)
+      .into_result()
+  }
+}
+
+/// Rules for one attribute's options. An option listed twice would expand
+/// into identical combinations.
+pub(crate) fn validate_selection(selection: &AttributeSelection) -> ValidationErrors {
+  let mut seen = HashSet::new();
+  let has_duplicates = !selection
+    .options
+    .iter()
+    .all(|option| seen.insert(option.option_id));
+
+  ValidationErrors::new()
+    .field(
+      "options",
+      [
+        rules::not_empty(&selection.options),
+        rules::reject_if(has_duplicates, "duplicate_options"),
+      ],
+    )
+    .each("options", &selection.options, |option| {
+      ValidationErrors::new().field("priceExtra", option.price_extra.map(rules::non_negative))
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum GenerateVariantsError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("empty_attribute_selection")]
+  EmptySelection,
+
+  #[error("empty_option_selection")]
+  EmptyOptionSelection(Uuid),
+
+  #[error("duplicate_attribute_selection")]
+  DuplicateAttribute(Uuid),
+
+  #[error("unknown_attribute_option")]
+  UnknownOptions(Vec<Uuid>),
+
+  #[error("too_many_variant_combinations")]
+  TooManyCombinations(usize),
+}
+
+impl From<GenerateVariantsError> for AppError {
+  fn from(err: GenerateVariantsError) -> Self {
+    match err {
+      GenerateVariantsError::Database(err) => AppError::from(err),
+      GenerateVariantsError::EmptySelection => AppError::validation(err.to_string()),
+      GenerateVariantsError::EmptyOptionSelection(attribute_id)
+      | GenerateVariantsError::DuplicateAttribute(attribute_id) => {
+        AppError::validation(err.to_string()).with_details(json!({ "attributeId": attribute_id }))
+      }
+      GenerateVariantsError::UnknownOptions(ref option_ids) => {
+        AppError::validation(err.to_string()).with_details(json!({ "optionIds": option_ids }))
+      }
+      GenerateVariantsError::TooManyCombinations(count) => AppError::validation(err.to_string())
+        .with_details(json!({ "count": count, "max": MAX_GENERATED_VARIANTS })),
+    }
+  }
+}
+
+impl IntoResponse for GenerateVariantsError {
+  fn into_response(self) -> Response {
+    AppError::from(self)
+      .with_source("generate_variants")
+      .into_response()
+  }
+}
+
+impl GenerateVariantsUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<Vec<Variant>, GenerateVariantsError> {
+    generate_variants(&db, &self.attributes, self.price, self.cost).await
+  }
+}
+
+/// Expands the selected options of each attribute into their Cartesian
+/// product. Each variant is priced at `price` plus the extras of its options
+/// and costed at `cost`; attributes and options keep the order they were given in.
+pub async fn generate_variants<C>(
+  db: &C,
+  selections: &[AttributeSelection],
+  price: Decimal,
+  cost: Decimal,
+) -> Result<Vec<Variant>, GenerateVariantsError>
+where
+  C: ConnectionTrait,
+{
+  if selections.is_empty() {
+    return Err(GenerateVariantsError::EmptySelection);
+  }
+
+  let mut seen_attributes = HashSet::new();
+  let mut count: usize = 1;
+  for selection in selections.iter() {
+    if !seen_attributes.insert(selection.attribute_id) {
+      return Err(GenerateVariantsError::DuplicateAttribute(
+        selection.attribute_id,
+      ));
+    }
+    if selection.options.is_empty() {
+      return Err(GenerateVariantsError::EmptyOptionSelection(
+        selection.attribute_id,
+      ));
+    }
+    count = count.saturating_mul(selection.options.len());
+  }
+  if count > MAX_GENERATED_VARIANTS {
+    return Err(GenerateVariantsError::TooManyCombinations(count));
+  }
+
+  let attributes = attribute::Entity::find()
+    .filter(attribute::Column::Id.is_in(seen_attributes))
+    .into_partial_model::<attribute::PartialModel>()
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|attribute| (attribute.id, attribute))
+    .collect::<HashMap<_, _>>();
+
+  let option_ids = selections
+    .iter()
+    .flat_map(|selection| selection.options.iter())
+    .map(|option| option.option_id)
+    .collect::<HashSet<_>>();
+  let options = attribute_option::Entity::find()
+    .filter(attribute_option::Column::Id.is_in(option_ids))
+    .all(db)
+    .await?
+    .into_iter()
+    .map(|option| (option.id, option))
+    .collect::<HashMap<_, _>>();
+
+  let unknown_options = selections
+    .iter()
+    .flat_map(|selection| {
+      selection
+        .options
+        .iter()
+        .filter(|option| match options.get(&option.option_id) {
+          Some(found) => {
+            found.attribute_id != selection.attribute_id
+              || !attributes.contains_key(&selection.attribute_id)
+          }
+          None => true,
+        })
+        .map(|option| option.option_id)
+    })
+    .collect::<Vec<_>>();
+  if !unknown_options.is_empty() {
+    return Err(GenerateVariantsError::UnknownOptions(unknown_options));
+  }
+
+  let variants = cartesian_product(selections)
+    .into_iter()
+    .map(|combination| {
+      let price_extra = combination
+        .iter()
+        .filter_map(|(_, option)| option.price_extra)
+        .sum::<Decimal>();
+      let attribute_options = combination
+        .into_iter()
+        .map(|(selection, option)| VariantAttributeOption {
+          attribute: attributes[&selection.attribute_id].clone(),
+          option: attribute_option::PartialModel {
+            id: option.option_id,
+            value: options[&option.option_id].value.clone(),
+          },
+        })
+        .collect();
+
+      Variant {
+        price: price + price_extra,
+        cost: Some(cost),
+        price_uom_id: None,
+        attribute_options,
+      }
+    })
+    .collect();
+
+  Ok(variants)
+}
+
+/// Every way of picking one option per selection, varying the last selection
+/// fastest.
+fn cartesian_product(
+  selections: &[AttributeSelection],
+) -> Vec<Vec<(&AttributeSelection, &OptionSelection)>> {
+  let mut combinations: Vec<Vec<(&AttributeSelection, &OptionSelection)>> = vec![vec![]];
+  for selection in selections.iter() {
+    combinations = combinations
+      .into_iter()
+      .flat_map(|combination| {
+        selection.options.iter().map(move |option| {
+          let mut combination = combination.clone();
+          combination.push((selection, option));
+          combination
+        })
+      })
+      .collect();
+  }
+
+  combinations
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn selection(option_ids: &[Uuid]) -> AttributeSelection {
+    AttributeSelection {
+      attribute_id: Uuid::new(),
+      options: option_ids
+        .iter()
+        .map(|&option_id| OptionSelection {
+          option_id,
+          price_extra: None,
+        })
+        .collect(),
+    }
+  }
+
+  fn option_ids(combination: &[(&AttributeSelection, &OptionSelection)]) -> Vec<Uuid> {
+    combination
+      .iter()
+      .map(|(_, option)| option.option_id)
+      .collect()
+  }
+
+  #[test]
+  fn cartesian_product_picks_one_option_per_attribute_in_order() {
+    let [red, blue, small, medium, large] = [(); 5].map(|_| Uuid::new());
+    let selections = [selection(&[red, blue]), selection(&[small, medium, large])];
+
+    let combinations = cartesian_product(&selections)
+      .iter()
+      .map(|combination| option_ids(combination))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      combinations,
+      vec![
+        vec![red, small],
+        vec![red, medium],
+        vec![red, large],
+        vec![blue, small],
+        vec![blue, medium],
+        vec![blue, large],
+      ]
+    );
+  }
+
+  #[test]
+  fn cartesian_product_keeps_selection_of_each_option() {
+    let selections = [selection(&[Uuid::new()]), selection(&[Uuid::new()])];
+
+    let combinations = cartesian_product(&selections);
+
+    assert_eq!(combinations.len(), 1);
+    for ((selection, _), expected) in combinations[0].iter().zip(&selections) {
+      assert_eq!(selection.attribute_id, expected.attribute_id);
+    }
+  }
+
+  #[test]
+  fn cartesian_product_of_no_selections_is_one_empty_combination() {
+    assert_eq!(cartesian_product(&[]).len(), 1);
+    assert!(cartesian_product(&[])[0].is_empty());
+  }
+
+  #[test]
+  fn cartesian_product_with_empty_selection_is_empty() {
+    let selections = [selection(&[Uuid::new(), Uuid::new()]), selection(&[])];
+
+    assert!(cartesian_product(&selections).is_empty());
+  }
+
+  #[test]
+  fn selection_with_repeated_option_is_rejected() {
+    let option_id = Uuid::new();
+
+    assert!(validate_selection(&selection(&[option_id, option_id]))
+      .into_result()
+      .is_err());
+    assert!(validate_selection(&selection(&[option_id, Uuid::new()]))
+      .into_result()
+      .is_ok());
+  }
+}
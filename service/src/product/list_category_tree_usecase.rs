@@ -0,0 +1,42 @@
+use axum::response::{IntoResponse, Response};
+use domain::product::category::CategoryTreeNode;
+use infra::{db::ReadConnection, error::AppError};
+use sea_orm::DbErr;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::category_hierarchy::CategoryHierarchy;
+
+#[derive(Debug, Deserialize)]
+pub struct ListCategoryTreeUsecase {
+  pub include_archived: Option<bool>,
+}
+
+pub type ListCategoryTreeParams = ListCategoryTreeUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListCategoryTreeError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl IntoResponse for ListCategoryTreeError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListCategoryTreeError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("list_category_tree").into_response()
+  }
+}
+
+impl ListCategoryTreeUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<Vec<CategoryTreeNode>, ListCategoryTreeError> {
+    let hierarchy = CategoryHierarchy::load(&db).await?;
+
+    Ok(hierarchy.tree(self.include_archived.unwrap_or(false)))
+  }
+}
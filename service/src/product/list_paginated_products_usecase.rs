@@ -1,165 +1,163 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
-};
+use std::collections::{BTreeSet, HashMap};
+
+use axum::response::{IntoResponse, Response};
 use domain::product::{
-  attribute, attribute_option,
   product::{self, Column, Entity as Product},
   product_combination,
-  product_template::{Column as ProductTemplateColumn, Entity as ProductTemplate},
+  product_template::{
+    Column as ProductTemplateColumn, Entity as ProductTemplate, ProductSubtype,
+    ProductTemplateListDTO, ProductType,
+  },
+};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  response::{CursorPaginationMeta, PaginationMeta},
+  uuid::Uuid,
 };
-use infra::{response::PaginationMeta, util::error, uuid::Uuid};
 use sea_orm::{
   prelude::Expr,
-  sea_query::{Alias, Query},
-  ConnectionTrait, DbErr, EntityTrait, FromQueryResult, PaginatorTrait,
+  sea_query::{Func, Query, SelectStatement, SimpleExpr},
+  ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, Order,
+  PaginatorTrait, QueryFilter, QuerySelect,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use super::{
+  category_hierarchy::CategoryHierarchy, product_combinations::load_product_combinations,
+};
+use crate::{
+  archive::archived_condition,
+  list_query::{
+    comma_separated, cursor_page, name_contains, page_index, page_size, Cursor, CursorError,
+    PageError, PaginationMode, SortOrder,
+  },
+};
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSortBy {
+  Name,
+  Price,
+  CreatedAt,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductListMode {
+  /// One row per product variant.
+  #[default]
+  Variant,
+  /// One row per product template with its matching variants nested.
+  Template,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum ProductListItem {
+  Variant(product::ProductDTO),
+  Template(ProductTemplateListDTO),
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TemplateIdResult {
+  id: Uuid,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListPaginatedProductsUsecase {
   pub page: Option<u64>,
   pub per_page: Option<u64>,
+  pub include_archived: Option<bool>,
+  pub only_archived: Option<bool>,
+  pub name: Option<String>,
+  pub category_id: Option<Uuid>,
+  pub include_subcategories: Option<bool>,
+  pub uom_id: Option<Uuid>,
+  pub product_type: Option<ProductType>,
+  pub product_subtype: Option<ProductSubtype>,
+  pub is_track_inventory: Option<bool>,
+  #[serde(default, deserialize_with = "comma_separated")]
+  pub attribute_option_ids: Option<Vec<Uuid>>,
+  pub sort_by: Option<ProductSortBy>,
+  pub order: Option<SortOrder>,
+  pub mode: Option<ProductListMode>,
+  pub pagination: Option<PaginationMode>,
+  pub cursor: Option<Cursor>,
+  pub with_total: Option<bool>,
 }
 
 pub type ListPaginatedProductsParams = ListPaginatedProductsUsecase;
 
 #[derive(Error, Debug)]
 pub enum ListPaginatedProductsError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] DbErr),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Page(#[from] PageError),
+
+  #[error(transparent)]
+  Cursor(#[from] CursorError),
 }
 
 impl IntoResponse for ListPaginatedProductsError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      ListPaginatedProductsError::InternalServerError(e) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
-      }
+    let error = match self {
+      ListPaginatedProductsError::Database(err) => AppError::from(err),
+      ListPaginatedProductsError::Page(err) => AppError::from(err),
+      ListPaginatedProductsError::Cursor(err) => AppError::from(err),
     };
 
-    (
-      status,
-      error(code, Some("list_paginated_products".to_string())),
-    )
-      .into_response()
+    error.with_source("list_paginated_products").into_response()
   }
 }
 
 impl ListPaginatedProductsUsecase {
+  /// Pages over distinct products (or templates in `template` mode) first and
+  /// then loads the combinations of the page in one batched query, so a page
+  /// always holds `per_page` complete rows in a stable order.
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait,
-  ) -> Result<(Vec<product::ProductDTO>, PaginationMeta), ListPaginatedProductsError> {
-    let per_page = self.per_page.unwrap_or(30);
-    let page = self.page.unwrap_or(1) - 1;
-
-    let product_query = Query::select()
-      .column((Product, Column::Id))
-      .column((Product, Column::ProductTemplateId))
-      .column((Product, Column::IsProductVariant))
-      .column((ProductTemplate, ProductTemplateColumn::Name))
-      .expr_as(
-        Expr::col((attribute::Entity, attribute::Column::Id)),
-        Alias::new("attribute_id"),
-      )
-      .expr_as(
-        Expr::col((attribute::Entity, attribute::Column::Name)),
-        Alias::new("attribute_name"),
-      )
-      .expr_as(
-        Expr::col((attribute_option::Entity, attribute_option::Column::Id)),
-        Alias::new("attribute_option_id"),
-      )
-      .expr_as(
-        Expr::col((attribute_option::Entity, attribute_option::Column::Value)),
-        Alias::new("attribute_option_value"),
-      )
-      .from(Product)
-      .left_join(
-        ProductTemplate,
-        Expr::col((Product, Column::ProductTemplateId))
-          .equals((ProductTemplate, ProductTemplateColumn::Id)),
-      )
-      .left_join(
-        product_combination::Entity,
-        Expr::col((Product, Column::Id)).equals((
-          product_combination::Entity,
-          product_combination::Column::ProductId,
-        )),
-      )
-      .left_join(
-        attribute_option::Entity,
-        Expr::col((
-          product_combination::Entity,
-          product_combination::Column::AttributeOptionId,
-        ))
-        .equals((attribute_option::Entity, attribute_option::Column::Id)),
-      )
-      .left_join(
-        attribute::Entity,
-        Expr::col((
-          attribute_option::Entity,
-          attribute_option::Column::AttributeId,
-        ))
-        .equals((attribute::Entity, attribute::Column::Id)),
-      )
-      .offset(page * per_page)
-      .limit(per_page)
-      .to_owned();
-    let builder = db.get_database_backend();
-    let product_result =
-      product::QueryProductResult::find_by_statement(builder.build(&product_query))
-        .all(&db)
-        .await?;
-
-    let mut product_map: std::collections::HashMap<Uuid, product::ProductDTO> =
-      std::collections::HashMap::new();
-
-    for product in product_result {
-      let entry = product_map
-        .entry(product.id)
-        .or_insert_with(|| product::ProductDTO {
-          id: product.id,
-          name: product.name.clone(),
-          is_product_variant: product.is_product_variant,
-          combinations: Vec::new(),
-        });
-
-      if product.is_product_variant {
-        let attribute_option = attribute_option::PartialModel {
-          id: product.attribute_option_id.unwrap(),
-          value: product.attribute_option_value.unwrap(),
-        };
-
-        if let Some(attr) = entry
-          .combinations
-          .iter_mut()
-          .find(|attr| attr.attribute.id == product.attribute_id.unwrap())
-        {
-          attr.option = attribute_option;
-        } else {
-          entry
-            .combinations
-            .push(domain::product::product::AttributeWithOptionDTO {
-              attribute: attribute::PartialModel {
-                id: product.attribute_id.unwrap(),
-                name: product.attribute_name.clone().unwrap(),
-              },
-              option: attribute_option,
-            });
-        }
-      }
-    }
-
-    let products: Vec<product::ProductDTO> = product_map.into_values().collect();
+    db: impl ReadConnection,
+  ) -> Result<(Vec<ProductListItem>, PaginationMeta), ListPaginatedProductsError> {
+    let per_page = page_size(self.per_page)?;
+    let page = page_index(self.page)?;
+    let condition = self.filter_condition(&db).await?;
+    let order = Order::from(self.order.unwrap_or_default());
 
-    let total = Product::find().count(&db).await?;
+    let items = match self.mode.unwrap_or_default() {
+      ProductListMode::Variant => {
+        let query = self
+          .variant_query(condition.clone(), order)
+          .offset(page * per_page)
+          .limit(per_page)
+          .to_owned();
+        let rows = find_products(&db, &query).await?;
+        with_combinations(&db, rows)
+          .await?
+          .into_iter()
+          .map(ProductListItem::Variant)
+          .collect()
+      }
+      ProductListMode::Template => {
+        let query = self
+          .template_query(condition.clone(), order)
+          .offset(page * per_page)
+          .limit(per_page)
+          .to_owned();
+        let template_ids = find_template_ids(&db, &query).await?;
+        self
+          .load_templates(&db, condition.clone(), template_ids)
+          .await?
+      }
+    };
+    let total = self.count(&db, condition).await?;
     let total_pages = (total as f64 / per_page as f64).ceil() as u64;
 
     Ok((
-      products,
+      items,
       PaginationMeta {
         total,
         total_pages,
@@ -168,4 +166,396 @@ impl ListPaginatedProductsUsecase {
       },
     ))
   }
+
+  /// Keyset variant of `invoke`: reads `per_page` products (or templates)
+  /// after or before the cursor and only counts the whole result set when
+  /// `with_total` is set.
+  pub async fn invoke_cursor(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<ProductListItem>, CursorPaginationMeta), ListPaginatedProductsError> {
+    let per_page = page_size(self.per_page)?;
+    let condition = self.filter_condition(&db).await?;
+    let cursor = self.cursor.as_ref();
+    let query_order = Cursor::query_order(cursor, self.order.unwrap_or_default().into());
+
+    let (items, next_cursor, prev_cursor) = match self.mode.unwrap_or_default() {
+      ProductListMode::Variant => {
+        let mut query = self.variant_query(condition.clone(), query_order.clone());
+        if let Some(cursor) = cursor {
+          query.and_where(
+            cursor
+              .keyset_condition(
+                &db,
+                vec![self.sort_expr(), Expr::col((Product, Column::Id)).into()],
+                joined_product_query()
+                  .expr(self.sort_expr())
+                  .column((Product, Column::Id))
+                  .and_where(Column::Id.eq(cursor.id))
+                  .to_owned(),
+                query_order,
+              )
+              .await?,
+          );
+        }
+        query.limit(per_page + 1);
+        let rows = find_products(&db, &query).await?;
+        let (rows, next_cursor, prev_cursor) = cursor_page(rows, per_page, cursor, |row| row.id);
+        let items = with_combinations(&db, rows)
+          .await?
+          .into_iter()
+          .map(ProductListItem::Variant)
+          .collect();
+        (items, next_cursor, prev_cursor)
+      }
+      ProductListMode::Template => {
+        let mut query = self.template_query(condition.clone(), query_order.clone());
+        if let Some(cursor) = cursor {
+          query.and_having(
+            cursor
+              .keyset_condition(
+                &db,
+                vec![
+                  self.template_sort_expr(),
+                  Expr::col((ProductTemplate, ProductTemplateColumn::Id)).into(),
+                ],
+                grouped_template_query(condition.clone())
+                  .expr(self.template_sort_expr())
+                  .column((ProductTemplate, ProductTemplateColumn::Id))
+                  .and_where(ProductTemplateColumn::Id.eq(cursor.id))
+                  .to_owned(),
+                query_order,
+              )
+              .await?,
+          );
+        }
+        query.limit(per_page + 1);
+        let template_ids = find_template_ids(&db, &query).await?;
+        let (template_ids, next_cursor, prev_cursor) =
+          cursor_page(template_ids, per_page, cursor, |id| *id);
+        let items = self
+          .load_templates(&db, condition.clone(), template_ids)
+          .await?;
+        (items, next_cursor, prev_cursor)
+      }
+    };
+
+    let total = match self.with_total {
+      Some(true) => Some(self.count(&db, condition).await?),
+      _ => None,
+    };
+
+    Ok((
+      items,
+      CursorPaginationMeta {
+        per_page,
+        next_cursor,
+        prev_cursor,
+        total,
+      },
+    ))
+  }
+
+  fn variant_query(&self, condition: Condition, order: Order) -> SelectStatement {
+    product_query()
+      .cond_where(condition)
+      .order_by_expr(self.sort_expr(), order.clone())
+      .order_by((Product, Column::Id), order)
+      .to_owned()
+  }
+
+  fn template_query(&self, condition: Condition, order: Order) -> SelectStatement {
+    grouped_template_query(condition)
+      .column((ProductTemplate, ProductTemplateColumn::Id))
+      .order_by_expr(self.template_sort_expr(), order.clone())
+      .order_by((ProductTemplate, ProductTemplateColumn::Id), order)
+      .to_owned()
+  }
+
+  /// Builds one row per template in `template_ids` order, nesting the
+  /// variants that match `condition`.
+  async fn load_templates<C>(
+    &self,
+    db: &C,
+    condition: Condition,
+    template_ids: Vec<Uuid>,
+  ) -> Result<Vec<ProductListItem>, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let mut templates = ProductTemplate::find()
+      .filter(ProductTemplateColumn::Id.is_in(template_ids.clone()))
+      .all(db)
+      .await?
+      .into_iter()
+      .map(|template| (template.id, template))
+      .collect::<HashMap<_, _>>();
+
+    let variant_query = self
+      .variant_query(condition, Order::from(self.order.unwrap_or_default()))
+      .and_where(Column::ProductTemplateId.is_in(template_ids.clone()))
+      .to_owned();
+    let rows = find_products(db, &variant_query).await?;
+    let mut variants: HashMap<Uuid, Vec<product::ProductDTO>> = HashMap::new();
+    for product in with_combinations(db, rows).await? {
+      variants
+        .entry(product.product_template_id)
+        .or_default()
+        .push(product);
+    }
+
+    Ok(
+      template_ids
+        .into_iter()
+        .filter_map(|id| templates.remove(&id))
+        .map(|template| {
+          ProductListItem::Template(ProductTemplateListDTO {
+            id: template.id,
+            name: template.name,
+            product_type: template.product_type,
+            product_subtype: template.product_subtype,
+            variants: variants.remove(&template.id).unwrap_or_default(),
+          })
+        })
+        .collect(),
+    )
+  }
+
+  async fn count<C>(&self, db: &C, condition: Condition) -> Result<u64, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let query = Product::find()
+      .inner_join(ProductTemplate)
+      .filter(condition);
+
+    match self.mode.unwrap_or_default() {
+      ProductListMode::Variant => query.count(db).await,
+      ProductListMode::Template => {
+        query
+          .select_only()
+          .column(Column::ProductTemplateId)
+          .distinct()
+          .count(db)
+          .await
+      }
+    }
+  }
+
+  async fn filter_condition<C>(&self, db: &C) -> Result<Condition, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let category_condition = match self.category_id {
+      Some(category_id) if self.include_subcategories.unwrap_or(false) => {
+        let hierarchy = CategoryHierarchy::load(db).await?;
+        Some(ProductTemplateColumn::CategoryId.is_in(hierarchy.descendant_ids(category_id)))
+      }
+      Some(category_id) => Some(ProductTemplateColumn::CategoryId.eq(category_id)),
+      None => None,
+    };
+
+    let attribute_option_condition = self
+      .attribute_option_ids
+      .as_deref()
+      .and_then(attribute_option_condition);
+
+    Ok(
+      Condition::all()
+        .add(Column::ArchivedAt.is_null())
+        .add(archived_condition(
+          ProductTemplateColumn::ArchivedAt,
+          self.include_archived,
+          self.only_archived,
+        ))
+        .add_option(
+          self
+            .name
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+            .map(|name| name_contains(ProductTemplateColumn::Name, name)),
+        )
+        .add_option(category_condition)
+        .add_option(
+          self
+            .uom_id
+            .map(|uom_id| ProductTemplateColumn::UomId.eq(uom_id)),
+        )
+        .add_option(
+          self
+            .product_type
+            .clone()
+            .map(|product_type| ProductTemplateColumn::ProductType.eq(product_type)),
+        )
+        .add_option(
+          self
+            .product_subtype
+            .clone()
+            .map(|product_subtype| ProductTemplateColumn::ProductSubtype.eq(product_subtype)),
+        )
+        .add_option(
+          self.is_track_inventory.map(|is_track_inventory| {
+            ProductTemplateColumn::IsTrackInventory.eq(is_track_inventory)
+          }),
+        )
+        .add_option(attribute_option_condition),
+    )
+  }
+
+  fn sort_expr(&self) -> SimpleExpr {
+    match self.sort_by {
+      Some(ProductSortBy::Name) => Expr::col((ProductTemplate, ProductTemplateColumn::Name)).into(),
+      Some(ProductSortBy::Price) => Expr::col((Product, Column::Price)).into(),
+      Some(ProductSortBy::CreatedAt) | None => Expr::col((Product, Column::CreatedAt)).into(),
+    }
+  }
+
+  /// Sort key of a template row in `template` mode; price sorts by the
+  /// cheapest matching variant.
+  fn template_sort_expr(&self) -> SimpleExpr {
+    match self.sort_by {
+      Some(ProductSortBy::Name) => Expr::col((ProductTemplate, ProductTemplateColumn::Name)).into(),
+      Some(ProductSortBy::Price) => Expr::col((Product, Column::Price)).min(),
+      Some(ProductSortBy::CreatedAt) | None => {
+        Expr::col((ProductTemplate, ProductTemplateColumn::CreatedAt)).into()
+      }
+    }
+  }
+}
+
+fn joined_product_query() -> SelectStatement {
+  Query::select()
+    .from(Product)
+    .inner_join(
+      ProductTemplate,
+      Expr::col((Product, Column::ProductTemplateId))
+        .equals((ProductTemplate, ProductTemplateColumn::Id)),
+    )
+    .to_owned()
+}
+
+fn product_query() -> SelectStatement {
+  joined_product_query()
+    .column((Product, Column::Id))
+    .column((Product, Column::ProductTemplateId))
+    .column((Product, Column::IsProductVariant))
+    .column((ProductTemplate, ProductTemplateColumn::Name))
+    .to_owned()
+}
+
+/// Products joined to their template, grouped per template and filtered by
+/// `condition`; callers pick the selected expressions.
+fn grouped_template_query(condition: Condition) -> SelectStatement {
+  joined_product_query()
+    .cond_where(condition)
+    .group_by_col((ProductTemplate, ProductTemplateColumn::Id))
+    .to_owned()
+}
+
+async fn find_template_ids<C>(db: &C, query: &SelectStatement) -> Result<Vec<Uuid>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let builder = db.get_database_backend();
+  Ok(
+    TemplateIdResult::find_by_statement(builder.build(query))
+      .all(db)
+      .await?
+      .into_iter()
+      .map(|row| row.id)
+      .collect(),
+  )
+}
+
+async fn find_products<C>(
+  db: &C,
+  query: &SelectStatement,
+) -> Result<Vec<product::QueryProductResult>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let builder = db.get_database_backend();
+  product::QueryProductResult::find_by_statement(builder.build(query))
+    .all(db)
+    .await
+}
+
+/// Attaches combinations to `rows`, keeping the order the rows came in.
+async fn with_combinations<C>(
+  db: &C,
+  rows: Vec<product::QueryProductResult>,
+) -> Result<Vec<product::ProductDTO>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let product_ids = rows.iter().map(|row| row.id).collect::<Vec<_>>();
+  let mut combinations = load_product_combinations(db, &product_ids).await?;
+
+  Ok(
+    rows
+      .into_iter()
+      .map(|row| product::ProductDTO {
+        id: row.id,
+        product_template_id: row.product_template_id,
+        name: row.name,
+        is_product_variant: row.is_product_variant,
+        combinations: combinations.remove(&row.id).unwrap_or_default(),
+      })
+      .collect(),
+  )
+}
+
+/// Variants combining every option of `ids`, repeated ids counting once.
+/// `None` when no option is asked for.
+fn attribute_option_condition(ids: &[Uuid]) -> Option<SimpleExpr> {
+  let ids = ids.iter().copied().collect::<BTreeSet<_>>();
+  if ids.is_empty() {
+    return None;
+  }
+
+  Some(
+    Expr::col((Product, Column::Id)).in_subquery(
+      Query::select()
+        .column(product_combination::Column::ProductId)
+        .from(product_combination::Entity)
+        .and_where(product_combination::Column::AttributeOptionId.is_in(ids.iter().copied()))
+        .group_by_col(product_combination::Column::ProductId)
+        .and_having(
+          Expr::expr(Func::count_distinct(Expr::col(
+            product_combination::Column::AttributeOptionId,
+          )))
+          .eq(ids.len() as i64),
+        )
+        .to_owned(),
+    ),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use sea_orm::{DatabaseBackend, QueryTrait, Value};
+
+  use super::*;
+
+  #[test]
+  fn attribute_options_are_counted_once_and_bound() {
+    let [red, large] = [Uuid::new(), Uuid::new()];
+
+    let statement = Product::find()
+      .filter(attribute_option_condition(&[red, large, red]).unwrap())
+      .build(DatabaseBackend::Postgres);
+
+    assert!(statement
+      .sql
+      .contains(r#"HAVING COUNT(DISTINCT "attribute_option_id") = $3"#));
+    assert_eq!(
+      statement.values.unwrap().0.last(),
+      Some(&Value::BigInt(Some(2)))
+    );
+  }
+
+  #[test]
+  fn no_attribute_option_condition_without_options() {
+    assert!(attribute_option_condition(&[]).is_none());
+  }
 }
@@ -21,3 +21,32 @@ pub use find_options_by_attribute_id::*;
 
 pub mod list_paginated_products_usecase;
 pub use list_paginated_products_usecase::*;
+
+pub mod category_hierarchy;
+
+pub mod create_category_usecase;
+pub use create_category_usecase::*;
+
+pub mod find_category_usecase;
+pub use find_category_usecase::*;
+
+pub mod update_category_usecase;
+pub use update_category_usecase::*;
+
+pub mod list_category_tree_usecase;
+pub use list_category_tree_usecase::*;
+
+pub mod product_combinations;
+
+pub mod find_product_usecase;
+pub use find_product_usecase::*;
+
+pub mod update_product_usecase;
+pub use update_product_usecase::*;
+
+pub mod variant_validation;
+
+pub mod template_uoms;
+
+pub mod generate_variants_usecase;
+pub use generate_variants_usecase::*;
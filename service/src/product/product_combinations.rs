@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use domain::product::{
+  attribute, attribute_option, product::AttributeWithOptionDTO, product_combination,
+};
+use infra::uuid::Uuid;
+use sea_orm::{
+  ColumnTrait, ConnectionTrait, DbErr, EntityTrait, FromQueryResult, JoinType, QueryFilter,
+  QueryOrder, QuerySelect, RelationTrait,
+};
+
+#[derive(Debug, FromQueryResult)]
+struct CombinationRow {
+  product_id: Uuid,
+  attribute_id: Uuid,
+  attribute_name: String,
+  attribute_option_id: Uuid,
+  attribute_option_value: String,
+}
+
+/// Loads the attribute/option pairs of every given product in a single query,
+/// keyed by product id and ordered by attribute name.
+pub async fn load_product_combinations<C>(
+  db: &C,
+  product_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<AttributeWithOptionDTO>>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let mut combinations: HashMap<Uuid, Vec<AttributeWithOptionDTO>> = HashMap::new();
+
+  if product_ids.is_empty() {
+    return Ok(combinations);
+  }
+
+  let rows = product_combination::Entity::find()
+    .select_only()
+    .column(product_combination::Column::ProductId)
+    .column_as(attribute::Column::Id, "attribute_id")
+    .column_as(attribute::Column::Name, "attribute_name")
+    .column_as(attribute_option::Column::Id, "attribute_option_id")
+    .column_as(attribute_option::Column::Value, "attribute_option_value")
+    .join(
+      JoinType::InnerJoin,
+      product_combination::Relation::AttributeOption.def(),
+    )
+    .join(
+      JoinType::InnerJoin,
+      attribute_option::Relation::Attribute.def(),
+    )
+    .filter(product_combination::Column::ProductId.is_in(product_ids.to_vec()))
+    .order_by_asc(attribute::Column::Name)
+    .order_by_asc(attribute::Column::Id)
+    .into_model::<CombinationRow>()
+    .all(db)
+    .await?;
+
+  for row in rows {
+    combinations
+      .entry(row.product_id)
+      .or_default()
+      .push(AttributeWithOptionDTO {
+        attribute: attribute::PartialModel {
+          id: row.attribute_id,
+          name: row.attribute_name,
+        },
+        option: attribute_option::PartialModel {
+          id: row.attribute_option_id,
+          value: row.attribute_option_value,
+        },
+      });
+  }
+
+  Ok(combinations)
+}
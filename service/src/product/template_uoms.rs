@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use domain::measurement::uom;
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+
+/// A unit field of a product payload that is unknown or measures something
+/// else than the template's stock unit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UomViolation {
+  pub field: String,
+  pub message: &'static str,
+}
+
+/// Checks that every unit in `related_uoms` exists and shares the category of
+/// the stock unit `uom_id`. Fields are named with their JSON spelling, e.g.
+/// `salesUomId` or `variants[0].priceUomId`.
+pub async fn validate_template_uoms<C>(
+  db: &C,
+  uom_id: Uuid,
+  related_uoms: &[(String, Uuid)],
+) -> Result<Vec<UomViolation>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let uom_ids = related_uoms
+    .iter()
+    .map(|(_, uom_id)| *uom_id)
+    .chain([uom_id])
+    .collect::<HashSet<_>>();
+  let uoms = uom::Entity::find()
+    .filter(uom::Column::Id.is_in(uom_ids))
+    .all(db)
+    .await?;
+  let find = |id: Uuid| uoms.iter().find(|uom| uom.id == id);
+
+  let Some(stock_uom) = find(uom_id) else {
+    return Ok(vec![UomViolation {
+      field: "uomId".to_string(),
+      message: "not_found",
+    }]);
+  };
+
+  let violations = related_uoms
+    .iter()
+    .filter_map(|(field, related_id)| {
+      let message = match find(*related_id) {
+        None => "not_found",
+        Some(related) if related.category != stock_uom.category => "incompatible_uom_category",
+        Some(_) => return None,
+      };
+      Some(UomViolation {
+        field: field.to_owned(),
+        message,
+      })
+    })
+    .collect();
+
+  Ok(violations)
+}
+
+pub fn uom_violations_error(code: String, violations: &[UomViolation]) -> AppError {
+  violations
+    .iter()
+    .fold(AppError::validation(code), |error, violation| {
+      error.with_field(violation.field.to_owned(), violation.message)
+    })
+}
@@ -1,25 +1,32 @@
-use axum::{
-  http::StatusCode,
-  response::{IntoResponse, Response},
-};
+use axum::response::{IntoResponse, Response};
 use domain::product::{
   attribute::{self, ActiveModel as Attribute},
-  attribute_option,
+  attribute_option, product_combination,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
 };
-use infra::{util::error, uuid::Uuid};
 use sea_orm::{
-  sea_query::OnConflict, ActiveModelTrait, ConnectionTrait, DbErr, EntityTrait, Set,
-  TransactionError, TransactionTrait,
+  prelude::Expr, sea_query::OnConflict, ActiveModelTrait, ColumnTrait, DbErr, EntityTrait,
+  QueryFilter, QuerySelect, Set, TransactionError,
 };
 use serde::Deserialize;
+use serde_json::json;
 use thiserror::Error;
 
+use crate::unique_name::{name_conflict, same_name};
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct UpdateAttributeUsecase {
   pub id: Uuid,
   pub name: String,
   #[serde(rename(deserialize = "attributeOptions"))]
   pub attribute_options: Vec<AttributeOption>,
+  #[serde(rename(deserialize = "forceArchive"), default)]
+  pub force_archive: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -30,43 +37,206 @@ pub struct AttributeOption {
 
 pub type UpdateAttributePayload = UpdateAttributeUsecase;
 
+impl Validate for UpdateAttributeUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    let duplicates = rules::duplicate_indexes(
+      self
+        .attribute_options
+        .iter()
+        .map(|option| option.value.as_str()),
+    );
+
+    let errors = ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .each("attributeOptions", &self.attribute_options, |option| {
+        ValidationErrors::new().field("value", [rules::required(&option.value)])
+      });
+
+    duplicates
+      .into_iter()
+      .fold(errors, |errors, index| {
+        errors.field(
+          format!("attributeOptions[{}].value", index),
+          [Err("duplicate")],
+        )
+      })
+      .into_result()
+  }
+}
+
 #[derive(Error, Debug)]
 pub enum UpdateAttributeError {
-  #[error("internal_server_error")]
-  InternalServerError(#[from] TransactionError<DbErr>),
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+
+  #[error("attribute_option_belongs_to_other_attribute")]
+  ForeignOptions(Vec<usize>),
+
+  #[error("attribute_options_in_use")]
+  OptionsInUse(Vec<Uuid>),
+
+  #[error("attribute_option_already_exists")]
+  OptionValueConflict { index: usize, existing_id: Uuid },
+}
+
+impl From<TransactionError<UpdateAttributeError>> for UpdateAttributeError {
+  fn from(err: TransactionError<UpdateAttributeError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdateAttributeError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
 }
 
 impl IntoResponse for UpdateAttributeError {
   fn into_response(self) -> Response {
-    let (status, code) = match self {
-      UpdateAttributeError::InternalServerError(_) => {
-        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string())
+    let error = match self {
+      UpdateAttributeError::Database(err) => AppError::from(err),
+      UpdateAttributeError::NameConflict(existing_id) => {
+        name_conflict(self.to_string(), existing_id)
+      }
+      UpdateAttributeError::ForeignOptions(ref indexes) => {
+        indexes
+          .iter()
+          .fold(AppError::validation(self.to_string()), |error, index| {
+            error.with_field(
+              format!("attributeOptions[{}].id", index),
+              "belongs_to_other_attribute",
+            )
+          })
+      }
+      UpdateAttributeError::OptionsInUse(ref option_ids) => AppError::conflict(self.to_string())
+        .with_field("attributeOptions", "in_use")
+        .with_details(json!({ "optionIds": option_ids })),
+      UpdateAttributeError::OptionValueConflict { index, existing_id } => {
+        AppError::conflict(self.to_string())
+          .with_field(
+            format!("attributeOptions[{}].value", index),
+            "already_exists",
+          )
+          .with_details(json!({ "existingId": existing_id }))
       }
     };
 
-    (status, error(code, Some("create_attribute".to_string()))).into_response()
+    error.with_source("update_attribute").into_response()
   }
 }
 
 impl UpdateAttributeUsecase {
   pub async fn invoke(
     &self,
-    db: impl ConnectionTrait + TransactionTrait,
+    db: impl WriteConnection,
   ) -> Result<attribute::Model, UpdateAttributeError> {
     let payload = self.clone();
 
     let attribute = db
-      .transaction::<_, attribute::Model, DbErr>(move |txn| {
+      .transaction::<_, attribute::Model, UpdateAttributeError>(move |txn| {
         Box::pin(async move {
           let id = payload.id;
-          let name = payload.name.to_string();
+          let existing = attribute::Entity::find()
+            .filter(same_name(attribute::Column::Name, &payload.name))
+            .filter(attribute::Column::Id.ne(id))
+            .one(txn)
+            .await?;
+          if let Some(existing) = existing {
+            return Err(UpdateAttributeError::NameConflict(existing.id));
+          }
+
+          let kept_ids = payload
+            .attribute_options
+            .iter()
+            .filter_map(|option| option.id)
+            .collect::<Vec<_>>();
+
+          let foreign_ids = attribute_option::Entity::find()
+            .select_only()
+            .column(attribute_option::Column::Id)
+            .filter(attribute_option::Column::Id.is_in(kept_ids.clone()))
+            .filter(attribute_option::Column::AttributeId.ne(id))
+            .into_tuple::<Uuid>()
+            .all(txn)
+            .await?;
+          if !foreign_ids.is_empty() {
+            let indexes = payload
+              .attribute_options
+              .iter()
+              .enumerate()
+              .filter(|(_, option)| option.id.is_some_and(|id| foreign_ids.contains(&id)))
+              .map(|(index, _)| index)
+              .collect();
+            return Err(UpdateAttributeError::ForeignOptions(indexes));
+          }
+
           let attribute = Attribute {
             id: Set(id),
-            name: Set(name),
+            name: Set(payload.name.to_string()),
             ..Default::default()
           };
           let attribute = attribute.update(txn).await?;
 
+          let removed_ids = attribute_option::Entity::find()
+            .select_only()
+            .column(attribute_option::Column::Id)
+            .filter(attribute_option::Column::AttributeId.eq(id))
+            .filter(attribute_option::Column::Id.is_not_in(kept_ids.clone()))
+            .filter(attribute_option::Column::ArchivedAt.is_null())
+            .into_tuple::<Uuid>()
+            .all(txn)
+            .await?;
+          let in_use_ids = product_combination::Entity::find()
+            .select_only()
+            .column(product_combination::Column::AttributeOptionId)
+            .distinct()
+            .filter(product_combination::Column::AttributeOptionId.is_in(removed_ids.clone()))
+            .into_tuple::<Uuid>()
+            .all(txn)
+            .await?;
+
+          if !in_use_ids.is_empty() {
+            if !payload.force_archive {
+              return Err(UpdateAttributeError::OptionsInUse(in_use_ids));
+            }
+
+            attribute_option::Entity::update_many()
+              .col_expr(
+                attribute_option::Column::ArchivedAt,
+                Expr::current_timestamp().into(),
+              )
+              .filter(attribute_option::Column::Id.is_in(in_use_ids.clone()))
+              .exec(txn)
+              .await?;
+          }
+
+          let unused_ids = removed_ids
+            .into_iter()
+            .filter(|option_id| !in_use_ids.contains(option_id))
+            .collect::<Vec<_>>();
+          attribute_option::Entity::delete_many()
+            .filter(attribute_option::Column::Id.is_in(unused_ids))
+            .exec(txn)
+            .await?;
+
+          // Options left out but kept archived still hold their value; sending
+          // the archived option back restores it instead.
+          for (index, option) in payload.attribute_options.iter().enumerate() {
+            let existing = attribute_option::Entity::find()
+              .filter(attribute_option::Column::AttributeId.eq(id))
+              .filter(attribute_option::Column::Id.is_not_in(kept_ids.clone()))
+              .filter(same_name(attribute_option::Column::Value, &option.value))
+              .one(txn)
+              .await?;
+            if let Some(existing) = existing {
+              return Err(UpdateAttributeError::OptionValueConflict {
+                index,
+                existing_id: existing.id,
+              });
+            }
+          }
+
+          // Sending an archived option back restores it.
           let options =
             payload
               .attribute_options
@@ -75,13 +245,17 @@ impl UpdateAttributeUsecase {
                 id: Set(option.id.unwrap_or(Uuid::new())),
                 value: Set(option.value.to_string()),
                 attribute_id: Set(id),
+                archived_at: Set(None),
               });
 
           attribute_option::Entity::insert_many(options)
             .on_empty_do_nothing()
             .on_conflict(
               OnConflict::columns([attribute_option::Column::Id])
-                .update_column(attribute_option::Column::Value)
+                .update_columns([
+                  attribute_option::Column::Value,
+                  attribute_option::Column::ArchivedAt,
+                ])
                 .to_owned(),
             )
             .exec(txn)
@@ -94,3 +268,71 @@ impl UpdateAttributeUsecase {
     Ok(attribute)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use chrono::Utc;
+  use infra::db::WriteDb;
+  use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult, Value};
+
+  use super::*;
+
+  #[tokio::test]
+  async fn new_value_colliding_with_archived_option_names_it() {
+    let attribute = attribute::Model {
+      id: Uuid::new(),
+      name: "Màu".to_string(),
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    };
+    let archived = attribute_option::Model {
+      id: Uuid::new(),
+      value: "Đỏ".to_string(),
+      attribute_id: attribute.id,
+      archived_at: Some(Utc::now().into()),
+    };
+    let no_ids: Vec<BTreeMap<&str, Value>> = vec![];
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([Vec::<attribute::Model>::new()])
+      .append_query_results([no_ids.clone()])
+      .append_query_results([vec![attribute.clone()]])
+      .append_query_results([no_ids.clone(), no_ids])
+      .append_exec_results([MockExecResult {
+        last_insert_id: 0,
+        rows_affected: 0,
+      }])
+      .append_query_results([
+        Vec::<attribute_option::Model>::new(),
+        vec![archived.clone()],
+      ])
+      .into_connection();
+
+    let err = UpdateAttributeUsecase {
+      id: attribute.id,
+      name: attribute.name,
+      attribute_options: vec![
+        AttributeOption {
+          id: None,
+          value: "Xanh".to_string(),
+        },
+        AttributeOption {
+          id: None,
+          value: "do".to_string(),
+        },
+      ],
+      force_archive: false,
+    }
+    .invoke(WriteDb::new(db))
+    .await
+    .err()
+    .unwrap();
+
+    assert!(matches!(
+      err,
+      UpdateAttributeError::OptionValueConflict { index: 1, existing_id } if existing_id == archived.id
+    ));
+  }
+}
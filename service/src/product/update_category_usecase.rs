@@ -0,0 +1,215 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  product::category::{self, ActiveModel as Category, CostingMethod},
+  tax::tax::TaxUse,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  util::double_option,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, NotSet, QueryFilter, Set, TransactionError,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  tax::line_taxes::{check_default_taxes, TaxInputError},
+  unique_name::{name_conflict, same_name},
+};
+
+use super::category_hierarchy::{parent_scope, CategoryHierarchy};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateCategoryUsecase {
+  pub id: Uuid,
+  pub name: String,
+  #[serde(rename(deserialize = "parentCategoryId"))]
+  pub parent_category_id: Option<Uuid>,
+  /// Left unchanged when omitted, cleared when `null`.
+  #[serde(
+    rename(deserialize = "salesTaxId"),
+    default,
+    deserialize_with = "double_option"
+  )]
+  pub sales_tax_id: Option<Option<Uuid>>,
+  #[serde(
+    rename(deserialize = "purchaseTaxId"),
+    default,
+    deserialize_with = "double_option"
+  )]
+  pub purchase_tax_id: Option<Option<Uuid>>,
+  /// Left unchanged when omitted.
+  #[serde(rename(deserialize = "costingMethod"), default)]
+  pub costing_method: Option<CostingMethod>,
+}
+
+pub type UpdateCategoryPayload = UpdateCategoryUsecase;
+
+impl Validate for UpdateCategoryUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateCategoryError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("parent_category_not_found")]
+  ParentCategoryNotFound,
+
+  #[error("cyclic_parent_category")]
+  CyclicParentCategory,
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+}
+
+impl From<TransactionError<UpdateCategoryError>> for UpdateCategoryError {
+  fn from(err: TransactionError<UpdateCategoryError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdateCategoryError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for UpdateCategoryError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdateCategoryError::Database(err) => AppError::from(err),
+      UpdateCategoryError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateCategoryError::ParentCategoryNotFound | UpdateCategoryError::CyclicParentCategory => {
+        AppError::validation(self.to_string()).with_field("parentCategoryId", self.to_string())
+      }
+      UpdateCategoryError::NameConflict(existing_id) => {
+        name_conflict(self.to_string(), existing_id)
+      }
+      UpdateCategoryError::Tax(err) => AppError::from(err),
+    };
+
+    error.with_source("update_category").into_response()
+  }
+}
+
+impl UpdateCategoryUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<category::Model, UpdateCategoryError> {
+    let payload = self.clone();
+
+    // The hierarchy stays locked until the update commits, so two categories
+    // cannot be moved under each other concurrently.
+    let category = db
+      .transaction::<_, category::Model, UpdateCategoryError>(move |txn| {
+        Box::pin(async move {
+          let hierarchy = CategoryHierarchy::load_for_update(txn).await?;
+
+          if hierarchy.get(payload.id).is_none() {
+            return Err(UpdateCategoryError::RecordNotFound);
+          }
+
+          if let Some(parent_category_id) = payload.parent_category_id {
+            if hierarchy.get(parent_category_id).is_none() {
+              return Err(UpdateCategoryError::ParentCategoryNotFound);
+            }
+            if hierarchy.would_create_cycle(payload.id, parent_category_id) {
+              return Err(UpdateCategoryError::CyclicParentCategory);
+            }
+          }
+
+          let existing = category::Entity::find()
+            .filter(same_name(category::Column::Name, &payload.name))
+            .filter(category::Column::Id.ne(payload.id))
+            .filter(parent_scope(payload.parent_category_id))
+            .one(txn)
+            .await?;
+          if let Some(existing) = existing {
+            return Err(UpdateCategoryError::NameConflict(existing.id));
+          }
+          check_default_taxes(
+            txn,
+            [
+              ("salesTaxId", payload.sales_tax_id.flatten(), TaxUse::Sales),
+              (
+                "purchaseTaxId",
+                payload.purchase_tax_id.flatten(),
+                TaxUse::Purchase,
+              ),
+            ],
+          )
+          .await?;
+
+          let category = payload.changes().update(txn).await?;
+
+          Ok(category)
+        })
+      })
+      .await?;
+
+    Ok(category)
+  }
+
+  fn changes(&self) -> Category {
+    Category {
+      id: Set(self.id),
+      name: Set(self.name.clone()),
+      parent_category_id: Set(self.parent_category_id),
+      costing_method: self.costing_method.map_or(NotSet, Set),
+      sales_tax_id: self.sales_tax_id.map_or(NotSet, Set),
+      purchase_tax_id: self.purchase_tax_id.map_or(NotSet, Set),
+      ..Default::default()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  fn payload(json: serde_json::Value) -> UpdateCategoryUsecase {
+    serde_json::from_value(json).unwrap()
+  }
+
+  #[test]
+  fn update_without_taxes_leaves_them_unchanged() {
+    let changes =
+      payload(json!({ "id": Uuid::new(), "name": "Paper", "parentCategoryId": null })).changes();
+
+    assert_eq!(changes.sales_tax_id, NotSet);
+    assert_eq!(changes.purchase_tax_id, NotSet);
+    assert_eq!(changes.costing_method, NotSet);
+  }
+
+  #[test]
+  fn update_with_null_taxes_clears_them() {
+    let tax_id = Uuid::new();
+    let changes = payload(json!({
+      "id": Uuid::new(),
+      "name": "Paper",
+      "parentCategoryId": null,
+      "salesTaxId": tax_id,
+      "purchaseTaxId": null,
+    }))
+    .changes();
+
+    assert_eq!(changes.sales_tax_id, Set(Some(tax_id)));
+    assert_eq!(changes.purchase_tax_id, Set(None));
+  }
+}
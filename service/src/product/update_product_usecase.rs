@@ -0,0 +1,642 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::response::{IntoResponse, Response};
+use domain::{
+  inventory::stock_move,
+  product::{
+    product::{self, AttributeWithOptionDTO},
+    product_combination, product_template,
+  },
+  tax::tax::TaxUse,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  util::double_option,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{Decimal, Expr},
+  ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, NotSet, PaginatorTrait, QueryFilter, Set,
+  TransactionError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use super::create_product_usecase::{tracking_rule, Variant, VariantAttributeOption};
+use super::product_combinations::load_product_combinations;
+use super::template_uoms::{uom_violations_error, validate_template_uoms, UomViolation};
+use super::variant_validation::{
+  load_option_attributes, validate_variant_combinations, VariantViolation,
+};
+use crate::{
+  currency::currency_conversion::{currency_or_base, CurrencyConversionError},
+  tax::line_taxes::{check_default_taxes, TaxInputError},
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateVariant {
+  pub id: Option<Uuid>,
+  pub price: Decimal,
+  pub cost: Option<Decimal>,
+  #[serde(rename(deserialize = "priceUomId"), default)]
+  pub price_uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "variantAttributeOptions"), default)]
+  pub attribute_options: Vec<VariantAttributeOption>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateProductUsecase {
+  pub id: Uuid,
+  pub name: String,
+  pub description: Option<String>,
+  #[serde(rename(deserialize = "productType"))]
+  pub product_type: product_template::ProductType,
+  #[serde(rename(deserialize = "productSubtype"))]
+  pub product_subtype: product_template::ProductSubtype,
+  #[serde(rename(deserialize = "isTrackInventory"))]
+  pub is_track_inventory: bool,
+  /// Left unchanged when omitted.
+  #[serde(default)]
+  pub tracking: Option<product_template::Tracking>,
+  #[serde(rename(deserialize = "uomId"))]
+  pub uom_id: Uuid,
+  #[serde(rename(deserialize = "purchaseUomId"), default)]
+  pub purchase_uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "salesUomId"), default)]
+  pub sales_uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "categoryId"))]
+  pub category_id: Option<Uuid>,
+  /// Left unchanged when omitted, cleared when `null`.
+  #[serde(
+    rename(deserialize = "salesTaxId"),
+    default,
+    deserialize_with = "double_option"
+  )]
+  pub sales_tax_id: Option<Option<Uuid>>,
+  #[serde(
+    rename(deserialize = "purchaseTaxId"),
+    default,
+    deserialize_with = "double_option"
+  )]
+  pub purchase_tax_id: Option<Option<Uuid>>,
+  /// Left unchanged when omitted.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  pub variants: Vec<UpdateVariant>,
+}
+
+pub type UpdateProductPayload = UpdateProductUsecase;
+
+impl Validate for UpdateProductUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .field("name", [rules::required(&self.name)])
+      .field(
+        "tracking",
+        self
+          .tracking
+          .map(|tracking| tracking_rule(tracking, &self.product_type, self.is_track_inventory)),
+      )
+      .field("variants", [rules::not_empty(&self.variants)])
+      .each("variants", &self.variants, |variant| {
+        ValidationErrors::new()
+          .field("price", [rules::non_negative(variant.price)])
+          .field("cost", variant.cost.map(rules::non_negative))
+      })
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateProductError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("variant_not_found")]
+  VariantNotFound(Uuid),
+
+  #[error("product_requires_variant")]
+  NoActiveVariants,
+
+  #[error("invalid_variant_combinations")]
+  InvalidVariants(Vec<VariantViolation>),
+
+  #[error("invalid_uoms")]
+  InvalidUoms(Vec<UomViolation>),
+
+  #[error("tracking_locked_by_stock_moves")]
+  TrackingLocked,
+
+  #[error("uom_locked_by_stock_moves")]
+  UomLocked,
+
+  #[error("product_type_locked_by_stock_moves")]
+  ProductTypeLocked,
+
+  #[error("track_inventory_locked_by_stock_moves")]
+  TrackInventoryLocked,
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl From<TransactionError<UpdateProductError>> for UpdateProductError {
+  fn from(err: TransactionError<UpdateProductError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdateProductError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for UpdateProductError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdateProductError::Database(err) => AppError::from(err),
+      UpdateProductError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateProductError::VariantNotFound(id) => {
+        AppError::validation(self.to_string()).with_details(json!({ "variantId": id }))
+      }
+      UpdateProductError::NoActiveVariants => {
+        AppError::validation(self.to_string()).with_field("variants", self.to_string())
+      }
+      UpdateProductError::InvalidVariants(ref violations) => {
+        AppError::validation(self.to_string()).with_details(json!({ "variants": violations }))
+      }
+      UpdateProductError::InvalidUoms(ref violations) => {
+        uom_violations_error(self.to_string(), violations)
+      }
+      UpdateProductError::TrackingLocked => {
+        AppError::conflict(self.to_string()).with_field("tracking", "locked")
+      }
+      UpdateProductError::UomLocked => {
+        AppError::conflict(self.to_string()).with_field("uomId", "locked")
+      }
+      UpdateProductError::ProductTypeLocked => {
+        AppError::conflict(self.to_string()).with_field("productType", "locked")
+      }
+      UpdateProductError::TrackInventoryLocked => {
+        AppError::conflict(self.to_string()).with_field("isTrackInventory", "locked")
+      }
+      UpdateProductError::Tax(err) => AppError::from(err),
+      UpdateProductError::Currency(err) => AppError::from(err),
+    };
+
+    error.with_source("update_product").into_response()
+  }
+}
+
+impl UpdateProductUsecase {
+  /// Updates the template and syncs its variants: variants with an `id` are
+  /// repriced, variants without one are created, and active variants missing
+  /// from the payload are retired by setting `archived_at`.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<product_template::Model, UpdateProductError> {
+    let payload = self.clone();
+
+    let product_template = db
+      .transaction::<_, product_template::Model, UpdateProductError>(move |txn| {
+        Box::pin(async move {
+          let existing_template = product_template::Entity::find_by_id(payload.id)
+            .one(txn)
+            .await?
+            .ok_or(UpdateProductError::RecordNotFound)?;
+
+          // Units left out of the payload keep their current value.
+          let purchase_uom_id = payload
+            .purchase_uom_id
+            .unwrap_or(existing_template.purchase_uom_id);
+          let sales_uom_id = payload
+            .sales_uom_id
+            .unwrap_or(existing_template.sales_uom_id);
+          let related_uoms = [
+            ("purchaseUomId".to_string(), purchase_uom_id),
+            ("salesUomId".to_string(), sales_uom_id),
+          ]
+          .into_iter()
+          .chain(
+            payload
+              .variants
+              .iter()
+              .enumerate()
+              .filter_map(|(index, variant)| {
+                variant
+                  .price_uom_id
+                  .map(|uom_id| (format!("variants[{}].priceUomId", index), uom_id))
+              }),
+          )
+          .collect::<Vec<_>>();
+          let violations = validate_template_uoms(txn, payload.uom_id, &related_uoms).await?;
+          if !violations.is_empty() {
+            return Err(UpdateProductError::InvalidUoms(violations));
+          }
+          check_default_taxes(
+            txn,
+            [
+              ("salesTaxId", payload.sales_tax_id.flatten(), TaxUse::Sales),
+              (
+                "purchaseTaxId",
+                payload.purchase_tax_id.flatten(),
+                TaxUse::Purchase,
+              ),
+            ],
+          )
+          .await?;
+          let currency_id = match payload.currency_id {
+            Some(currency_id) => currency_or_base(txn, Some(currency_id)).await?,
+            None => existing_template.currency_id,
+          };
+
+          if let Some(locked) = payload.locked_by_stock_moves(&existing_template) {
+            let moves = stock_move::Entity::find()
+              .inner_join(product::Entity)
+              .filter(product::Column::ProductTemplateId.eq(payload.id))
+              .count(txn)
+              .await?;
+            if moves > 0 {
+              return Err(locked);
+            }
+          }
+
+          let product_template = payload
+            .template_changes(purchase_uom_id, sales_uom_id, currency_id)
+            .update(txn)
+            .await?;
+
+          let active_products = product::Entity::find()
+            .filter(product::Column::ProductTemplateId.eq(product_template.id))
+            .filter(product::Column::ArchivedAt.is_null())
+            .all(txn)
+            .await?;
+          let active_ids = active_products
+            .iter()
+            .map(|product| product.id)
+            .collect::<HashSet<_>>();
+
+          // Added variants must cover the same attributes as the kept ones
+          // without repeating a combination, as on creation.
+          if payload.variants.iter().any(|variant| variant.id.is_none()) {
+            let variant_ids = payload
+              .variants
+              .iter()
+              .filter_map(|variant| variant.id)
+              .collect::<Vec<_>>();
+            if let Some(id) = variant_ids.iter().find(|id| !active_ids.contains(id)) {
+              return Err(UpdateProductError::VariantNotFound(*id));
+            }
+            let kept_combinations = load_product_combinations(txn, &variant_ids).await?;
+            let variants = resulting_variants(&payload.variants, &kept_combinations);
+            let option_attributes = load_option_attributes(txn, &variants).await?;
+            let violations = validate_variant_combinations(&variants, &option_attributes);
+            if !violations.is_empty() {
+              return Err(UpdateProductError::InvalidVariants(violations));
+            }
+          }
+
+          let mut kept_ids = HashSet::new();
+
+          for variant in payload.variants.iter() {
+            match variant.id {
+              Some(id) => {
+                if !active_ids.contains(&id) {
+                  return Err(UpdateProductError::VariantNotFound(id));
+                }
+                let product = product::ActiveModel {
+                  id: Set(id),
+                  price: Set(variant.price),
+                  price_uom_id: variant.price_uom_id.map_or(NotSet, Set),
+                  cost: variant.cost.map_or(NotSet, Set),
+                  ..Default::default()
+                };
+                product.update(txn).await?;
+                kept_ids.insert(id);
+              }
+              None => {
+                let product = product::ActiveModel {
+                  product_template_id: Set(product_template.id),
+                  price: Set(variant.price),
+                  price_uom_id: Set(variant.price_uom_id.unwrap_or(sales_uom_id)),
+                  cost: Set(variant.cost.unwrap_or_default()),
+                  is_product_variant: Set(!variant.attribute_options.is_empty()),
+                  ..Default::default()
+                };
+                let product = product.insert(txn).await?;
+                let product_combinations = variant
+                  .attribute_options
+                  .iter()
+                  .map(|option| product_combination::ActiveModel {
+                    product_id: Set(product.id),
+                    attribute_option_id: Set(option.option.id),
+                  })
+                  .collect::<Vec<_>>();
+
+                product_combination::Entity::insert_many(product_combinations)
+                  .on_empty_do_nothing()
+                  .exec(txn)
+                  .await?;
+                kept_ids.insert(product.id);
+              }
+            }
+          }
+
+          if kept_ids.is_empty() {
+            return Err(UpdateProductError::NoActiveVariants);
+          }
+
+          let retired_ids = active_ids
+            .difference(&kept_ids)
+            .copied()
+            .collect::<Vec<_>>();
+          if !retired_ids.is_empty() {
+            product::Entity::update_many()
+              .col_expr(product::Column::ArchivedAt, Expr::cust("CURRENT_TIMESTAMP"))
+              .col_expr(product::Column::UpdatedAt, Expr::cust("CURRENT_TIMESTAMP"))
+              .filter(product::Column::Id.is_in(retired_ids))
+              .exec(txn)
+              .await?;
+          }
+
+          Ok(product_template)
+        })
+      })
+      .await?;
+
+    Ok(product_template)
+  }
+
+  /// Error the update fails with if the template's variants already have
+  /// stock moves. Moves recorded under one tracking mode would not satisfy
+  /// another, their quantities are in the stock unit they were made in, and
+  /// on-hand quantities and valuation only exist for tracked goods.
+  fn locked_by_stock_moves(
+    &self,
+    existing_template: &product_template::Model,
+  ) -> Option<UpdateProductError> {
+    if self
+      .tracking
+      .is_some_and(|tracking| tracking != existing_template.tracking)
+    {
+      Some(UpdateProductError::TrackingLocked)
+    } else if self.uom_id != existing_template.uom_id {
+      Some(UpdateProductError::UomLocked)
+    } else if self.product_type != existing_template.product_type {
+      Some(UpdateProductError::ProductTypeLocked)
+    } else if self.is_track_inventory != existing_template.is_track_inventory {
+      Some(UpdateProductError::TrackInventoryLocked)
+    } else {
+      None
+    }
+  }
+
+  fn template_changes(
+    &self,
+    purchase_uom_id: Uuid,
+    sales_uom_id: Uuid,
+    currency_id: Uuid,
+  ) -> product_template::ActiveModel {
+    product_template::ActiveModel {
+      id: Set(self.id),
+      name: Set(self.name.clone()),
+      description: self.description.clone().map_or(NotSet, Set),
+      product_type: Set(self.product_type.clone()),
+      product_subtype: Set(self.product_subtype.clone()),
+      is_track_inventory: Set(self.is_track_inventory),
+      tracking: self.tracking.map_or(NotSet, Set),
+      uom_id: Set(self.uom_id),
+      purchase_uom_id: Set(purchase_uom_id),
+      sales_uom_id: Set(sales_uom_id),
+      category_id: Set(self.category_id),
+      sales_tax_id: self.sales_tax_id.map_or(NotSet, Set),
+      purchase_tax_id: self.purchase_tax_id.map_or(NotSet, Set),
+      currency_id: Set(currency_id),
+      ..Default::default()
+    }
+  }
+}
+
+/// Variants the template ends up with, in payload order: kept variants with
+/// their stored combinations and new ones with the options they were sent
+/// with.
+fn resulting_variants(
+  variants: &[UpdateVariant],
+  kept_combinations: &HashMap<Uuid, Vec<AttributeWithOptionDTO>>,
+) -> Vec<Variant> {
+  variants
+    .iter()
+    .map(|variant| Variant {
+      price: variant.price,
+      cost: variant.cost,
+      price_uom_id: variant.price_uom_id,
+      attribute_options: match variant.id {
+        Some(id) => kept_combinations
+          .get(&id)
+          .into_iter()
+          .flatten()
+          .map(|combination| VariantAttributeOption {
+            attribute: combination.attribute.clone(),
+            option: combination.option.clone(),
+          })
+          .collect(),
+        None => variant.attribute_options.clone(),
+      },
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use domain::product::{attribute, attribute_option};
+  use serde_json::json;
+
+  use super::*;
+
+  fn payload(taxes: serde_json::Value) -> UpdateProductUsecase {
+    let mut payload = json!({
+      "id": Uuid::new(),
+      "name": "Carton box",
+      "productType": "goods",
+      "productSubtype": "normal",
+      "isTrackInventory": true,
+      "uomId": Uuid::new(),
+      "categoryId": null,
+      "variants": [{ "price": 1 }],
+    });
+    payload
+      .as_object_mut()
+      .unwrap()
+      .extend(taxes.as_object().unwrap().clone());
+
+    serde_json::from_value(payload).unwrap()
+  }
+
+  fn template_changes(payload: &UpdateProductUsecase) -> product_template::ActiveModel {
+    payload.template_changes(Uuid::new(), Uuid::new(), Uuid::new())
+  }
+
+  #[test]
+  fn update_without_taxes_leaves_them_unchanged() {
+    let changes = template_changes(&payload(json!({})));
+
+    assert_eq!(changes.sales_tax_id, NotSet);
+    assert_eq!(changes.purchase_tax_id, NotSet);
+  }
+
+  #[test]
+  fn update_with_taxes_sets_or_clears_them() {
+    let tax_id = Uuid::new();
+    let changes = template_changes(&payload(
+      json!({ "salesTaxId": tax_id, "purchaseTaxId": null }),
+    ));
+
+    assert_eq!(changes.sales_tax_id, Set(Some(tax_id)));
+    assert_eq!(changes.purchase_tax_id, Set(None));
+  }
+
+  fn existing_template(payload: &UpdateProductUsecase) -> product_template::Model {
+    product_template::Model {
+      id: payload.id,
+      name: payload.name.clone(),
+      description: String::new(),
+      uom_id: payload.uom_id,
+      purchase_uom_id: payload.uom_id,
+      sales_uom_id: payload.uom_id,
+      category_id: None,
+      sales_tax_id: None,
+      purchase_tax_id: None,
+      currency_id: Uuid::new(),
+      product_type: payload.product_type.clone(),
+      product_subtype: payload.product_subtype.clone(),
+      is_track_inventory: payload.is_track_inventory,
+      tracking: product_template::Tracking::None,
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    }
+  }
+
+  #[test]
+  fn unchanged_stock_fields_need_no_stock_move_check() {
+    let payload = payload(json!({}));
+
+    assert!(payload
+      .locked_by_stock_moves(&existing_template(&payload))
+      .is_none());
+  }
+
+  #[test]
+  fn product_type_and_inventory_tracking_are_locked_by_stock_moves() {
+    let payload = payload(json!({}));
+    let service = product_template::Model {
+      product_type: product_template::ProductType::Service,
+      ..existing_template(&payload)
+    };
+    let untracked = product_template::Model {
+      is_track_inventory: false,
+      ..existing_template(&payload)
+    };
+
+    assert!(matches!(
+      payload.locked_by_stock_moves(&service),
+      Some(UpdateProductError::ProductTypeLocked)
+    ));
+    assert!(matches!(
+      payload.locked_by_stock_moves(&untracked),
+      Some(UpdateProductError::TrackInventoryLocked)
+    ));
+  }
+
+  fn attribute_option(attribute_id: Uuid, option_id: Uuid) -> VariantAttributeOption {
+    VariantAttributeOption {
+      attribute: attribute::PartialModel {
+        id: attribute_id,
+        name: "Color".to_string(),
+      },
+      option: attribute_option::PartialModel {
+        id: option_id,
+        value: option_id.to_string(),
+      },
+    }
+  }
+
+  fn update_variant(
+    id: Option<Uuid>,
+    attribute_options: Vec<VariantAttributeOption>,
+  ) -> UpdateVariant {
+    UpdateVariant {
+      id,
+      price: Decimal::ONE,
+      cost: None,
+      price_uom_id: None,
+      attribute_options,
+    }
+  }
+
+  #[test]
+  fn resulting_variants_combine_kept_and_new_variants() {
+    let [color, red, blue] = [(); 3].map(|_| Uuid::new());
+    let kept_id = Uuid::new();
+    let kept = attribute_option(color, red);
+    let kept_combinations = HashMap::from([(
+      kept_id,
+      vec![AttributeWithOptionDTO {
+        attribute: kept.attribute.clone(),
+        option: kept.option.clone(),
+      }],
+    )]);
+    let payload_variants = [
+      update_variant(Some(kept_id), vec![]),
+      update_variant(None, vec![attribute_option(color, blue)]),
+    ];
+
+    let variants = resulting_variants(&payload_variants, &kept_combinations);
+    let option_ids = variants
+      .iter()
+      .map(|variant| {
+        variant
+          .attribute_options
+          .iter()
+          .map(|option| option.option.id)
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(option_ids, vec![vec![red], vec![blue]]);
+  }
+
+  #[test]
+  fn new_variant_repeating_kept_combination_is_rejected() {
+    let [color, red] = [(); 2].map(|_| Uuid::new());
+    let kept_id = Uuid::new();
+    let kept = attribute_option(color, red);
+    let kept_combinations = HashMap::from([(
+      kept_id,
+      vec![AttributeWithOptionDTO {
+        attribute: kept.attribute.clone(),
+        option: kept.option.clone(),
+      }],
+    )]);
+    let payload_variants = [
+      update_variant(Some(kept_id), vec![]),
+      update_variant(None, vec![attribute_option(color, red)]),
+    ];
+
+    let variants = resulting_variants(&payload_variants, &kept_combinations);
+    let violations = validate_variant_combinations(&variants, &HashMap::from([(red, color)]));
+
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].variant_index, 1);
+    assert_eq!(violations[0].conflicts_with, Some(0));
+  }
+}
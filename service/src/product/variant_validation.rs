@@ -0,0 +1,289 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use domain::product::attribute_option;
+use infra::uuid::Uuid;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use serde::Serialize;
+
+use super::create_product_usecase::Variant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantViolationReason {
+  UnknownOption,
+  OptionAttributeMismatch,
+  DuplicateAttribute,
+  AttributeSetMismatch,
+  DuplicateCombination,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariantViolation {
+  pub variant_index: usize,
+  pub reason: VariantViolationReason,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub attribute_id: Option<Uuid>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub option_id: Option<Uuid>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub conflicts_with: Option<usize>,
+}
+
+impl VariantViolation {
+  fn new(variant_index: usize, reason: VariantViolationReason) -> Self {
+    Self {
+      variant_index,
+      reason,
+      attribute_id: None,
+      option_id: None,
+      conflicts_with: None,
+    }
+  }
+}
+
+/// Maps every attribute option referenced by `variants` to the attribute it
+/// actually belongs to. Options missing from the database are left out.
+pub async fn load_option_attributes<C>(
+  db: &C,
+  variants: &[Variant],
+) -> Result<HashMap<Uuid, Uuid>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  let option_ids = variants
+    .iter()
+    .flat_map(|variant| variant.attribute_options.iter())
+    .map(|attribute_option| attribute_option.option.id)
+    .collect::<HashSet<_>>();
+
+  if option_ids.is_empty() {
+    return Ok(HashMap::new());
+  }
+
+  let options = attribute_option::Entity::find()
+    .filter(attribute_option::Column::Id.is_in(option_ids))
+    .all(db)
+    .await?;
+
+  Ok(
+    options
+      .into_iter()
+      .map(|option| (option.id, option.attribute_id))
+      .collect(),
+  )
+}
+
+/// Checks that every option belongs to its stated attribute, that a variant
+/// uses each attribute at most once, that all variants cover the same
+/// attribute set as the first one and that no two variants share a combination.
+pub fn validate_variant_combinations(
+  variants: &[Variant],
+  option_attributes: &HashMap<Uuid, Uuid>,
+) -> Vec<VariantViolation> {
+  let mut violations = vec![];
+  let mut reference_attributes: Option<BTreeSet<Uuid>> = None;
+  let mut combinations: HashMap<BTreeSet<Uuid>, usize> = HashMap::new();
+
+  for (index, variant) in variants.iter().enumerate() {
+    let mut attributes = BTreeSet::new();
+    let mut combination = BTreeSet::new();
+
+    for attribute_option in variant.attribute_options.iter() {
+      let attribute_id = attribute_option.attribute.id;
+      let option_id = attribute_option.option.id;
+
+      match option_attributes.get(&option_id) {
+        None => violations.push(VariantViolation {
+          attribute_id: Some(attribute_id),
+          option_id: Some(option_id),
+          ..VariantViolation::new(index, VariantViolationReason::UnknownOption)
+        }),
+        Some(actual_attribute_id) if *actual_attribute_id != attribute_id => {
+          violations.push(VariantViolation {
+            attribute_id: Some(attribute_id),
+            option_id: Some(option_id),
+            ..VariantViolation::new(index, VariantViolationReason::OptionAttributeMismatch)
+          })
+        }
+        Some(_) => {}
+      }
+
+      if !attributes.insert(attribute_id) {
+        violations.push(VariantViolation {
+          attribute_id: Some(attribute_id),
+          ..VariantViolation::new(index, VariantViolationReason::DuplicateAttribute)
+        });
+      }
+      combination.insert(option_id);
+    }
+
+    match &reference_attributes {
+      None => reference_attributes = Some(attributes),
+      Some(reference) if *reference != attributes => violations.push(VariantViolation {
+        conflicts_with: Some(0),
+        ..VariantViolation::new(index, VariantViolationReason::AttributeSetMismatch)
+      }),
+      Some(_) => {}
+    }
+
+    match combinations.get(&combination) {
+      Some(first_index) => violations.push(VariantViolation {
+        conflicts_with: Some(*first_index),
+        ..VariantViolation::new(index, VariantViolationReason::DuplicateCombination)
+      }),
+      None => {
+        combinations.insert(combination, index);
+      }
+    }
+  }
+
+  violations
+}
+
+#[cfg(test)]
+mod tests {
+  use domain::product::attribute;
+  use sea_orm::prelude::Decimal;
+
+  use super::*;
+  use crate::product::create_product_usecase::VariantAttributeOption;
+
+  struct Catalog {
+    color: Uuid,
+    size: Uuid,
+    red: Uuid,
+    blue: Uuid,
+    small: Uuid,
+    large: Uuid,
+  }
+
+  impl Catalog {
+    fn new() -> Self {
+      Self {
+        color: Uuid::new(),
+        size: Uuid::new(),
+        red: Uuid::new(),
+        blue: Uuid::new(),
+        small: Uuid::new(),
+        large: Uuid::new(),
+      }
+    }
+
+    fn option_attributes(&self) -> HashMap<Uuid, Uuid> {
+      HashMap::from([
+        (self.red, self.color),
+        (self.blue, self.color),
+        (self.small, self.size),
+        (self.large, self.size),
+      ])
+    }
+  }
+
+  fn variant(options: &[(Uuid, Uuid)]) -> Variant {
+    Variant {
+      price: Decimal::ONE,
+      cost: None,
+      price_uom_id: None,
+      attribute_options: options
+        .iter()
+        .map(|&(attribute_id, option_id)| VariantAttributeOption {
+          attribute: attribute::PartialModel {
+            id: attribute_id,
+            name: attribute_id.to_string(),
+          },
+          option: attribute_option::PartialModel {
+            id: option_id,
+            value: option_id.to_string(),
+          },
+        })
+        .collect(),
+    }
+  }
+
+  fn reasons(violations: &[VariantViolation]) -> Vec<(usize, VariantViolationReason)> {
+    violations
+      .iter()
+      .map(|violation| (violation.variant_index, violation.reason))
+      .collect()
+  }
+
+  #[test]
+  fn accepts_distinct_combinations_over_same_attributes() {
+    let c = Catalog::new();
+    let variants = [
+      variant(&[(c.color, c.red), (c.size, c.small)]),
+      variant(&[(c.color, c.red), (c.size, c.large)]),
+      variant(&[(c.color, c.blue), (c.size, c.small)]),
+    ];
+
+    assert!(validate_variant_combinations(&variants, &c.option_attributes()).is_empty());
+  }
+
+  #[test]
+  fn rejects_variant_missing_an_attribute() {
+    let c = Catalog::new();
+    let variants = [
+      variant(&[(c.color, c.red), (c.size, c.small)]),
+      variant(&[(c.color, c.blue)]),
+    ];
+
+    let violations = validate_variant_combinations(&variants, &c.option_attributes());
+
+    assert_eq!(
+      reasons(&violations),
+      vec![(1, VariantViolationReason::AttributeSetMismatch)]
+    );
+    assert_eq!(violations[0].conflicts_with, Some(0));
+  }
+
+  #[test]
+  fn rejects_two_options_of_same_attribute() {
+    let c = Catalog::new();
+    let variants = [variant(&[(c.color, c.red), (c.color, c.blue)])];
+
+    let violations = validate_variant_combinations(&variants, &c.option_attributes());
+
+    assert_eq!(
+      reasons(&violations),
+      vec![(0, VariantViolationReason::DuplicateAttribute)]
+    );
+    assert_eq!(violations[0].attribute_id, Some(c.color));
+  }
+
+  #[test]
+  fn rejects_duplicate_combinations_in_any_order() {
+    let c = Catalog::new();
+    let variants = [
+      variant(&[(c.color, c.red), (c.size, c.small)]),
+      variant(&[(c.size, c.small), (c.color, c.red)]),
+    ];
+
+    let violations = validate_variant_combinations(&variants, &c.option_attributes());
+
+    assert_eq!(
+      reasons(&violations),
+      vec![(1, VariantViolationReason::DuplicateCombination)]
+    );
+    assert_eq!(violations[0].conflicts_with, Some(0));
+  }
+
+  #[test]
+  fn rejects_unknown_and_misattributed_options() {
+    let c = Catalog::new();
+    let unknown = Uuid::new();
+    let variants = [variant(&[(c.color, unknown), (c.size, c.red)])];
+
+    let violations = validate_variant_combinations(&variants, &c.option_attributes());
+
+    assert_eq!(
+      reasons(&violations),
+      vec![
+        (0, VariantViolationReason::UnknownOption),
+        (0, VariantViolationReason::OptionAttributeMismatch),
+      ]
+    );
+    assert_eq!(violations[0].option_id, Some(unknown));
+    assert_eq!(violations[1].option_id, Some(c.red));
+  }
+}
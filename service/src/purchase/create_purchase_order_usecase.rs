@@ -0,0 +1,131 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::purchase::{
+  purchase_order::{self, PurchaseOrderState},
+  purchase_order_line,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone, ActiveModelTrait, DbErr, EntityTrait, Set, TransactionError,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::order_input::{
+  build_lines, find_supplier, validate_lines, PurchaseOrderInputError, PurchaseOrderLine,
+};
+use crate::currency::currency_conversion::DocumentCurrency;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreatePurchaseOrderUsecase {
+  #[serde(rename(deserialize = "partnerId"))]
+  pub partner_id: Uuid,
+  #[serde(rename(deserialize = "orderDate"), default)]
+  pub order_date: Option<DateTimeWithTimeZone>,
+  /// Currency the supplier invoices the order in, the base currency by
+  /// default.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  #[serde(default)]
+  pub note: String,
+  pub lines: Vec<PurchaseOrderLine>,
+}
+
+pub type CreatePurchaseOrderPayload = CreatePurchaseOrderUsecase;
+
+impl Validate for CreatePurchaseOrderUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_lines(&self.lines).into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreatePurchaseOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Input(#[from] PurchaseOrderInputError),
+}
+
+impl From<TransactionError<CreatePurchaseOrderError>> for CreatePurchaseOrderError {
+  fn from(err: TransactionError<CreatePurchaseOrderError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => CreatePurchaseOrderError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for CreatePurchaseOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreatePurchaseOrderError::Database(err) => AppError::from(err),
+      CreatePurchaseOrderError::Input(err) => AppError::from(err),
+    };
+
+    error.with_source("create_purchase_order").into_response()
+  }
+}
+
+impl CreatePurchaseOrderUsecase {
+  /// Creates a request for quotation to the supplier. The reference is
+  /// assigned by the database.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<purchase_order::Model, CreatePurchaseOrderError> {
+    let payload = self.clone();
+
+    let purchase_order = db
+      .transaction::<_, purchase_order::Model, CreatePurchaseOrderError>(move |txn| {
+        Box::pin(async move {
+          let supplier = find_supplier(txn, payload.partner_id).await?;
+
+          let purchase_order_id = Uuid::new();
+          let order_date = payload.order_date.unwrap_or_else(|| Utc::now().into());
+          let currency = DocumentCurrency::load(txn, payload.currency_id, order_date)
+            .await
+            .map_err(PurchaseOrderInputError::from)?;
+          let lines = build_lines(
+            txn,
+            purchase_order_id,
+            supplier.id,
+            order_date,
+            &currency,
+            &payload.lines,
+          )
+          .await?;
+          let amounts = lines.amounts();
+
+          let purchase_order = purchase_order::ActiveModel {
+            id: Set(purchase_order_id),
+            partner_id: Set(supplier.id),
+            state: Set(PurchaseOrderState::Rfq),
+            order_date: Set(order_date),
+            expected_date: Set(lines.expected_date),
+            note: Set(payload.note.trim().to_string()),
+            currency_id: Set(currency.currency.id),
+            amount_untaxed: Set(amounts.amount_untaxed),
+            amount_tax: Set(amounts.amount_tax),
+            amount_total: Set(amounts.amount_total),
+            ..Default::default()
+          };
+          let purchase_order = purchase_order.insert(txn).await?;
+          purchase_order_line::Entity::insert_many(lines.lines)
+            .exec(txn)
+            .await?;
+
+          Ok(purchase_order)
+        })
+      })
+      .await?;
+
+    Ok(purchase_order)
+  }
+}
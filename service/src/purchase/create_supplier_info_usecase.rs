@@ -0,0 +1,261 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  partner::partner,
+  product::{product, product_template},
+  purchase::supplier_info,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  currency::currency_conversion::{currency_or_base, CurrencyConversionError},
+  measurement::{convert_quantity, UomConversionError},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSupplierInfoUsecase {
+  #[serde(rename(deserialize = "partnerId"))]
+  pub partner_id: Uuid,
+  /// Variant the price applies to; exclusive with `product_template_id`,
+  /// which prices every variant of a template.
+  #[serde(rename(deserialize = "productId"), default)]
+  pub product_id: Option<Uuid>,
+  #[serde(rename(deserialize = "productTemplateId"), default)]
+  pub product_template_id: Option<Uuid>,
+  #[serde(rename(deserialize = "supplierSku"), default)]
+  pub supplier_sku: Option<String>,
+  pub price: Decimal,
+  /// Currency the supplier invoices in, the base currency by default.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  /// Defaults to the template's purchase unit.
+  #[serde(rename(deserialize = "uomId"), default)]
+  pub uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "minQuantity"), default)]
+  pub min_quantity: Decimal,
+  #[serde(rename(deserialize = "leadTimeDays"), default)]
+  pub lead_time_days: i32,
+  #[serde(rename(deserialize = "dateStart"), default)]
+  pub date_start: Option<DateTimeWithTimeZone>,
+  #[serde(rename(deserialize = "dateEnd"), default)]
+  pub date_end: Option<DateTimeWithTimeZone>,
+}
+
+pub type CreateSupplierInfoPayload = CreateSupplierInfoUsecase;
+
+impl Validate for CreateSupplierInfoUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_supplier_info(
+      self.price,
+      self.min_quantity,
+      self.lead_time_days,
+      self.date_start,
+      self.date_end,
+    )
+    .field(
+      "productId",
+      [rules::reject_if(
+        self.product_id.is_none() && self.product_template_id.is_none(),
+        "required",
+      )],
+    )
+    .field(
+      "productTemplateId",
+      [rules::reject_if(
+        self.product_id.is_some() && self.product_template_id.is_some(),
+        "not_allowed",
+      )],
+    )
+    .into_result()
+  }
+}
+
+pub(crate) fn validate_supplier_info(
+  price: Decimal,
+  min_quantity: Decimal,
+  lead_time_days: i32,
+  date_start: Option<DateTimeWithTimeZone>,
+  date_end: Option<DateTimeWithTimeZone>,
+) -> ValidationErrors {
+  ValidationErrors::new()
+    .field("price", [rules::non_negative(price)])
+    .field("minQuantity", [rules::non_negative(min_quantity)])
+    .field(
+      "leadTimeDays",
+      [rules::reject_if(lead_time_days < 0, "must_not_be_negative")],
+    )
+    .field(
+      "dateEnd",
+      [rules::reject_if(
+        date_start
+          .zip(date_end)
+          .is_some_and(|(start, end)| end <= start),
+        "must_be_after_date_start",
+      )],
+    )
+}
+
+#[derive(Error, Debug)]
+pub enum SupplierInfoInputError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("partner_not_found")]
+  PartnerNotFound,
+
+  #[error("partner_not_supplier")]
+  NotSupplier,
+
+  #[error("product_not_found")]
+  ProductNotFound,
+
+  #[error("product_template_not_found")]
+  TemplateNotFound,
+
+  #[error(transparent)]
+  Conversion(#[from] UomConversionError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl From<SupplierInfoInputError> for AppError {
+  fn from(err: SupplierInfoInputError) -> Self {
+    match err {
+      SupplierInfoInputError::Database(err) => AppError::from(err),
+      SupplierInfoInputError::PartnerNotFound => {
+        AppError::validation(err.to_string()).with_field("partnerId", "not_found")
+      }
+      SupplierInfoInputError::NotSupplier => {
+        AppError::validation(err.to_string()).with_field("partnerId", "not_supplier")
+      }
+      SupplierInfoInputError::ProductNotFound => {
+        AppError::validation(err.to_string()).with_field("productId", "not_found")
+      }
+      SupplierInfoInputError::TemplateNotFound => {
+        AppError::validation(err.to_string()).with_field("productTemplateId", "not_found")
+      }
+      SupplierInfoInputError::Conversion(err) => AppError::from(err).with_field("uomId", "invalid"),
+      SupplierInfoInputError::Currency(err) => AppError::from(err),
+    }
+  }
+}
+
+/// Checks that `partner_id` is a supplier and that `uom_id` measures the
+/// template of the priced variant or the priced template, returning the unit
+/// to store.
+pub(crate) async fn resolve_supplier_info<C>(
+  db: &C,
+  partner_id: Uuid,
+  product_id: Option<Uuid>,
+  product_template_id: Option<Uuid>,
+  uom_id: Option<Uuid>,
+) -> Result<Uuid, SupplierInfoInputError>
+where
+  C: ConnectionTrait,
+{
+  let partner = partner::Entity::find_by_id(partner_id)
+    .filter(partner::Column::ArchivedAt.is_null())
+    .one(db)
+    .await?
+    .ok_or(SupplierInfoInputError::PartnerNotFound)?;
+  if !partner.is_supplier {
+    return Err(SupplierInfoInputError::NotSupplier);
+  }
+
+  let template = match (product_id, product_template_id) {
+    (Some(product_id), _) => product::Entity::find_by_id(product_id)
+      .find_also_related(product_template::Entity)
+      .one(db)
+      .await?
+      .and_then(|(_, template)| template)
+      .ok_or(SupplierInfoInputError::ProductNotFound)?,
+    (None, Some(product_template_id)) => product_template::Entity::find_by_id(product_template_id)
+      .one(db)
+      .await?
+      .ok_or(SupplierInfoInputError::TemplateNotFound)?,
+    (None, None) => return Err(SupplierInfoInputError::ProductNotFound),
+  };
+
+  let uom_id = uom_id.unwrap_or(template.purchase_uom_id);
+  convert_quantity(db, Decimal::ONE, uom_id, template.uom_id).await?;
+
+  Ok(uom_id)
+}
+
+/// Trimmed supplier SKU, `None` when blank.
+pub(crate) fn supplier_sku(value: Option<&str>) -> Option<String> {
+  value
+    .map(str::trim)
+    .filter(|value| !value.is_empty())
+    .map(str::to_string)
+}
+
+#[derive(Error, Debug)]
+pub enum CreateSupplierInfoError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Input(#[from] SupplierInfoInputError),
+}
+
+impl IntoResponse for CreateSupplierInfoError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateSupplierInfoError::Database(err) => AppError::from(err),
+      CreateSupplierInfoError::Input(err) => AppError::from(err),
+    };
+
+    error.with_source("create_supplier_info").into_response()
+  }
+}
+
+impl CreateSupplierInfoUsecase {
+  /// Records a supplier's price for a product variant or template from
+  /// `min_quantity` on.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<supplier_info::Model, CreateSupplierInfoError> {
+    let uom_id = resolve_supplier_info(
+      &db,
+      self.partner_id,
+      self.product_id,
+      self.product_template_id,
+      self.uom_id,
+    )
+    .await?;
+    let currency_id = currency_or_base(&db, self.currency_id)
+      .await
+      .map_err(SupplierInfoInputError::from)?;
+
+    let supplier_info = supplier_info::ActiveModel {
+      partner_id: Set(self.partner_id),
+      product_id: Set(self.product_id),
+      product_template_id: Set(self.product_template_id),
+      supplier_sku: Set(supplier_sku(self.supplier_sku.as_deref())),
+      price: Set(self.price),
+      currency_id: Set(currency_id),
+      uom_id: Set(uom_id),
+      min_quantity: Set(self.min_quantity),
+      lead_time_days: Set(self.lead_time_days),
+      date_start: Set(self.date_start),
+      date_end: Set(self.date_end),
+      ..Default::default()
+    };
+    let supplier_info = supplier_info.insert(&db).await?;
+
+    Ok(supplier_info)
+  }
+}
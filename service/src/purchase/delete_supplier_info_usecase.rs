@@ -0,0 +1,49 @@
+use axum::response::{IntoResponse, Response};
+use domain::purchase::supplier_info;
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{DbErr, EntityTrait};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteSupplierInfoUsecase {
+  pub id: Uuid,
+}
+
+pub type DeleteSupplierInfoPayload = DeleteSupplierInfoUsecase;
+
+#[derive(Error, Debug)]
+pub enum DeleteSupplierInfoError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for DeleteSupplierInfoError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      DeleteSupplierInfoError::Database(err) => AppError::from(err),
+      DeleteSupplierInfoError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("delete_supplier_info").into_response()
+  }
+}
+
+impl DeleteSupplierInfoUsecase {
+  /// Removes a supplier price. Order lines priced from it keep their price and
+  /// lose the link.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), DeleteSupplierInfoError> {
+    let result = supplier_info::Entity::delete_by_id(self.id)
+      .exec(&db)
+      .await?;
+
+    if result.rows_affected > 0 {
+      Ok(())
+    } else {
+      Err(DeleteSupplierInfoError::RecordNotFound)
+    }
+  }
+}
@@ -0,0 +1,82 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  partner::partner,
+  purchase::{
+    purchase_order::{self, PurchaseOrderDTO},
+    purchase_order_line,
+  },
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct FindPurchaseOrderUsecase {
+  pub id: Uuid,
+}
+
+pub type FindPurchaseOrderParams = FindPurchaseOrderUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindPurchaseOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindPurchaseOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindPurchaseOrderError::Database(err) => AppError::from(err),
+      FindPurchaseOrderError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_purchase_order").into_response()
+  }
+}
+
+impl FindPurchaseOrderUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<PurchaseOrderDTO, FindPurchaseOrderError> {
+    let purchase_order = purchase_order::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindPurchaseOrderError::RecordNotFound)?;
+
+    let partner = partner::Entity::find_by_id(purchase_order.partner_id)
+      .into_partial_model::<partner::PartialModel>()
+      .one(&db)
+      .await?
+      .ok_or(FindPurchaseOrderError::RecordNotFound)?;
+    let lines = purchase_order_line::Entity::find()
+      .filter(purchase_order_line::Column::PurchaseOrderId.eq(purchase_order.id))
+      .order_by_asc(purchase_order_line::Column::Sequence)
+      .into_partial_model::<purchase_order_line::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(PurchaseOrderDTO {
+      id: purchase_order.id,
+      reference: purchase_order.reference,
+      partner,
+      state: purchase_order.state,
+      order_date: purchase_order.order_date,
+      expected_date: purchase_order.expected_date,
+      confirmed_at: purchase_order.confirmed_at,
+      received_at: purchase_order.received_at,
+      note: purchase_order.note,
+      currency_id: purchase_order.currency_id,
+      amount_untaxed: purchase_order.amount_untaxed,
+      amount_tax: purchase_order.amount_tax,
+      amount_total: purchase_order.amount_total,
+      lines,
+      created_at: purchase_order.created_at,
+      updated_at: purchase_order.updated_at,
+    })
+  }
+}
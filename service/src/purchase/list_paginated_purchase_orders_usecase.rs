@@ -0,0 +1,225 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  partner::partner,
+  purchase::purchase_order::{
+    Column, Entity as PurchaseOrder, PurchaseOrderListItem, PurchaseOrderState,
+  },
+};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  response::{CursorPaginationMeta, PaginationMeta},
+  uuid::Uuid,
+};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone,
+  sea_query::{Expr, Query},
+  ColumnTrait, Condition, DbErr, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+  QuerySelect, Select,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::list_query::{
+  cursor_page, name_contains, page_index, page_size, Cursor, CursorError, PageError,
+  PaginationMode, SortOrder,
+};
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PurchaseOrderSortBy {
+  OrderDate,
+  Reference,
+  AmountTotal,
+  CreatedAt,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListPaginatedPurchaseOrdersUsecase {
+  pub page: Option<u64>,
+  pub per_page: Option<u64>,
+  /// Matches the reference or the supplier name.
+  pub q: Option<String>,
+  pub state: Option<PurchaseOrderState>,
+  pub partner_id: Option<Uuid>,
+  /// Inclusive lower bound of `order_date`.
+  pub date_from: Option<DateTimeWithTimeZone>,
+  /// Exclusive upper bound of `order_date`.
+  pub date_to: Option<DateTimeWithTimeZone>,
+  pub sort_by: Option<PurchaseOrderSortBy>,
+  pub order: Option<SortOrder>,
+  pub pagination: Option<PaginationMode>,
+  pub cursor: Option<Cursor>,
+  pub with_total: Option<bool>,
+}
+
+pub type ListPaginatedPurchaseOrdersParams = ListPaginatedPurchaseOrdersUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListPaginatedPurchaseOrdersError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Page(#[from] PageError),
+
+  #[error(transparent)]
+  Cursor(#[from] CursorError),
+}
+
+impl IntoResponse for ListPaginatedPurchaseOrdersError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListPaginatedPurchaseOrdersError::Database(err) => AppError::from(err),
+      ListPaginatedPurchaseOrdersError::Page(err) => AppError::from(err),
+      ListPaginatedPurchaseOrdersError::Cursor(err) => AppError::from(err),
+    };
+
+    error
+      .with_source("list_paginated_purchase_orders")
+      .into_response()
+  }
+}
+
+impl ListPaginatedPurchaseOrdersUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<PurchaseOrderListItem>, PaginationMeta), ListPaginatedPurchaseOrdersError> {
+    let per_page = page_size(self.per_page)?;
+    let page = page_index(self.page)?;
+    let order = Order::from(self.order.unwrap_or_default());
+
+    let purchase_order_pages = self
+      .select()
+      .order_by(self.sort_column(), order)
+      .order_by_asc(Column::Id)
+      .into_model::<PurchaseOrderListItem>()
+      .paginate(&db, per_page);
+    let purchase_orders = purchase_order_pages.fetch_page(page).await?;
+    let items_and_pages = purchase_order_pages.num_items_and_pages().await?;
+    let total = items_and_pages.number_of_items;
+    let total_pages = items_and_pages.number_of_pages;
+
+    Ok((
+      purchase_orders,
+      PaginationMeta {
+        total,
+        total_pages,
+        page: page + 1,
+        per_page,
+      },
+    ))
+  }
+
+  /// Keyset variant of `invoke`: reads `per_page` rows after (or before) the
+  /// cursor and only counts the whole result set when `with_total` is set.
+  pub async fn invoke_cursor(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<PurchaseOrderListItem>, CursorPaginationMeta), ListPaginatedPurchaseOrdersError>
+  {
+    let per_page = page_size(self.per_page)?;
+    let cursor = self.cursor.as_ref();
+    let query_order = Cursor::query_order(cursor, self.order.unwrap_or_default().into());
+    let sort_column = self.sort_column();
+
+    let keyset_condition = match cursor {
+      Some(cursor) => Some(
+        cursor
+          .keyset_condition(
+            &db,
+            vec![
+              Expr::col((PurchaseOrder, sort_column)).into(),
+              Expr::col((PurchaseOrder, Column::Id)).into(),
+            ],
+            Query::select()
+              .column(sort_column)
+              .column(Column::Id)
+              .from(PurchaseOrder)
+              .and_where(Column::Id.eq(cursor.id))
+              .to_owned(),
+            query_order.clone(),
+          )
+          .await?,
+      ),
+      None => None,
+    };
+
+    let rows = self
+      .select()
+      .filter(Condition::all().add_option(keyset_condition))
+      .order_by(sort_column, query_order.clone())
+      .order_by(Column::Id, query_order)
+      .limit(per_page + 1)
+      .into_model::<PurchaseOrderListItem>()
+      .all(&db)
+      .await?;
+    let (purchase_orders, next_cursor, prev_cursor) =
+      cursor_page(rows, per_page, cursor, |purchase_order| purchase_order.id);
+
+    let total = match self.with_total {
+      Some(true) => Some(self.select().count(&db).await?),
+      _ => None,
+    };
+
+    Ok((
+      purchase_orders,
+      CursorPaginationMeta {
+        per_page,
+        next_cursor,
+        prev_cursor,
+        total,
+      },
+    ))
+  }
+
+  /// Orders matching the filters, joined to their supplier for its name.
+  fn select(&self) -> Select<PurchaseOrder> {
+    PurchaseOrder::find()
+      .select_only()
+      .columns([
+        Column::Id,
+        Column::Reference,
+        Column::PartnerId,
+        Column::State,
+        Column::OrderDate,
+        Column::ExpectedDate,
+        Column::CurrencyId,
+        Column::AmountTotal,
+      ])
+      .column_as(partner::Column::Name, "partner_name")
+      .inner_join(partner::Entity)
+      .filter(self.filter_condition())
+  }
+
+  fn filter_condition(&self) -> Condition {
+    Condition::all()
+      .add_option(self.q.as_deref().filter(|q| !q.trim().is_empty()).map(|q| {
+        Condition::any()
+          .add(name_contains(Column::Reference, q))
+          .add(name_contains(partner::Column::Name, q))
+      }))
+      .add_option(self.state.map(|state| Column::State.eq(state)))
+      .add_option(
+        self
+          .partner_id
+          .map(|partner_id| Column::PartnerId.eq(partner_id)),
+      )
+      .add_option(
+        self
+          .date_from
+          .map(|date_from| Column::OrderDate.gte(date_from)),
+      )
+      .add_option(self.date_to.map(|date_to| Column::OrderDate.lt(date_to)))
+  }
+
+  fn sort_column(&self) -> Column {
+    match self.sort_by {
+      Some(PurchaseOrderSortBy::Reference) => Column::Reference,
+      Some(PurchaseOrderSortBy::AmountTotal) => Column::AmountTotal,
+      Some(PurchaseOrderSortBy::CreatedAt) => Column::CreatedAt,
+      Some(PurchaseOrderSortBy::OrderDate) | None => Column::OrderDate,
+    }
+  }
+}
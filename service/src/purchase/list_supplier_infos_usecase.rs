@@ -0,0 +1,59 @@
+use axum::response::{IntoResponse, Response};
+use domain::purchase::supplier_info::{self, Column, Entity as SupplierInfo};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, Condition, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct ListSupplierInfosUsecase {
+  pub product_id: Option<Uuid>,
+  pub product_template_id: Option<Uuid>,
+  pub partner_id: Option<Uuid>,
+}
+
+pub type ListSupplierInfosParams = ListSupplierInfosUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListSupplierInfosError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+}
+
+impl IntoResponse for ListSupplierInfosError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListSupplierInfosError::Database(err) => AppError::from(err),
+    };
+
+    error.with_source("list_supplier_infos").into_response()
+  }
+}
+
+impl ListSupplierInfosUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<Vec<supplier_info::PartialModel>, ListSupplierInfosError> {
+    let supplier_infos = SupplierInfo::find()
+      .filter(
+        Condition::all()
+          .add_option(self.product_id.map(|id| Column::ProductId.eq(id)))
+          .add_option(
+            self
+              .product_template_id
+              .map(|id| Column::ProductTemplateId.eq(id)),
+          )
+          .add_option(self.partner_id.map(|id| Column::PartnerId.eq(id))),
+      )
+      .order_by_asc(Column::ProductId)
+      .order_by_asc(Column::ProductTemplateId)
+      .order_by_asc(Column::PartnerId)
+      .order_by_asc(Column::MinQuantity)
+      .into_partial_model::<supplier_info::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(supplier_infos)
+  }
+}
@@ -0,0 +1,33 @@
+pub mod supplier_price;
+
+pub mod order_input;
+
+pub mod create_supplier_info_usecase;
+pub use create_supplier_info_usecase::*;
+
+pub mod update_supplier_info_usecase;
+pub use update_supplier_info_usecase::*;
+
+pub mod delete_supplier_info_usecase;
+pub use delete_supplier_info_usecase::*;
+
+pub mod list_supplier_infos_usecase;
+pub use list_supplier_infos_usecase::*;
+
+pub mod list_paginated_purchase_orders_usecase;
+pub use list_paginated_purchase_orders_usecase::*;
+
+pub mod create_purchase_order_usecase;
+pub use create_purchase_order_usecase::*;
+
+pub mod find_purchase_order_usecase;
+pub use find_purchase_order_usecase::*;
+
+pub mod update_purchase_order_usecase;
+pub use update_purchase_order_usecase::*;
+
+pub mod purchase_order_state_usecase;
+pub use purchase_order_state_usecase::*;
+
+pub mod receive_purchase_order_usecase;
+pub use receive_purchase_order_usecase::*;
@@ -0,0 +1,424 @@
+use chrono::Duration;
+use domain::{
+  partner::partner,
+  product::{product, product_template},
+  purchase::purchase_order_line,
+  tax::tax::TaxUse,
+};
+use infra::{
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::supplier_price::best_supplier_price;
+use crate::{
+  currency::currency_conversion::{
+    CurrencyConversionError, DocumentCurrency, PriceConversionError,
+  },
+  measurement::{convert_quantity, convert_unit_price, UomConversionError},
+  tax::{
+    line_taxes::{LineTaxes, TaxInputError},
+    tax_computation::{compute_line, DocumentAmounts, TaxableLine},
+  },
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PurchaseOrderLine {
+  #[serde(rename(deserialize = "productId"))]
+  pub product_id: Uuid,
+  pub description: Option<String>,
+  pub quantity: Decimal,
+  /// Defaults to the template's purchase unit.
+  #[serde(rename(deserialize = "uomId"), default)]
+  pub uom_id: Option<Uuid>,
+  /// Price per `uom_id` unit in the order's currency. Defaults to the
+  /// supplier's best price for the quantity, then to the variant's cost.
+  #[serde(rename(deserialize = "unitPrice"), default)]
+  pub unit_price: Option<Decimal>,
+  /// Defaults to the product's category or template purchase tax.
+  #[serde(rename(deserialize = "taxId"), default)]
+  pub tax_id: Option<Uuid>,
+}
+
+pub fn validate_lines(lines: &[PurchaseOrderLine]) -> ValidationErrors {
+  ValidationErrors::new()
+    .field("lines", [rules::not_empty(lines)])
+    .each("lines", lines, |line| {
+      ValidationErrors::new()
+        .field("quantity", [rules::positive(line.quantity)])
+        .field("unitPrice", line.unit_price.map(rules::non_negative))
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum PurchaseOrderInputError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("partner_not_found")]
+  PartnerNotFound,
+
+  #[error("partner_not_supplier")]
+  NotSupplier,
+
+  #[error("product_not_found")]
+  ProductNotFound(usize),
+
+  #[error("incompatible_uom")]
+  IncompatibleUom(usize, UomConversionError),
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl PurchaseOrderInputError {
+  /// Conversion failure of the line at `index`; database and currency errors
+  /// stay as such.
+  fn conversion<E>(index: usize) -> impl Fn(E) -> Self
+  where
+    E: Into<PriceConversionError>,
+  {
+    move |err| match err.into() {
+      PriceConversionError::Database(err) => PurchaseOrderInputError::Database(err),
+      PriceConversionError::Uom(err) => PurchaseOrderInputError::IncompatibleUom(index, err),
+      PriceConversionError::Currency(err) => PurchaseOrderInputError::Currency(err),
+    }
+  }
+}
+
+impl From<PurchaseOrderInputError> for AppError {
+  fn from(err: PurchaseOrderInputError) -> Self {
+    match err {
+      PurchaseOrderInputError::Database(err) => AppError::from(err),
+      PurchaseOrderInputError::PartnerNotFound => {
+        AppError::validation(err.to_string()).with_field("partnerId", "not_found")
+      }
+      PurchaseOrderInputError::NotSupplier => {
+        AppError::validation(err.to_string()).with_field("partnerId", "not_supplier")
+      }
+      PurchaseOrderInputError::ProductNotFound(index) => AppError::validation(err.to_string())
+        .with_field(format!("lines[{}].productId", index), "not_found"),
+      PurchaseOrderInputError::IncompatibleUom(index, ref conversion) => {
+        AppError::validation(err.to_string())
+          .with_field(format!("lines[{}].uomId", index), conversion.to_string())
+      }
+      PurchaseOrderInputError::Tax(err) => AppError::from(err),
+      PurchaseOrderInputError::Currency(err) => AppError::from(err),
+    }
+  }
+}
+
+pub async fn find_supplier<C>(
+  db: &C,
+  partner_id: Uuid,
+) -> Result<partner::Model, PurchaseOrderInputError>
+where
+  C: ConnectionTrait,
+{
+  let partner = partner::Entity::find_by_id(partner_id)
+    .filter(partner::Column::ArchivedAt.is_null())
+    .one(db)
+    .await?
+    .ok_or(PurchaseOrderInputError::PartnerNotFound)?;
+  if !partner.is_supplier {
+    return Err(PurchaseOrderInputError::NotSupplier);
+  }
+
+  Ok(partner)
+}
+
+/// Lines of an order with the date the goods are expected, if any supplier
+/// price with a lead time was used.
+pub struct PurchaseOrderLines {
+  pub lines: Vec<purchase_order_line::ActiveModel>,
+  pub expected_date: Option<DateTimeWithTimeZone>,
+}
+
+impl PurchaseOrderLines {
+  /// Totals of the order.
+  pub fn amounts(&self) -> DocumentAmounts {
+    DocumentAmounts::sum(self.lines.iter().filter_map(|line| {
+      line
+        .subtotal
+        .clone()
+        .take()
+        .zip(line.tax_amount.clone().take())
+    }))
+  }
+}
+
+/// Turns the payload lines into rows of `purchase_order_id`, filling in units,
+/// prices, taxes and descriptions and computing amounts in `currency`. Prices
+/// left out come from the supplier's best matching price for the quantity,
+/// converted at the rate of `order_date`.
+pub async fn build_lines<C>(
+  db: &C,
+  purchase_order_id: Uuid,
+  partner_id: Uuid,
+  order_date: DateTimeWithTimeZone,
+  currency: &DocumentCurrency,
+  lines: &[PurchaseOrderLine],
+) -> Result<PurchaseOrderLines, PurchaseOrderInputError>
+where
+  C: ConnectionTrait,
+{
+  let line_taxes = LineTaxes::load(db, TaxUse::Purchase).await?;
+  let mut models = Vec::with_capacity(lines.len());
+  let mut lead_time_days = None;
+
+  for (index, line) in lines.iter().enumerate() {
+    let (product, template) = product::Entity::find_by_id(line.product_id)
+      .filter(product::Column::ArchivedAt.is_null())
+      .find_also_related(product_template::Entity)
+      .one(db)
+      .await?
+      .ok_or(PurchaseOrderInputError::ProductNotFound(index))?;
+    let template = template.ok_or(PurchaseOrderInputError::ProductNotFound(index))?;
+
+    let uom_id = line.uom_id.unwrap_or(template.purchase_uom_id);
+    let product_quantity = convert_quantity(db, line.quantity, uom_id, template.uom_id)
+      .await
+      .map_err(PurchaseOrderInputError::conversion(index))?;
+
+    // A price given by the caller discards the supplier's, lead time included.
+    let supplier_price = match line.unit_price {
+      Some(_) => None,
+      None => best_supplier_price(
+        db,
+        partner_id,
+        &product,
+        line.quantity,
+        uom_id,
+        order_date,
+        currency,
+      )
+      .await
+      .map_err(PurchaseOrderInputError::conversion(index))?,
+    };
+    let unit_price = match (line.unit_price, &supplier_price) {
+      (Some(unit_price), _) => unit_price,
+      (None, Some(supplier_price)) => supplier_price.unit_price,
+      (None, None) => currency.from_base(
+        convert_unit_price(db, product.cost, template.uom_id, uom_id)
+          .await
+          .map_err(PurchaseOrderInputError::conversion(index))?,
+      ),
+    };
+    if let Some(supplier_price) = &supplier_price {
+      lead_time_days = lead_time_days.max(Some(supplier_price.supplier_info.lead_time_days));
+    }
+
+    let tax = line_taxes
+      .resolve(
+        db,
+        line.tax_id,
+        &template,
+        &format!("lines[{}].taxId", index),
+      )
+      .await?;
+    let amounts = compute_line(
+      &TaxableLine {
+        amount: line.quantity * unit_price,
+        product_quantity,
+        tax,
+      },
+      currency,
+    );
+
+    let description = line
+      .description
+      .as_deref()
+      .map(str::trim)
+      .filter(|description| !description.is_empty())
+      .unwrap_or(&template.name)
+      .to_string();
+
+    models.push(purchase_order_line::ActiveModel {
+      purchase_order_id: Set(purchase_order_id),
+      sequence: Set(index as i32),
+      product_id: Set(product.id),
+      supplier_info_id: Set(supplier_price.map(|supplier_price| supplier_price.supplier_info.id)),
+      description: Set(description),
+      quantity: Set(line.quantity),
+      uom_id: Set(uom_id),
+      product_quantity: Set(product_quantity),
+      unit_price: Set(unit_price),
+      subtotal: Set(amounts.subtotal),
+      tax_id: Set(amounts.tax_id),
+      tax_amount: Set(amounts.tax_amount),
+      stock_move_id: Set(None),
+      ..Default::default()
+    });
+  }
+
+  Ok(PurchaseOrderLines {
+    lines: models,
+    expected_date: lead_time_days.map(|days| order_date + Duration::days(days.into())),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use domain::{
+    measurement::uom::{self, UomCategory},
+    product::product_template::{ProductSubtype, ProductType, Tracking},
+    purchase::supplier_info,
+    tax::tax,
+  };
+  use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+
+  use super::*;
+  use crate::test_support::{at, currency, dec, uom};
+
+  struct Fixture {
+    product: product::Model,
+    template: product_template::Model,
+    piece: uom::Model,
+    currency: DocumentCurrency,
+  }
+
+  impl Fixture {
+    fn new() -> Self {
+      let piece = uom(UomCategory::Unit, "1", "1");
+      let currency = currency("VND", "1", true);
+      let template = product_template::Model {
+        id: Uuid::new(),
+        name: "Carton box".to_string(),
+        description: String::new(),
+        uom_id: piece.id,
+        purchase_uom_id: piece.id,
+        sales_uom_id: piece.id,
+        category_id: None,
+        sales_tax_id: None,
+        purchase_tax_id: None,
+        currency_id: currency.id,
+        product_type: ProductType::Goods,
+        product_subtype: ProductSubtype::Normal,
+        is_track_inventory: true,
+        tracking: Tracking::None,
+        created_at: Utc::now().into(),
+        updated_at: None,
+        archived_at: None,
+      };
+      let product = product::Model {
+        id: Uuid::new(),
+        product_template_id: template.id,
+        price: dec("12000"),
+        price_uom_id: piece.id,
+        cost: dec("8000"),
+        is_product_variant: false,
+        created_at: Utc::now().into(),
+        updated_at: None,
+        archived_at: None,
+      };
+
+      Self {
+        product,
+        template,
+        piece,
+        currency: DocumentCurrency {
+          currency,
+          rate: Decimal::ONE,
+          at: at("2025-01-15T00:00:00Z"),
+        },
+      }
+    }
+
+    fn supplier_info(&self) -> supplier_info::Model {
+      supplier_info::Model {
+        id: Uuid::new(),
+        partner_id: Uuid::new(),
+        product_id: Some(self.product.id),
+        product_template_id: None,
+        supplier_sku: None,
+        price: dec("7000"),
+        currency_id: self.currency.currency.id,
+        uom_id: self.piece.id,
+        min_quantity: Decimal::ZERO,
+        lead_time_days: 5,
+        date_start: None,
+        date_end: None,
+        created_at: Utc::now().into(),
+        updated_at: None,
+      }
+    }
+
+    /// Mock answering the tax load, then the line's variant
+    /// and the unit lookup of its quantity.
+    fn db(&self) -> MockDatabase {
+      MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([Vec::<tax::Model>::new()])
+        .append_query_results([vec![(self.product, self.template.clone())]])
+        .append_query_results([vec![self.piece.clone()]])
+    }
+
+    async fn build(&self, db: &DatabaseConnection, unit_price: Option<&str>) -> PurchaseOrderLines {
+      let line = PurchaseOrderLine {
+        product_id: self.product.id,
+        description: None,
+        quantity: dec("10"),
+        uom_id: None,
+        unit_price: unit_price.map(dec),
+        tax_id: None,
+      };
+
+      build_lines(
+        db,
+        Uuid::new(),
+        Uuid::new(),
+        self.currency.at,
+        &self.currency,
+        &[line],
+      )
+      .await
+      .unwrap()
+    }
+  }
+
+  #[tokio::test]
+  async fn supplier_price_sets_price_and_expected_date() {
+    let fixture = Fixture::new();
+    let supplier_info = fixture.supplier_info();
+    let db = fixture
+      .db()
+      .append_query_results([vec![supplier_info.clone()]])
+      .append_query_results(vec![vec![fixture.piece.clone()]; 2])
+      .into_connection();
+
+    let lines = fixture.build(&db, None).await;
+
+    let line = &lines.lines[0];
+    assert_eq!(line.unit_price.clone().unwrap(), dec("7000"));
+    assert_eq!(
+      line.supplier_info_id.clone().unwrap(),
+      Some(supplier_info.id)
+    );
+    assert_eq!(lines.expected_date, Some(at("2025-01-20T00:00:00Z")));
+  }
+
+  #[tokio::test]
+  async fn given_unit_price_discards_supplier_price_and_lead_time() {
+    let fixture = Fixture::new();
+    let db = fixture.db().into_connection();
+
+    let lines = fixture.build(&db, Some("6500")).await;
+
+    let line = &lines.lines[0];
+    assert_eq!(line.unit_price.clone().unwrap(), dec("6500"));
+    assert_eq!(line.supplier_info_id.clone().unwrap(), None);
+    assert_eq!(line.subtotal.clone().unwrap(), dec("65000"));
+    assert_eq!(lines.expected_date, None);
+    assert_eq!(db.into_transaction_log().len(), 3);
+  }
+}
@@ -0,0 +1,125 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::purchase::purchase_order::{self, PurchaseOrderState};
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, QuerySelect, Set, TransactionError};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmPurchaseOrderUsecase {
+  pub id: Uuid,
+}
+
+pub type ConfirmPurchaseOrderPayload = ConfirmPurchaseOrderUsecase;
+
+#[derive(Debug, Deserialize)]
+pub struct CancelPurchaseOrderUsecase {
+  pub id: Uuid,
+}
+
+pub type CancelPurchaseOrderPayload = CancelPurchaseOrderUsecase;
+
+#[derive(Error, Debug)]
+pub enum PurchaseOrderStateError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("invalid_state_transition")]
+  InvalidTransition {
+    from: PurchaseOrderState,
+    to: PurchaseOrderState,
+  },
+}
+
+impl From<TransactionError<PurchaseOrderStateError>> for PurchaseOrderStateError {
+  fn from(err: TransactionError<PurchaseOrderStateError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => PurchaseOrderStateError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for PurchaseOrderStateError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      PurchaseOrderStateError::Database(err) => AppError::from(err),
+      PurchaseOrderStateError::RecordNotFound => AppError::not_found(self.to_string()),
+      PurchaseOrderStateError::InvalidTransition { from, to } => {
+        AppError::conflict(self.to_string()).with_details(json!({ "from": from, "to": to }))
+      }
+    };
+
+    error.with_source("purchase_order_state").into_response()
+  }
+}
+
+impl ConfirmPurchaseOrderUsecase {
+  /// Turns an RFQ into a purchase order.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), PurchaseOrderStateError> {
+    transition(
+      db,
+      self.id,
+      &[PurchaseOrderState::Rfq],
+      PurchaseOrderState::Confirmed,
+    )
+    .await
+  }
+}
+
+impl CancelPurchaseOrderUsecase {
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), PurchaseOrderStateError> {
+    transition(
+      db,
+      self.id,
+      &[PurchaseOrderState::Rfq, PurchaseOrderState::Confirmed],
+      PurchaseOrderState::Cancelled,
+    )
+    .await
+  }
+}
+
+/// Moves the order to `to` when it currently is in one of `from`. Confirming
+/// stamps `confirmed_at`.
+async fn transition(
+  db: impl WriteConnection,
+  id: Uuid,
+  from: &'static [PurchaseOrderState],
+  to: PurchaseOrderState,
+) -> Result<(), PurchaseOrderStateError> {
+  db.transaction::<_, (), PurchaseOrderStateError>(move |txn| {
+    Box::pin(async move {
+      let purchase_order = purchase_order::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or(PurchaseOrderStateError::RecordNotFound)?;
+      if !from.contains(&purchase_order.state) {
+        return Err(PurchaseOrderStateError::InvalidTransition {
+          from: purchase_order.state,
+          to,
+        });
+      }
+
+      let mut active = purchase_order::ActiveModel {
+        id: Set(purchase_order.id),
+        state: Set(to),
+        ..Default::default()
+      };
+      if to == PurchaseOrderState::Confirmed {
+        active.confirmed_at = Set(Some(Utc::now().into()));
+      }
+      active.update(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await?;
+
+  Ok(())
+}
@@ -0,0 +1,243 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::{
+  inventory::{
+    location::{self, LocationType},
+    stock_move::{self, StockMoveState},
+  },
+  purchase::{
+    purchase_order::{self, PurchaseOrderState},
+    purchase_order_line,
+  },
+};
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{
+  ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+  TransactionError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+  currency::currency_conversion::{find_currency, rate_at, CurrencyConversionError},
+  inventory::{
+    lot_assignment::{check_lot_assignment, LotAssignmentError},
+    stockable_product::{find_stockable_product, StockableProductError},
+    validate_stock_move_usecase::{complete_stock_move, ValidateStockMoveError},
+  },
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReceivedLot {
+  #[serde(rename(deserialize = "lineId"))]
+  pub line_id: Uuid,
+  #[serde(rename(deserialize = "lotId"))]
+  pub lot_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ReceivePurchaseOrderUsecase {
+  pub id: Uuid,
+  /// Internal location the goods are put away in.
+  #[serde(rename(deserialize = "destinationLocationId"))]
+  pub destination_location_id: Uuid,
+  /// Defaults to the oldest active supplier location.
+  #[serde(rename(deserialize = "sourceLocationId"), default)]
+  pub source_location_id: Option<Uuid>,
+  /// Lot or serial received on each line of a tracked product.
+  #[serde(default)]
+  pub lots: Vec<ReceivedLot>,
+}
+
+pub type ReceivePurchaseOrderPayload = ReceivePurchaseOrderUsecase;
+
+#[derive(Error, Debug)]
+pub enum ReceivePurchaseOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("purchase_order_not_confirmed")]
+  NotConfirmed(PurchaseOrderState),
+
+  #[error("location_not_found")]
+  LocationNotFound(&'static str),
+
+  #[error("destination_not_internal")]
+  DestinationNotInternal,
+
+  #[error("source_not_supplier")]
+  SourceNotSupplier,
+
+  #[error(transparent)]
+  Lot(#[from] LotAssignmentError),
+
+  #[error(transparent)]
+  StockMove(#[from] ValidateStockMoveError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl From<TransactionError<ReceivePurchaseOrderError>> for ReceivePurchaseOrderError {
+  fn from(err: TransactionError<ReceivePurchaseOrderError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => ReceivePurchaseOrderError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for ReceivePurchaseOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ReceivePurchaseOrderError::Database(err) => AppError::from(err),
+      ReceivePurchaseOrderError::RecordNotFound => AppError::not_found(self.to_string()),
+      ReceivePurchaseOrderError::NotConfirmed(state) => {
+        AppError::conflict(self.to_string()).with_details(json!({ "state": state }))
+      }
+      ReceivePurchaseOrderError::LocationNotFound(field) => {
+        AppError::validation(self.to_string()).with_field(field, "not_found")
+      }
+      ReceivePurchaseOrderError::DestinationNotInternal => {
+        AppError::validation(self.to_string()).with_field("destinationLocationId", "not_internal")
+      }
+      ReceivePurchaseOrderError::SourceNotSupplier => {
+        AppError::validation(self.to_string()).with_field("sourceLocationId", "not_supplier")
+      }
+      ReceivePurchaseOrderError::Lot(err) => AppError::from(err),
+      ReceivePurchaseOrderError::StockMove(err) => AppError::from(err),
+      ReceivePurchaseOrderError::Currency(err) => AppError::from(err),
+    };
+
+    error.with_source("receive_purchase_order").into_response()
+  }
+}
+
+impl ReceivePurchaseOrderUsecase {
+  /// Receives a confirmed order in full. Every line of a product that tracks
+  /// inventory becomes a done move from the supplier location into
+  /// `destination_location_id`, valued at the line's unit price converted into
+  /// the base currency at the rate of the day; services and untracked goods
+  /// are received without a move.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), ReceivePurchaseOrderError> {
+    let payload = self.clone();
+
+    db.transaction::<_, (), ReceivePurchaseOrderError>(move |txn| {
+      Box::pin(async move {
+        let purchase_order = purchase_order::Entity::find_by_id(payload.id)
+          .lock_exclusive()
+          .one(txn)
+          .await?
+          .ok_or(ReceivePurchaseOrderError::RecordNotFound)?;
+        if purchase_order.state != PurchaseOrderState::Confirmed {
+          return Err(ReceivePurchaseOrderError::NotConfirmed(
+            purchase_order.state,
+          ));
+        }
+
+        let destination = location::Entity::find_by_id(payload.destination_location_id)
+          .one(txn)
+          .await?
+          .ok_or(ReceivePurchaseOrderError::LocationNotFound(
+            "destinationLocationId",
+          ))?;
+        if destination.location_type != LocationType::Internal {
+          return Err(ReceivePurchaseOrderError::DestinationNotInternal);
+        }
+        let source = match payload.source_location_id {
+          Some(source_location_id) => location::Entity::find_by_id(source_location_id)
+            .one(txn)
+            .await?
+            .ok_or(ReceivePurchaseOrderError::LocationNotFound(
+              "sourceLocationId",
+            ))?,
+          None => location::Entity::find()
+            .filter(location::Column::LocationType.eq(LocationType::Supplier))
+            .filter(location::Column::ArchivedAt.is_null())
+            .order_by_asc(location::Column::CreatedAt)
+            .one(txn)
+            .await?
+            .ok_or(ReceivePurchaseOrderError::LocationNotFound(
+              "sourceLocationId",
+            ))?,
+        };
+        if source.location_type != LocationType::Supplier {
+          return Err(ReceivePurchaseOrderError::SourceNotSupplier);
+        }
+
+        let received_at = Utc::now().into();
+        let currency = find_currency(txn, purchase_order.currency_id).await?;
+        let rate = rate_at(txn, &currency, received_at).await?;
+
+        let lines = purchase_order_line::Entity::find()
+          .filter(purchase_order_line::Column::PurchaseOrderId.eq(purchase_order.id))
+          .order_by_asc(purchase_order_line::Column::Sequence)
+          .all(txn)
+          .await?;
+        for line in lines {
+          let stockable = match find_stockable_product(txn, line.product_id).await {
+            Ok(stockable) => stockable,
+            Err(StockableProductError::ProductNotTracked) => continue,
+            Err(err) => return Err(ValidateStockMoveError::from(err).into()),
+          };
+
+          let lot_id = payload
+            .lots
+            .iter()
+            .find(|lot| lot.line_id == line.id)
+            .map(|lot| lot.lot_id);
+          check_lot_assignment(
+            txn,
+            &stockable.template,
+            line.product_id,
+            lot_id,
+            line.product_quantity,
+          )
+          .await?;
+
+          // Valued untaxed: a price-included tax is recovered, not stocked.
+          let stock_move = stock_move::ActiveModel {
+            reference: Set(purchase_order.reference.clone()),
+            product_id: Set(line.product_id),
+            source_location_id: Set(source.id),
+            destination_location_id: Set(destination.id),
+            quantity: Set(line.quantity),
+            uom_id: Set(line.uom_id),
+            product_quantity: Set(line.product_quantity),
+            unit_cost: Set(Some(line.subtotal * rate / line.quantity)),
+            lot_id: Set(lot_id),
+            produced_lot_id: Set(None),
+            state: Set(StockMoveState::Draft),
+            ..Default::default()
+          };
+          let stock_move = stock_move.insert(txn).await?;
+          let stock_move = complete_stock_move(txn, stock_move).await?;
+
+          let line = purchase_order_line::ActiveModel {
+            id: Set(line.id),
+            stock_move_id: Set(Some(stock_move.id)),
+            ..Default::default()
+          };
+          line.update(txn).await?;
+        }
+
+        let purchase_order = purchase_order::ActiveModel {
+          id: Set(purchase_order.id),
+          state: Set(PurchaseOrderState::Received),
+          received_at: Set(Some(received_at)),
+          ..Default::default()
+        };
+        purchase_order.update(txn).await?;
+
+        Ok(())
+      })
+    })
+    .await?;
+
+    Ok(())
+  }
+}
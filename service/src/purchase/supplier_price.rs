@@ -0,0 +1,281 @@
+use domain::{product::product, purchase::supplier_info};
+use infra::uuid::Uuid;
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, Condition, ConnectionTrait, EntityTrait, QueryFilter, QueryOrder,
+};
+
+use crate::{
+  currency::currency_conversion::{DocumentCurrency, PriceConversionError},
+  measurement::{convert_quantity, convert_unit_price},
+};
+
+/// Supplier price row chosen for a purchase line.
+pub struct SupplierPrice {
+  pub supplier_info: supplier_info::Model,
+  /// The row's price converted into a price per unit of the line, in the
+  /// order's currency.
+  pub unit_price: Decimal,
+}
+
+/// Cheapest price `partner_id` quotes for `quantity` of `product` in `uom_id`
+/// at `at`, among the rows whose minimum quantity the order reaches. Rows for
+/// the variant itself take precedence over rows for its whole template. Rows
+/// are compared per unit of the order line and in the order's `currency`, so
+/// tiers quoted in different units or currencies compete fairly.
+pub async fn best_supplier_price<C>(
+  db: &C,
+  partner_id: Uuid,
+  product: &product::Model,
+  quantity: Decimal,
+  uom_id: Uuid,
+  at: DateTimeWithTimeZone,
+  currency: &DocumentCurrency,
+) -> Result<Option<SupplierPrice>, PriceConversionError>
+where
+  C: ConnectionTrait,
+{
+  let rows = supplier_info::Entity::find()
+    .filter(supplier_info::Column::PartnerId.eq(partner_id))
+    .filter(
+      Condition::any()
+        .add(supplier_info::Column::ProductId.eq(product.id))
+        .add(supplier_info::Column::ProductTemplateId.eq(product.product_template_id)),
+    )
+    .order_by_desc(supplier_info::Column::MinQuantity)
+    .order_by_asc(supplier_info::Column::CreatedAt)
+    .all(db)
+    .await?;
+
+  let mut best: Option<SupplierPrice> = None;
+  for row in rows {
+    if row.date_start.is_some_and(|date_start| date_start > at)
+      || row.date_end.is_some_and(|date_end| date_end <= at)
+    {
+      continue;
+    }
+    let row_quantity = convert_quantity(db, quantity, uom_id, row.uom_id).await?;
+    if row_quantity < row.min_quantity {
+      continue;
+    }
+
+    let unit_price = convert_unit_price(db, row.price, row.uom_id, uom_id).await?;
+    let unit_price = currency
+      .convert_price(db, unit_price, row.currency_id)
+      .await?;
+    let precedence = |row: &supplier_info::Model| row.product_id.is_none();
+    if best.as_ref().is_none_or(|best| {
+      (precedence(&row), unit_price) < (precedence(&best.supplier_info), best.unit_price)
+    }) {
+      best = Some(SupplierPrice {
+        supplier_info: row,
+        unit_price,
+      });
+    }
+  }
+
+  Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use domain::{
+    currency::currency_rate,
+    measurement::uom::{self, UomCategory},
+  };
+  use sea_orm::{DatabaseBackend, DatabaseConnection, MockDatabase};
+
+  use super::*;
+  use crate::test_support::{at, currency, dec, uom};
+
+  struct Fixture {
+    product: product::Model,
+    piece: uom::Model,
+    currency: DocumentCurrency,
+  }
+
+  impl Fixture {
+    fn new() -> Self {
+      let product = product::Model {
+        id: Uuid::new(),
+        product_template_id: Uuid::new(),
+        price: Decimal::ZERO,
+        price_uom_id: Uuid::new(),
+        cost: Decimal::ZERO,
+        is_product_variant: true,
+        created_at: Utc::now().into(),
+        updated_at: None,
+        archived_at: None,
+      };
+
+      Self {
+        product,
+        piece: uom(UomCategory::Unit, "1", "1"),
+        currency: DocumentCurrency {
+          currency: currency("VND", "1", true),
+          rate: Decimal::ONE,
+          at: at("2025-01-15T00:00:00Z"),
+        },
+      }
+    }
+
+    fn variant_row(&self, price: &str, min_quantity: &str) -> supplier_info::Model {
+      supplier_info::Model {
+        id: Uuid::new(),
+        partner_id: Uuid::new(),
+        product_id: Some(self.product.id),
+        product_template_id: None,
+        supplier_sku: None,
+        price: dec(price),
+        currency_id: self.currency.currency.id,
+        uom_id: self.piece.id,
+        min_quantity: dec(min_quantity),
+        lead_time_days: 0,
+        date_start: None,
+        date_end: None,
+        created_at: Utc::now().into(),
+        updated_at: None,
+      }
+    }
+
+    fn template_row(&self, price: &str, min_quantity: &str) -> supplier_info::Model {
+      supplier_info::Model {
+        product_id: None,
+        product_template_id: Some(self.product.product_template_id),
+        ..self.variant_row(price, min_quantity)
+      }
+    }
+
+    /// Mock answering the supplier rows query, then the unit lookups of the
+    /// quantity check and of the price conversion of `priced` rows.
+    fn db(&self, rows: Vec<supplier_info::Model>, priced: usize) -> MockDatabase {
+      MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results([rows])
+        .append_query_results(vec![vec![self.piece.clone()]; priced * 2])
+    }
+
+    async fn best(&self, db: &DatabaseConnection, quantity: &str) -> Option<SupplierPrice> {
+      best_supplier_price(
+        db,
+        Uuid::new(),
+        &self.product,
+        dec(quantity),
+        self.piece.id,
+        self.currency.at,
+        &self.currency,
+      )
+      .await
+      .unwrap()
+    }
+  }
+
+  #[tokio::test]
+  async fn cheapest_tier_reached_by_the_quantity_wins() {
+    let fixture = Fixture::new();
+    let tier_1 = fixture.variant_row("100", "1");
+    let tier_10 = fixture.variant_row("90", "10");
+    let tier_100 = fixture.variant_row("80", "100");
+    let db = fixture
+      .db(vec![tier_100, tier_10.clone(), tier_1], 2)
+      .append_query_results([vec![fixture.piece.clone()]])
+      .into_connection();
+
+    let best = fixture.best(&db, "12").await.unwrap();
+
+    assert_eq!(best.supplier_info.id, tier_10.id);
+    assert_eq!(best.unit_price, dec("90"));
+  }
+
+  #[tokio::test]
+  async fn rows_outside_their_validity_dates_are_skipped() {
+    let fixture = Fixture::new();
+    let expired = supplier_info::Model {
+      date_end: Some(at("2025-01-15T00:00:00Z")),
+      ..fixture.variant_row("50", "0")
+    };
+    let upcoming = supplier_info::Model {
+      date_start: Some(at("2025-01-16T00:00:00Z")),
+      ..fixture.variant_row("60", "0")
+    };
+    let current = supplier_info::Model {
+      date_start: Some(at("2025-01-15T00:00:00Z")),
+      date_end: Some(at("2025-01-16T00:00:00Z")),
+      ..fixture.variant_row("100", "0")
+    };
+    let db = fixture
+      .db(vec![expired, upcoming, current.clone()], 1)
+      .into_connection();
+
+    let best = fixture.best(&db, "1").await.unwrap();
+
+    assert_eq!(best.supplier_info.id, current.id);
+  }
+
+  #[tokio::test]
+  async fn variant_rows_take_precedence_over_cheaper_template_rows() {
+    let fixture = Fixture::new();
+    let template = fixture.template_row("80", "0");
+    let variant = fixture.variant_row("100", "0");
+    let db = fixture
+      .db(vec![template, variant.clone()], 2)
+      .into_connection();
+
+    let best = fixture.best(&db, "1").await.unwrap();
+
+    assert_eq!(best.supplier_info.id, variant.id);
+  }
+
+  #[tokio::test]
+  async fn template_rows_price_variants_without_their_own() {
+    let fixture = Fixture::new();
+    let template = fixture.template_row("80", "0");
+    let db = fixture.db(vec![template.clone()], 1).into_connection();
+
+    let best = fixture.best(&db, "1").await.unwrap();
+
+    assert_eq!(best.supplier_info.id, template.id);
+    assert_eq!(best.unit_price, dec("80"));
+  }
+
+  #[tokio::test]
+  async fn prices_in_other_currencies_are_compared_in_the_order_currency() {
+    let fixture = Fixture::new();
+    let usd = currency("USD", "0.01", false);
+    let in_vnd = fixture.variant_row("31000", "0");
+    let in_usd = supplier_info::Model {
+      currency_id: usd.id,
+      ..fixture.variant_row("1.2", "0")
+    };
+    let rate = currency_rate::Model {
+      id: Uuid::new(),
+      currency_id: usd.id,
+      rate: dec("25000"),
+      date_start: at("2025-01-01T00:00:00Z"),
+      created_at: Utc::now().into(),
+      updated_at: None,
+    };
+    let db = fixture
+      .db(vec![in_vnd, in_usd.clone()], 1)
+      .append_query_results(vec![vec![fixture.piece.clone()]; 2])
+      .append_query_results([vec![usd]])
+      .append_query_results([vec![rate]])
+      .into_connection();
+
+    let best = fixture.best(&db, "1").await.unwrap();
+
+    assert_eq!(best.supplier_info.id, in_usd.id);
+    assert_eq!(best.unit_price, dec("30000"));
+  }
+
+  #[tokio::test]
+  async fn no_price_when_no_tier_is_reached() {
+    let fixture = Fixture::new();
+    let db = fixture
+      .db(vec![fixture.variant_row("90", "10")], 0)
+      .append_query_results([vec![fixture.piece.clone()]])
+      .into_connection();
+
+    assert!(fixture.best(&db, "9").await.is_none());
+  }
+}
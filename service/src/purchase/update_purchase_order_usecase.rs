@@ -0,0 +1,148 @@
+use axum::response::{IntoResponse, Response};
+use domain::purchase::{
+  purchase_order::{self, PurchaseOrderState},
+  purchase_order_line,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone, ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter,
+  QuerySelect, Set, TransactionError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use super::order_input::{
+  build_lines, find_supplier, validate_lines, PurchaseOrderInputError, PurchaseOrderLine,
+};
+use crate::currency::currency_conversion::DocumentCurrency;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdatePurchaseOrderUsecase {
+  pub id: Uuid,
+  #[serde(rename(deserialize = "partnerId"))]
+  pub partner_id: Uuid,
+  #[serde(rename(deserialize = "orderDate"))]
+  pub order_date: DateTimeWithTimeZone,
+  /// Defaults to the base currency.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  #[serde(default)]
+  pub note: String,
+  pub lines: Vec<PurchaseOrderLine>,
+}
+
+pub type UpdatePurchaseOrderPayload = UpdatePurchaseOrderUsecase;
+
+impl Validate for UpdatePurchaseOrderUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_lines(&self.lines).into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdatePurchaseOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("purchase_order_not_rfq")]
+  NotRfq(PurchaseOrderState),
+
+  #[error(transparent)]
+  Input(#[from] PurchaseOrderInputError),
+}
+
+impl From<TransactionError<UpdatePurchaseOrderError>> for UpdatePurchaseOrderError {
+  fn from(err: TransactionError<UpdatePurchaseOrderError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdatePurchaseOrderError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for UpdatePurchaseOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdatePurchaseOrderError::Database(err) => AppError::from(err),
+      UpdatePurchaseOrderError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdatePurchaseOrderError::NotRfq(state) => {
+        AppError::conflict(self.to_string()).with_details(json!({ "state": state }))
+      }
+      UpdatePurchaseOrderError::Input(err) => AppError::from(err),
+    };
+
+    error.with_source("update_purchase_order").into_response()
+  }
+}
+
+impl UpdatePurchaseOrderUsecase {
+  /// Rewrites an RFQ, replacing all of its lines and repricing the ones
+  /// without an explicit price. Confirmed orders can no longer be edited.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), UpdatePurchaseOrderError> {
+    let payload = self.clone();
+
+    db.transaction::<_, (), UpdatePurchaseOrderError>(move |txn| {
+      Box::pin(async move {
+        let purchase_order = purchase_order::Entity::find_by_id(payload.id)
+          .lock_exclusive()
+          .one(txn)
+          .await?
+          .ok_or(UpdatePurchaseOrderError::RecordNotFound)?;
+        if purchase_order.state != PurchaseOrderState::Rfq {
+          return Err(UpdatePurchaseOrderError::NotRfq(purchase_order.state));
+        }
+
+        let supplier = find_supplier(txn, payload.partner_id).await?;
+        let currency = DocumentCurrency::load(txn, payload.currency_id, payload.order_date)
+          .await
+          .map_err(PurchaseOrderInputError::from)?;
+        let lines = build_lines(
+          txn,
+          purchase_order.id,
+          supplier.id,
+          payload.order_date,
+          &currency,
+          &payload.lines,
+        )
+        .await?;
+        let amounts = lines.amounts();
+
+        let purchase_order = purchase_order::ActiveModel {
+          id: Set(purchase_order.id),
+          partner_id: Set(supplier.id),
+          order_date: Set(payload.order_date),
+          expected_date: Set(lines.expected_date),
+          note: Set(payload.note.trim().to_string()),
+          currency_id: Set(currency.currency.id),
+          amount_untaxed: Set(amounts.amount_untaxed),
+          amount_tax: Set(amounts.amount_tax),
+          amount_total: Set(amounts.amount_total),
+          ..Default::default()
+        };
+        purchase_order.update(txn).await?;
+
+        purchase_order_line::Entity::delete_many()
+          .filter(purchase_order_line::Column::PurchaseOrderId.eq(payload.id))
+          .exec(txn)
+          .await?;
+        purchase_order_line::Entity::insert_many(lines.lines)
+          .exec(txn)
+          .await?;
+
+        Ok(())
+      })
+    })
+    .await?;
+
+    Ok(())
+  }
+}
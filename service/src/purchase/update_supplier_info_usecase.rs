@@ -0,0 +1,120 @@
+use axum::response::{IntoResponse, Response};
+use domain::purchase::supplier_info;
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ActiveModelTrait, DbErr, EntityTrait, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::create_supplier_info_usecase::{
+  resolve_supplier_info, supplier_sku, validate_supplier_info, SupplierInfoInputError,
+};
+use crate::currency::currency_conversion::currency_or_base;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateSupplierInfoUsecase {
+  pub id: Uuid,
+  #[serde(rename(deserialize = "supplierSku"), default)]
+  pub supplier_sku: Option<String>,
+  pub price: Decimal,
+  /// Left unchanged when omitted, like `uom_id`.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  #[serde(rename(deserialize = "uomId"), default)]
+  pub uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "minQuantity"), default)]
+  pub min_quantity: Decimal,
+  #[serde(rename(deserialize = "leadTimeDays"), default)]
+  pub lead_time_days: i32,
+  #[serde(rename(deserialize = "dateStart"), default)]
+  pub date_start: Option<DateTimeWithTimeZone>,
+  #[serde(rename(deserialize = "dateEnd"), default)]
+  pub date_end: Option<DateTimeWithTimeZone>,
+}
+
+pub type UpdateSupplierInfoPayload = UpdateSupplierInfoUsecase;
+
+impl Validate for UpdateSupplierInfoUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_supplier_info(
+      self.price,
+      self.min_quantity,
+      self.lead_time_days,
+      self.date_start,
+      self.date_end,
+    )
+    .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateSupplierInfoError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error(transparent)]
+  Input(#[from] SupplierInfoInputError),
+}
+
+impl IntoResponse for UpdateSupplierInfoError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdateSupplierInfoError::Database(err) => AppError::from(err),
+      UpdateSupplierInfoError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateSupplierInfoError::Input(err) => AppError::from(err),
+    };
+
+    error.with_source("update_supplier_info").into_response()
+  }
+}
+
+impl UpdateSupplierInfoUsecase {
+  /// Changes the terms of a supplier price. The supplier and the product it
+  /// applies to are fixed once created.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), UpdateSupplierInfoError> {
+    let existing = supplier_info::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(UpdateSupplierInfoError::RecordNotFound)?;
+    let uom_id = resolve_supplier_info(
+      &db,
+      existing.partner_id,
+      existing.product_id,
+      existing.product_template_id,
+      Some(self.uom_id.unwrap_or(existing.uom_id)),
+    )
+    .await?;
+    let currency_id = match self.currency_id {
+      Some(currency_id) => currency_or_base(&db, Some(currency_id))
+        .await
+        .map_err(SupplierInfoInputError::from)?,
+      None => existing.currency_id,
+    };
+
+    let supplier_info = supplier_info::ActiveModel {
+      id: Set(existing.id),
+      supplier_sku: Set(supplier_sku(self.supplier_sku.as_deref())),
+      price: Set(self.price),
+      currency_id: Set(currency_id),
+      uom_id: Set(uom_id),
+      min_quantity: Set(self.min_quantity),
+      lead_time_days: Set(self.lead_time_days),
+      date_start: Set(self.date_start),
+      date_end: Set(self.date_end),
+      ..Default::default()
+    };
+    supplier_info.update(&db).await?;
+
+    Ok(())
+  }
+}
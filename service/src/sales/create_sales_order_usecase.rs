@@ -0,0 +1,156 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::sales::{
+  sales_order::{self, SalesOrderState},
+  sales_order_line,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone, ActiveModelTrait, DbErr, EntityTrait, Set, TransactionError,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::order_input::{
+  build_lines, order_amounts, resolve_customer, validate_lines, SalesOrderInputError,
+  SalesOrderLine,
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreateSalesOrderUsecase {
+  #[serde(rename(deserialize = "partnerId"))]
+  pub partner_id: Uuid,
+  #[serde(rename(deserialize = "invoiceAddressId"), default)]
+  pub invoice_address_id: Option<Uuid>,
+  #[serde(rename(deserialize = "deliveryAddressId"), default)]
+  pub delivery_address_id: Option<Uuid>,
+  #[serde(rename(deserialize = "orderDate"), default)]
+  pub order_date: Option<DateTimeWithTimeZone>,
+  /// Defaults to the customer's payment term.
+  #[serde(rename(deserialize = "paymentTermDays"), default)]
+  pub payment_term_days: Option<i32>,
+  /// Defaults to the currency of the customer's pricelist, then to the base
+  /// currency.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  #[serde(default)]
+  pub note: String,
+  pub lines: Vec<SalesOrderLine>,
+}
+
+pub type CreateSalesOrderPayload = CreateSalesOrderUsecase;
+
+impl Validate for CreateSalesOrderUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_lines(&self.lines)
+      .field(
+        "paymentTermDays",
+        self
+          .payment_term_days
+          .map(|days| rules::reject_if(days < 0, "must_not_be_negative")),
+      )
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum CreateSalesOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Input(#[from] SalesOrderInputError),
+}
+
+impl From<TransactionError<CreateSalesOrderError>> for CreateSalesOrderError {
+  fn from(err: TransactionError<CreateSalesOrderError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => CreateSalesOrderError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for CreateSalesOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateSalesOrderError::Database(err) => AppError::from(err),
+      CreateSalesOrderError::Input(err) => AppError::from(err),
+    };
+
+    error.with_source("create_sales_order").into_response()
+  }
+}
+
+impl CreateSalesOrderUsecase {
+  /// Creates a quotation for the customer. The reference is assigned by the
+  /// database.
+  pub async fn invoke(
+    &self,
+    db: impl WriteConnection,
+  ) -> Result<sales_order::Model, CreateSalesOrderError> {
+    let payload = self.clone();
+
+    let sales_order = db
+      .transaction::<_, sales_order::Model, CreateSalesOrderError>(move |txn| {
+        Box::pin(async move {
+          let customer = resolve_customer(
+            txn,
+            payload.partner_id,
+            payload.invoice_address_id,
+            payload.delivery_address_id,
+          )
+          .await?;
+
+          let sales_order_id = Uuid::new();
+          let order_date = payload.order_date.unwrap_or_else(|| Utc::now().into());
+          let currency = customer
+            .currency(txn, payload.currency_id, order_date)
+            .await?;
+          let lines = build_lines(
+            txn,
+            sales_order_id,
+            customer.pricelist.as_ref(),
+            &currency,
+            &payload.lines,
+          )
+          .await?;
+          let amounts = order_amounts(&lines);
+
+          let sales_order = sales_order::ActiveModel {
+            id: Set(sales_order_id),
+            partner_id: Set(customer.partner.id),
+            invoice_address_id: Set(customer.invoice_address_id),
+            delivery_address_id: Set(customer.delivery_address_id),
+            state: Set(SalesOrderState::Quotation),
+            order_date: Set(order_date),
+            payment_term_days: Set(
+              payload
+                .payment_term_days
+                .unwrap_or(customer.partner.payment_term_days),
+            ),
+            note: Set(payload.note.trim().to_string()),
+            currency_id: Set(currency.currency.id),
+            amount_untaxed: Set(amounts.amount_untaxed),
+            amount_tax: Set(amounts.amount_tax),
+            amount_total: Set(amounts.amount_total),
+            ..Default::default()
+          };
+          let sales_order = sales_order.insert(txn).await?;
+          sales_order_line::Entity::insert_many(lines)
+            .exec(txn)
+            .await?;
+
+          Ok(sales_order)
+        })
+      })
+      .await?;
+
+    Ok(sales_order)
+  }
+}
@@ -0,0 +1,103 @@
+use axum::response::{IntoResponse, Response};
+use domain::{
+  partner::{partner, partner_address},
+  sales::{
+    sales_order::{self, SalesOrderDTO},
+    sales_order_line,
+  },
+};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct FindSalesOrderUsecase {
+  pub id: Uuid,
+}
+
+pub type FindSalesOrderParams = FindSalesOrderUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindSalesOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindSalesOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindSalesOrderError::Database(err) => AppError::from(err),
+      FindSalesOrderError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_sales_order").into_response()
+  }
+}
+
+impl FindSalesOrderUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<SalesOrderDTO, FindSalesOrderError> {
+    let sales_order = sales_order::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindSalesOrderError::RecordNotFound)?;
+
+    let partner = partner::Entity::find_by_id(sales_order.partner_id)
+      .into_partial_model::<partner::PartialModel>()
+      .one(&db)
+      .await?
+      .ok_or(FindSalesOrderError::RecordNotFound)?;
+    let addresses = partner_address::Entity::find()
+      .filter(
+        partner_address::Column::Id.is_in(
+          [
+            sales_order.invoice_address_id,
+            sales_order.delivery_address_id,
+          ]
+          .into_iter()
+          .flatten(),
+        ),
+      )
+      .into_partial_model::<partner_address::PartialModel>()
+      .all(&db)
+      .await?;
+    let address = |address_id: Option<Uuid>| {
+      addresses
+        .iter()
+        .find(|address| Some(address.id) == address_id)
+        .cloned()
+    };
+    let lines = sales_order_line::Entity::find()
+      .filter(sales_order_line::Column::SalesOrderId.eq(sales_order.id))
+      .order_by_asc(sales_order_line::Column::Sequence)
+      .into_partial_model::<sales_order_line::PartialModel>()
+      .all(&db)
+      .await?;
+
+    Ok(SalesOrderDTO {
+      id: sales_order.id,
+      reference: sales_order.reference,
+      partner,
+      invoice_address: address(sales_order.invoice_address_id),
+      delivery_address: address(sales_order.delivery_address_id),
+      state: sales_order.state,
+      order_date: sales_order.order_date,
+      confirmed_at: sales_order.confirmed_at,
+      payment_term_days: sales_order.payment_term_days,
+      note: sales_order.note,
+      currency_id: sales_order.currency_id,
+      amount_untaxed: sales_order.amount_untaxed,
+      amount_tax: sales_order.amount_tax,
+      amount_total: sales_order.amount_total,
+      lines,
+      created_at: sales_order.created_at,
+      updated_at: sales_order.updated_at,
+    })
+  }
+}
@@ -0,0 +1,16 @@
+pub mod order_input;
+
+pub mod list_paginated_sales_orders_usecase;
+pub use list_paginated_sales_orders_usecase::*;
+
+pub mod create_sales_order_usecase;
+pub use create_sales_order_usecase::*;
+
+pub mod find_sales_order_usecase;
+pub use find_sales_order_usecase::*;
+
+pub mod update_sales_order_usecase;
+pub use update_sales_order_usecase::*;
+
+pub mod sales_order_state_usecase;
+pub use sales_order_state_usecase::*;
@@ -0,0 +1,412 @@
+use domain::{
+  partner::{partner, partner_address},
+  pricelist::pricelist,
+  product::{product, product_template},
+  sales::sales_order_line,
+  tax::tax::TaxUse,
+};
+use infra::{
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  currency::currency_conversion::{
+    CurrencyConversionError, DocumentCurrency, PriceConversionError,
+  },
+  measurement::{convert_quantity, UomConversionError},
+  pricelist::price_resolution::resolve_price,
+  tax::{
+    line_taxes::{LineTaxes, TaxInputError},
+    tax_computation::{compute_line, DocumentAmounts, TaxableLine},
+  },
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SalesOrderLine {
+  #[serde(rename(deserialize = "productId"))]
+  pub product_id: Uuid,
+  pub description: Option<String>,
+  pub quantity: Decimal,
+  /// Defaults to the template's sales unit.
+  #[serde(rename(deserialize = "uomId"), default)]
+  pub uom_id: Option<Uuid>,
+  /// Price per `uom_id` unit in the order's currency. Defaults to the
+  /// customer's pricelist price, or the variant's price when the customer has
+  /// no active pricelist.
+  #[serde(rename(deserialize = "unitPrice"), default)]
+  pub unit_price: Option<Decimal>,
+  #[serde(default)]
+  pub discount: Decimal,
+  /// Defaults to the product's category or template sales tax.
+  #[serde(rename(deserialize = "taxId"), default)]
+  pub tax_id: Option<Uuid>,
+}
+
+pub fn validate_lines(lines: &[SalesOrderLine]) -> ValidationErrors {
+  ValidationErrors::new()
+    .field("lines", [rules::not_empty(lines)])
+    .each("lines", lines, |line| {
+      ValidationErrors::new()
+        .field("quantity", [rules::positive(line.quantity)])
+        .field("unitPrice", line.unit_price.map(rules::non_negative))
+        .field(
+          "discount",
+          [
+            rules::non_negative(line.discount),
+            rules::reject_if(line.discount > Decimal::ONE_HUNDRED, "must_not_exceed_100"),
+          ],
+        )
+    })
+}
+
+#[derive(Error, Debug)]
+pub enum SalesOrderInputError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("partner_not_found")]
+  PartnerNotFound,
+
+  #[error("partner_not_customer")]
+  NotCustomer,
+
+  #[error("address_not_found")]
+  AddressNotFound(&'static str),
+
+  #[error("product_not_found")]
+  ProductNotFound(usize),
+
+  #[error("incompatible_uom")]
+  IncompatibleUom(usize, UomConversionError),
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+}
+
+impl SalesOrderInputError {
+  /// Conversion failure of the line at `index`; database and currency errors
+  /// stay as such.
+  fn conversion<E>(index: usize) -> impl Fn(E) -> Self
+  where
+    E: Into<PriceConversionError>,
+  {
+    move |err| match err.into() {
+      PriceConversionError::Database(err) => SalesOrderInputError::Database(err),
+      PriceConversionError::Uom(err) => SalesOrderInputError::IncompatibleUom(index, err),
+      PriceConversionError::Currency(err) => SalesOrderInputError::Currency(err),
+    }
+  }
+}
+
+impl From<SalesOrderInputError> for AppError {
+  fn from(err: SalesOrderInputError) -> Self {
+    match err {
+      SalesOrderInputError::Database(err) => AppError::from(err),
+      SalesOrderInputError::PartnerNotFound => {
+        AppError::validation(err.to_string()).with_field("partnerId", "not_found")
+      }
+      SalesOrderInputError::NotCustomer => {
+        AppError::validation(err.to_string()).with_field("partnerId", "not_customer")
+      }
+      SalesOrderInputError::AddressNotFound(field) => {
+        AppError::validation(err.to_string()).with_field(field, "not_found")
+      }
+      SalesOrderInputError::ProductNotFound(index) => AppError::validation(err.to_string())
+        .with_field(format!("lines[{}].productId", index), "not_found"),
+      SalesOrderInputError::IncompatibleUom(index, ref conversion) => {
+        AppError::validation(err.to_string())
+          .with_field(format!("lines[{}].uomId", index), conversion.to_string())
+      }
+      SalesOrderInputError::Tax(err) => AppError::from(err),
+      SalesOrderInputError::Currency(err) => AppError::from(err),
+    }
+  }
+}
+
+/// Customer of an order with its pricelist and the addresses it is invoiced
+/// and delivered to. Addresses left out fall back to the partner's default of
+/// each type.
+pub struct OrderCustomer {
+  pub partner: partner::Model,
+  pub pricelist: Option<pricelist::Model>,
+  pub invoice_address_id: Option<Uuid>,
+  pub delivery_address_id: Option<Uuid>,
+}
+
+pub async fn resolve_customer<C>(
+  db: &C,
+  partner_id: Uuid,
+  invoice_address_id: Option<Uuid>,
+  delivery_address_id: Option<Uuid>,
+) -> Result<OrderCustomer, SalesOrderInputError>
+where
+  C: ConnectionTrait,
+{
+  let partner = partner::Entity::find_by_id(partner_id)
+    .filter(partner::Column::ArchivedAt.is_null())
+    .one(db)
+    .await?
+    .ok_or(SalesOrderInputError::PartnerNotFound)?;
+  if !partner.is_customer {
+    return Err(SalesOrderInputError::NotCustomer);
+  }
+
+  // An archived pricelist no longer prices orders; they fall back to the
+  // variants' own prices as for customers without one.
+  let pricelist = match partner.pricelist_id {
+    Some(pricelist_id) => {
+      pricelist::Entity::find_by_id(pricelist_id)
+        .filter(pricelist::Column::ArchivedAt.is_null())
+        .one(db)
+        .await?
+    }
+    None => None,
+  };
+
+  let addresses = partner_address::Entity::find()
+    .filter(partner_address::Column::PartnerId.eq(partner_id))
+    .all(db)
+    .await?;
+  let pick = |field: &'static str, address_id: Option<Uuid>, address_type| match address_id {
+    Some(address_id) => addresses
+      .iter()
+      .find(|address| address.id == address_id)
+      .map(|address| Some(address.id))
+      .ok_or(SalesOrderInputError::AddressNotFound(field)),
+    None => Ok(
+      addresses
+        .iter()
+        .find(|address| address.address_type == address_type && address.is_default)
+        .map(|address| address.id),
+    ),
+  };
+
+  Ok(OrderCustomer {
+    invoice_address_id: pick(
+      "invoiceAddressId",
+      invoice_address_id,
+      partner_address::AddressType::Billing,
+    )?,
+    delivery_address_id: pick(
+      "deliveryAddressId",
+      delivery_address_id,
+      partner_address::AddressType::Delivery,
+    )?,
+    partner,
+    pricelist,
+  })
+}
+
+/// Price of `quantity` units at `unit_price` less `discount` percent,
+/// unrounded.
+pub fn line_amount(quantity: Decimal, unit_price: Decimal, discount: Decimal) -> Decimal {
+  let gross = quantity * unit_price;
+  gross - gross * discount / Decimal::ONE_HUNDRED
+}
+
+impl OrderCustomer {
+  /// Currency of the order: `currency_id` when given, else the pricelist's,
+  /// else the base currency, with its rate on the order date.
+  pub async fn currency<C>(
+    &self,
+    db: &C,
+    currency_id: Option<Uuid>,
+    order_date: DateTimeWithTimeZone,
+  ) -> Result<DocumentCurrency, SalesOrderInputError>
+  where
+    C: ConnectionTrait,
+  {
+    let currency_id = currency_id.or(
+      self
+        .pricelist
+        .as_ref()
+        .map(|pricelist| pricelist.currency_id),
+    );
+
+    Ok(DocumentCurrency::load(db, currency_id, order_date).await?)
+  }
+}
+
+/// Turns the payload lines into rows of `sales_order_id`, filling in units,
+/// taxes and descriptions from the variant, prices from the customer's
+/// `pricelist` as of the order date, and computing amounts in `currency`.
+pub async fn build_lines<C>(
+  db: &C,
+  sales_order_id: Uuid,
+  pricelist: Option<&pricelist::Model>,
+  currency: &DocumentCurrency,
+  lines: &[SalesOrderLine],
+) -> Result<Vec<sales_order_line::ActiveModel>, SalesOrderInputError>
+where
+  C: ConnectionTrait,
+{
+  let line_taxes = LineTaxes::load(db, TaxUse::Sales).await?;
+  let mut models = Vec::with_capacity(lines.len());
+
+  for (index, line) in lines.iter().enumerate() {
+    let (product, template) = product::Entity::find_by_id(line.product_id)
+      .filter(product::Column::ArchivedAt.is_null())
+      .find_also_related(product_template::Entity)
+      .one(db)
+      .await?
+      .ok_or(SalesOrderInputError::ProductNotFound(index))?;
+    let template = template.ok_or(SalesOrderInputError::ProductNotFound(index))?;
+
+    let uom_id = line.uom_id.unwrap_or(template.sales_uom_id);
+    let product_quantity = convert_quantity(db, line.quantity, uom_id, template.uom_id)
+      .await
+      .map_err(SalesOrderInputError::conversion(index))?;
+    let unit_price = match line.unit_price {
+      Some(unit_price) => unit_price,
+      None => {
+        resolve_price(
+          db,
+          pricelist,
+          &product,
+          &template,
+          product_quantity,
+          uom_id,
+          currency,
+        )
+        .await
+        .map_err(SalesOrderInputError::conversion(index))?
+        .unit_price
+      }
+    };
+    let tax = line_taxes
+      .resolve(
+        db,
+        line.tax_id,
+        &template,
+        &format!("lines[{}].taxId", index),
+      )
+      .await?;
+    let amounts = compute_line(
+      &TaxableLine {
+        amount: line_amount(line.quantity, unit_price, line.discount),
+        product_quantity,
+        tax,
+      },
+      currency,
+    );
+
+    let description = line
+      .description
+      .as_deref()
+      .map(str::trim)
+      .filter(|description| !description.is_empty())
+      .unwrap_or(&template.name)
+      .to_string();
+
+    models.push(sales_order_line::ActiveModel {
+      sales_order_id: Set(sales_order_id),
+      sequence: Set(index as i32),
+      product_id: Set(product.id),
+      description: Set(description),
+      quantity: Set(line.quantity),
+      uom_id: Set(uom_id),
+      product_quantity: Set(product_quantity),
+      unit_price: Set(unit_price),
+      discount: Set(line.discount),
+      subtotal: Set(amounts.subtotal),
+      tax_id: Set(amounts.tax_id),
+      tax_amount: Set(amounts.tax_amount),
+      ..Default::default()
+    });
+  }
+
+  Ok(models)
+}
+
+/// Totals of an order made of `lines`.
+pub fn order_amounts(lines: &[sales_order_line::ActiveModel]) -> DocumentAmounts {
+  DocumentAmounts::sum(lines.iter().filter_map(|line| {
+    line
+      .subtotal
+      .clone()
+      .take()
+      .zip(line.tax_amount.clone().take())
+  }))
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use domain::partner::partner::PartnerType;
+  use sea_orm::{DatabaseBackend, MockDatabase, QuerySelect, QueryTrait, Transaction};
+
+  use super::*;
+  use crate::test_support::dec;
+
+  #[tokio::test]
+  async fn archived_pricelist_falls_back_to_variant_prices() {
+    let partner = partner::Model {
+      id: Uuid::new(),
+      name: "Customer".to_string(),
+      partner_type: PartnerType::Company,
+      tax_code: None,
+      is_customer: true,
+      is_supplier: false,
+      email: None,
+      phone: None,
+      payment_term_days: 0,
+      pricelist_id: Some(Uuid::new()),
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    };
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![partner.clone()]])
+      .append_query_results([Vec::<pricelist::Model>::new()])
+      .append_query_results([Vec::<partner_address::Model>::new()])
+      .into_connection();
+
+    let customer = resolve_customer(&db, partner.id, None, None).await.unwrap();
+
+    assert_eq!(customer.pricelist, None);
+    assert_eq!(
+      db.into_transaction_log()[1],
+      Transaction::one(
+        pricelist::Entity::find_by_id(partner.pricelist_id.unwrap())
+          .filter(pricelist::Column::ArchivedAt.is_null())
+          .limit(1)
+          .build(DatabaseBackend::Postgres)
+      )
+    );
+  }
+
+  #[test]
+  fn line_amount_is_quantity_times_price() {
+    assert_eq!(
+      line_amount(dec("3"), dec("12.5"), Decimal::ZERO),
+      dec("37.5")
+    );
+  }
+
+  #[test]
+  fn line_amount_takes_discount_percentage_off_unrounded() {
+    assert_eq!(
+      line_amount(dec("3"), dec("9.99"), dec("12.5")),
+      dec("26.22375")
+    );
+  }
+
+  #[test]
+  fn line_amount_is_zero_at_full_discount() {
+    assert_eq!(
+      line_amount(dec("2.5"), dec("4"), Decimal::ONE_HUNDRED),
+      Decimal::ZERO
+    );
+  }
+}
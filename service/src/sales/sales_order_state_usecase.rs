@@ -0,0 +1,144 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::sales::sales_order::{self, SalesOrderState};
+use infra::{db::WriteConnection, error::AppError, uuid::Uuid};
+use sea_orm::{ActiveModelTrait, DbErr, EntityTrait, QuerySelect, Set, TransactionError};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct ConfirmSalesOrderUsecase {
+  pub id: Uuid,
+}
+
+pub type ConfirmSalesOrderPayload = ConfirmSalesOrderUsecase;
+
+#[derive(Debug, Deserialize)]
+pub struct CancelSalesOrderUsecase {
+  pub id: Uuid,
+}
+
+pub type CancelSalesOrderPayload = CancelSalesOrderUsecase;
+
+#[derive(Debug, Deserialize)]
+pub struct MarkSalesOrderDoneUsecase {
+  pub id: Uuid,
+}
+
+pub type MarkSalesOrderDonePayload = MarkSalesOrderDoneUsecase;
+
+#[derive(Error, Debug)]
+pub enum SalesOrderStateError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("invalid_state_transition")]
+  InvalidTransition {
+    from: SalesOrderState,
+    to: SalesOrderState,
+  },
+}
+
+impl From<TransactionError<SalesOrderStateError>> for SalesOrderStateError {
+  fn from(err: TransactionError<SalesOrderStateError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => SalesOrderStateError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for SalesOrderStateError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      SalesOrderStateError::Database(err) => AppError::from(err),
+      SalesOrderStateError::RecordNotFound => AppError::not_found(self.to_string()),
+      SalesOrderStateError::InvalidTransition { from, to } => {
+        AppError::conflict(self.to_string()).with_details(json!({ "from": from, "to": to }))
+      }
+    };
+
+    error.with_source("sales_order_state").into_response()
+  }
+}
+
+impl ConfirmSalesOrderUsecase {
+  /// Turns a quotation into a sales order.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), SalesOrderStateError> {
+    transition(
+      db,
+      self.id,
+      &[SalesOrderState::Quotation],
+      SalesOrderState::Confirmed,
+    )
+    .await
+  }
+}
+
+impl CancelSalesOrderUsecase {
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), SalesOrderStateError> {
+    transition(
+      db,
+      self.id,
+      &[SalesOrderState::Quotation, SalesOrderState::Confirmed],
+      SalesOrderState::Cancelled,
+    )
+    .await
+  }
+}
+
+impl MarkSalesOrderDoneUsecase {
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), SalesOrderStateError> {
+    transition(
+      db,
+      self.id,
+      &[SalesOrderState::Confirmed],
+      SalesOrderState::Done,
+    )
+    .await
+  }
+}
+
+/// Moves the order to `to` when it currently is in one of `from`. Confirming
+/// stamps `confirmed_at`.
+async fn transition(
+  db: impl WriteConnection,
+  id: Uuid,
+  from: &'static [SalesOrderState],
+  to: SalesOrderState,
+) -> Result<(), SalesOrderStateError> {
+  db.transaction::<_, (), SalesOrderStateError>(move |txn| {
+    Box::pin(async move {
+      let sales_order = sales_order::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or(SalesOrderStateError::RecordNotFound)?;
+      if !from.contains(&sales_order.state) {
+        return Err(SalesOrderStateError::InvalidTransition {
+          from: sales_order.state,
+          to,
+        });
+      }
+
+      let mut active = sales_order::ActiveModel {
+        id: Set(sales_order.id),
+        state: Set(to),
+        ..Default::default()
+      };
+      if to == SalesOrderState::Confirmed {
+        active.confirmed_at = Set(Some(Utc::now().into()));
+      }
+      active.update(txn).await?;
+
+      Ok(())
+    })
+  })
+  .await?;
+
+  Ok(())
+}
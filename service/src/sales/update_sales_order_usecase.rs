@@ -0,0 +1,170 @@
+use axum::response::{IntoResponse, Response};
+use domain::sales::{
+  sales_order::{self, SalesOrderState},
+  sales_order_line,
+};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::DateTimeWithTimeZone, ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter,
+  QuerySelect, Set, TransactionError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use thiserror::Error;
+
+use super::order_input::{
+  build_lines, order_amounts, resolve_customer, validate_lines, SalesOrderInputError,
+  SalesOrderLine,
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateSalesOrderUsecase {
+  pub id: Uuid,
+  #[serde(rename(deserialize = "partnerId"))]
+  pub partner_id: Uuid,
+  #[serde(rename(deserialize = "invoiceAddressId"), default)]
+  pub invoice_address_id: Option<Uuid>,
+  #[serde(rename(deserialize = "deliveryAddressId"), default)]
+  pub delivery_address_id: Option<Uuid>,
+  #[serde(rename(deserialize = "orderDate"))]
+  pub order_date: DateTimeWithTimeZone,
+  #[serde(rename(deserialize = "paymentTermDays"))]
+  pub payment_term_days: i32,
+  /// Defaults to the currency of the customer's pricelist, then to the base
+  /// currency.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  #[serde(default)]
+  pub note: String,
+  pub lines: Vec<SalesOrderLine>,
+}
+
+pub type UpdateSalesOrderPayload = UpdateSalesOrderUsecase;
+
+impl Validate for UpdateSalesOrderUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_lines(&self.lines)
+      .field(
+        "paymentTermDays",
+        [rules::reject_if(
+          self.payment_term_days < 0,
+          "must_not_be_negative",
+        )],
+      )
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateSalesOrderError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("sales_order_not_quotation")]
+  NotQuotation(SalesOrderState),
+
+  #[error(transparent)]
+  Input(#[from] SalesOrderInputError),
+}
+
+impl From<TransactionError<UpdateSalesOrderError>> for UpdateSalesOrderError {
+  fn from(err: TransactionError<UpdateSalesOrderError>) -> Self {
+    match err {
+      TransactionError::Connection(err) => UpdateSalesOrderError::Database(err),
+      TransactionError::Transaction(err) => err,
+    }
+  }
+}
+
+impl IntoResponse for UpdateSalesOrderError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdateSalesOrderError::Database(err) => AppError::from(err),
+      UpdateSalesOrderError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateSalesOrderError::NotQuotation(state) => {
+        AppError::conflict(self.to_string()).with_details(json!({ "state": state }))
+      }
+      UpdateSalesOrderError::Input(err) => AppError::from(err),
+    };
+
+    error.with_source("update_sales_order").into_response()
+  }
+}
+
+impl UpdateSalesOrderUsecase {
+  /// Rewrites a quotation, replacing all of its lines. Confirmed orders can no
+  /// longer be edited.
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<(), UpdateSalesOrderError> {
+    let payload = self.clone();
+
+    db.transaction::<_, (), UpdateSalesOrderError>(move |txn| {
+      Box::pin(async move {
+        let sales_order = sales_order::Entity::find_by_id(payload.id)
+          .lock_exclusive()
+          .one(txn)
+          .await?
+          .ok_or(UpdateSalesOrderError::RecordNotFound)?;
+        if sales_order.state != SalesOrderState::Quotation {
+          return Err(UpdateSalesOrderError::NotQuotation(sales_order.state));
+        }
+
+        let customer = resolve_customer(
+          txn,
+          payload.partner_id,
+          payload.invoice_address_id,
+          payload.delivery_address_id,
+        )
+        .await?;
+        let currency = customer
+          .currency(txn, payload.currency_id, payload.order_date)
+          .await?;
+        let lines = build_lines(
+          txn,
+          sales_order.id,
+          customer.pricelist.as_ref(),
+          &currency,
+          &payload.lines,
+        )
+        .await?;
+        let amounts = order_amounts(&lines);
+
+        let sales_order = sales_order::ActiveModel {
+          id: Set(sales_order.id),
+          partner_id: Set(customer.partner.id),
+          invoice_address_id: Set(customer.invoice_address_id),
+          delivery_address_id: Set(customer.delivery_address_id),
+          order_date: Set(payload.order_date),
+          payment_term_days: Set(payload.payment_term_days),
+          note: Set(payload.note.trim().to_string()),
+          currency_id: Set(currency.currency.id),
+          amount_untaxed: Set(amounts.amount_untaxed),
+          amount_tax: Set(amounts.amount_tax),
+          amount_total: Set(amounts.amount_total),
+          ..Default::default()
+        };
+        sales_order.update(txn).await?;
+
+        sales_order_line::Entity::delete_many()
+          .filter(sales_order_line::Column::SalesOrderId.eq(payload.id))
+          .exec(txn)
+          .await?;
+        sales_order_line::Entity::insert_many(lines)
+          .exec(txn)
+          .await?;
+
+        Ok(())
+      })
+    })
+    .await?;
+
+    Ok(())
+  }
+}
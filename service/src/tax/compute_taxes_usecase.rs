@@ -0,0 +1,186 @@
+use axum::response::{IntoResponse, Response};
+use chrono::Utc;
+use domain::{
+  product::{product, product_template},
+  tax::tax::{TaxComputationDTO, TaxUse},
+};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::{DateTimeWithTimeZone, Decimal},
+  DbErr, EntityTrait,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+  line_taxes::{LineTaxes, TaxInputError},
+  tax_computation::{compute_taxes, TaxableLine},
+};
+use crate::{
+  currency::currency_conversion::{CurrencyConversionError, DocumentCurrency},
+  measurement::{convert_quantity, UomConversionError},
+};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TaxedLine {
+  /// Lines without a product are only taxed by an explicit `tax_id`.
+  #[serde(rename(deserialize = "productId"), default)]
+  pub product_id: Option<Uuid>,
+  /// Defaults to the product's category or template tax.
+  #[serde(rename(deserialize = "taxId"), default)]
+  pub tax_id: Option<Uuid>,
+  pub quantity: Decimal,
+  /// Defaults to the template's sales or purchase unit.
+  #[serde(rename(deserialize = "uomId"), default)]
+  pub uom_id: Option<Uuid>,
+  #[serde(rename(deserialize = "unitPrice"))]
+  pub unit_price: Decimal,
+  #[serde(default)]
+  pub discount: Decimal,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ComputeTaxesUsecase {
+  #[serde(rename(deserialize = "taxUse"))]
+  pub tax_use: TaxUse,
+  /// Currency of the unit prices, the base currency by default.
+  #[serde(rename(deserialize = "currencyId"), default)]
+  pub currency_id: Option<Uuid>,
+  /// Date whose rate converts fixed taxes, now by default.
+  #[serde(default)]
+  pub date: Option<DateTimeWithTimeZone>,
+  pub lines: Vec<TaxedLine>,
+}
+
+pub type ComputeTaxesPayload = ComputeTaxesUsecase;
+
+impl Validate for ComputeTaxesUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    ValidationErrors::new()
+      .each("lines", &self.lines, |line| {
+        ValidationErrors::new()
+          .field("quantity", [rules::positive(line.quantity)])
+          .field("unitPrice", [rules::non_negative(line.unit_price)])
+          .field(
+            "discount",
+            [
+              rules::non_negative(line.discount),
+              rules::reject_if(line.discount > Decimal::ONE_HUNDRED, "must_not_exceed_100"),
+            ],
+          )
+      })
+      .into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum ComputeTaxesError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Tax(#[from] TaxInputError),
+
+  #[error(transparent)]
+  Currency(#[from] CurrencyConversionError),
+
+  #[error("product_not_found")]
+  ProductNotFound(usize),
+
+  #[error("incompatible_uom")]
+  IncompatibleUom(usize, UomConversionError),
+}
+
+impl ComputeTaxesError {
+  /// Conversion failure of the line at `index`; database errors stay as such.
+  fn conversion(index: usize) -> impl Fn(UomConversionError) -> Self {
+    move |err| match err {
+      UomConversionError::Database(err) => ComputeTaxesError::Database(err),
+      err => ComputeTaxesError::IncompatibleUom(index, err),
+    }
+  }
+}
+
+impl IntoResponse for ComputeTaxesError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ComputeTaxesError::Database(err) => AppError::from(err),
+      ComputeTaxesError::Tax(err) => AppError::from(err),
+      ComputeTaxesError::Currency(err) => AppError::from(err),
+      ComputeTaxesError::ProductNotFound(index) => AppError::validation(self.to_string())
+        .with_field(format!("lines[{}].productId", index), "not_found"),
+      ComputeTaxesError::IncompatibleUom(index, ref conversion) => {
+        AppError::validation(self.to_string())
+          .with_field(format!("lines[{}].uomId", index), conversion.to_string())
+      }
+    };
+
+    error.with_source("compute_taxes").into_response()
+  }
+}
+
+impl ComputeTaxesUsecase {
+  /// Previews the untaxed amount, tax breakdown and total of a document
+  /// without saving anything.
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<TaxComputationDTO, ComputeTaxesError> {
+    let currency = DocumentCurrency::load(
+      &db,
+      self.currency_id,
+      self.date.unwrap_or_else(|| Utc::now().into()),
+    )
+    .await?;
+    let line_taxes = LineTaxes::load(&db, self.tax_use).await?;
+    let mut taxable_lines = Vec::with_capacity(self.lines.len());
+
+    for (index, line) in self.lines.iter().enumerate() {
+      let field = format!("lines[{}].taxId", index);
+      let amount = line.quantity * line.unit_price
+        - line.quantity * line.unit_price * line.discount / Decimal::ONE_HUNDRED;
+
+      let Some(product_id) = line.product_id else {
+        taxable_lines.push(TaxableLine {
+          amount,
+          product_quantity: line.quantity,
+          tax: line
+            .tax_id
+            .map(|tax_id| line_taxes.get(tax_id, &field))
+            .transpose()?,
+        });
+        continue;
+      };
+
+      let (_, template) = product::Entity::find_by_id(product_id)
+        .find_also_related(product_template::Entity)
+        .one(&db)
+        .await?
+        .ok_or(ComputeTaxesError::ProductNotFound(index))?;
+      let template = template.ok_or(ComputeTaxesError::ProductNotFound(index))?;
+
+      let uom_id = line.uom_id.unwrap_or(match self.tax_use {
+        TaxUse::Sales => template.sales_uom_id,
+        TaxUse::Purchase => template.purchase_uom_id,
+      });
+      let product_quantity = convert_quantity(&db, line.quantity, uom_id, template.uom_id)
+        .await
+        .map_err(ComputeTaxesError::conversion(index))?;
+
+      taxable_lines.push(TaxableLine {
+        amount,
+        product_quantity,
+        tax: line_taxes
+          .resolve(&db, line.tax_id, &template, &field)
+          .await?,
+      });
+    }
+
+    Ok(compute_taxes(&taxable_lines, &currency))
+  }
+}
@@ -0,0 +1,127 @@
+use axum::response::{IntoResponse, Response};
+use domain::tax::tax::{self, TaxAmountType, TaxUse, VatGroup};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{rules, Validate, ValidationErrors},
+};
+use sea_orm::{
+  prelude::Decimal, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait,
+  QueryFilter, Set,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::unique_name::{name_conflict, same_name};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CreateTaxUsecase {
+  pub name: String,
+  #[serde(rename(deserialize = "taxUse"))]
+  pub tax_use: TaxUse,
+  #[serde(rename(deserialize = "amountType"))]
+  pub amount_type: TaxAmountType,
+  pub amount: Decimal,
+  #[serde(rename(deserialize = "priceInclude"), default)]
+  pub price_include: bool,
+  #[serde(rename(deserialize = "vatGroup"), default)]
+  pub vat_group: Option<VatGroup>,
+}
+
+pub type CreateTaxPayload = CreateTaxUsecase;
+
+impl Validate for CreateTaxUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_tax(&self.name, self.amount_type, self.amount, self.vat_group).into_result()
+  }
+}
+
+/// A VAT group fixes the rate.
+pub(crate) fn validate_tax(
+  name: &str,
+  amount_type: TaxAmountType,
+  amount: Decimal,
+  vat_group: Option<VatGroup>,
+) -> ValidationErrors {
+  ValidationErrors::new()
+    .field("name", [rules::required(name)])
+    .field(
+      "amount",
+      [
+        rules::non_negative(amount),
+        rules::reject_if(
+          amount_type == TaxAmountType::Percent && amount > Decimal::ONE_HUNDRED,
+          "must_not_exceed_100",
+        ),
+      ],
+    )
+    .field(
+      "amountType",
+      vat_group.map(|_| rules::reject_if(amount_type != TaxAmountType::Percent, "must_be_percent")),
+    )
+    .field(
+      "amount",
+      vat_group
+        .map(|vat_group| rules::reject_if(amount != vat_group.rate(), "must_match_vat_group")),
+    )
+}
+
+/// Tax other than `except_id` of the same use already named `name`.
+pub(crate) async fn find_by_name<C>(
+  db: &C,
+  name: &str,
+  tax_use: TaxUse,
+  except_id: Option<Uuid>,
+) -> Result<Option<tax::Model>, DbErr>
+where
+  C: ConnectionTrait,
+{
+  tax::Entity::find()
+    .filter(same_name(tax::Column::Name, name))
+    .filter(tax::Column::TaxUse.eq(tax_use))
+    .filter(Condition::all().add_option(except_id.map(|id| tax::Column::Id.ne(id))))
+    .one(db)
+    .await
+}
+
+#[derive(Error, Debug)]
+pub enum CreateTaxError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+}
+
+impl IntoResponse for CreateTaxError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      CreateTaxError::Database(err) => AppError::from(err),
+      CreateTaxError::NameConflict(existing_id) => name_conflict(self.to_string(), existing_id),
+    };
+
+    error.with_source("create_tax").into_response()
+  }
+}
+
+impl CreateTaxUsecase {
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<tax::Model, CreateTaxError> {
+    if let Some(existing) = find_by_name(&db, &self.name, self.tax_use, None).await? {
+      return Err(CreateTaxError::NameConflict(existing.id));
+    }
+
+    let tax = tax::ActiveModel {
+      name: Set(self.name.trim().to_string()),
+      tax_use: Set(self.tax_use),
+      amount_type: Set(self.amount_type),
+      amount: Set(self.amount),
+      price_include: Set(self.price_include),
+      vat_group: Set(self.vat_group),
+      ..Default::default()
+    };
+    let tax = tax.insert(&db).await?;
+
+    Ok(tax)
+  }
+}
@@ -0,0 +1,55 @@
+use axum::response::{IntoResponse, Response};
+use domain::tax::tax::{self, TaxDTO};
+use infra::{db::ReadConnection, error::AppError, uuid::Uuid};
+use sea_orm::{DbErr, EntityTrait};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Debug, Deserialize)]
+pub struct FindTaxUsecase {
+  pub id: Uuid,
+}
+
+pub type FindTaxParams = FindTaxUsecase;
+
+#[derive(Error, Debug)]
+pub enum FindTaxError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+}
+
+impl IntoResponse for FindTaxError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      FindTaxError::Database(err) => AppError::from(err),
+      FindTaxError::RecordNotFound => AppError::not_found(self.to_string()),
+    };
+
+    error.with_source("find_tax").into_response()
+  }
+}
+
+impl FindTaxUsecase {
+  pub async fn invoke(&self, db: impl ReadConnection) -> Result<TaxDTO, FindTaxError> {
+    let tax = tax::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(FindTaxError::RecordNotFound)?;
+
+    Ok(TaxDTO {
+      id: tax.id,
+      name: tax.name,
+      tax_use: tax.tax_use,
+      amount_type: tax.amount_type,
+      amount: tax.amount,
+      price_include: tax.price_include,
+      vat_group: tax.vat_group,
+      created_at: tax.created_at,
+      updated_at: tax.updated_at,
+      archived_at: tax.archived_at,
+    })
+  }
+}
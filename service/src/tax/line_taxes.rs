@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+
+use domain::{
+  product::{category, product_template},
+  tax::tax::{self, TaxUse},
+};
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use thiserror::Error;
+
+use crate::product::category_hierarchy::ancestor_category_ids;
+
+#[derive(Error, Debug)]
+pub enum TaxInputError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("tax_not_found")]
+  TaxNotFound(String),
+
+  #[error("tax_use_mismatch")]
+  TaxUseMismatch(String),
+}
+
+impl From<TaxInputError> for AppError {
+  fn from(err: TaxInputError) -> Self {
+    match err {
+      TaxInputError::Database(err) => AppError::from(err),
+      TaxInputError::TaxNotFound(ref field) => {
+        AppError::validation(err.to_string()).with_field(field.clone(), "not_found")
+      }
+      TaxInputError::TaxUseMismatch(ref field) => {
+        AppError::validation(err.to_string()).with_field(field.clone(), err.to_string())
+      }
+    }
+  }
+}
+
+/// Active taxes, from which line taxes are resolved.
+pub struct LineTaxes {
+  tax_use: TaxUse,
+  taxes: HashMap<Uuid, tax::Model>,
+}
+
+impl LineTaxes {
+  pub async fn load<C>(db: &C, tax_use: TaxUse) -> Result<Self, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let taxes = tax::Entity::find()
+      .filter(tax::Column::ArchivedAt.is_null())
+      .all(db)
+      .await?;
+
+    Ok(Self {
+      tax_use,
+      taxes: taxes.into_iter().map(|tax| (tax.id, tax)).collect(),
+    })
+  }
+
+  /// Active tax `tax_id`, which must be meant for the loaded use. `field`
+  /// names the input the id came from.
+  pub fn get(&self, tax_id: Uuid, field: &str) -> Result<&tax::Model, TaxInputError> {
+    let tax = self
+      .taxes
+      .get(&tax_id)
+      .ok_or_else(|| TaxInputError::TaxNotFound(field.to_string()))?;
+    if tax.tax_use != self.tax_use {
+      return Err(TaxInputError::TaxUseMismatch(field.to_string()));
+    }
+
+    Ok(tax)
+  }
+
+  /// Tax of a line of `template`: `tax_id` when given, otherwise the default
+  /// of the product's category or its closest ancestor setting one, otherwise
+  /// the template's default. Defaults pointing to archived taxes are skipped.
+  /// Only the branch of the template's category is read.
+  pub async fn resolve<C>(
+    &self,
+    db: &C,
+    tax_id: Option<Uuid>,
+    template: &product_template::Model,
+    field: &str,
+  ) -> Result<Option<&tax::Model>, TaxInputError>
+  where
+    C: ConnectionTrait,
+  {
+    if let Some(tax_id) = tax_id {
+      return self.get(tax_id, field).map(Some);
+    }
+
+    let category_defaults = match template.category_id {
+      Some(category_id) => self.category_defaults(db, category_id).await?,
+      None => vec![],
+    };
+    let template_default = match self.tax_use {
+      TaxUse::Sales => template.sales_tax_id,
+      TaxUse::Purchase => template.purchase_tax_id,
+    };
+
+    Ok(
+      category_defaults
+        .into_iter()
+        .chain([template_default])
+        .flatten()
+        .find_map(|tax_id| self.get(tax_id, field).ok()),
+    )
+  }
+
+  /// Default taxes of `category_id` then of its ancestors, closest first.
+  async fn category_defaults<C>(
+    &self,
+    db: &C,
+    category_id: Uuid,
+  ) -> Result<Vec<Option<Uuid>>, DbErr>
+  where
+    C: ConnectionTrait,
+  {
+    let category_ids = ancestor_category_ids(db, category_id).await?;
+    let categories = category::Entity::find()
+      .filter(category::Column::Id.is_in(category_ids.clone()))
+      .all(db)
+      .await?;
+
+    Ok(
+      category_ids
+        .iter()
+        .filter_map(|category_id| {
+          categories
+            .iter()
+            .find(|category| category.id == *category_id)
+        })
+        .map(|category| match self.tax_use {
+          TaxUse::Sales => category.sales_tax_id,
+          TaxUse::Purchase => category.purchase_tax_id,
+        })
+        .collect(),
+    )
+  }
+}
+
+/// Checks default taxes set on a template or category: each must be an active
+/// tax of the use it is the default for.
+pub async fn check_default_taxes<C>(
+  db: &C,
+  defaults: [(&str, Option<Uuid>, TaxUse); 2],
+) -> Result<(), TaxInputError>
+where
+  C: ConnectionTrait,
+{
+  for (field, tax_id, tax_use) in defaults {
+    let Some(tax_id) = tax_id else {
+      continue;
+    };
+    let tax = tax::Entity::find_by_id(tax_id)
+      .filter(tax::Column::ArchivedAt.is_null())
+      .one(db)
+      .await?
+      .ok_or_else(|| TaxInputError::TaxNotFound(field.to_string()))?;
+    if tax.tax_use != tax_use {
+      return Err(TaxInputError::TaxUseMismatch(field.to_string()));
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::BTreeMap;
+
+  use chrono::Utc;
+  use domain::{
+    product::{
+      category::CostingMethod,
+      product_template::{ProductSubtype, ProductType, Tracking},
+    },
+    tax::tax::TaxAmountType,
+  };
+  use sea_orm::{DatabaseBackend, MockDatabase, Value};
+
+  use super::*;
+  use crate::test_support::dec;
+
+  fn sales_tax(name: &str) -> tax::Model {
+    tax::Model {
+      id: Uuid::new(),
+      name: name.to_string(),
+      tax_use: TaxUse::Sales,
+      amount_type: TaxAmountType::Percent,
+      amount: dec("10"),
+      price_include: false,
+      vat_group: None,
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    }
+  }
+
+  fn category(parent_category_id: Option<Uuid>, sales_tax_id: Option<Uuid>) -> category::Model {
+    category::Model {
+      id: Uuid::new(),
+      name: "Category".to_string(),
+      parent_category_id,
+      costing_method: CostingMethod::Standard,
+      sales_tax_id,
+      purchase_tax_id: None,
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    }
+  }
+
+  fn template(category_id: Option<Uuid>, sales_tax_id: Option<Uuid>) -> product_template::Model {
+    let uom_id = Uuid::new();
+    product_template::Model {
+      id: Uuid::new(),
+      name: "Carton box".to_string(),
+      description: String::new(),
+      uom_id,
+      purchase_uom_id: uom_id,
+      sales_uom_id: uom_id,
+      category_id,
+      sales_tax_id,
+      purchase_tax_id: None,
+      currency_id: Uuid::new(),
+      product_type: ProductType::Goods,
+      product_subtype: ProductSubtype::Normal,
+      is_track_inventory: true,
+      tracking: Tracking::None,
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    }
+  }
+
+  #[tokio::test]
+  async fn closest_category_default_wins_over_template_default() {
+    let category_tax = sales_tax("Category VAT");
+    let template_tax = sales_tax("Template VAT");
+    let parent = category(None, Some(category_tax.id));
+    let child = category(Some(parent.id), None);
+    let branch = vec![
+      BTreeMap::from([("id", Value::from(child.id))]),
+      BTreeMap::from([("id", Value::from(parent.id))]),
+    ];
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![category_tax.clone(), template_tax.clone()]])
+      .append_query_results([branch])
+      .append_query_results([vec![parent, child.clone()]])
+      .into_connection();
+    let line_taxes = LineTaxes::load(&db, TaxUse::Sales).await.unwrap();
+
+    let tax = line_taxes
+      .resolve(
+        &db,
+        None,
+        &template(Some(child.id), Some(template_tax.id)),
+        "taxId",
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(tax.unwrap().id, category_tax.id);
+  }
+
+  #[tokio::test]
+  async fn template_without_category_reads_no_category() {
+    let template_tax = sales_tax("Template VAT");
+    let db = MockDatabase::new(DatabaseBackend::Postgres)
+      .append_query_results([vec![template_tax.clone()]])
+      .into_connection();
+    let line_taxes = LineTaxes::load(&db, TaxUse::Sales).await.unwrap();
+
+    let tax = line_taxes
+      .resolve(&db, None, &template(None, Some(template_tax.id)), "taxId")
+      .await
+      .unwrap();
+
+    assert_eq!(tax.unwrap().id, template_tax.id);
+    assert_eq!(db.into_transaction_log().len(), 1);
+  }
+}
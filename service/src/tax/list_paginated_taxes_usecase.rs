@@ -0,0 +1,184 @@
+use axum::response::{IntoResponse, Response};
+use domain::tax::tax::{self, Column, Entity as Tax, TaxUse};
+use infra::{
+  db::ReadConnection,
+  error::AppError,
+  response::{CursorPaginationMeta, PaginationMeta},
+};
+use sea_orm::{
+  sea_query::{Expr, Query},
+  ColumnTrait, Condition, DbErr, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder,
+  QuerySelect,
+};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{
+  archive::archived_condition,
+  list_query::{
+    cursor_page, name_contains, page_index, page_size, Cursor, CursorError, PageError,
+    PaginationMode, SortBy, SortOrder,
+  },
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ListPaginatedTaxesUsecase {
+  pub page: Option<u64>,
+  pub per_page: Option<u64>,
+  pub include_archived: Option<bool>,
+  pub only_archived: Option<bool>,
+  pub name: Option<String>,
+  pub tax_use: Option<TaxUse>,
+  pub sort_by: Option<SortBy>,
+  pub order: Option<SortOrder>,
+  pub pagination: Option<PaginationMode>,
+  pub cursor: Option<Cursor>,
+  pub with_total: Option<bool>,
+}
+
+pub type ListPaginatedTaxesParams = ListPaginatedTaxesUsecase;
+
+#[derive(Error, Debug)]
+pub enum ListPaginatedTaxesError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error(transparent)]
+  Page(#[from] PageError),
+
+  #[error(transparent)]
+  Cursor(#[from] CursorError),
+}
+
+impl IntoResponse for ListPaginatedTaxesError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      ListPaginatedTaxesError::Database(err) => AppError::from(err),
+      ListPaginatedTaxesError::Page(err) => AppError::from(err),
+      ListPaginatedTaxesError::Cursor(err) => AppError::from(err),
+    };
+
+    error.with_source("list_paginated_taxes").into_response()
+  }
+}
+
+impl ListPaginatedTaxesUsecase {
+  pub async fn invoke(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<tax::PartialModel>, PaginationMeta), ListPaginatedTaxesError> {
+    let per_page = page_size(self.per_page)?;
+    let page = page_index(self.page)?;
+    let order = Order::from(self.order.unwrap_or_default());
+
+    let tax_pages = Tax::find()
+      .filter(self.filter_condition())
+      .order_by(self.sort_column(), order)
+      .order_by_asc(Column::Id)
+      .into_partial_model::<tax::PartialModel>()
+      .paginate(&db, per_page);
+    let taxes = tax_pages.fetch_page(page).await?;
+    let items_and_pages = tax_pages.num_items_and_pages().await?;
+    let total = items_and_pages.number_of_items;
+    let total_pages = items_and_pages.number_of_pages;
+
+    Ok((
+      taxes,
+      PaginationMeta {
+        total,
+        total_pages,
+        page: page + 1,
+        per_page,
+      },
+    ))
+  }
+
+  /// Keyset variant of `invoke`: reads `per_page` rows after (or before) the
+  /// cursor and only counts the whole result set when `with_total` is set.
+  pub async fn invoke_cursor(
+    &self,
+    db: impl ReadConnection,
+  ) -> Result<(Vec<tax::PartialModel>, CursorPaginationMeta), ListPaginatedTaxesError> {
+    let per_page = page_size(self.per_page)?;
+    let cursor = self.cursor.as_ref();
+    let query_order = Cursor::query_order(cursor, self.order.unwrap_or_default().into());
+    let sort_column = self.sort_column();
+
+    let keyset_condition = match cursor {
+      Some(cursor) => Some(
+        cursor
+          .keyset_condition(
+            &db,
+            vec![
+              Expr::col((Tax, sort_column)).into(),
+              Expr::col((Tax, Column::Id)).into(),
+            ],
+            Query::select()
+              .column(sort_column)
+              .column(Column::Id)
+              .from(Tax)
+              .and_where(Column::Id.eq(cursor.id))
+              .to_owned(),
+            query_order.clone(),
+          )
+          .await?,
+      ),
+      None => None,
+    };
+
+    let rows = Tax::find()
+      .filter(self.filter_condition())
+      .filter(Condition::all().add_option(keyset_condition))
+      .order_by(sort_column, query_order.clone())
+      .order_by(Column::Id, query_order)
+      .limit(per_page + 1)
+      .into_partial_model::<tax::PartialModel>()
+      .all(&db)
+      .await?;
+    let (taxes, next_cursor, prev_cursor) = cursor_page(rows, per_page, cursor, |tax| tax.id);
+
+    let total = match self.with_total {
+      Some(true) => Some(
+        Tax::find()
+          .filter(self.filter_condition())
+          .count(&db)
+          .await?,
+      ),
+      _ => None,
+    };
+
+    Ok((
+      taxes,
+      CursorPaginationMeta {
+        per_page,
+        next_cursor,
+        prev_cursor,
+        total,
+      },
+    ))
+  }
+
+  fn filter_condition(&self) -> Condition {
+    Condition::all()
+      .add(archived_condition(
+        Column::ArchivedAt,
+        self.include_archived,
+        self.only_archived,
+      ))
+      .add_option(
+        self
+          .name
+          .as_deref()
+          .filter(|name| !name.trim().is_empty())
+          .map(|name| name_contains(Column::Name, name)),
+      )
+      .add_option(self.tax_use.map(|tax_use| Column::TaxUse.eq(tax_use)))
+  }
+
+  fn sort_column(&self) -> Column {
+    match self.sort_by {
+      Some(SortBy::Name) => Column::Name,
+      Some(SortBy::CreatedAt) | None => Column::CreatedAt,
+    }
+  }
+}
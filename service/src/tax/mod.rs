@@ -0,0 +1,17 @@
+pub mod line_taxes;
+pub mod tax_computation;
+
+pub mod create_tax_usecase;
+pub use create_tax_usecase::*;
+
+pub mod update_tax_usecase;
+pub use update_tax_usecase::*;
+
+pub mod find_tax_usecase;
+pub use find_tax_usecase::*;
+
+pub mod list_paginated_taxes_usecase;
+pub use list_paginated_taxes_usecase::*;
+
+pub mod compute_taxes_usecase;
+pub use compute_taxes_usecase::*;
@@ -0,0 +1,275 @@
+use domain::tax::tax::{self, LineTaxDTO, TaxAmountType, TaxBreakdownDTO, TaxComputationDTO};
+use sea_orm::prelude::Decimal;
+
+use crate::currency::currency_conversion::DocumentCurrency;
+
+/// Line to be taxed.
+pub struct TaxableLine<'a> {
+  /// Quantity times unit price after discount, unrounded. Includes the tax
+  /// when the tax is price-included.
+  pub amount: Decimal,
+  /// Stock-unit quantity, which fixed taxes are charged on.
+  pub product_quantity: Decimal,
+  pub tax: Option<&'a tax::Model>,
+}
+
+/// Untaxed amount and tax of a single line, both rounded to the precision of
+/// the document's currency. Price-included taxes are taken out of the rounded
+/// gross amount so that the two always add back up to it. Fixed taxes are set
+/// in the base currency and converted at the document's rate.
+pub fn compute_line(line: &TaxableLine, currency: &DocumentCurrency) -> LineTaxDTO {
+  let Some(tax) = line.tax else {
+    let subtotal = currency.round(line.amount);
+    return LineTaxDTO {
+      tax_id: None,
+      subtotal,
+      tax_amount: Decimal::ZERO,
+      total: subtotal,
+    };
+  };
+
+  let rate = tax.amount / Decimal::ONE_HUNDRED;
+  let fixed_amount = || currency.round(line.product_quantity * currency.from_base(tax.amount));
+  let (subtotal, tax_amount) = match (tax.amount_type, tax.price_include) {
+    (TaxAmountType::Percent, false) => {
+      let subtotal = currency.round(line.amount);
+      (subtotal, currency.round(subtotal * rate))
+    }
+    (TaxAmountType::Percent, true) => {
+      let total = currency.round(line.amount);
+      let subtotal = currency.round(total / (Decimal::ONE + rate));
+      (subtotal, total - subtotal)
+    }
+    (TaxAmountType::Fixed, false) => (currency.round(line.amount), fixed_amount()),
+    (TaxAmountType::Fixed, true) => {
+      let tax_amount = fixed_amount();
+      (currency.round(line.amount) - tax_amount, tax_amount)
+    }
+  };
+
+  LineTaxDTO {
+    tax_id: Some(tax.id),
+    subtotal,
+    tax_amount,
+    total: subtotal + tax_amount,
+  }
+}
+
+/// Untaxed, tax and total amounts of a document.
+pub struct DocumentAmounts {
+  pub amount_untaxed: Decimal,
+  pub amount_tax: Decimal,
+  pub amount_total: Decimal,
+}
+
+impl DocumentAmounts {
+  /// Totals of lines given as `(subtotal, tax_amount)` pairs.
+  pub fn sum<I>(lines: I) -> Self
+  where
+    I: IntoIterator<Item = (Decimal, Decimal)>,
+  {
+    let (amount_untaxed, amount_tax) = lines.into_iter().fold(
+      (Decimal::ZERO, Decimal::ZERO),
+      |(untaxed, tax), (subtotal, tax_amount)| (untaxed + subtotal, tax + tax_amount),
+    );
+
+    Self {
+      amount_untaxed,
+      amount_tax,
+      amount_total: amount_untaxed + amount_tax,
+    }
+  }
+}
+
+/// Taxes of a whole document: every line, the amount charged under each tax in
+/// the order the taxes first appear, and the totals. Totals are sums of the
+/// rounded line amounts.
+pub fn compute_taxes(lines: &[TaxableLine], currency: &DocumentCurrency) -> TaxComputationDTO {
+  let lines = lines
+    .iter()
+    .map(|line| (compute_line(line, currency), line.tax))
+    .collect::<Vec<_>>();
+
+  let mut taxes: Vec<TaxBreakdownDTO> = vec![];
+  for (line, tax) in &lines {
+    let Some(tax) = tax else {
+      continue;
+    };
+    match taxes
+      .iter_mut()
+      .find(|breakdown| breakdown.tax_id == tax.id)
+    {
+      Some(breakdown) => {
+        breakdown.base += line.subtotal;
+        breakdown.amount += line.tax_amount;
+      }
+      None => taxes.push(TaxBreakdownDTO {
+        tax_id: tax.id,
+        name: tax.name.clone(),
+        vat_group: tax.vat_group,
+        base: line.subtotal,
+        amount: line.tax_amount,
+      }),
+    }
+  }
+
+  let lines = lines.into_iter().map(|(line, _)| line).collect::<Vec<_>>();
+  let amounts = DocumentAmounts::sum(lines.iter().map(|line| (line.subtotal, line.tax_amount)));
+
+  TaxComputationDTO {
+    currency_id: currency.currency.id,
+    lines,
+    taxes,
+    amount_untaxed: amounts.amount_untaxed,
+    amount_tax: amounts.amount_tax,
+    amount_total: amounts.amount_total,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Utc;
+  use domain::tax::tax::{TaxUse, VatGroup};
+  use infra::uuid::Uuid;
+
+  use super::*;
+  use crate::test_support::{currency, dec};
+
+  fn document_currency(rounding: &str, rate: &str) -> DocumentCurrency {
+    DocumentCurrency {
+      currency: currency("USD", rounding, rate == "1"),
+      rate: dec(rate),
+      at: Utc::now().into(),
+    }
+  }
+
+  fn tax(amount_type: TaxAmountType, amount: &str, price_include: bool) -> tax::Model {
+    tax::Model {
+      id: Uuid::new(),
+      name: format!("Tax {amount}"),
+      tax_use: TaxUse::Sales,
+      amount_type,
+      amount: dec(amount),
+      price_include,
+      vat_group: Some(VatGroup::Vat8),
+      created_at: Utc::now().into(),
+      updated_at: None,
+      archived_at: None,
+    }
+  }
+
+  fn line<'a>(
+    amount: &str,
+    product_quantity: &str,
+    tax: Option<&'a tax::Model>,
+  ) -> TaxableLine<'a> {
+    TaxableLine {
+      amount: dec(amount),
+      product_quantity: dec(product_quantity),
+      tax,
+    }
+  }
+
+  #[test]
+  fn untaxed_line_is_rounded_to_currency() {
+    let result = compute_line(&line("10.555", "1", None), &document_currency("0.01", "1"));
+
+    assert_eq!(result.tax_id, None);
+    assert_eq!(result.subtotal, dec("10.56"));
+    assert_eq!(result.tax_amount, Decimal::ZERO);
+    assert_eq!(result.total, dec("10.56"));
+  }
+
+  #[test]
+  fn excluded_percent_tax_is_charged_on_rounded_subtotal() {
+    let vat = tax(TaxAmountType::Percent, "10", false);
+
+    let result = compute_line(
+      &line("10.555", "1", Some(&vat)),
+      &document_currency("0.01", "1"),
+    );
+
+    assert_eq!(result.subtotal, dec("10.56"));
+    assert_eq!(result.tax_amount, dec("1.06"));
+    assert_eq!(result.total, dec("11.62"));
+  }
+
+  #[test]
+  fn included_percent_tax_adds_back_up_to_rounded_gross() {
+    let vat = tax(TaxAmountType::Percent, "8", true);
+
+    let result = compute_line(
+      &line("10", "1", Some(&vat)),
+      &document_currency("0.01", "1"),
+    );
+
+    assert_eq!(result.subtotal, dec("9.26"));
+    assert_eq!(result.tax_amount, dec("0.74"));
+    assert_eq!(result.total, dec("10.00"));
+  }
+
+  #[test]
+  fn included_percent_tax_rounds_to_whole_units() {
+    let vat = tax(TaxAmountType::Percent, "8", true);
+
+    let result = compute_line(
+      &line("100000.4", "1", Some(&vat)),
+      &document_currency("1", "1"),
+    );
+
+    assert_eq!(result.subtotal, dec("92593"));
+    assert_eq!(result.tax_amount, dec("7407"));
+    assert_eq!(result.total, dec("100000"));
+  }
+
+  #[test]
+  fn fixed_tax_is_charged_per_stock_unit_in_document_currency() {
+    let fee = tax(TaxAmountType::Fixed, "5000", false);
+
+    let result = compute_line(
+      &line("30", "3", Some(&fee)),
+      &document_currency("0.01", "25000"),
+    );
+
+    assert_eq!(result.subtotal, dec("30"));
+    assert_eq!(result.tax_amount, dec("0.6"));
+    assert_eq!(result.total, dec("30.6"));
+  }
+
+  #[test]
+  fn included_fixed_tax_is_taken_out_of_gross() {
+    let fee = tax(TaxAmountType::Fixed, "2", true);
+
+    let result = compute_line(
+      &line("50", "3", Some(&fee)),
+      &document_currency("0.01", "1"),
+    );
+
+    assert_eq!(result.subtotal, dec("44"));
+    assert_eq!(result.tax_amount, dec("6"));
+    assert_eq!(result.total, dec("50"));
+  }
+
+  #[test]
+  fn document_totals_sum_rounded_lines_per_tax() {
+    let vat = tax(TaxAmountType::Percent, "8", true);
+    let currency = document_currency("0.01", "1");
+    let lines = [
+      line("10", "1", Some(&vat)),
+      line("10", "1", Some(&vat)),
+      line("5.004", "1", None),
+    ];
+
+    let result = compute_taxes(&lines, &currency);
+
+    assert_eq!(result.currency_id, currency.currency.id);
+    assert_eq!(result.lines.len(), 3);
+    assert_eq!(result.taxes.len(), 1);
+    assert_eq!(result.taxes[0].tax_id, vat.id);
+    assert_eq!(result.taxes[0].base, dec("18.52"));
+    assert_eq!(result.taxes[0].amount, dec("1.48"));
+    assert_eq!(result.amount_untaxed, dec("23.52"));
+    assert_eq!(result.amount_tax, dec("1.48"));
+    assert_eq!(result.amount_total, dec("25.00"));
+  }
+}
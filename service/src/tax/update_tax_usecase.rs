@@ -0,0 +1,89 @@
+use axum::response::{IntoResponse, Response};
+use domain::tax::tax::{self, TaxAmountType, TaxUse, VatGroup};
+use infra::{
+  db::WriteConnection,
+  error::AppError,
+  uuid::Uuid,
+  validation::{Validate, ValidationErrors},
+};
+use sea_orm::{prelude::Decimal, ActiveModelTrait, DbErr, EntityTrait, Set};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::create_tax_usecase::{find_by_name, validate_tax};
+use crate::unique_name::name_conflict;
+
+/// Changing a tax leaves the amounts already computed on order lines as they
+/// are.
+#[derive(Debug, Deserialize, Clone)]
+pub struct UpdateTaxUsecase {
+  pub id: Uuid,
+  pub name: String,
+  #[serde(rename(deserialize = "taxUse"))]
+  pub tax_use: TaxUse,
+  #[serde(rename(deserialize = "amountType"))]
+  pub amount_type: TaxAmountType,
+  pub amount: Decimal,
+  #[serde(rename(deserialize = "priceInclude"), default)]
+  pub price_include: bool,
+  #[serde(rename(deserialize = "vatGroup"), default)]
+  pub vat_group: Option<VatGroup>,
+}
+
+pub type UpdateTaxPayload = UpdateTaxUsecase;
+
+impl Validate for UpdateTaxUsecase {
+  fn validate(&self) -> Result<(), ValidationErrors> {
+    validate_tax(&self.name, self.amount_type, self.amount, self.vat_group).into_result()
+  }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateTaxError {
+  #[error(transparent)]
+  Database(#[from] DbErr),
+
+  #[error("record_not_found")]
+  RecordNotFound,
+
+  #[error("name_already_exists")]
+  NameConflict(Uuid),
+}
+
+impl IntoResponse for UpdateTaxError {
+  fn into_response(self) -> Response {
+    let error = match self {
+      UpdateTaxError::Database(err) => AppError::from(err),
+      UpdateTaxError::RecordNotFound => AppError::not_found(self.to_string()),
+      UpdateTaxError::NameConflict(existing_id) => name_conflict(self.to_string(), existing_id),
+    };
+
+    error.with_source("update_tax").into_response()
+  }
+}
+
+impl UpdateTaxUsecase {
+  pub async fn invoke(&self, db: impl WriteConnection) -> Result<tax::Model, UpdateTaxError> {
+    tax::Entity::find_by_id(self.id)
+      .one(&db)
+      .await?
+      .ok_or(UpdateTaxError::RecordNotFound)?;
+    if let Some(existing) = find_by_name(&db, &self.name, self.tax_use, Some(self.id)).await? {
+      return Err(UpdateTaxError::NameConflict(existing.id));
+    }
+
+    let tax = tax::ActiveModel {
+      id: Set(self.id),
+      name: Set(self.name.trim().to_string()),
+      tax_use: Set(self.tax_use),
+      amount_type: Set(self.amount_type),
+      amount: Set(self.amount),
+      price_include: Set(self.price_include),
+      vat_group: Set(self.vat_group),
+      ..Default::default()
+    };
+    let tax = tax.update(&db).await?;
+
+    Ok(tax)
+  }
+}
@@ -0,0 +1,48 @@
+//! Helpers shared by the unit tests of the usecases.
+
+use chrono::Utc;
+use domain::{
+  currency::currency,
+  measurement::uom::{self, UomCategory},
+};
+use infra::uuid::Uuid;
+use sea_orm::prelude::{DateTimeWithTimeZone, Decimal};
+
+pub fn dec(value: &str) -> Decimal {
+  value.parse().unwrap()
+}
+
+pub fn at(value: &str) -> DateTimeWithTimeZone {
+  DateTimeWithTimeZone::parse_from_rfc3339(value).unwrap()
+}
+
+/// Unit worth `ratio` reference units of `category`.
+pub fn uom(category: UomCategory, ratio: &str, rounding: &str) -> uom::Model {
+  uom::Model {
+    id: Uuid::new(),
+    name: format!("{ratio} units"),
+    category,
+    ratio: dec(ratio),
+    rounding: dec(rounding),
+    is_reference: ratio == "1",
+    created_at: Utc::now().into(),
+    updated_at: None,
+    archived_at: None,
+  }
+}
+
+/// Active currency rounded to `rounding`.
+pub fn currency(code: &str, rounding: &str, is_base: bool) -> currency::Model {
+  currency::Model {
+    id: Uuid::new(),
+    code: code.to_string(),
+    name: code.to_string(),
+    symbol: code.to_string(),
+    decimal_places: dec(rounding).scale() as i32,
+    rounding: dec(rounding),
+    is_base,
+    created_at: Utc::now().into(),
+    updated_at: None,
+    archived_at: None,
+  }
+}
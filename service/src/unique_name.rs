@@ -0,0 +1,31 @@
+use infra::{error::AppError, uuid::Uuid};
+use sea_orm::{
+  sea_query::{Alias, Expr, Func, SimpleExpr},
+  ColumnTrait,
+};
+use serde_json::json;
+
+/// `lower(f_unaccent(expr))`, the key the case- and accent-insensitive unique
+/// indexes are built on.
+pub fn name_key<T>(expr: T) -> SimpleExpr
+where
+  T: Into<SimpleExpr>,
+{
+  Func::lower(Func::cust(Alias::new("f_unaccent")).arg(expr)).into()
+}
+
+/// Matches rows whose `column` equals `value` under the same normalisation as
+/// the unique indexes.
+pub fn same_name<C>(column: C, value: &str) -> SimpleExpr
+where
+  C: ColumnTrait,
+{
+  Expr::expr(name_key(Expr::col((column.entity_name(), column)))).eq(name_key(value))
+}
+
+/// 409 raised when a name collides with the existing record `existing_id`.
+pub fn name_conflict(code: String, existing_id: Uuid) -> AppError {
+  AppError::conflict(code)
+    .with_field("name", "already_exists")
+    .with_details(json!({ "existingId": existing_id }))
+}